@@ -33,6 +33,7 @@ interface AttnUsdResponse {
   nav_sol: number;
   price_per_share: number;
   seven_day_apy: number;
+  thirty_day_apy?: number;
   last_rebalance_slot: number;
   updated_at: ISODateTimeString;
 }
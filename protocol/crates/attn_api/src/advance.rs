@@ -1,12 +1,17 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use anyhow::anyhow;
+use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use super::ApiError;
+use crate::pricing::{MarketInputs, PricedQuote, PricingBreakdown, PricingConfig};
 
 const SIX_DECIMAL_FACTOR: f64 = 1_000_000.0;
 const EPSILON: f64 = 0.000_001;
@@ -97,6 +102,15 @@ pub enum QuoteRoute {
     Amm,
 }
 
+impl QuoteRoute {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuoteRoute::Rfq => "rfq",
+            QuoteRoute::Amm => "amm",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct QuoteRecord {
     pub id: String,
@@ -111,11 +125,12 @@ pub struct QuoteRecord {
     pub maturity_ts: i64,
     pub cursor: String,
     pub expires_at: DateTime<Utc>,
+    pub pricing: Option<PricingBreakdown>,
 }
 
 impl QuoteRecord {
     pub fn epoch_key(&self) -> String {
-        format!("{}:{}", self.market, self.maturity_ts)
+        compose_epoch_key(&self.market, self.maturity_ts)
     }
 
     pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
@@ -136,6 +151,7 @@ impl QuoteRecord {
                 .expires_at
                 .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
             cursor: self.cursor.clone(),
+            pricing: self.pricing.clone(),
         }
     }
 }
@@ -152,6 +168,8 @@ pub struct QuoteResponse {
     pub side: QuoteSide,
     pub expires_at: String,
     pub cursor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<PricingBreakdown>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -195,29 +213,31 @@ pub struct QuoteService {
 }
 
 struct QuoteState {
-    cache: RwLock<QuoteCache>,
-    execution: RwLock<ExecutionBook>,
+    book: Arc<dyn QuoteBook>,
     limits: AdvanceLimits,
     ttl: Duration,
     cluster: Cluster,
     lp_wallet: String,
+    pricing: PricingConfig,
 }
 
 impl QuoteService {
-    pub fn new(
+    pub fn with_book(
         cluster: Cluster,
         limits: AdvanceLimits,
         ttl: Duration,
         lp_wallet: impl Into<String>,
+        book: Arc<dyn QuoteBook>,
+        pricing: PricingConfig,
     ) -> Self {
         Self {
             inner: Arc::new(QuoteState {
-                cache: RwLock::new(QuoteCache::default()),
-                execution: RwLock::new(ExecutionBook::default()),
+                book,
                 limits,
                 ttl,
                 cluster,
                 lp_wallet: lp_wallet.into(),
+                pricing,
             }),
         }
     }
@@ -230,13 +250,17 @@ impl QuoteService {
         &self.inner.lp_wallet
     }
 
+    pub fn pricing(&self) -> &PricingConfig {
+        &self.inner.pricing
+    }
+
     pub async fn get_or_create_quote(
         &self,
         market: &str,
         size_yt: f64,
         side: QuoteSide,
         maturity_ts: i64,
-        implied_apr: f64,
+        inputs: &MarketInputs<'_>,
     ) -> Result<QuoteRecord, ApiError> {
         if !size_yt.is_finite() || size_yt <= 0.0 {
             return Err(ApiError::bad_request(
@@ -253,25 +277,41 @@ impl QuoteService {
             ));
         }
 
-        let mut cache = self.inner.cache.write().await;
-        cache.cleanup_expired(now);
-
         let normalized_size = round_six(size_yt);
-        let key = QuoteCache::compose_key(market, side, maturity_ts, normalized_size);
-        if let Some(existing) = cache.by_key.get(&key) {
-            return Ok(existing.clone());
+        let key = compose_quote_key(market, side, maturity_ts, normalized_size);
+        if let Some(existing) = self.inner.book.find_live(&key, now).await? {
+            return Ok(existing);
         }
 
+        let utilization = match self.inner.limits.epoch_limit_opt() {
+            Some(limit) => {
+                let used = self
+                    .inner
+                    .book
+                    .epoch_usage(&compose_epoch_key(market, maturity_ts))
+                    .await?;
+                used / limit
+            }
+            None => 0.0,
+        };
+        let priced = self.inner.pricing.price(
+            normalized_size,
+            side,
+            maturity_ts - now.timestamp(),
+            inputs,
+            utilization,
+            now,
+        );
         let quote = build_quote(
             market,
             normalized_size,
             side,
             maturity_ts,
-            implied_apr,
+            priced,
             self.inner.ttl,
+            now,
         );
-        cache.insert(quote.clone());
-        Ok(quote)
+        self.inner.book.insert(quote, now).await
     }
 
     pub async fn finalize_execution(
@@ -301,15 +341,99 @@ impl QuoteService {
             ));
         }
 
-        let now = Utc::now();
-        let mut cache = self.inner.cache.write().await;
+        self.inner
+            .book
+            .execute(
+                ExecutionRequest {
+                    quote_id,
+                    wallet,
+                    direction,
+                    limits: &self.inner.limits,
+                },
+                Utc::now(),
+            )
+            .await
+    }
+}
+
+/// Everything a [`QuoteBook`] needs to consume a quote and move the caps.
+pub struct ExecutionRequest<'a> {
+    pub quote_id: &'a str,
+    pub wallet: &'a str,
+    pub direction: TradeDirection,
+    pub limits: &'a AdvanceLimits,
+}
+
+/// Storage for issued quotes, consumed quote ids and per-epoch advance usage.
+///
+/// `execute` must be atomic: a quote is consumed at most once and the cap
+/// check and the usage update happen under the same lock or transaction.
+#[async_trait]
+pub trait QuoteBook: Send + Sync {
+    async fn find_live(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<QuoteRecord>, ApiError>;
+
+    /// Stores `quote` unless a live quote with the same key already exists,
+    /// in which case the existing quote is returned instead.
+    async fn insert(&self, quote: QuoteRecord, now: DateTime<Utc>)
+        -> Result<QuoteRecord, ApiError>;
+
+    async fn execute(
+        &self,
+        request: ExecutionRequest<'_>,
+        now: DateTime<Utc>,
+    ) -> Result<(QuoteRecord, AdvanceCapSnapshot), ApiError>;
+
+    /// Total advanced USDC recorded against `epoch_key` (`market:maturity_ts`).
+    async fn epoch_usage(&self, epoch_key: &str) -> Result<f64, ApiError>;
+}
+
+#[derive(Default)]
+pub struct InMemoryQuoteBook {
+    cache: RwLock<QuoteCache>,
+    execution: RwLock<ExecutionBook>,
+}
+
+#[async_trait]
+impl QuoteBook for InMemoryQuoteBook {
+    async fn find_live(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<QuoteRecord>, ApiError> {
+        let mut cache = self.cache.write().await;
+        cache.cleanup_expired(now);
+        Ok(cache.by_key.get(key).cloned())
+    }
+
+    async fn insert(
+        &self,
+        quote: QuoteRecord,
+        now: DateTime<Utc>,
+    ) -> Result<QuoteRecord, ApiError> {
+        let mut cache = self.cache.write().await;
+        cache.cleanup_expired(now);
+        if let Some(existing) = cache.by_key.get(&quote.key) {
+            return Ok(existing.clone());
+        }
+        cache.insert(quote.clone());
+        Ok(quote)
+    }
+
+    async fn execute(
+        &self,
+        request: ExecutionRequest<'_>,
+        now: DateTime<Utc>,
+    ) -> Result<(QuoteRecord, AdvanceCapSnapshot), ApiError> {
+        let quote_id = request.quote_id;
+        let mut cache = self.cache.write().await;
         cache.cleanup_expired(now);
 
         if cache.consumed.contains(quote_id) {
-            return Err(ApiError::bad_request(
-                "quote_consumed",
-                "quote was already executed",
-            ));
+            return Err(quote_consumed());
         }
         if !cache.inflight.insert(quote_id.to_string()) {
             return Err(ApiError::bad_request(
@@ -322,57 +446,387 @@ impl QuoteService {
             Some(quote) => quote,
             None => {
                 cache.inflight.remove(quote_id);
-                return Err(ApiError::bad_request("quote_not_found", "quote not found"));
+                return Err(quote_not_found());
             }
         };
 
         if quote.is_expired(now) {
             cache.inflight.remove(quote_id);
             cache.remove_by_id(quote_id);
-            return Err(ApiError::bad_request("quote_expired", "quote has expired"));
+            return Err(quote_expired());
         }
 
-        let expected_side = match direction {
-            TradeDirection::Advance => QuoteSide::Sell,
-            TradeDirection::Buyback => QuoteSide::Buyback,
-        };
-        if quote.side != expected_side {
+        if let Err(err) = check_quote_side(&quote, request.direction) {
             cache.inflight.remove(quote_id);
-            return Err(ApiError::bad_request(
-                "wrong_quote_side",
-                "quote side does not match requested trade",
-            ));
+            return Err(err);
         }
 
         drop(cache);
 
         let result = {
-            let mut execution = self.inner.execution.write().await;
+            let mut execution = self.execution.write().await;
             execution.apply(
                 &quote,
-                wallet,
+                request.wallet,
                 quote.price_usdc,
-                direction,
-                &self.inner.limits,
+                request.direction,
+                request.limits,
             )
         };
 
-        match result {
-            Ok(snapshot) => {
-                let mut cache = self.inner.cache.write().await;
-                cache.inflight.remove(quote_id);
-                cache.mark_consumed(&quote);
-                Ok((quote, snapshot))
+        let mut cache = self.cache.write().await;
+        cache.inflight.remove(quote_id);
+        let snapshot = result?;
+        cache.mark_consumed(&quote);
+        Ok((quote, snapshot))
+    }
+
+    async fn epoch_usage(&self, epoch_key: &str) -> Result<f64, ApiError> {
+        let execution = self.execution.read().await;
+        Ok(execution
+            .epochs
+            .get(epoch_key)
+            .map(|totals| totals.total)
+            .unwrap_or(0.0))
+    }
+}
+
+/// Postgres-backed quote book so consumed quotes and advance usage survive
+/// restarts and are shared between API instances.
+#[derive(Clone)]
+pub struct PgQuoteBook {
+    pool: PgPool,
+}
+
+impl PgQuoteBook {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl QuoteBook for PgQuoteBook {
+    async fn find_live(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<QuoteRecord>, ApiError> {
+        let row = sqlx::query(
+            "select * from rfq_quotes
+             where quote_key = $1
+               and consumed_at is null
+               and expires_at > $2",
+        )
+        .bind(key)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+        row.map(row_to_quote).transpose()
+    }
+
+    async fn insert(
+        &self,
+        quote: QuoteRecord,
+        now: DateTime<Utc>,
+    ) -> Result<QuoteRecord, ApiError> {
+        let quote_id =
+            Uuid::parse_str(&quote.id).map_err(|err| ApiError::from(anyhow::Error::from(err)))?;
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query(
+            "delete from rfq_quotes
+             where quote_key = $1
+               and consumed_at is null
+               and expires_at <= $2",
+        )
+        .bind(&quote.key)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let inserted = sqlx::query(
+            "insert into rfq_quotes (
+                id, quote_key, market, side, route, maturity_ts, size_yt,
+                price_usdc, implied_apr, est_slippage, cursor, expires_at, pricing
+             )
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             on conflict do nothing
+             returning *",
+        )
+        .bind(quote_id)
+        .bind(&quote.key)
+        .bind(&quote.market)
+        .bind(quote.side.as_str())
+        .bind(quote.route.as_str())
+        .bind(quote.maturity_ts)
+        .bind(quote.size_yt)
+        .bind(quote.price_usdc)
+        .bind(quote.implied_apr)
+        .bind(quote.est_slippage)
+        .bind(&quote.cursor)
+        .bind(quote.expires_at)
+        .bind(quote.pricing.as_ref().map(sqlx::types::Json))
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let row = match inserted {
+            Some(row) => row,
+            None => sqlx::query(
+                "select * from rfq_quotes
+                 where quote_key = $1
+                   and consumed_at is null",
+            )
+            .bind(&quote.key)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(db_error)?,
+        };
+        tx.commit().await.map_err(db_error)?;
+        row_to_quote(row)
+    }
+
+    async fn execute(
+        &self,
+        request: ExecutionRequest<'_>,
+        now: DateTime<Utc>,
+    ) -> Result<(QuoteRecord, AdvanceCapSnapshot), ApiError> {
+        let Ok(quote_id) = Uuid::parse_str(request.quote_id) else {
+            return Err(quote_not_found());
+        };
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let row = sqlx::query("select * from rfq_quotes where id = $1 for update")
+            .bind(quote_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(quote_not_found)?;
+        let consumed_at: Option<DateTime<Utc>> = row.get("consumed_at");
+        let quote = row_to_quote(row)?;
+        if consumed_at.is_some() {
+            return Err(quote_consumed());
+        }
+        if quote.is_expired(now) {
+            sqlx::query("delete from rfq_quotes where id = $1")
+                .bind(quote_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?;
+            tx.commit().await.map_err(db_error)?;
+            return Err(quote_expired());
+        }
+        check_quote_side(&quote, request.direction)?;
+
+        let epoch_key = quote.epoch_key();
+        sqlx::query(
+            "insert into rfq_epoch_totals (epoch_key, market, maturity_ts)
+             values ($1, $2, $3)
+             on conflict (epoch_key) do nothing",
+        )
+        .bind(&epoch_key)
+        .bind(&quote.market)
+        .bind(quote.maturity_ts)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let epoch_used: f64 =
+            sqlx::query("select total_usdc from rfq_epoch_totals where epoch_key = $1 for update")
+                .bind(&epoch_key)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(db_error)?
+                .get("total_usdc");
+        let wallet_used: f64 = sqlx::query(
+            "select used_usdc from rfq_wallet_totals
+             where epoch_key = $1 and wallet = $2
+             for update",
+        )
+        .bind(&epoch_key)
+        .bind(request.wallet)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .map(|row| row.get("used_usdc"))
+        .unwrap_or(0.0);
+
+        let (new_wallet_total, new_epoch_total) = next_totals(
+            wallet_used,
+            epoch_used,
+            quote.price_usdc,
+            request.direction,
+            request.limits,
+        )?;
+
+        if new_wallet_total > EPSILON {
+            sqlx::query(
+                "insert into rfq_wallet_totals (epoch_key, wallet, used_usdc, updated_at)
+                 values ($1, $2, $3, now())
+                 on conflict (epoch_key, wallet)
+                 do update set used_usdc = excluded.used_usdc, updated_at = now()",
+            )
+            .bind(&epoch_key)
+            .bind(request.wallet)
+            .bind(round_six(new_wallet_total))
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        } else {
+            sqlx::query("delete from rfq_wallet_totals where epoch_key = $1 and wallet = $2")
+                .bind(&epoch_key)
+                .bind(request.wallet)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?;
+        }
+        sqlx::query(
+            "update rfq_epoch_totals set total_usdc = $2, updated_at = now() where epoch_key = $1",
+        )
+        .bind(&epoch_key)
+        .bind(round_six(new_epoch_total))
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        sqlx::query("update rfq_quotes set consumed_at = $2, consumed_by = $3 where id = $1")
+            .bind(quote_id)
+            .bind(now)
+            .bind(request.wallet)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        let wallet_used = if new_wallet_total > EPSILON {
+            round_six(new_wallet_total)
+        } else {
+            0.0
+        };
+        let snapshot =
+            AdvanceCapSnapshot::new(wallet_used, round_six(new_epoch_total), request.limits);
+        Ok((quote, snapshot))
+    }
+
+    async fn epoch_usage(&self, epoch_key: &str) -> Result<f64, ApiError> {
+        let total: Option<f64> =
+            sqlx::query_scalar("select total_usdc from rfq_epoch_totals where epoch_key = $1")
+                .bind(epoch_key)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+        Ok(total.unwrap_or(0.0))
+    }
+}
+
+fn db_error(err: sqlx::Error) -> ApiError {
+    ApiError::from(anyhow::Error::from(err))
+}
+
+fn row_to_quote(row: PgRow) -> Result<QuoteRecord, ApiError> {
+    let side = match row.get::<String, _>("side").as_str() {
+        "sell" => QuoteSide::Sell,
+        "buyback" => QuoteSide::Buyback,
+        other => return Err(ApiError::from(anyhow!("unknown quote side: {other}"))),
+    };
+    let route = match row.get::<String, _>("route").as_str() {
+        "rfq" => QuoteRoute::Rfq,
+        "amm" => QuoteRoute::Amm,
+        other => return Err(ApiError::from(anyhow!("unknown quote route: {other}"))),
+    };
+    Ok(QuoteRecord {
+        id: row.get::<Uuid, _>("id").to_string(),
+        key: row.get("quote_key"),
+        market: row.get("market"),
+        size_yt: row.get("size_yt"),
+        price_usdc: row.get("price_usdc"),
+        implied_apr: row.get("implied_apr"),
+        est_slippage: row.get("est_slippage"),
+        route,
+        side,
+        maturity_ts: row.get("maturity_ts"),
+        cursor: row.get("cursor"),
+        expires_at: row.get("expires_at"),
+        pricing: row
+            .get::<Option<sqlx::types::Json<PricingBreakdown>>, _>("pricing")
+            .map(|json| json.0),
+    })
+}
+
+fn quote_not_found() -> ApiError {
+    ApiError::bad_request("quote_not_found", "quote not found")
+}
+
+fn quote_consumed() -> ApiError {
+    ApiError::bad_request("quote_consumed", "quote was already executed")
+}
+
+fn quote_expired() -> ApiError {
+    ApiError::bad_request("quote_expired", "quote has expired")
+}
+
+fn check_quote_side(quote: &QuoteRecord, direction: TradeDirection) -> Result<(), ApiError> {
+    let expected_side = match direction {
+        TradeDirection::Advance => QuoteSide::Sell,
+        TradeDirection::Buyback => QuoteSide::Buyback,
+    };
+    if quote.side != expected_side {
+        return Err(ApiError::bad_request(
+            "wrong_quote_side",
+            "quote side does not match requested trade",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the wallet and epoch usage after applying a trade, enforcing the
+/// caps on advances. Buybacks only ever reduce usage.
+fn next_totals(
+    wallet_used: f64,
+    epoch_used: f64,
+    amount_usdc: f64,
+    direction: TradeDirection,
+    limits: &AdvanceLimits,
+) -> Result<(f64, f64), ApiError> {
+    match direction {
+        TradeDirection::Advance => {
+            let proposed_wallet_total = wallet_used + amount_usdc;
+            if let Some(limit) = limits.wallet_limit_opt() {
+                if proposed_wallet_total - limit > EPSILON {
+                    return Err(ApiError::bad_request(
+                        "wallet_cap_exceeded",
+                        "wallet advance cap exceeded",
+                    ));
+                }
             }
-            Err(err) => {
-                let mut cache = self.inner.cache.write().await;
-                cache.inflight.remove(quote_id);
-                Err(err)
+            let proposed_epoch_total = epoch_used + amount_usdc;
+            if let Some(limit) = limits.epoch_limit_opt() {
+                if proposed_epoch_total - limit > EPSILON {
+                    return Err(ApiError::bad_request(
+                        "epoch_cap_exceeded",
+                        "epoch advance cap exceeded",
+                    ));
+                }
             }
+            Ok((proposed_wallet_total, proposed_epoch_total))
         }
+        TradeDirection::Buyback => Ok((
+            (wallet_used - amount_usdc).max(0.0),
+            (epoch_used - amount_usdc).max(0.0),
+        )),
     }
 }
 
+fn compose_epoch_key(market: &str, maturity_ts: i64) -> String {
+    format!("{}:{}", market, maturity_ts)
+}
+
+fn compose_quote_key(market: &str, side: QuoteSide, maturity_ts: i64, size_yt: f64) -> String {
+    format!(
+        "{}:{}:{}:{:.6}",
+        market,
+        maturity_ts,
+        side.as_str(),
+        size_yt
+    )
+}
+
 #[derive(Default)]
 struct QuoteCache {
     by_key: HashMap<String, QuoteRecord>,
@@ -382,16 +836,6 @@ struct QuoteCache {
 }
 
 impl QuoteCache {
-    fn compose_key(market: &str, side: QuoteSide, maturity_ts: i64, size_yt: f64) -> String {
-        format!(
-            "{}:{}:{}:{:.6}",
-            market,
-            maturity_ts,
-            side.as_str(),
-            size_yt
-        )
-    }
-
     fn cleanup_expired(&mut self, now: DateTime<Utc>) {
         let expired: Vec<String> = self
             .by_id
@@ -447,33 +891,13 @@ impl ExecutionBook {
                 .copied()
                 .unwrap_or(0.0);
 
-            let (new_wallet_total, new_epoch_total) = match direction {
-                TradeDirection::Advance => {
-                    let proposed_wallet_total = current_wallet_total + amount_usdc;
-                    if let Some(limit) = limits.wallet_limit_opt() {
-                        if proposed_wallet_total - limit > EPSILON {
-                            return Err(ApiError::bad_request(
-                                "wallet_cap_exceeded",
-                                "wallet advance cap exceeded",
-                            ));
-                        }
-                    }
-                    let proposed_epoch_total = totals.total + amount_usdc;
-                    if let Some(limit) = limits.epoch_limit_opt() {
-                        if proposed_epoch_total - limit > EPSILON {
-                            return Err(ApiError::bad_request(
-                                "epoch_cap_exceeded",
-                                "epoch advance cap exceeded",
-                            ));
-                        }
-                    }
-                    (proposed_wallet_total, proposed_epoch_total)
-                }
-                TradeDirection::Buyback => (
-                    (current_wallet_total - amount_usdc).max(0.0),
-                    (totals.total - amount_usdc).max(0.0),
-                ),
-            };
+            let (new_wallet_total, new_epoch_total) = next_totals(
+                current_wallet_total,
+                totals.total,
+                amount_usdc,
+                direction,
+                limits,
+            )?;
 
             if new_wallet_total > EPSILON {
                 totals
@@ -514,42 +938,19 @@ fn build_quote(
     size_yt: f64,
     side: QuoteSide,
     maturity_ts: i64,
-    implied_apr: f64,
+    priced: PricedQuote,
     ttl: Duration,
+    now: DateTime<Utc>,
 ) -> QuoteRecord {
-    let now = Utc::now();
     let expires_at = now + ttl;
-    let apr = if implied_apr.is_finite() {
-        implied_apr.max(0.0)
-    } else {
-        0.0
-    };
-
-    let seconds_to_maturity = (maturity_ts - now.timestamp()).max(0);
-    let period_fraction = if seconds_to_maturity == 0 {
-        15.0 / 365.0
-    } else {
-        ((seconds_to_maturity as f64) / 86_400.0 / 365.0).clamp(1.0 / 365.0, 0.5)
-    };
-
-    let base_price = size_yt * apr * period_fraction;
-
-    let (adjusted_price, slippage_raw) = match side {
-        QuoteSide::Sell => {
-            let price = base_price * (1.0 - 0.0015);
-            let slip = 0.002 + (size_yt / 100_000.0).min(0.01);
-            (price, slip)
-        }
-        QuoteSide::Buyback => {
-            let price = base_price * (1.0 + 0.0025);
-            let slip = 0.003 + (size_yt / 120_000.0).min(0.012);
-            (price, slip)
-        }
+    let slippage_raw = match side {
+        QuoteSide::Sell => 0.002 + (size_yt / 100_000.0).min(0.01),
+        QuoteSide::Buyback => 0.003 + (size_yt / 120_000.0).min(0.012),
     };
 
-    let price_usdc = round_six(adjusted_price.max(0.0));
+    let price_usdc = round_six(priced.price_usdc.max(0.0));
     let slippage = round_six(slippage_raw.max(0.0));
-    let key = QuoteCache::compose_key(market, side, maturity_ts, size_yt);
+    let key = compose_quote_key(market, side, maturity_ts, size_yt);
     let cursor = format!("{}:{}:{}", market, side.as_str(), now.timestamp_millis());
 
     QuoteRecord {
@@ -558,12 +959,13 @@ fn build_quote(
         market: market.to_string(),
         size_yt,
         price_usdc,
-        implied_apr: round_six(apr),
+        implied_apr: round_six(priced.breakdown.forward_apr),
         est_slippage: slippage,
         route: QuoteRoute::Rfq,
         side,
         maturity_ts,
         cursor,
         expires_at,
+        pricing: Some(priced.breakdown),
     }
 }
@@ -3,12 +3,13 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
+use attn_client::nonblocking::TransactionSigner;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use gcp_auth::AuthenticationManager;
 use reqwest::Client as HttpClient;
 use serde::Deserialize;
-use solana_sdk::signature::Signature;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
 
 const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
 
@@ -102,6 +103,7 @@ impl KmsClient for HttpKmsClient {
 #[derive(Clone)]
 pub struct KmsSigner<C: KmsClient> {
     resource_name: String,
+    pubkey: Pubkey,
     client: Arc<C>,
 }
 
@@ -109,14 +111,17 @@ impl<C: KmsClient> fmt::Debug for KmsSigner<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("KmsSigner")
             .field("resource_name", &self.resource_name)
+            .field("pubkey", &self.pubkey)
             .finish()
     }
 }
 
 impl<C: KmsClient> KmsSigner<C> {
-    pub fn new(resource_name: impl Into<String>, client: C) -> Self {
+    /// `pubkey` is the address the KMS key signs for; KMS does not report it.
+    pub fn new(resource_name: impl Into<String>, pubkey: Pubkey, client: C) -> Self {
         Self {
             resource_name: resource_name.into(),
+            pubkey,
             client: Arc::new(client),
         }
     }
@@ -125,6 +130,10 @@ impl<C: KmsClient> KmsSigner<C> {
         &self.resource_name
     }
 
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
     pub async fn sign(&self, message: &[u8]) -> Result<Signature> {
         let raw = self
             .client
@@ -142,10 +151,21 @@ impl<C: KmsClient> KmsSigner<C> {
     }
 }
 
+#[async_trait]
+impl<C: KmsClient> TransactionSigner for KmsSigner<C> {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.sign(message).await
+    }
+}
+
 impl KmsSigner<HttpKmsClient> {
-    pub async fn google_cloud(resource_name: impl Into<String>) -> Result<Self> {
+    pub async fn google_cloud(resource_name: impl Into<String>, pubkey: Pubkey) -> Result<Self> {
         let client = HttpKmsClient::new().await?;
-        Ok(Self::new(resource_name, client))
+        Ok(Self::new(resource_name, pubkey, client))
     }
 }
 
@@ -173,6 +193,7 @@ mod tests {
         let bytes = vec![42u8; 64];
         let signer = KmsSigner::new(
             "resource",
+            Pubkey::new_unique(),
             MockKmsClient {
                 response: Ok(bytes),
             },
@@ -185,6 +206,7 @@ mod tests {
     async fn signer_detects_invalid_length() {
         let signer = KmsSigner::new(
             "resource",
+            Pubkey::new_unique(),
             MockKmsClient {
                 response: Ok(vec![1, 2, 3]),
             },
@@ -197,6 +219,7 @@ mod tests {
     async fn signer_propagates_error() {
         let signer = KmsSigner::new(
             "resource",
+            Pubkey::new_unique(),
             MockKmsClient {
                 response: Err("boom".to_string()),
             },
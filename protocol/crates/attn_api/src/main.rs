@@ -1,7 +1,10 @@
 mod advance;
 mod kms;
+mod pricing;
 mod security;
 mod squads;
+mod stream;
+mod webhooks;
 
 use std::collections::HashSet;
 use std::convert::TryInto;
@@ -11,13 +14,19 @@ use std::sync::Arc;
 use std::time::{Duration as StdDuration, Instant};
 
 use advance::{
-    AdvanceCapSnapshot, AdvanceLimits, Cluster, QuoteRoute, QuoteService, QuoteSide, TradeDirection,
+    AdvanceCapSnapshot, AdvanceLimits, Cluster, InMemoryQuoteBook, PgQuoteBook, QuoteBook,
+    QuoteRoute, QuoteService, QuoteSide, TradeDirection,
 };
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
+use attn_client::errors::ProgramError;
 use attn_indexer::{
-    connect_pool, mock_store, run_migrations, DynStore, RewardsPoolSummary, SqlxStore,
+    connect_pool, mock_store, run_migrations, snapshots::DEFAULT_SNAPSHOT_INTERVAL_SECS,
+    stream::Topic, Alert, AlertFilter, DynStore, GovernanceProposal, HistoryInterval, HistoryRange,
+    ProposalFilter, RewardsPoolSummary, SqlxStore, ALERT_KINDS, ALERT_SEVERITIES,
+    PROPOSAL_STATUSES,
 };
+use attn_webhook as webhook;
 use axum::{
     extract::{ConnectInfo, Path, Query, State},
     http::{
@@ -26,7 +35,10 @@ use axum::{
         },
         HeaderMap, HeaderValue, Method, Request, StatusCode,
     },
-    response::{IntoResponse, Response},
+    response::{
+        sse::{KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     routing::{get, post},
     Json, Router,
 };
@@ -34,6 +46,7 @@ use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use ed25519_dalek::{PublicKey, Signature};
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use once_cell::sync::Lazy;
+use pricing::{MarketInputs, PricingConfig};
 use regex::Regex;
 use security::{
     default_security_config, parse_allowlist_values, parse_api_keys,
@@ -48,6 +61,7 @@ use squads::{
     NewSafeRequest, SafeRequestRecord, SafeStatus, SquadsConfig, SquadsModeKind,
     SquadsSafeRepository, SquadsService, StatusSyncUpdate,
 };
+use stream::StreamHub;
 use tokio::time::Duration as TokioDuration;
 use tokio::{net::TcpListener, time::sleep};
 use tower_http::{
@@ -56,6 +70,11 @@ use tower_http::{
 };
 use tracing::{error, info, info_span, warn, Level};
 use uuid::Uuid;
+use webhooks::{
+    generate_secret, InMemoryWebhookStore, NewWebhookEndpoint, PgWebhookStore, ReplayTarget,
+    WebhookDelivery, WebhookDispatcher, WebhookEndpoint, WebhookEndpointUpdate, WebhookStore,
+    MAX_ENDPOINTS_PER_KEY, WEBHOOK_EVENT_TYPES,
+};
 
 #[derive(Serialize, Deserialize)]
 struct HealthResponse {
@@ -70,6 +89,14 @@ struct AppState {
     squads_repo: Option<SquadsSafeRepository>,
     security: SecurityState,
     metrics: Option<PrometheusHandle>,
+    stream: StreamHub,
+    webhooks: Arc<dyn WebhookStore>,
+    /// Lets webhook endpoints target private and loopback hosts (local
+    /// development only).
+    webhook_allow_private: bool,
+    /// Indexer snapshot cadence; market history buckets finer than this
+    /// would hold at most one observation.
+    snapshot_interval: StdDuration,
 }
 
 #[async_trait]
@@ -162,6 +189,7 @@ trait StalledSafeStore: Clone + Send + Sync + 'static {
         request_id: Uuid,
         upstream: &CreateSafeResult,
         backoff: Duration,
+        verification_error: Option<&str>,
     ) -> Result<SafeRequestRecord>;
     async fn record_retry_error(
         &self,
@@ -201,8 +229,10 @@ impl StalledSafeStore for SquadsSafeRepository {
         request_id: Uuid,
         upstream: &CreateSafeResult,
         backoff: Duration,
+        verification_error: Option<&str>,
     ) -> Result<SafeRequestRecord> {
-        self.update_submission(request_id, upstream, backoff).await
+        self.update_submission(request_id, upstream, backoff, verification_error)
+            .await
     }
 
     async fn record_retry_error(
@@ -228,6 +258,11 @@ impl StalledSafeStore for SquadsSafeRepository {
 #[async_trait]
 trait SafeRetryClient: Clone + Send + Sync + 'static {
     async fn retry_create_safe(&self, input: CreateSafeInput) -> Result<CreateSafeResult>;
+    async fn safe_verification_error(
+        &self,
+        record: &SafeRequestRecord,
+        safe_address: &str,
+    ) -> Option<String>;
 }
 
 #[async_trait]
@@ -235,6 +270,14 @@ impl SafeRetryClient for SquadsService {
     async fn retry_create_safe(&self, input: CreateSafeInput) -> Result<CreateSafeResult> {
         self.create_safe(input).await
     }
+
+    async fn safe_verification_error(
+        &self,
+        record: &SafeRequestRecord,
+        safe_address: &str,
+    ) -> Option<String> {
+        safe_verification_error(self, record, safe_address).await
+    }
 }
 
 fn make_weak_etag(input: &[u8]) -> String {
@@ -291,6 +334,8 @@ const STALLED_STALE_SECS: i64 = 600;
 const STALLED_BATCH_SIZE: i64 = 50;
 const STALLED_MAX_AUTO_ATTEMPTS: i32 = 3;
 const STALLED_RETRY_BACKOFF_SECS: i64 = 300;
+const HISTORY_DEFAULT_BUCKETS: i64 = 168;
+const HISTORY_MAX_BUCKETS: i64 = 2_000;
 
 fn format_timestamp(dt: &DateTime<Utc>) -> String {
     dt.to_rfc3339_opts(SecondsFormat::Millis, true)
@@ -338,6 +383,7 @@ fn record_to_response(
         next_retry_at: format_option_timestamp(&record.next_retry_at),
         status_last_checked_at: format_option_timestamp(&record.status_last_checked_at),
         status_sync_error: record.status_sync_error.clone(),
+        creation_signature: record.creation_signature.clone(),
         status_last_response_hash: record.status_last_response_hash.clone(),
         creator_vault: record.creator_vault.clone(),
         governance_linked_at: format_option_timestamp(&record.governance_linked_at),
@@ -437,6 +483,7 @@ struct ApiConfig {
     advance_limits: AdvanceLimits,
     quote_ttl_secs: u64,
     rfq_lp_wallet: String,
+    pricing: PricingConfig,
     squads: Option<SquadsConfig>,
     security: ApiSecurityConfig,
 }
@@ -495,6 +542,7 @@ impl ApiConfig {
             .max(5);
         let rfq_lp_wallet = env::var("ATTN_API_RFQ_LP_WALLET")
             .unwrap_or_else(|_| "LpWallet11111111111111111111111111111111".to_string());
+        let pricing = pricing_config_from_env();
         let squads = SquadsConfig::from_env()?;
         let mut security = default_security_config();
         let api_keys_raw = env::var("ATTN_API_KEYS").ok();
@@ -543,12 +591,61 @@ impl ApiConfig {
             advance_limits,
             quote_ttl_secs,
             rfq_lp_wallet,
+            pricing,
             squads,
             security,
         })
     }
 }
 
+fn pricing_config_from_env() -> PricingConfig {
+    let defaults = PricingConfig::default();
+    let float = |name: &str, default: f64| {
+        env::var(name)
+            .ok()
+            .and_then(|raw| raw.parse::<f64>().ok())
+            .filter(|value| value.is_finite() && *value >= 0.0)
+            .unwrap_or(default)
+    };
+    PricingConfig {
+        history_days: env::var("ATTN_API_PRICING_HISTORY_DAYS")
+            .ok()
+            .and_then(|raw| raw.parse::<i64>().ok())
+            .unwrap_or(defaults.history_days)
+            .clamp(1, 365),
+        ewma_half_life_days: float(
+            "ATTN_API_PRICING_HALF_LIFE_DAYS",
+            defaults.ewma_half_life_days,
+        ),
+        min_history_samples: env::var("ATTN_API_PRICING_MIN_SAMPLES")
+            .ok()
+            .and_then(|raw| raw.parse::<usize>().ok())
+            .unwrap_or(defaults.min_history_samples),
+        base_haircut_bps: float(
+            "ATTN_API_PRICING_BASE_HAIRCUT_BPS",
+            defaults.base_haircut_bps,
+        ),
+        haircut_per_30d_bps: float(
+            "ATTN_API_PRICING_HAIRCUT_PER_30D_BPS",
+            defaults.haircut_per_30d_bps,
+        ),
+        volatility_haircut_multiplier: float(
+            "ATTN_API_PRICING_VOL_MULTIPLIER",
+            defaults.volatility_haircut_multiplier,
+        ),
+        max_haircut_bps: float("ATTN_API_PRICING_MAX_HAIRCUT_BPS", defaults.max_haircut_bps),
+        sell_spread_bps: float("ATTN_API_PRICING_SELL_SPREAD_BPS", defaults.sell_spread_bps),
+        buyback_spread_bps: float(
+            "ATTN_API_PRICING_BUYBACK_SPREAD_BPS",
+            defaults.buyback_spread_bps,
+        ),
+        inventory_spread_bps: float(
+            "ATTN_API_PRICING_INVENTORY_SPREAD_BPS",
+            defaults.inventory_spread_bps,
+        ),
+    }
+}
+
 fn parse_allowlist(raw: &str) -> Option<HashSet<String>> {
     let entries: HashSet<String> = raw
         .split(',')
@@ -565,11 +662,30 @@ fn parse_allowlist(raw: &str) -> Option<HashSet<String>> {
 
 #[derive(Debug)]
 enum ApiError {
-    BadRequest { code: &'static str, message: String },
-    Forbidden { code: &'static str, message: String },
-    NotFound { resource: &'static str, id: String },
-    ServiceUnavailable { code: &'static str, message: String },
-    Upstream { code: &'static str, message: String },
+    BadRequest {
+        code: &'static str,
+        message: String,
+    },
+    Forbidden {
+        code: &'static str,
+        message: String,
+    },
+    NotFound {
+        resource: &'static str,
+        id: String,
+    },
+    ServiceUnavailable {
+        code: &'static str,
+        message: String,
+    },
+    Upstream {
+        code: &'static str,
+        message: String,
+    },
+    Program {
+        code: &'static str,
+        error: ProgramError,
+    },
     Internal(anyhow::Error),
 }
 
@@ -608,6 +724,12 @@ impl ApiError {
             message: message.into(),
         }
     }
+
+    /// An upstream failure that decoded to a program error: the transaction reached
+    /// the cluster and was rejected, so retrying unchanged will not help.
+    fn program(code: &'static str, error: ProgramError) -> Self {
+        Self::Program { code, error }
+    }
 }
 
 impl From<anyhow::Error> for ApiError {
@@ -659,6 +781,15 @@ impl IntoResponse for ApiError {
                 }));
                 (StatusCode::BAD_GATEWAY, body).into_response()
             }
+            ApiError::Program { code, error } => {
+                let body = Json(serde_json::json!({
+                    "error": "program_error",
+                    "code": code,
+                    "message": error.to_string(),
+                    "program_error": error,
+                }));
+                (StatusCode::BAD_GATEWAY, body).into_response()
+            }
             ApiError::Internal(err) => {
                 error!(error = ?err, "internal server error");
                 let body = Json(serde_json::json!({
@@ -671,6 +802,13 @@ impl IntoResponse for ApiError {
     }
 }
 
+fn squads_create_error(err: &anyhow::Error) -> ApiError {
+    match ProgramError::find(err) {
+        Some(error) => ApiError::program("squads_create_failed", error),
+        None => ApiError::upstream("squads_create_failed", UPSTREAM_ERROR_MESSAGE),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct RewardsQuery {
     cursor: Option<String>,
@@ -683,6 +821,160 @@ struct RewardsListResponse {
     next_cursor: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GovernanceProposalsQuery {
+    creator_vault: Option<String>,
+    multisig: Option<String>,
+    status: Option<String>,
+    limit: Option<u16>,
+}
+
+impl GovernanceProposalsQuery {
+    fn filter(self) -> Result<ProposalFilter, ApiError> {
+        if let Some(status) = self.status.as_deref() {
+            if !PROPOSAL_STATUSES.contains(&status) {
+                return Err(ApiError::bad_request(
+                    "invalid_status",
+                    format!("status must be one of {}", PROPOSAL_STATUSES.join(", ")),
+                ));
+            }
+        }
+        Ok(ProposalFilter {
+            creator_vault: self.creator_vault,
+            multisig: self.multisig,
+            status: self.status,
+            limit: self.limit.unwrap_or(50),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct GovernanceProposalsResponse {
+    proposals: Vec<GovernanceProposal>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlertsQuery {
+    creator_vault: Option<String>,
+    kind: Option<String>,
+    /// Minimum severity.
+    severity: Option<String>,
+    /// `open` (default) or `all`.
+    status: Option<String>,
+    limit: Option<u16>,
+}
+
+impl AlertsQuery {
+    fn filter(self) -> Result<AlertFilter, ApiError> {
+        if let Some(kind) = self.kind.as_deref() {
+            if !ALERT_KINDS.contains(&kind) {
+                return Err(ApiError::bad_request(
+                    "invalid_kind",
+                    format!("kind must be one of {}", ALERT_KINDS.join(", ")),
+                ));
+            }
+        }
+        if let Some(severity) = self.severity.as_deref() {
+            if !ALERT_SEVERITIES.contains(&severity) {
+                return Err(ApiError::bad_request(
+                    "invalid_severity",
+                    format!("severity must be one of {}", ALERT_SEVERITIES.join(", ")),
+                ));
+            }
+        }
+        let include_resolved = match self.status.as_deref() {
+            None | Some("open") => false,
+            Some("all") => true,
+            Some(_) => {
+                return Err(ApiError::bad_request(
+                    "invalid_status",
+                    "status must be one of open, all",
+                ))
+            }
+        };
+        Ok(AlertFilter {
+            creator_vault: self.creator_vault,
+            kind: self.kind,
+            severity: self.severity,
+            include_resolved,
+            limit: self.limit.unwrap_or(50),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AlertsResponse {
+    alerts: Vec<Alert>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    interval: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+}
+
+impl HistoryQuery {
+    /// Resolves the requested window. `from`/`to` accept RFC 3339 or unix
+    /// seconds; `to` defaults to now and `from` to 168 buckets before `to`.
+    fn range(&self, now: DateTime<Utc>) -> Result<HistoryRange, ApiError> {
+        let interval = match self.interval.as_deref() {
+            Some(raw) => raw.parse::<HistoryInterval>().map_err(|_| {
+                ApiError::bad_request(
+                    "invalid_interval",
+                    "interval must be one of 5m, 15m, 1h, 4h, 1d",
+                )
+            })?,
+            None => HistoryInterval::OneHour,
+        };
+        let to = match self.to.as_deref() {
+            Some(raw) => parse_history_timestamp(raw, "to")?,
+            None => now,
+        };
+        let from = match self.from.as_deref() {
+            Some(raw) => parse_history_timestamp(raw, "from")?,
+            None => to - Duration::seconds(interval.seconds() * HISTORY_DEFAULT_BUCKETS),
+        };
+        if from >= to {
+            return Err(ApiError::bad_request(
+                "invalid_range",
+                "from must be earlier than to",
+            ));
+        }
+        let range = HistoryRange { interval, from, to };
+        if range.bucket_count() > HISTORY_MAX_BUCKETS {
+            return Err(ApiError::bad_request(
+                "range_too_large",
+                format!(
+                    "requested range exceeds {HISTORY_MAX_BUCKETS} buckets; use a wider interval"
+                ),
+            ));
+        }
+        Ok(range)
+    }
+}
+
+fn parse_history_timestamp(raw: &str, field: &str) -> Result<DateTime<Utc>, ApiError> {
+    let parsed = match raw.parse::<i64>() {
+        Ok(secs) => DateTime::<Utc>::from_timestamp(secs, 0),
+        Err(_) => DateTime::parse_from_rfc3339(raw)
+            .ok()
+            .map(|value| value.with_timezone(&Utc)),
+    };
+    parsed.ok_or_else(|| {
+        ApiError::bad_request(
+            "invalid_timestamp",
+            format!("{field} must be an RFC 3339 timestamp or unix seconds"),
+        )
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamQuery {
+    #[serde(default)]
+    topics: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct QuoteQuery {
     size: f64,
@@ -780,6 +1072,8 @@ struct CreateSquadsSafeResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     status_sync_error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    creation_signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     status_last_response_hash: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     creator_vault: Option<String>,
@@ -845,6 +1139,60 @@ struct LinkGovernanceRequest {
     attn_signature: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct CreateWebhookRequest {
+    url: String,
+    /// Empty subscribes to every event type.
+    #[serde(default)]
+    event_types: Vec<String>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateWebhookRequest {
+    url: Option<String>,
+    event_types: Option<Vec<String>>,
+    description: Option<String>,
+    active: Option<bool>,
+    #[serde(default)]
+    rotate_secret: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReplayWebhookRequest {
+    event_id: Option<i64>,
+    since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WebhookDeliveriesQuery {
+    limit: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct WebhookResponse {
+    #[serde(flatten)]
+    endpoint: WebhookEndpoint,
+    /// Signing secret, present only on creation and rotation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WebhooksResponse {
+    webhooks: Vec<WebhookEndpoint>,
+}
+
+#[derive(Serialize)]
+struct WebhookDeliveriesResponse {
+    deliveries: Vec<WebhookDelivery>,
+}
+
+#[derive(Serialize)]
+struct WebhookReplayResponse {
+    queued: u64,
+}
+
 fn build_router(state: AppState) -> Router {
     Router::new()
         .route("/metrics", get(metrics_endpoint))
@@ -855,11 +1203,26 @@ fn build_router(state: AppState) -> Router {
         .route("/v1/markets", get(list_markets))
         .route("/v1/markets/:market", get(get_market))
         .route("/v1/markets/:market/yt-quote", get(get_market_yt_quote))
+        .route("/v1/markets/:market/history", get(get_market_history))
         .route("/v1/portfolio/:wallet", get(get_portfolio))
         .route("/v1/attnusd", get(get_attnusd))
+        .route("/v1/attnusd/history", get(get_attnusd_history))
         .route("/v1/rewards", get(list_rewards))
         .route("/v1/rewards/:pool", get(get_rewards_pool))
+        .route("/v1/rewards/:pool/history", get(get_rewards_pool_history))
         .route("/v1/governance", get(get_governance))
+        .route("/v1/governance/proposals", get(list_governance_proposals))
+        .route("/v1/alerts", get(list_alerts))
+        .route("/v1/stream", get(get_stream))
+        .route("/v1/webhooks", get(list_webhooks).post(create_webhook))
+        .route(
+            "/v1/webhooks/:id",
+            get(get_webhook)
+                .patch(update_webhook)
+                .delete(delete_webhook),
+        )
+        .route("/v1/webhooks/:id/deliveries", get(list_webhook_deliveries))
+        .route("/v1/webhooks/:id/replay", post(replay_webhook))
         .route("/v1/rfq/yt-sell", post(post_rfq_sell))
         .route("/v1/rfq/yt-buyback", post(post_rfq_buyback))
         .route(
@@ -1130,6 +1493,34 @@ where
             .map(|addr| !addr.is_empty())
             .unwrap_or(false);
     if ready {
+        if let Some(address) = update.safe_address.as_ref() {
+            let failure = match service.verify_safe_account(&job, address).await {
+                Ok(Some(check)) => check.failure_message(),
+                Ok(None) => None,
+                Err(err) => Some(format!("safe verification unavailable: {err}")),
+            };
+            if let Some(message) = failure {
+                repo.mark_error(
+                    job.id,
+                    &message,
+                    Duration::seconds(STATUS_SYNC_ERROR_BACKOFF_SECS),
+                )
+                .await?;
+                metrics::counter!(
+                    "squads_status_sync_total",
+                    "result" => "verification_failed",
+                    "cluster" => job.cluster.clone()
+                )
+                .increment(1);
+                warn!(
+                    request_id = %job.id,
+                    safe = %address,
+                    error = %message,
+                    "safe failed on-chain verification"
+                );
+                return Ok(());
+            }
+        }
         repo.mark_ready(job.id, &update).await?;
         metrics::counter!(
             "squads_status_sync_total",
@@ -1143,22 +1534,6 @@ where
             "result" => "ready"
         )
         .record(start.elapsed().as_secs_f64());
-        if let Some(address) = update.safe_address.as_ref() {
-            match service.verify_safe_account(&job.cluster, address).await {
-                Ok(Some(false)) => warn!(
-                    request_id = %job.id,
-                    safe = %address,
-                    "safe not yet visible on rpc after ready"
-                ),
-                Err(err) => warn!(
-                    request_id = %job.id,
-                    safe = %address,
-                    error = %err,
-                    "rpc verification for ready safe failed"
-                ),
-                _ => {}
-            }
-        }
         info!(
             request_id = %job.id,
             status = status_value,
@@ -1265,8 +1640,16 @@ where
     };
     match service.retry_create_safe(input).await {
         Ok(result) => {
+            let verification_error = service
+                .safe_verification_error(&prepared, &result.safe_address)
+                .await;
             let stored = repo
-                .store_retry_submission(prepared.id, &result, backoff)
+                .store_retry_submission(
+                    prepared.id,
+                    &result,
+                    backoff,
+                    verification_error.as_deref(),
+                )
                 .await?;
             let outcome = if stored.status == SafeStatus::Ready {
                 "ready"
@@ -1317,13 +1700,25 @@ async fn main() -> Result<()> {
         advance_limits,
         quote_ttl_secs,
         rfq_lp_wallet,
+        pricing,
         squads,
         security,
     } = config;
-    let (store, squads_repo): (DynStore, Option<SquadsSafeRepository>) = match data_mode {
+    let stream_hub = StreamHub::default();
+    let (store, squads_repo, quote_book, webhook_store): (
+        DynStore,
+        Option<SquadsSafeRepository>,
+        Arc<dyn QuoteBook>,
+        Arc<dyn WebhookStore>,
+    ) = match data_mode {
         DataMode::Mock => {
             warn!("ATTN_API_DATA_MODE=mock; serving static dataset");
-            (mock_store(), None)
+            (
+                mock_store(),
+                None,
+                Arc::new(InMemoryQuoteBook::default()),
+                Arc::new(InMemoryWebhookStore::default()),
+            )
         }
         DataMode::Postgres {
             database_url,
@@ -1334,16 +1729,26 @@ async fn main() -> Result<()> {
                 warn!(error = ?err, "failed to run migrations");
             }
             let repo = SquadsSafeRepository::new(pool.clone());
-            (Arc::new(SqlxStore::new(pool)), Some(repo))
+            let quote_book = Arc::new(PgQuoteBook::new(pool.clone()));
+            let webhook_store = Arc::new(PgWebhookStore::new(pool.clone()));
+            stream::spawn_listener(pool.clone(), stream_hub.clone());
+            (
+                Arc::new(SqlxStore::new(pool)),
+                Some(repo),
+                quote_book,
+                webhook_store,
+            )
         }
     };
     let ttl_secs = quote_ttl_secs.min(i64::MAX as u64) as i64;
     let quote_ttl = Duration::seconds(ttl_secs);
-    let quote_service = QuoteService::new(
+    let quote_service = QuoteService::with_book(
         cluster.clone(),
         advance_limits.clone(),
         quote_ttl,
         rfq_lp_wallet.clone(),
+        quote_book,
+        pricing,
     );
     let squads_service = if let Some(config) = squads {
         Some(
@@ -1369,7 +1774,17 @@ async fn main() -> Result<()> {
         squads_repo,
         security: security_state,
         metrics: metrics_handle.clone(),
+        stream: stream_hub,
+        webhooks: webhook_store,
+        webhook_allow_private: parse_webhook_allow_private(),
+        snapshot_interval: parse_snapshot_interval(),
     };
+    WebhookDispatcher::new(
+        state.webhooks.clone(),
+        parse_webhook_interval(),
+        state.webhook_allow_private,
+    )?
+    .spawn();
     if let Some(service) = state.squads.as_ref() {
         info!(
             config_digest = %service.config_digest(),
@@ -1407,6 +1822,36 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+fn parse_webhook_interval() -> StdDuration {
+    let secs = env::var("ATTN_API_WEBHOOK_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(5)
+        .max(1);
+    StdDuration::from_secs(secs)
+}
+
+/// Indexer snapshot cadence, the finest market history bucket, from
+/// `ATTN_API_SNAPSHOT_INTERVAL_SECS`. Set it to the indexer's
+/// `ATTN_INDEXER_SNAPSHOT_INTERVAL_SECS`; at least 60s like the indexer.
+fn parse_snapshot_interval() -> StdDuration {
+    let secs = env::var("ATTN_API_SNAPSHOT_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL_SECS)
+        .max(60);
+    StdDuration::from_secs(secs)
+}
+
+fn parse_webhook_allow_private() -> bool {
+    let allow = env::var("ATTN_API_WEBHOOK_ALLOW_PRIVATE")
+        .is_ok_and(|raw| matches!(raw.as_str(), "1" | "true"));
+    if allow {
+        warn!("ATTN_API_WEBHOOK_ALLOW_PRIVATE set; webhooks may target private addresses");
+    }
+    allow
+}
+
 async fn health() -> Json<HealthResponse> {
     Json(HealthResponse { status: "ok" })
 }
@@ -1509,6 +1954,13 @@ async fn get_market_yt_quote(
         ));
     }
 
+    let history_start = state.quotes.pricing().history_start(Utc::now());
+    let fee_history = state.store.fee_history(&market, history_start).await?;
+    let inputs = MarketInputs {
+        fallback_apr: detail.summary.implied_apy,
+        tvl_sol: detail.tvl_sol,
+        fee_history: &fee_history,
+    };
     let quote = state
         .quotes
         .get_or_create_quote(
@@ -1516,7 +1968,7 @@ async fn get_market_yt_quote(
             query.size,
             query.side,
             detail.summary.maturity_ts,
-            detail.summary.implied_apy,
+            &inputs,
         )
         .await?;
 
@@ -1532,6 +1984,35 @@ async fn get_market_yt_quote(
     Ok(apply_cache_headers(response, &etag))
 }
 
+async fn get_market_history(
+    Path(market): Path<String>,
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let range = query.range(Utc::now())?;
+    let min_secs = state.snapshot_interval.as_secs() as i64;
+    if range.interval.seconds() < min_secs {
+        return Err(ApiError::bad_request(
+            "interval_too_fine",
+            format!("market history is sampled every {min_secs}s; use a wider interval"),
+        ));
+    }
+    let history = state
+        .store
+        .market_history(&market, &range)
+        .await?
+        .ok_or_else(|| ApiError::not_found("market", market.clone()))?;
+    let etag = etag_for(&history);
+    if let Some(value) = headers.get(IF_NONE_MATCH) {
+        if header_matches_if_none(value, &etag) {
+            return Ok(StatusCode::NOT_MODIFIED.into_response());
+        }
+    }
+    let response = Json(history).into_response();
+    Ok(apply_cache_headers(response, &etag))
+}
+
 async fn get_portfolio(
     Path(wallet): Path<String>,
     State(state): State<AppState>,
@@ -1567,6 +2048,23 @@ async fn get_attnusd(
     Ok(apply_cache_headers(response, &etag))
 }
 
+async fn get_attnusd_history(
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let range = query.range(Utc::now())?;
+    let history = state.store.attnusd_history(&range).await?;
+    let etag = etag_for(&history);
+    if let Some(value) = headers.get(IF_NONE_MATCH) {
+        if header_matches_if_none(value, &etag) {
+            return Ok(StatusCode::NOT_MODIFIED.into_response());
+        }
+    }
+    let response = Json(history).into_response();
+    Ok(apply_cache_headers(response, &etag))
+}
+
 async fn list_rewards(
     State(state): State<AppState>,
     Query(query): Query<RewardsQuery>,
@@ -1618,6 +2116,28 @@ async fn get_rewards_pool(
     Ok(response)
 }
 
+async fn get_rewards_pool_history(
+    Path(pool): Path<String>,
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let range = query.range(Utc::now())?;
+    let history = state
+        .store
+        .rewards_pool_history(&pool, &range)
+        .await?
+        .ok_or_else(|| ApiError::not_found("rewards_pool", pool.clone()))?;
+    let etag = etag_for(&history);
+    if let Some(value) = headers.get(IF_NONE_MATCH) {
+        if header_matches_if_none(value, &etag) {
+            return Ok(StatusCode::NOT_MODIFIED.into_response());
+        }
+    }
+    let response = Json(history).into_response();
+    Ok(apply_cache_headers(response, &etag))
+}
+
 async fn get_governance(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -1633,6 +2153,62 @@ async fn get_governance(
     Ok(apply_cache_headers(response, &etag))
 }
 
+async fn list_governance_proposals(
+    State(state): State<AppState>,
+    Query(query): Query<GovernanceProposalsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let filter = query.filter()?;
+    let proposals = state.store.governance_proposals(&filter).await?;
+    let body = GovernanceProposalsResponse { proposals };
+    let etag = etag_for(&body);
+    if let Some(value) = headers.get(IF_NONE_MATCH) {
+        if header_matches_if_none(value, &etag) {
+            return Ok(StatusCode::NOT_MODIFIED.into_response());
+        }
+    }
+    let response = Json(body).into_response();
+    Ok(apply_cache_headers(response, &etag))
+}
+
+async fn list_alerts(
+    State(state): State<AppState>,
+    Query(query): Query<AlertsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let filter = query.filter()?;
+    let alerts = state.store.alerts(&filter).await?;
+    let body = AlertsResponse { alerts };
+    let etag = etag_for(&body);
+    if let Some(value) = headers.get(IF_NONE_MATCH) {
+        if header_matches_if_none(value, &etag) {
+            return Ok(StatusCode::NOT_MODIFIED.into_response());
+        }
+    }
+    let response = Json(body).into_response();
+    Ok(apply_cache_headers(response, &etag))
+}
+
+async fn get_stream(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
+) -> Result<Response, ApiError> {
+    let topics = stream::parse_topics(&query.topics)
+        .map_err(|message| ApiError::bad_request("invalid_topic", message))?;
+    if topics.iter().any(Topic::is_private) {
+        state
+            .security
+            .authenticate(&headers, Some(remote_addr.ip()))
+            .map_err(|err| ApiError::forbidden("auth_failed", err.to_string()))?;
+    }
+    let events = state.stream.events(topics);
+    Ok(Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
 async fn post_rfq_sell(
     State(state): State<AppState>,
     Json(payload): Json<RfqSellRequest>,
@@ -2003,33 +2579,16 @@ async fn create_squads_safe(
                     backoff,
                 )
                 .await;
-            return Err(ApiError::upstream(
-                "squads_create_failed",
-                UPSTREAM_ERROR_MESSAGE,
-            ));
+            return Err(squads_create_error(&err));
         }
     };
 
+    let verification_error =
+        safe_verification_error(service, &pending, &created.safe_address).await;
     let stored = repo
-        .update_submission(pending.id, &created, backoff)
+        .update_submission(pending.id, &created, backoff, verification_error.as_deref())
         .await
         .map_err(ApiError::from)?;
-    if let Some(address) = stored.safe_address.as_ref() {
-        match service.verify_safe_account(&stored.cluster, address).await {
-            Ok(Some(false)) => warn!(
-                request_id = %stored.id,
-                safe = %address,
-                "safe account not yet present on rpc"
-            ),
-            Err(err) => warn!(
-                request_id = %stored.id,
-                safe = %address,
-                error = %err,
-                "failed to verify safe account"
-            ),
-            _ => {}
-        }
-    }
     let status_label = stored.status;
     if status_label == SafeStatus::Ready {
         metrics::counter!(
@@ -2219,6 +2778,31 @@ async fn get_squads_safe_by_creator(
     Ok(Json(response))
 }
 
+/// Checks the multisig at `safe_address` before `record` may become `ready`
+/// and returns why it may not: the account is missing or does not match, or
+/// the RPC check itself failed.
+async fn safe_verification_error(
+    service: &SquadsService,
+    record: &SafeRequestRecord,
+    safe_address: &str,
+) -> Option<String> {
+    if safe_address.is_empty() {
+        return None;
+    }
+    let message = match service.verify_safe_account(record, safe_address).await {
+        Ok(Some(check)) => check.failure_message()?,
+        Ok(None) => return None,
+        Err(err) => format!("safe verification unavailable: {err}"),
+    };
+    warn!(
+        request_id = %record.id,
+        safe = %safe_address,
+        error = %message,
+        "safe failed on-chain verification"
+    );
+    Some(message)
+}
+
 async fn import_squads_safe(
     State(state): State<AppState>,
     ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
@@ -2334,22 +2918,37 @@ async fn import_squads_safe(
         .clone()
         .unwrap_or_else(|| "external".to_string());
 
-    let record = repo
-        .upsert_imported_safe(ImportSafeInput {
-            creator_wallet,
-            attn_wallet,
-            cluster,
-            threshold,
-            safe_address,
-            members,
-            safe_name: payload.safe_name.clone(),
-            contact_email: payload.contact_email.clone(),
-            note: payload.note.clone(),
-            transaction_url: payload.transaction_url.clone(),
-            status_url: payload.status_url.clone(),
-            import_source,
-            import_metadata: payload.import_metadata.clone(),
-        })
+    let input = ImportSafeInput {
+        creator_wallet,
+        attn_wallet,
+        cluster,
+        threshold,
+        safe_address,
+        members,
+        safe_name: payload.safe_name.clone(),
+        contact_email: payload.contact_email.clone(),
+        note: payload.note.clone(),
+        transaction_url: payload.transaction_url.clone(),
+        status_url: payload.status_url.clone(),
+        import_source,
+        import_metadata: payload.import_metadata.clone(),
+    };
+    if let Some(service) = service {
+        let check = service.verify_imported_safe(&input).await.map_err(|err| {
+            ApiError::service_unavailable("safe_verification_unavailable", err.to_string())
+        })?;
+        if let Some(message) = check.and_then(|check| check.failure_message()) {
+            warn!(
+                creator = %input.creator_wallet,
+                safe = %input.safe_address,
+                error = %message,
+                "imported safe failed on-chain verification"
+            );
+            return Err(ApiError::bad_request("safe_verification_failed", message));
+        }
+    }
+    let record = repo
+        .upsert_imported_safe(input)
         .await
         .map_err(ApiError::from)?;
 
@@ -2479,32 +3078,15 @@ async fn resubmit_squads_safe(
                 )
                 .await;
             error!(request_id = %pending.id, error = %err, "squads safe resubmit failed");
-            return Err(ApiError::upstream(
-                "squads_create_failed",
-                UPSTREAM_ERROR_MESSAGE,
-            ));
+            return Err(squads_create_error(&err));
         }
     };
+    let verification_error =
+        safe_verification_error(service, &pending, &created.safe_address).await;
     let stored = repo
-        .update_submission(pending.id, &created, backoff)
+        .update_submission(pending.id, &created, backoff, verification_error.as_deref())
         .await
         .map_err(ApiError::from)?;
-    if let Some(address) = stored.safe_address.as_ref() {
-        match service.verify_safe_account(&stored.cluster, address).await {
-            Ok(Some(false)) => warn!(
-                request_id = %stored.id,
-                safe = %address,
-                "safe account not yet present on rpc"
-            ),
-            Err(err) => warn!(
-                request_id = %stored.id,
-                safe = %address,
-                error = %err,
-                "failed to verify safe account"
-            ),
-            _ => {}
-        }
-    }
     let response = record_to_response(&stored, Some(service));
     Ok(Json(response))
 }
@@ -2658,25 +3240,251 @@ async fn metrics_endpoint(State(state): State<AppState>) -> Result<Response, Api
     Ok(response)
 }
 
+fn authenticate_webhook_client(
+    state: &AppState,
+    remote_addr: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<String, ApiError> {
+    let auth = state
+        .security
+        .authenticate(headers, Some(remote_addr.ip()))
+        .map_err(|err| ApiError::forbidden("auth_failed", err.to_string()))?;
+    Ok(auth.api_key_id)
+}
+
+/// Endpoints must be HTTPS on a public host; see `webhook::check_url`.
+fn validate_webhook_url(raw: &str, allow_private: bool) -> Result<String, ApiError> {
+    webhook::check_url(raw, allow_private)
+        .map(|url| url.to_string())
+        .map_err(|message| ApiError::bad_request("invalid_url", message))
+}
+
+fn validate_event_types(event_types: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let mut validated: Vec<String> = Vec::with_capacity(event_types.len());
+    for event_type in event_types {
+        if !WEBHOOK_EVENT_TYPES.contains(&event_type.as_str()) {
+            return Err(ApiError::bad_request(
+                "invalid_event_type",
+                format!(
+                    "event_types must be drawn from {}",
+                    WEBHOOK_EVENT_TYPES.join(", ")
+                ),
+            ));
+        }
+        if !validated.contains(&event_type) {
+            validated.push(event_type);
+        }
+    }
+    Ok(validated)
+}
+
+fn parse_webhook_id(id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(id)
+        .map_err(|_| ApiError::bad_request("invalid_webhook_id", "id must be a valid UUID"))
+}
+
+async fn list_webhooks(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Result<Json<WebhooksResponse>, ApiError> {
+    let api_key_id = authenticate_webhook_client(&state, remote_addr, &headers)?;
+    let webhooks = state.webhooks.list_endpoints(&api_key_id).await?;
+    Ok(Json(WebhooksResponse { webhooks }))
+}
+
+async fn create_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<WebhookResponse>), ApiError> {
+    let api_key_id = authenticate_webhook_client(&state, remote_addr, &headers)?;
+    let endpoint = NewWebhookEndpoint {
+        url: validate_webhook_url(&payload.url, state.webhook_allow_private)?,
+        event_types: validate_event_types(payload.event_types)?,
+        description: payload.description.filter(|value| !value.trim().is_empty()),
+    };
+    if state.webhooks.list_endpoints(&api_key_id).await?.len() >= MAX_ENDPOINTS_PER_KEY {
+        return Err(ApiError::bad_request(
+            "webhook_limit_reached",
+            format!("at most {MAX_ENDPOINTS_PER_KEY} webhooks per API key"),
+        ));
+    }
+    let secret = generate_secret();
+    let endpoint = state
+        .webhooks
+        .create_endpoint(&api_key_id, endpoint, secret.clone())
+        .await?;
+    info!(webhook = %endpoint.id, api_key = %api_key_id, "webhook registered");
+    Ok((
+        StatusCode::CREATED,
+        Json(WebhookResponse {
+            endpoint,
+            secret: Some(secret),
+        }),
+    ))
+}
+
+async fn get_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let api_key_id = authenticate_webhook_client(&state, remote_addr, &headers)?;
+    let endpoint = state
+        .webhooks
+        .endpoint(&api_key_id, parse_webhook_id(&id)?)
+        .await?
+        .ok_or_else(|| ApiError::not_found("webhook", id.clone()))?;
+    Ok(Json(WebhookResponse {
+        endpoint,
+        secret: None,
+    }))
+}
+
+async fn update_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let api_key_id = authenticate_webhook_client(&state, remote_addr, &headers)?;
+    let webhook_id = parse_webhook_id(&id)?;
+    let secret = payload.rotate_secret.then(generate_secret);
+    let update = WebhookEndpointUpdate {
+        url: payload
+            .url
+            .as_deref()
+            .map(|url| validate_webhook_url(url, state.webhook_allow_private))
+            .transpose()?,
+        event_types: payload.event_types.map(validate_event_types).transpose()?,
+        description: payload.description,
+        active: payload.active,
+        secret: secret.clone(),
+    };
+    let endpoint = state
+        .webhooks
+        .update_endpoint(&api_key_id, webhook_id, update)
+        .await?
+        .ok_or_else(|| ApiError::not_found("webhook", id.clone()))?;
+    Ok(Json(WebhookResponse { endpoint, secret }))
+}
+
+async fn delete_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let api_key_id = authenticate_webhook_client(&state, remote_addr, &headers)?;
+    if !state
+        .webhooks
+        .delete_endpoint(&api_key_id, parse_webhook_id(&id)?)
+        .await?
+    {
+        return Err(ApiError::not_found("webhook", id));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(query): Query<WebhookDeliveriesQuery>,
+) -> Result<Json<WebhookDeliveriesResponse>, ApiError> {
+    let api_key_id = authenticate_webhook_client(&state, remote_addr, &headers)?;
+    let endpoint = state
+        .webhooks
+        .endpoint(&api_key_id, parse_webhook_id(&id)?)
+        .await?
+        .ok_or_else(|| ApiError::not_found("webhook", id.clone()))?;
+    let limit = i64::from(query.limit.unwrap_or(50).clamp(1, 200));
+    let deliveries = state.webhooks.deliveries(endpoint.id, limit).await?;
+    Ok(Json(WebhookDeliveriesResponse { deliveries }))
+}
+
+async fn replay_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(payload): Json<ReplayWebhookRequest>,
+) -> Result<(StatusCode, Json<WebhookReplayResponse>), ApiError> {
+    let api_key_id = authenticate_webhook_client(&state, remote_addr, &headers)?;
+    let target = match (payload.event_id, payload.since) {
+        (Some(event_id), None) => ReplayTarget::Event(event_id),
+        (None, Some(since)) => ReplayTarget::Since(since),
+        _ => {
+            return Err(ApiError::bad_request(
+                "invalid_replay",
+                "provide exactly one of event_id or since",
+            ))
+        }
+    };
+    let endpoint = state
+        .webhooks
+        .endpoint(&api_key_id, parse_webhook_id(&id)?)
+        .await?
+        .ok_or_else(|| ApiError::not_found("webhook", id.clone()))?;
+    let queued = state.webhooks.replay(&endpoint, target).await?;
+    info!(webhook = %endpoint.id, queued, "webhook replay queued");
+    Ok((StatusCode::ACCEPTED, Json(WebhookReplayResponse { queued })))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use attn_indexer::Overview;
+    use attn_client::squads::{Permissions, SQUADS_PROGRAM_ID};
+    use attn_client::tx::FeeStrategy;
+    use attn_indexer::store::MockData;
+    use attn_indexer::{MockStore, Overview};
     use axum::body::Body;
     use axum::http::{Method, Request};
+    use base64::Engine;
     use chrono::Duration;
     use http_body_util::BodyExt;
+    use httpmock::prelude::{MockServer, POST};
     use reqwest::Client;
     use serde_json::{json, Value};
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::{Keypair, Signer};
     use std::collections::VecDeque;
     use tower::ServiceExt;
 
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
+    const FIXTURE_MARKET: &str = "Market1111111111111111111111111111111111";
+    const FIXTURE_WALLET: &str = "Wallet1111111111111111111111111111111111";
+
     fn test_app() -> (Router, DynStore) {
-        let store = mock_store();
-        let quote_service = QuoteService::new(
+        test_app_with_book(Arc::new(InMemoryQuoteBook::default()))
+    }
+
+    fn test_app_with_book(book: Arc<dyn QuoteBook>) -> (Router, DynStore) {
+        test_app_with_parts(book, StreamHub::default())
+    }
+
+    fn test_app_with_parts(book: Arc<dyn QuoteBook>, stream: StreamHub) -> (Router, DynStore) {
+        let (state, store) = test_state(book, stream);
+        (build_router(state), store)
+    }
+
+    fn test_state(book: Arc<dyn QuoteBook>, stream: StreamHub) -> (AppState, DynStore) {
+        test_state_with_store(book, stream, mock_store())
+    }
+
+    fn test_state_with_store(
+        book: Arc<dyn QuoteBook>,
+        stream: StreamHub,
+        store: DynStore,
+    ) -> (AppState, DynStore) {
+        let quote_service = QuoteService::with_book(
             Cluster::new("devnet"),
             AdvanceLimits {
                 per_wallet_usdc: 5_000.0,
@@ -2685,6 +3493,8 @@ mod tests {
             },
             Duration::seconds(60),
             "LpWallet11111111111111111111111111111111",
+            book,
+            PricingConfig::default(),
         );
         let mut security_config = default_security_config();
         security_config
@@ -2698,8 +3508,14 @@ mod tests {
             squads_repo: None,
             security,
             metrics: None,
+            stream,
+            webhooks: Arc::new(InMemoryWebhookStore::default()),
+            webhook_allow_private: false,
+            snapshot_interval: StdDuration::from_secs(
+                attn_indexer::snapshots::DEFAULT_SNAPSHOT_INTERVAL_SECS,
+            ),
         };
-        (build_router(state), store)
+        (state, store)
     }
 
     #[tokio::test]
@@ -2809,6 +3625,397 @@ mod tests {
         assert!(payload["built_at_unix"].is_number());
     }
 
+    async fn get_json(app: &Router, uri: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .uri(uri)
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn governance_proposals_filter_and_validate() {
+        let (app, _) = test_app();
+        let (status, payload) = get_json(
+            &app,
+            "/v1/governance/proposals?creator_vault=CreatorVault1111111111111111111111111111111&status=active",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let proposals = payload["proposals"].as_array().unwrap();
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0]["approvals"], 1);
+        assert_eq!(proposals[0]["threshold"], 2);
+        assert_eq!(proposals[0]["actions"][0]["instruction"], "set_pause");
+
+        let (status, payload) = get_json(&app, "/v1/governance/proposals?status=executed").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(payload["proposals"].as_array().unwrap().is_empty());
+
+        let (status, payload) = get_json(&app, "/v1/governance/proposals?status=pending").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_status");
+    }
+
+    #[tokio::test]
+    async fn alerts_filter_and_validate() {
+        let (app, _) = test_app();
+        let (status, payload) = get_json(&app, "/v1/alerts").await;
+        assert_eq!(status, StatusCode::OK);
+        let alerts = payload["alerts"].as_array().unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0]["kind"], "fee_drop");
+        assert_eq!(alerts[0]["severity"], "critical");
+        assert_eq!(alerts[0]["priority"], true);
+
+        let (status, payload) = get_json(&app, "/v1/alerts?status=all&severity=warning").await;
+        assert_eq!(status, StatusCode::OK);
+        let alerts = payload["alerts"].as_array().unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts[1]["resolved_at"].is_string());
+
+        let (status, payload) =
+            get_json(&app, "/v1/alerts?status=all&kind=fee_recipient_rerouted").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(payload["alerts"].as_array().unwrap().len(), 1);
+
+        let (status, payload) = get_json(&app, "/v1/alerts?severity=urgent").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_severity");
+        let (status, payload) = get_json(&app, "/v1/alerts?kind=outage").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_kind");
+        let (status, payload) = get_json(&app, "/v1/alerts?status=closed").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_status");
+    }
+
+    async fn send_authed(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-api-key", "secret")
+            .header("x-attn-client", "attn-dapp")
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        let body = match body {
+            Some(body) => {
+                request = request.header("content-type", "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+        let response = app
+            .clone()
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn webhooks_crud_and_replay() {
+        let (app, _) = test_app();
+        let (status, payload) = send_authed(
+            &app,
+            Method::POST,
+            "/v1/webhooks",
+            Some(json!({
+                "url": "https://hooks.example.com/attn",
+                "event_types": ["rfq.filled", "rfq.filled", "vault.lock_changed"],
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(payload["secret"].as_str().unwrap().starts_with("whsec_"));
+        assert_eq!(
+            payload["event_types"],
+            json!(["rfq.filled", "vault.lock_changed"])
+        );
+        let id = payload["id"].as_str().unwrap().to_string();
+
+        let (status, payload) = send_authed(&app, Method::GET, "/v1/webhooks", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let webhooks = payload["webhooks"].as_array().unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert!(webhooks[0].get("secret").is_none());
+
+        let uri = format!("/v1/webhooks/{id}");
+        let (status, payload) = send_authed(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(json!({ "active": false, "rotate_secret": true })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(payload["active"], false);
+        assert!(payload["secret"].is_string());
+
+        let (status, payload) = send_authed(
+            &app,
+            Method::POST,
+            &format!("{uri}/replay"),
+            Some(json!({ "since": "2024-01-01T00:00:00Z" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(payload["queued"], 0);
+        let (status, payload) = send_authed(
+            &app,
+            Method::POST,
+            &format!("{uri}/replay"),
+            Some(json!({})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_replay");
+
+        let (status, payload) =
+            send_authed(&app, Method::GET, &format!("{uri}/deliveries"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(payload["deliveries"].as_array().unwrap().is_empty());
+
+        let (status, payload) = send_authed(
+            &app,
+            Method::POST,
+            "/v1/webhooks",
+            Some(json!({ "url": "http://hooks.example.com/attn" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_url");
+        for url in [
+            "https://localhost/attn",
+            "https://10.0.0.5/attn",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/attn",
+        ] {
+            let (status, payload) = send_authed(
+                &app,
+                Method::POST,
+                "/v1/webhooks",
+                Some(json!({ "url": url })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{url}");
+            assert_eq!(payload["code"], "invalid_url");
+        }
+        let (status, payload) = send_authed(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(json!({ "url": "https://192.168.1.10/attn" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_url");
+        let (status, payload) = send_authed(
+            &app,
+            Method::POST,
+            "/v1/webhooks",
+            Some(json!({ "url": "https://hooks.example.com", "event_types": ["vault.created"] })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_event_type");
+        let (status, payload) =
+            send_authed(&app, Method::GET, "/v1/webhooks/not-a-uuid", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_webhook_id");
+
+        let (status, _) = send_authed(&app, Method::DELETE, &uri, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send_authed(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/webhooks")
+                    .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn stream_rejects_invalid_topics() {
+        let (app, _) = test_app();
+        let (status, payload) = get_json(&app, "/v1/stream").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_topic");
+        let (status, _) = get_json(&app, "/v1/stream?topics=vault:abc").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn stream_delivers_matching_updates() {
+        use attn_indexer::stream::StreamUpdate;
+
+        let hub = StreamHub::default();
+        let (app, _) = test_app_with_parts(Arc::new(InMemoryQuoteBook::default()), hub.clone());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/stream?topics=market:M1,attnusd")
+                    .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/event-stream");
+
+        let update = |topic: Topic, kind: &str| StreamUpdate {
+            topic,
+            kind: kind.to_string(),
+            slot: Some(7),
+            signature: Some(format!("sig-{kind}")),
+            data: json!({}),
+        };
+        hub.publish(update(Topic::Market("M2".into()), "FeeCollected"));
+        hub.publish(update(Topic::Market("M1".into()), "FeeCollected"));
+
+        let mut body = response.into_body();
+        let frame = tokio::time::timeout(StdDuration::from_secs(1), body.frame())
+            .await
+            .expect("stream update")
+            .unwrap()
+            .unwrap();
+        let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+        assert!(text.contains("event: update"), "{text}");
+        assert!(text.contains("id: sig-FeeCollected"), "{text}");
+        assert!(text.contains(r#""topic":"market:M1""#), "{text}");
+    }
+
+    #[tokio::test]
+    async fn stream_requires_auth_for_private_topics() {
+        let (app, _) = test_app();
+        for topics in ["squads_safe:abc", "market:M1,portfolio:Wallet1"] {
+            let (status, payload) = get_json(&app, &format!("/v1/stream?topics={topics}")).await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{topics}");
+            assert_eq!(payload["code"], "auth_failed");
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/stream?topics=squads_safe:abc")
+                    .header("x-api-key", "secret")
+                    .header("x-attn-client", "attn-dapp")
+                    .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn market_history_returns_bucketed_series() {
+        let (app, _) = test_app();
+        let (status, payload) = get_json(
+            &app,
+            "/v1/markets/Market1111111111111111111111111111111111/history?interval=4h",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(payload["interval"], "4h");
+        let points = payload["points"].as_array().unwrap();
+        assert!(!points.is_empty());
+        let last = points.last().unwrap();
+        assert!(last["fee_index"]["high"].as_f64() >= last["fee_index"]["low"].as_f64());
+        assert!(last["tvl_sol"]["close"].is_number());
+
+        let (status, _) = get_json(&app, "/v1/markets/Unknown/history").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn market_history_rejects_buckets_finer_than_snapshots() {
+        let uri = "/v1/markets/Market1111111111111111111111111111111111/history?interval=5m";
+        let (app, _) = test_app();
+        let (status, _) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (mut state, _) =
+            test_state(Arc::new(InMemoryQuoteBook::default()), StreamHub::default());
+        state.snapshot_interval = StdDuration::from_secs(3_600);
+        let app = build_router(state);
+        let (status, payload) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "interval_too_fine");
+        let (status, _) = get_json(
+            &app,
+            "/v1/markets/Market1111111111111111111111111111111111/history?interval=1h",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn history_endpoints_validate_range() {
+        let (app, _) = test_app();
+        let (status, payload) = get_json(&app, "/v1/attnusd/history?interval=2h").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_interval");
+
+        let (status, payload) =
+            get_json(&app, "/v1/attnusd/history?from=1700000100&to=1700000000").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "invalid_range");
+
+        let (status, payload) = get_json(
+            &app,
+            "/v1/attnusd/history?interval=5m&from=2024-01-01T00:00:00Z&to=2024-03-01T00:00:00Z",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(payload["code"], "range_too_large");
+    }
+
+    #[tokio::test]
+    async fn attnusd_and_rewards_history_are_served() {
+        let (app, _) = test_app();
+        let (status, payload) = get_json(&app, "/v1/attnusd/history?interval=1d").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(payload["points"][0]["price_per_share"]["open"].is_number());
+
+        let (status, payload) = get_json(
+            &app,
+            "/v1/rewards/RewardsPool11111111111111111111111111111111/history?interval=1d",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(payload["points"][0]["total_staked_attnusd"]["close"].is_number());
+
+        let (status, _) = get_json(&app, "/v1/rewards/Unknown/history").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn yt_quote_endpoint_returns_cached_payload() {
         let (app, store) = test_app();
@@ -2835,6 +4042,12 @@ mod tests {
         let payload: Value = serde_json::from_slice(&bytes).unwrap();
         assert_eq!(payload["route"], "rfq");
         assert_eq!(payload["side"], "sell");
+        assert_eq!(payload["pricing"]["yield_source"], "fee_history");
+        assert_eq!(payload["pricing"]["history_samples"], 30);
+        assert!(
+            payload["price_usdc"].as_f64().unwrap()
+                < payload["pricing"]["fair_value_usdc"].as_f64().unwrap()
+        );
 
         let second = app
             .clone()
@@ -2850,10 +4063,126 @@ mod tests {
         assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
     }
 
+    /// Quote book backed by `ATTN_API_TEST_DATABASE_URL`, or `None` when no
+    /// database is available and the SQL variant of a test should be skipped.
+    async fn pg_quote_book() -> Option<Arc<dyn QuoteBook>> {
+        let url = std::env::var("ATTN_API_TEST_DATABASE_URL").ok()?;
+        let pool = connect_pool(&url, 2).await.expect("connect test database");
+        run_migrations(&pool).await.expect("run migrations");
+        Some(Arc::new(PgQuoteBook::new(pool)))
+    }
+
+    /// App over a copy of the fixture market under a random key, with a
+    /// random wallet: Postgres caps and epoch totals persist across runs
+    /// against the same database, so fixed keys would exhaust them.
+    fn pg_test_app(book: Arc<dyn QuoteBook>) -> ((Router, DynStore), String, String) {
+        let mut data = MockData::default();
+        let market_id = Keypair::new().pubkey().to_string();
+        let mut market = data.markets[FIXTURE_MARKET].clone();
+        market.summary.market = market_id.clone();
+        data.markets.insert(market_id.clone(), market);
+        let store: DynStore = Arc::new(MockStore::with_data(data));
+        let (state, store) = test_state_with_store(book, StreamHub::default(), store);
+        let wallet = Keypair::new().pubkey().to_string();
+        ((build_router(state), store), market_id, wallet)
+    }
+
     #[tokio::test]
     async fn rfq_sell_executes_and_updates_caps() {
-        let (app, store) = test_app();
+        assert_rfq_sell_executes_and_updates_caps(test_app(), FIXTURE_MARKET, FIXTURE_WALLET).await;
+    }
+
+    #[tokio::test]
+    async fn rfq_sell_executes_and_updates_caps_with_pg_book() {
+        let Some(book) = pg_quote_book().await else {
+            return;
+        };
+        let (app, market_id, wallet) = pg_test_app(book);
+        assert_rfq_sell_executes_and_updates_caps(app, &market_id, &wallet).await;
+    }
+
+    #[tokio::test]
+    async fn rfq_buyback_reduces_wallet_usage() {
+        assert_rfq_buyback_reduces_wallet_usage(test_app(), FIXTURE_MARKET, FIXTURE_WALLET).await;
+    }
+
+    #[tokio::test]
+    async fn rfq_buyback_reduces_wallet_usage_with_pg_book() {
+        let Some(book) = pg_quote_book().await else {
+            return;
+        };
+        let (app, market_id, wallet) = pg_test_app(book);
+        assert_rfq_buyback_reduces_wallet_usage(app, &market_id, &wallet).await;
+    }
+
+    #[tokio::test]
+    async fn consumed_quotes_are_rejected_across_service_instances() {
+        let book: Arc<dyn QuoteBook> = Arc::new(InMemoryQuoteBook::default());
+        let (first_app, store) = test_app_with_book(book.clone());
         let market_id = "Market1111111111111111111111111111111111";
+        let maturity = store
+            .market(market_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .summary
+            .maturity_ts;
+        let quote_url = format!(
+            "/v1/markets/{}/yt-quote?size=75&maturity={}",
+            market_id, maturity
+        );
+        let response = first_app
+            .oneshot(
+                Request::builder()
+                    .uri(&quote_url)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let quote: Value = serde_json::from_slice(&bytes).unwrap();
+        let body = json!({
+            "quote_id": quote["quote_id"],
+            "wallet": "Wallet1111111111111111111111111111111111"
+        })
+        .to_string();
+
+        let (second_app, _) = test_app_with_book(book.clone());
+        let trade = second_app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/v1/rfq/yt-sell")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(trade.status(), StatusCode::OK);
+
+        let (third_app, _) = test_app_with_book(book);
+        let replay = third_app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/v1/rfq/yt-sell")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(replay.status(), StatusCode::BAD_REQUEST);
+    }
+
+    async fn assert_rfq_sell_executes_and_updates_caps(
+        (app, store): (Router, DynStore),
+        market_id: &str,
+        wallet: &str,
+    ) {
         let maturity = store
             .market(market_id)
             .await
@@ -2897,7 +4226,7 @@ mod tests {
 
         let body = serde_json::json!({
             "quote_id": quote_id,
-            "wallet": "Wallet1111111111111111111111111111111111"
+            "wallet": wallet
         })
         .to_string();
 
@@ -2934,7 +4263,7 @@ mod tests {
                     .body(Body::from(
                         serde_json::json!({
                             "quote_id": quote_id,
-                            "wallet": "Wallet1111111111111111111111111111111111"
+                            "wallet": wallet
                         })
                         .to_string(),
                     ))
@@ -2945,11 +4274,11 @@ mod tests {
         assert_eq!(replay.status(), StatusCode::BAD_REQUEST);
     }
 
-    #[tokio::test]
-    async fn rfq_buyback_reduces_wallet_usage() {
-        let (app, store) = test_app();
-        let wallet = "Wallet1111111111111111111111111111111111";
-        let market_id = "Market1111111111111111111111111111111111";
+    async fn assert_rfq_buyback_reduces_wallet_usage(
+        (app, store): (Router, DynStore),
+        market_id: &str,
+        wallet: &str,
+    ) {
         let maturity = store
             .market(market_id)
             .await
@@ -3220,6 +4549,7 @@ mod tests {
             status_last_response: None,
             status_last_response_hash: None,
             status_sync_error: None,
+            creation_signature: None,
             request_payload: json!({}),
             requester_api_key: None,
             requester_wallet: "Creator1111111111111111111111111111111111111".to_string(),
@@ -3259,6 +4589,7 @@ mod tests {
             allow_invalid_certs: false,
             create_paths: vec!["/squads".to_string()],
             mode_override: None,
+            priority_fee: FeeStrategy::None,
         }
     }
 
@@ -3328,23 +4659,21 @@ mod tests {
             request_id: Uuid,
             upstream: &CreateSafeResult,
             backoff: Duration,
+            verification_error: Option<&str>,
         ) -> Result<SafeRequestRecord> {
             let mut inner = self.inner.lock().await;
             let record = inner
                 .iter_mut()
                 .find(|record| record.id == request_id)
                 .ok_or_else(|| anyhow!("missing record"))?;
-            let ready = !upstream.safe_address.is_empty();
+            let ready = !upstream.safe_address.is_empty() && verification_error.is_none();
             record.status = if ready {
                 SafeStatus::Ready
             } else {
                 SafeStatus::Submitted
             };
-            record.safe_address = if ready {
-                Some(upstream.safe_address.clone())
-            } else {
-                None
-            };
+            record.safe_address =
+                Some(upstream.safe_address.clone()).filter(|address| !address.is_empty());
             record.transaction_url = upstream.transaction_url.clone();
             record.members = json!(upstream.members.clone());
             record.raw_response = Some(upstream.raw_response.clone());
@@ -3353,7 +4682,7 @@ mod tests {
             record.status_last_checked_at = Some(Utc::now());
             record.status_last_response = Some(upstream.raw_response.clone());
             record.status_last_response_hash = Some("hash".to_string());
-            record.status_sync_error = None;
+            record.status_sync_error = verification_error.map(str::to_string);
             record.last_attempt_at = Utc::now();
             record.next_retry_at = if ready {
                 None
@@ -3406,12 +4735,14 @@ mod tests {
     #[derive(Clone)]
     struct StubRetryService {
         responses: Arc<Mutex<VecDeque<Result<CreateSafeResult>>>>,
+        verification_error: Option<String>,
     }
 
     impl StubRetryService {
         fn new(responses: Vec<Result<CreateSafeResult>>) -> Self {
             Self {
                 responses: Arc::new(Mutex::new(VecDeque::from(responses))),
+                verification_error: None,
             }
         }
     }
@@ -3424,6 +4755,14 @@ mod tests {
                 .pop_front()
                 .unwrap_or_else(|| Err(anyhow!("no stub response configured")))
         }
+
+        async fn safe_verification_error(
+            &self,
+            _record: &SafeRequestRecord,
+            _safe_address: &str,
+        ) -> Option<String> {
+            self.verification_error.clone()
+        }
     }
 
     fn sample_ready_result(job: &SafeRequestRecord) -> CreateSafeResult {
@@ -3440,6 +4779,7 @@ mod tests {
             }),
             mode: SquadsModeKind::Local,
             status_url: Some("https://status.local/req/1".to_string()),
+            creation_signature: None,
         }
     }
 
@@ -3516,6 +4856,34 @@ mod tests {
         assert_eq!(updated.attempt_count, record.attempt_count + 1);
     }
 
+    #[tokio::test]
+    async fn squads_create_error_returns_decoded_program_error() {
+        let logs = [
+            "Program log: AnchorError caused by account: multisig. Error Code: InvalidThreshold. Error Number: 6004. Error Message: Invalid threshold.",
+            "Program SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf failed: custom program error: 0x1774",
+        ];
+        let program_error = ProgramError::from_logs(&logs).unwrap();
+        let err = anyhow::Error::new(program_error).context("submit multisig_create_v2");
+        let response = squads_create_error(&err).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"], "program_error");
+        assert_eq!(body["code"], "squads_create_failed");
+        assert_eq!(body["program_error"]["code"], 6004);
+        assert_eq!(body["program_error"]["name"], "InvalidThreshold");
+        assert_eq!(
+            body["program_error"]["program_id"],
+            "SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf"
+        );
+
+        let generic = squads_create_error(&anyhow!("rpc unavailable")).into_response();
+        assert_eq!(generic.status(), StatusCode::BAD_GATEWAY);
+        let bytes = generic.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"], "upstream");
+    }
+
     #[tokio::test]
     async fn http_server_serves_overview_endpoint() {
         let (app, _store) = test_app();
@@ -3594,4 +4962,329 @@ mod tests {
         let processed = status_sync_iteration(&service, &repo).await.unwrap();
         assert!(!processed);
     }
+
+    #[tokio::test]
+    async fn process_stalled_safe_keeps_unverified_safe_submitted() {
+        let mut record = sample_record(SafeStatus::Pending, None);
+        record.last_attempt_at = Utc::now() - Duration::seconds(STALLED_STALE_SECS + 5);
+        record.next_retry_at = Some(Utc::now() - Duration::seconds(5));
+        let repo = MockStalledRepo::new(vec![record.clone()]);
+        let mut service = StubRetryService::new(vec![Ok(sample_ready_result(&record))]);
+        service.verification_error = Some("safe verification failed: account not found".into());
+
+        process_stalled_safe(&service, &repo, record.clone())
+            .await
+            .unwrap();
+
+        let updated = repo.get(record.id).await.unwrap();
+        assert_eq!(updated.status, SafeStatus::Submitted);
+        assert!(updated.safe_address.is_some());
+        assert_eq!(
+            updated.status_sync_error.as_deref(),
+            Some("safe verification failed: account not found")
+        );
+        assert!(updated.next_retry_at.is_some());
+    }
+
+    /// Squads v4 `Multisig` account bytes with full-permission `members`.
+    fn multisig_account_data(members: &[Pubkey], threshold: u16) -> Vec<u8> {
+        let mut data = vec![224, 116, 121, 186, 68, 161, 79, 236];
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(Pubkey::default().as_ref());
+        data.extend_from_slice(&threshold.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(0);
+        data.push(255);
+        data.extend_from_slice(&(members.len() as u32).to_le_bytes());
+        for member in members {
+            data.extend_from_slice(member.as_ref());
+            data.push(Permissions::ALL);
+        }
+        data
+    }
+
+    /// Answers `getAccountInfo` for `address` with an account owned by `owner`.
+    fn mock_rpc_account<'a>(
+        server: &'a MockServer,
+        address: &str,
+        owner: &Pubkey,
+        data: &[u8],
+    ) -> httpmock::Mock<'a> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "context": { "slot": 1 },
+                "value": {
+                    "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+                    "executable": false,
+                    "lamports": 1_000_000,
+                    "owner": owner.to_string(),
+                    "rentEpoch": 0,
+                    "space": data.len()
+                }
+            }
+        });
+        let address = address.to_string();
+        server.mock(move |when, then| {
+            when.method(POST)
+                .body_contains("getAccountInfo")
+                .body_contains(address);
+            then.status(200).json_body(body);
+        })
+    }
+
+    /// Reports every account not mocked before it as missing.
+    fn mock_rpc_missing(server: &MockServer) -> httpmock::Mock<'_> {
+        server.mock(|when, then| {
+            when.method(POST);
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "context": { "slot": 1 }, "value": null }
+            }));
+        })
+    }
+
+    fn rpc_squads_config(rpc_url: String, attn_wallet: &Pubkey) -> SquadsConfig {
+        let mut config = local_squads_config();
+        config.rpc_url = Some(rpc_url);
+        config.default_attn_wallet = attn_wallet.to_string();
+        config
+    }
+
+    #[tokio::test]
+    async fn safe_verification_error_rejects_missing_and_mismatched_safes() {
+        let server = MockServer::start_async().await;
+        let creator = Pubkey::new_unique();
+        let attn = Pubkey::new_unique();
+        let verified = Pubkey::new_unique().to_string();
+        let creator_only = Pubkey::new_unique().to_string();
+        let foreign = Pubkey::new_unique().to_string();
+        mock_rpc_account(
+            &server,
+            &verified,
+            &SQUADS_PROGRAM_ID,
+            &multisig_account_data(&[creator, attn], 2),
+        );
+        mock_rpc_account(
+            &server,
+            &creator_only,
+            &SQUADS_PROGRAM_ID,
+            &multisig_account_data(&[creator], 1),
+        );
+        mock_rpc_account(&server, &foreign, &Pubkey::new_unique(), &[0; 64]);
+        mock_rpc_missing(&server);
+        let service = SquadsService::new(rpc_squads_config(server.url(""), &attn))
+            .await
+            .unwrap();
+        let mut record = sample_record(SafeStatus::Submitted, None);
+        record.creator_wallet = creator.to_string();
+        record.attn_wallet = attn.to_string();
+
+        assert_eq!(
+            safe_verification_error(&service, &record, &verified).await,
+            None
+        );
+        assert_eq!(safe_verification_error(&service, &record, "").await, None);
+        let missing = safe_verification_error(&service, &record, &Pubkey::new_unique().to_string())
+            .await
+            .unwrap();
+        assert!(missing.contains("account not found"), "{missing}");
+        let mismatch = safe_verification_error(&service, &record, &creator_only)
+            .await
+            .unwrap();
+        assert!(mismatch.contains("attn wallet"), "{mismatch}");
+        assert!(mismatch.contains("threshold is 1"), "{mismatch}");
+        let foreign = safe_verification_error(&service, &record, &foreign)
+            .await
+            .unwrap();
+        assert!(foreign.contains("not the Squads v4 program"), "{foreign}");
+    }
+
+    #[tokio::test]
+    async fn status_sync_iteration_keeps_missing_safe_submitted() {
+        let server = MockServer::start_async().await;
+        mock_rpc_missing(&server);
+        let attn = Pubkey::new_unique();
+        let service = SquadsService::new(rpc_squads_config(server.url(""), &attn))
+            .await
+            .unwrap();
+        let mut record = sample_record(
+            SafeStatus::Submitted,
+            Some("https://status.local/1".to_string()),
+        );
+        record.creator_wallet = Pubkey::new_unique().to_string();
+        record.attn_wallet = attn.to_string();
+        record.safe_address = Some(Pubkey::new_unique().to_string());
+        let request_id = record.id;
+        let repo = MockStatusRepo::new(vec![record]);
+
+        status_sync_iteration(&service, &repo).await.unwrap();
+        let updated = repo.get(request_id).await.unwrap();
+        assert_eq!(updated.status, SafeStatus::Submitted);
+        assert!(updated
+            .status_sync_error
+            .as_deref()
+            .unwrap()
+            .contains("account not found"));
+    }
+
+    /// App with Squads in local mode against `rpc_url` and a Postgres-backed
+    /// safe repository from `ATTN_API_TEST_DATABASE_URL`, or `None` when no
+    /// test database is configured. The `test` API key is an admin key.
+    async fn squads_pg_app(
+        rpc_url: String,
+        attn_wallet: &Pubkey,
+    ) -> Option<(Router, SquadsSafeRepository)> {
+        let url = std::env::var("ATTN_API_TEST_DATABASE_URL").ok()?;
+        let pool = connect_pool(&url, 2).await.expect("connect test database");
+        run_migrations(&pool).await.expect("run migrations");
+        let repo = SquadsSafeRepository::new(pool);
+        let service = SquadsService::new(rpc_squads_config(rpc_url, attn_wallet))
+            .await
+            .unwrap();
+        let (mut state, _) =
+            test_state(Arc::new(InMemoryQuoteBook::default()), StreamHub::default());
+        let mut security = default_security_config();
+        security
+            .api_keys
+            .insert("test".to_string(), "secret".to_string());
+        security.admin_keys.insert("test".to_string());
+        state.security = SecurityState::new(security);
+        state.squads = Some(service);
+        state.squads_repo = Some(repo.clone());
+        Some((build_router(state), repo))
+    }
+
+    #[tokio::test]
+    async fn created_and_resubmitted_safes_stay_submitted_until_verified() {
+        let server = MockServer::start_async().await;
+        let mut fallback = mock_rpc_missing(&server);
+        let attn = Pubkey::new_unique();
+        let Some((app, repo)) = squads_pg_app(server.url(""), &attn).await else {
+            return;
+        };
+        let creator = Keypair::new();
+        let wallet = creator.pubkey().to_string();
+        let nonce = repo
+            .issue_nonce(&wallet, Duration::minutes(5))
+            .await
+            .unwrap()
+            .nonce;
+        let message = format!("{SIGNATURE_MESSAGE_PREFIX}:{nonce}:{wallet}");
+        let signature = creator.sign_message(message.as_bytes()).to_string();
+
+        let (status, created) = send_authed(
+            &app,
+            Method::POST,
+            "/v1/squads/safes",
+            Some(json!({
+                "creator_wallet": wallet,
+                "nonce": nonce,
+                "creator_signature": signature
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{created}");
+        assert_eq!(created["status"], "submitted");
+        assert!(created["status_sync_error"]
+            .as_str()
+            .unwrap()
+            .contains("account not found"));
+        let safe = created["safe_address"].as_str().unwrap().to_string();
+        let uri = format!(
+            "/v1/squads/safes/{}/resubmit",
+            created["request_id"].as_str().unwrap()
+        );
+
+        // A multisig the attn wallet is not a member of must not become ready.
+        fallback.delete();
+        let mut foreign = mock_rpc_account(
+            &server,
+            &safe,
+            &SQUADS_PROGRAM_ID,
+            &multisig_account_data(&[creator.pubkey()], 2),
+        );
+        fallback = mock_rpc_missing(&server);
+        let (status, resubmitted) =
+            send_authed(&app, Method::POST, &uri, Some(json!({ "force": true }))).await;
+        assert_eq!(status, StatusCode::OK, "{resubmitted}");
+        assert_eq!(resubmitted["status"], "submitted");
+        assert!(resubmitted["status_sync_error"]
+            .as_str()
+            .unwrap()
+            .contains("attn wallet"));
+
+        foreign.delete();
+        fallback.delete();
+        mock_rpc_account(
+            &server,
+            &safe,
+            &SQUADS_PROGRAM_ID,
+            &multisig_account_data(&[creator.pubkey(), attn], 2),
+        );
+        mock_rpc_missing(&server);
+        let (status, resubmitted) =
+            send_authed(&app, Method::POST, &uri, Some(json!({ "force": true }))).await;
+        assert_eq!(status, StatusCode::OK, "{resubmitted}");
+        assert_eq!(resubmitted["status"], "ready");
+        assert!(resubmitted["status_sync_error"].is_null());
+    }
+
+    #[tokio::test]
+    async fn imported_safes_are_verified_before_storing() {
+        let server = MockServer::start_async().await;
+        // Random keys: `new_unique` repeats across runs against the same database.
+        let creator = Keypair::new().pubkey();
+        let attn = Pubkey::new_unique();
+        let creator_only = Keypair::new().pubkey().to_string();
+        let verified = Keypair::new().pubkey().to_string();
+        mock_rpc_account(
+            &server,
+            &creator_only,
+            &SQUADS_PROGRAM_ID,
+            &multisig_account_data(&[creator], 1),
+        );
+        mock_rpc_account(
+            &server,
+            &verified,
+            &SQUADS_PROGRAM_ID,
+            &multisig_account_data(&[creator, attn], 2),
+        );
+        mock_rpc_missing(&server);
+        let Some((app, repo)) = squads_pg_app(server.url(""), &attn).await else {
+            return;
+        };
+
+        for safe in [creator_only, Pubkey::new_unique().to_string()] {
+            let (status, payload) = send_authed(
+                &app,
+                Method::POST,
+                "/v1/squads/safes/import",
+                Some(json!({ "creator_wallet": creator.to_string(), "safe_address": safe })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{payload}");
+            assert_eq!(payload["code"], "safe_verification_failed");
+        }
+        assert!(repo
+            .find_latest_by_creator_and_cluster(&creator.to_string(), "devnet")
+            .await
+            .unwrap()
+            .is_none());
+
+        let (status, payload) = send_authed(
+            &app,
+            Method::POST,
+            "/v1/squads/safes/import",
+            Some(json!({ "creator_wallet": creator.to_string(), "safe_address": verified })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{payload}");
+        assert_eq!(payload["status"], "ready");
+        assert_eq!(payload["safe_address"], verified);
+    }
 }
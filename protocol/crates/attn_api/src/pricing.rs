@@ -0,0 +1,378 @@
+use attn_indexer::FeeHistoryPoint;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::advance::QuoteSide;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const DAYS_PER_YEAR: f64 = 365.0;
+const BPS: f64 = 10_000.0;
+
+/// Tunables for RFQ YT pricing. Spreads and haircuts are in basis points.
+#[derive(Debug, Clone)]
+pub struct PricingConfig {
+    pub history_days: i64,
+    pub ewma_half_life_days: f64,
+    pub min_history_samples: usize,
+    pub base_haircut_bps: f64,
+    pub haircut_per_30d_bps: f64,
+    pub volatility_haircut_multiplier: f64,
+    pub max_haircut_bps: f64,
+    pub sell_spread_bps: f64,
+    pub buyback_spread_bps: f64,
+    pub inventory_spread_bps: f64,
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            history_days: 30,
+            ewma_half_life_days: 7.0,
+            min_history_samples: 3,
+            base_haircut_bps: 250.0,
+            haircut_per_30d_bps: 150.0,
+            volatility_haircut_multiplier: 1.0,
+            max_haircut_bps: 5_000.0,
+            sell_spread_bps: 15.0,
+            buyback_spread_bps: 25.0,
+            inventory_spread_bps: 100.0,
+        }
+    }
+}
+
+/// Market data a quote is priced from.
+pub struct MarketInputs<'a> {
+    /// Annualised rate reported by the store, used when fee history is too thin.
+    pub fallback_apr: f64,
+    /// Principal backing the market's fees, in SOL.
+    pub tvl_sol: f64,
+    pub fee_history: &'a [FeeHistoryPoint],
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum YieldSource {
+    FeeHistory,
+    StoreFallback,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct YieldEstimate {
+    pub source: YieldSource,
+    pub forward_apr: f64,
+    pub ewma_daily_yield: f64,
+    pub daily_volatility: f64,
+    pub samples: usize,
+}
+
+/// How a quote price was derived, returned alongside the quote.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PricingBreakdown {
+    pub yield_source: YieldSource,
+    pub forward_apr: f64,
+    pub ewma_daily_yield: f64,
+    pub daily_volatility: f64,
+    pub history_samples: usize,
+    pub days_to_maturity: f64,
+    pub period_fraction: f64,
+    pub fair_value_usdc: f64,
+    pub time_haircut_bps: f64,
+    pub volatility_haircut_bps: f64,
+    pub total_haircut_bps: f64,
+    pub base_spread_bps: f64,
+    pub inventory_spread_bps: f64,
+    pub inventory_utilization: f64,
+}
+
+pub struct PricedQuote {
+    pub price_usdc: f64,
+    pub breakdown: PricingBreakdown,
+}
+
+impl PricingConfig {
+    /// Start of the fee history window, aligned to a UTC day boundary.
+    pub fn history_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now.duration_trunc(Duration::days(1)).unwrap_or(now);
+        today - Duration::days(self.history_days.max(1))
+    }
+
+    /// Estimates the forward fee yield from daily fee history using an
+    /// exponentially weighted mean and variance of the daily yield on TVL.
+    /// Days between the first sample and today without fees count as zero.
+    pub fn estimate_yield(&self, inputs: &MarketInputs<'_>, now: DateTime<Utc>) -> YieldEstimate {
+        let fallback = YieldEstimate {
+            source: YieldSource::StoreFallback,
+            forward_apr: sanitize_rate(inputs.fallback_apr),
+            ewma_daily_yield: sanitize_rate(inputs.fallback_apr) / DAYS_PER_YEAR,
+            daily_volatility: 0.0,
+            samples: 0,
+        };
+        if !(inputs.tvl_sol.is_finite() && inputs.tvl_sol > 0.0) {
+            return fallback;
+        }
+        let daily_yields = daily_yield_series(inputs.fee_history, inputs.tvl_sol, now);
+        if daily_yields.len() < self.min_history_samples.max(1) {
+            return fallback;
+        }
+
+        let alpha = ewma_alpha(self.ewma_half_life_days);
+        let mut mean = daily_yields[0];
+        let mut variance = 0.0;
+        for value in daily_yields.iter().skip(1) {
+            let delta = value - mean;
+            mean += alpha * delta;
+            variance = (1.0 - alpha) * (variance + alpha * delta * delta);
+        }
+
+        YieldEstimate {
+            source: YieldSource::FeeHistory,
+            forward_apr: (mean * DAYS_PER_YEAR).max(0.0),
+            ewma_daily_yield: mean.max(0.0),
+            daily_volatility: variance.max(0.0).sqrt(),
+            samples: daily_yields.len(),
+        }
+    }
+
+    /// Prices `size_yt` of YT. `inventory_utilization` is the share of the
+    /// epoch advance cap already carried by the LP, in `[0, 1]`.
+    pub fn price(
+        &self,
+        size_yt: f64,
+        side: QuoteSide,
+        seconds_to_maturity: i64,
+        inputs: &MarketInputs<'_>,
+        inventory_utilization: f64,
+        now: DateTime<Utc>,
+    ) -> PricedQuote {
+        let estimate = self.estimate_yield(inputs, now);
+        let period_fraction = period_fraction(seconds_to_maturity);
+        let days_to_maturity = period_fraction * DAYS_PER_YEAR;
+        let fair_value = size_yt * estimate.forward_apr * period_fraction;
+
+        let time_haircut_bps =
+            self.base_haircut_bps + self.haircut_per_30d_bps * days_to_maturity / 30.0;
+        let volatility_haircut_bps = if estimate.ewma_daily_yield > 0.0 {
+            let variation = estimate.daily_volatility / estimate.ewma_daily_yield;
+            self.volatility_haircut_multiplier * variation * BPS / days_to_maturity.max(1.0).sqrt()
+        } else {
+            0.0
+        };
+        let total_haircut_bps =
+            (time_haircut_bps + volatility_haircut_bps).clamp(0.0, self.max_haircut_bps.min(BPS));
+        let risk_adjusted = fair_value * (1.0 - total_haircut_bps / BPS);
+
+        let utilization = if inventory_utilization.is_finite() {
+            inventory_utilization.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        // Sells add to the LP's YT inventory and widen as it fills up; buybacks
+        // unwind it, so the LP gives some of the spread back.
+        let (base_spread_bps, inventory_spread_bps, price) = match side {
+            QuoteSide::Sell => {
+                let inventory = self.inventory_spread_bps * utilization;
+                let total = (self.sell_spread_bps + inventory).min(BPS);
+                (
+                    self.sell_spread_bps,
+                    inventory,
+                    risk_adjusted * (1.0 - total / BPS),
+                )
+            }
+            QuoteSide::Buyback => {
+                let inventory =
+                    -(self.inventory_spread_bps * utilization).min(self.buyback_spread_bps);
+                let total = self.buyback_spread_bps + inventory;
+                (
+                    self.buyback_spread_bps,
+                    inventory,
+                    risk_adjusted * (1.0 + total / BPS),
+                )
+            }
+        };
+
+        PricedQuote {
+            price_usdc: price.max(0.0),
+            breakdown: PricingBreakdown {
+                yield_source: estimate.source,
+                forward_apr: estimate.forward_apr,
+                ewma_daily_yield: estimate.ewma_daily_yield,
+                daily_volatility: estimate.daily_volatility,
+                history_samples: estimate.samples,
+                days_to_maturity,
+                period_fraction,
+                fair_value_usdc: fair_value.max(0.0),
+                time_haircut_bps,
+                volatility_haircut_bps,
+                total_haircut_bps,
+                base_spread_bps,
+                inventory_spread_bps,
+                inventory_utilization: utilization,
+            },
+        }
+    }
+}
+
+/// Fraction of a year covered by the quote. Matured markets price a fresh
+/// 15-day period.
+pub fn period_fraction(seconds_to_maturity: i64) -> f64 {
+    if seconds_to_maturity <= 0 {
+        15.0 / DAYS_PER_YEAR
+    } else {
+        ((seconds_to_maturity as f64) / SECONDS_PER_DAY / DAYS_PER_YEAR)
+            .clamp(1.0 / DAYS_PER_YEAR, 0.5)
+    }
+}
+
+fn sanitize_rate(value: f64) -> f64 {
+    if value.is_finite() {
+        value.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+fn ewma_alpha(half_life_days: f64) -> f64 {
+    if half_life_days.is_finite() && half_life_days > 0.0 {
+        1.0 - 0.5_f64.powf(1.0 / half_life_days)
+    } else {
+        1.0
+    }
+}
+
+/// Dense series of daily fee yields from the first bucket up to yesterday;
+/// today's partial bucket is ignored.
+fn daily_yield_series(history: &[FeeHistoryPoint], tvl_sol: f64, now: DateTime<Utc>) -> Vec<f64> {
+    let today = now.duration_trunc(Duration::days(1)).unwrap_or(now);
+    let mut points: Vec<&FeeHistoryPoint> = history
+        .iter()
+        .filter(|point| point.bucket_start < today && point.fees_sol.is_finite())
+        .collect();
+    points.sort_by_key(|point| point.bucket_start);
+    let Some(first) = points.first() else {
+        return Vec::new();
+    };
+    let first_day = first
+        .bucket_start
+        .duration_trunc(Duration::days(1))
+        .unwrap_or(first.bucket_start);
+    let days = (today - first_day).num_days().max(0) as usize;
+    let mut series = vec![0.0; days];
+    for point in points {
+        let index = (point.bucket_start - first_day).num_days();
+        if let Some(slot) = usize::try_from(index).ok().and_then(|i| series.get_mut(i)) {
+            *slot += point.fees_sol.max(0.0) / tvl_sol;
+        }
+    }
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(now: DateTime<Utc>, fees: &[f64]) -> Vec<FeeHistoryPoint> {
+        let today = now.duration_trunc(Duration::days(1)).unwrap();
+        fees.iter()
+            .enumerate()
+            .map(|(i, fees_sol)| FeeHistoryPoint {
+                bucket_start: today - Duration::days((fees.len() - i) as i64),
+                fees_sol: *fees_sol,
+            })
+            .collect()
+    }
+
+    fn inputs(history: &[FeeHistoryPoint]) -> MarketInputs<'_> {
+        MarketInputs {
+            fallback_apr: 0.05,
+            tvl_sol: 1_000.0,
+            fee_history: history,
+        }
+    }
+
+    #[test]
+    fn constant_fees_give_matching_apr_and_no_volatility() {
+        let now = Utc::now();
+        let points = history(now, &[1.0; 20]);
+        let estimate = PricingConfig::default().estimate_yield(&inputs(&points), now);
+        assert_eq!(estimate.source, YieldSource::FeeHistory);
+        assert_eq!(estimate.samples, 20);
+        assert!((estimate.forward_apr - 0.365).abs() < 1e-9);
+        assert!(estimate.daily_volatility < 1e-12);
+    }
+
+    #[test]
+    fn missing_days_count_as_zero_fees() {
+        let now = Utc::now();
+        let mut points = history(now, &[2.0; 10]);
+        points.retain(|point| (now - point.bucket_start).num_days() > 5);
+        let estimate = PricingConfig::default().estimate_yield(&inputs(&points), now);
+        assert_eq!(estimate.samples, 10);
+        assert!(estimate.forward_apr < 2.0 / 1_000.0 * 365.0);
+        assert!(estimate.daily_volatility > 0.0);
+    }
+
+    #[test]
+    fn thin_history_falls_back_to_store_rate() {
+        let now = Utc::now();
+        let points = history(now, &[1.0]);
+        let estimate = PricingConfig::default().estimate_yield(&inputs(&points), now);
+        assert_eq!(estimate.source, YieldSource::StoreFallback);
+        assert_eq!(estimate.forward_apr, 0.05);
+    }
+
+    #[test]
+    fn haircut_grows_with_time_to_maturity() {
+        let now = Utc::now();
+        let points = history(now, &[1.0; 20]);
+        let config = PricingConfig::default();
+        let short = config.price(
+            100.0,
+            QuoteSide::Sell,
+            7 * 86_400,
+            &inputs(&points),
+            0.0,
+            now,
+        );
+        let long = config.price(
+            100.0,
+            QuoteSide::Sell,
+            90 * 86_400,
+            &inputs(&points),
+            0.0,
+            now,
+        );
+        assert!(long.breakdown.total_haircut_bps > short.breakdown.total_haircut_bps);
+        assert!(long.price_usdc < long.breakdown.fair_value_usdc);
+    }
+
+    #[test]
+    fn inventory_widens_sells_and_tightens_buybacks() {
+        let now = Utc::now();
+        let points = history(now, &[1.0; 20]);
+        let config = PricingConfig::default();
+        let maturity = 15 * 86_400;
+        let empty = config.price(100.0, QuoteSide::Sell, maturity, &inputs(&points), 0.0, now);
+        let full = config.price(100.0, QuoteSide::Sell, maturity, &inputs(&points), 1.0, now);
+        assert!(full.price_usdc < empty.price_usdc);
+        assert_eq!(full.breakdown.inventory_spread_bps, 100.0);
+
+        let buyback_empty = config.price(
+            100.0,
+            QuoteSide::Buyback,
+            maturity,
+            &inputs(&points),
+            0.0,
+            now,
+        );
+        let buyback_full = config.price(
+            100.0,
+            QuoteSide::Buyback,
+            maturity,
+            &inputs(&points),
+            1.0,
+            now,
+        );
+        assert!(buyback_full.price_usdc < buyback_empty.price_usdc);
+        assert!(buyback_full.price_usdc >= empty.price_usdc);
+    }
+}
@@ -6,6 +6,8 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Result};
+use attn_client::nonblocking::{self, sign_transaction};
+use attn_client::tx::{FeeStrategy, SendConfig, TxSender};
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use reqwest::{Client, StatusCode};
 use serde::Serialize;
@@ -14,11 +16,21 @@ use sha2::{Digest, Sha256};
 use sqlx::{types::ipnetwork::IpNetwork, PgPool, QueryBuilder, Row};
 use uuid::Uuid;
 
-use crate::kms::{HttpKmsClient, KmsSigner};
+use crate::kms::{HttpKmsClient, KmsClient, KmsSigner};
+use attn_client::squads::{
+    build_multisig_create_v2_ix, multisig_pda, program_config_pda, Member, MultisigAccount,
+    MultisigCreateArgsV2, Permissions, ProgramConfig, SQUADS_PROGRAM_ID,
+};
 use solana_client::client_error::ClientErrorKind;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_client::rpc_request::RpcError;
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
 
 #[derive(Debug, Clone)]
 pub struct SquadsService {
@@ -45,6 +57,7 @@ struct SquadsInner {
 enum SquadsMode {
     Local,
     Http(HttpMode),
+    Native(NativeMode),
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +68,26 @@ struct HttpMode {
     create_paths: Arc<Vec<String>>,
 }
 
+/// Creates multisigs directly through the Squads v4 program, signing with
+/// the KMS-held attn key (multisig creator) and payer key (fee payer).
+#[derive(Clone)]
+struct NativeMode {
+    client: Arc<RpcClient>,
+    sender: TxSender,
+    attn_wallet: Pubkey,
+    attn_signer: Arc<KmsSigner<HttpKmsClient>>,
+    payer: Option<Arc<KmsSigner<HttpKmsClient>>>,
+}
+
+impl fmt::Debug for NativeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeMode")
+            .field("attn_wallet", &self.attn_wallet)
+            .field("payer", &self.payer.as_ref().map(|payer| payer.pubkey()))
+            .finish()
+    }
+}
+
 #[derive(Clone)]
 struct RpcSanity {
     client: Arc<RpcClient>,
@@ -75,6 +108,7 @@ impl fmt::Debug for RpcSanity {
 pub enum SquadsModeKind {
     Local,
     Http,
+    Native,
 }
 
 impl SquadsModeKind {
@@ -82,6 +116,7 @@ impl SquadsModeKind {
         match self {
             SquadsModeKind::Local => "local",
             SquadsModeKind::Http => "http",
+            SquadsModeKind::Native => "native",
         }
     }
 }
@@ -117,6 +152,8 @@ pub struct SquadsConfig {
     pub kms_payer_resource: Option<String>,
     pub create_paths: Vec<String>,
     pub mode_override: Option<SquadsModeKind>,
+    /// Compute-unit price strategy for natively submitted transactions.
+    pub priority_fee: FeeStrategy,
 }
 
 impl SquadsConfig {
@@ -152,6 +189,7 @@ impl SquadsConfig {
             .map(|value| match value.as_str() {
                 "http" => Ok(SquadsModeKind::Http),
                 "local" => Ok(SquadsModeKind::Local),
+                "native" => Ok(SquadsModeKind::Native),
                 other => Err(anyhow!("unsupported ATTN_API_SQUADS_MODE value: {}", other)),
             })
             .transpose()?;
@@ -209,6 +247,12 @@ impl SquadsConfig {
                 ));
             }
         }
+        let priority_fee = env::var("ATTN_API_SQUADS_PRIORITY_FEE")
+            .ok()
+            .map(|value| value.parse::<FeeStrategy>())
+            .transpose()
+            .map_err(|err| anyhow!("ATTN_API_SQUADS_PRIORITY_FEE: {err}"))?
+            .unwrap_or(FeeStrategy::Percentile(50));
         let rpc_url = env::var("ATTN_API_SQUADS_RPC_URL")
             .ok()
             .map(|value| value.trim().to_string())
@@ -326,6 +370,7 @@ impl SquadsConfig {
             create_paths,
             allow_invalid_certs,
             mode_override,
+            priority_fee,
         }))
     }
 }
@@ -372,6 +417,7 @@ pub struct CreateSafeResult {
     pub raw_response: Value,
     pub mode: SquadsModeKind,
     pub status_url: Option<String>,
+    pub creation_signature: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -398,6 +444,91 @@ pub struct RpcSanityOutcome {
     pub cluster: String,
 }
 
+/// Outcome of checking a safe address against the on-chain multisig.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SafeAccountCheck {
+    Missing,
+    Verified,
+    Mismatch(Vec<String>),
+}
+
+impl SafeAccountCheck {
+    /// Message recorded in `status_sync_error` when the safe may not be
+    /// marked `ready`: the account is missing or does not match.
+    pub fn failure_message(&self) -> Option<String> {
+        match self {
+            SafeAccountCheck::Verified => None,
+            SafeAccountCheck::Missing => {
+                Some("safe verification failed: account not found".to_string())
+            }
+            SafeAccountCheck::Mismatch(problems) => {
+                Some(format!("safe verification failed: {}", problems.join("; ")))
+            }
+        }
+    }
+}
+
+/// What a safe created or imported for a creator is expected to look like.
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedSafe {
+    pub creator_wallet: Pubkey,
+    pub attn_wallet: Pubkey,
+    pub threshold: u16,
+}
+
+/// Checks an account fetched at a safe address against `expected` and returns
+/// every mismatch found; an empty list means the safe is acceptable.
+pub fn check_multisig_account(owner: &Pubkey, data: &[u8], expected: &ExpectedSafe) -> Vec<String> {
+    if owner != &SQUADS_PROGRAM_ID {
+        return vec![format!(
+            "account is owned by {owner}, not the Squads v4 program"
+        )];
+    }
+    let multisig = match MultisigAccount::from_account_data(data) {
+        Ok(multisig) => multisig,
+        Err(err) => return vec![format!("account is not a Squads v4 multisig: {err}")],
+    };
+    let mut problems = Vec::new();
+    for (label, wallet) in [
+        ("creator", &expected.creator_wallet),
+        ("attn", &expected.attn_wallet),
+    ] {
+        match multisig.member(wallet) {
+            None => problems.push(format!("{label} wallet {wallet} is not a member")),
+            Some(member) if !member.permissions.has(Permissions::ALL) => problems.push(format!(
+                "{label} wallet {wallet} has permissions {:#05b}, expected initiate+vote+execute",
+                member.permissions.mask
+            )),
+            Some(_) => {}
+        }
+    }
+    if multisig.threshold != expected.threshold {
+        problems.push(format!(
+            "threshold is {}, expected {}",
+            multisig.threshold, expected.threshold
+        ));
+    }
+    if !multisig.is_autonomous() {
+        problems.push(format!(
+            "config authority {} is set; expected an autonomous multisig",
+            multisig.config_authority
+        ));
+    }
+    problems
+}
+
+fn expected_safe(creator_wallet: &str, attn_wallet: &str, threshold: i64) -> Result<ExpectedSafe> {
+    let parse = |label: &str, value: &str| {
+        Pubkey::from_str(value).map_err(|_| anyhow!("{} {} failed base58 decoding", label, value))
+    };
+    Ok(ExpectedSafe {
+        creator_wallet: parse("creator_wallet", creator_wallet)?,
+        attn_wallet: parse("attn_wallet", attn_wallet)?,
+        threshold: u16::try_from(threshold)
+            .map_err(|_| anyhow!("threshold {} out of range", threshold))?,
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct SafeStatusResult {
     pub status: Option<String>,
@@ -429,9 +560,11 @@ impl SquadsService {
         } else {
             None
         };
+        let attn_wallet = Pubkey::from_str(&config.default_attn_wallet)
+            .map_err(|_| anyhow!("ATTN_API_SQUADS_DEFAULT_MEMBER failed base58 decoding"))?;
         let kms_signer = if let Some(resource) = config.kms_signer_resource.as_ref() {
             Some(Arc::new(
-                KmsSigner::google_cloud(resource.clone())
+                KmsSigner::google_cloud(resource.clone(), attn_wallet)
                     .await
                     .context("initialize kms signer")?,
             ))
@@ -439,8 +572,13 @@ impl SquadsService {
             None
         };
         let kms_payer = if let Some(resource) = config.kms_payer_resource.as_ref() {
+            let payer_wallet = config.payer_wallet.as_deref().ok_or_else(|| {
+                anyhow!("ATTN_API_SQUADS_PAYER required when ATTN_KMS_PAYER_KEY is set")
+            })?;
+            let payer_wallet = Pubkey::from_str(payer_wallet)
+                .map_err(|_| anyhow!("ATTN_API_SQUADS_PAYER failed base58 decoding"))?;
             Some(Arc::new(
-                KmsSigner::google_cloud(resource.clone())
+                KmsSigner::google_cloud(resource.clone(), payer_wallet)
                     .await
                     .context("initialize kms payer signer")?,
             ))
@@ -475,6 +613,29 @@ impl SquadsService {
                 })
             }
             SquadsModeKind::Local => SquadsMode::Local,
+            SquadsModeKind::Native => {
+                let client = rpc
+                    .as_ref()
+                    .map(|rpc| Arc::clone(&rpc.client))
+                    .ok_or_else(|| anyhow!("ATTN_API_SQUADS_RPC_URL required for native mode"))?;
+                let attn_signer = kms_signer
+                    .clone()
+                    .ok_or_else(|| anyhow!("ATTN_KMS_SIGNER_KEY required for native mode"))?;
+                let sender = TxSender::new(
+                    Arc::clone(&client),
+                    SendConfig {
+                        fee: config.priority_fee,
+                        ..SendConfig::default()
+                    },
+                );
+                SquadsMode::Native(NativeMode {
+                    client,
+                    sender,
+                    attn_wallet,
+                    attn_signer,
+                    payer: kms_payer.clone(),
+                })
+            }
         };
         let inner = SquadsInner {
             mode,
@@ -603,11 +764,44 @@ impl SquadsService {
         }))
     }
 
+    /// Fetches the account at `safe_address` and checks it is a Squads v4
+    /// multisig matching `record`: both wallets as full-permission members,
+    /// the requested threshold and no config authority.
     pub async fn verify_safe_account(
+        &self,
+        record: &SafeRequestRecord,
+        safe_address: &str,
+    ) -> Result<Option<SafeAccountCheck>> {
+        let expected = expected_safe(
+            &record.creator_wallet,
+            &record.attn_wallet,
+            record.threshold.into(),
+        )?;
+        self.verify_safe(&record.cluster, &expected, safe_address)
+            .await
+    }
+
+    /// Same check as [`Self::verify_safe_account`] for a safe being imported,
+    /// before any request row exists for it.
+    pub async fn verify_imported_safe(
+        &self,
+        input: &ImportSafeInput,
+    ) -> Result<Option<SafeAccountCheck>> {
+        let expected = expected_safe(
+            &input.creator_wallet,
+            &input.attn_wallet,
+            input.threshold.into(),
+        )?;
+        self.verify_safe(&input.cluster, &expected, &input.safe_address)
+            .await
+    }
+
+    async fn verify_safe(
         &self,
         cluster: &str,
+        expected: &ExpectedSafe,
         safe_address: &str,
-    ) -> Result<Option<bool>> {
+    ) -> Result<Option<SafeAccountCheck>> {
         let Some(rpc) = &self.inner.rpc else {
             return Ok(None);
         };
@@ -617,7 +811,14 @@ impl SquadsService {
         let pubkey = Pubkey::from_str(safe_address)
             .map_err(|_| anyhow!("safe_address {} failed base58 decoding", safe_address))?;
         match rpc.client.get_account(&pubkey).await {
-            Ok(_) => Ok(Some(true)),
+            Ok(account) => {
+                let problems = check_multisig_account(&account.owner, &account.data, expected);
+                if problems.is_empty() {
+                    Ok(Some(SafeAccountCheck::Verified))
+                } else {
+                    Ok(Some(SafeAccountCheck::Mismatch(problems)))
+                }
+            }
             Err(err) => {
                 if is_account_not_found(err.kind()) {
                     if rpc.strict {
@@ -627,7 +828,7 @@ impl SquadsService {
                             cluster
                         ))
                     } else {
-                        Ok(Some(false))
+                        Ok(Some(SafeAccountCheck::Missing))
                     }
                 } else {
                     match err.kind() {
@@ -649,6 +850,15 @@ impl SquadsService {
 
     pub async fn fetch_status(&self, status_url: &str) -> Result<SafeStatusResult> {
         match &self.inner.mode {
+            SquadsMode::Native(_) => Ok(SafeStatusResult {
+                status: Some("ready".to_string()),
+                safe_address: None,
+                transaction_url: None,
+                raw_response: json!({
+                    "mode": "native",
+                    "status": "ready",
+                }),
+            }),
             SquadsMode::Local => Ok(SafeStatusResult {
                 status: Some("ready".to_string()),
                 safe_address: None,
@@ -733,9 +943,120 @@ impl SquadsService {
                 self.create_safe_http(http, &input, request_id, members)
                     .await
             }
+            SquadsMode::Native(native) => {
+                self.create_safe_native(native, &input, request_id, members)
+                    .await
+            }
         }
     }
 
+    async fn create_safe_native(
+        &self,
+        native: &NativeMode,
+        input: &CreateSafeInput,
+        request_id: String,
+        members: Vec<String>,
+    ) -> Result<CreateSafeResult> {
+        let creator = Pubkey::from_str(&input.creator_wallet)
+            .map_err(|_| anyhow!("creator_wallet failed base58 decoding"))?;
+        let attn = Pubkey::from_str(&input.attn_wallet)
+            .map_err(|_| anyhow!("attn_wallet failed base58 decoding"))?;
+        if attn != native.attn_wallet {
+            return Err(anyhow!(
+                "native mode can only create safes for attn wallet {}",
+                native.attn_wallet
+            ));
+        }
+        let create_key = derive_create_key(&native.attn_signer, &input.idempotency_key).await?;
+        let (multisig, _) = multisig_pda(&create_key.pubkey());
+        let safe_address = multisig.to_string();
+        let mut raw_response = json!({
+            "mode": "native",
+            "request_id": request_id,
+            "safe_address": safe_address,
+            "create_key": create_key.pubkey().to_string(),
+            "members": members,
+            "cluster": input.cluster,
+            "threshold": input.threshold,
+        });
+
+        // Retries with the same idempotency key derive the same address.
+        if native
+            .client
+            .get_account_with_commitment(&multisig, CommitmentConfig::confirmed())
+            .await
+            .context("check existing multisig")?
+            .value
+            .is_some()
+        {
+            raw_response["existing"] = json!(true);
+            return Ok(CreateSafeResult {
+                request_id,
+                safe_address,
+                transaction_url: None,
+                cluster: input.cluster.clone(),
+                threshold: input.threshold,
+                members,
+                raw_response,
+                mode: self.current_mode(),
+                status_url: None,
+                creation_signature: None,
+            });
+        }
+
+        let (program_config, _) = program_config_pda();
+        let config_account = native
+            .client
+            .get_account(&program_config)
+            .await
+            .context("fetch squads program config")?;
+        let treasury = ProgramConfig::from_account_data(&config_account.data)?.treasury;
+        let instruction = creator_safe_instruction(
+            treasury,
+            create_key.pubkey(),
+            creator,
+            attn,
+            input.threshold,
+            input.safe_name.clone(),
+        )?;
+        let fee_payer = native
+            .payer
+            .as_ref()
+            .map(|payer| payer.pubkey())
+            .unwrap_or(native.attn_wallet);
+        let prepared = native
+            .sender
+            .prepare(vec![instruction], &fee_payer, &[])
+            .await
+            .context("prepare multisig_create_v2")?;
+        let mut signers: Vec<&dyn nonblocking::TransactionSigner> =
+            vec![native.attn_signer.as_ref(), &create_key];
+        if let Some(payer) = native.payer.as_ref() {
+            signers.push(payer.as_ref());
+        }
+        let transaction = sign_transaction(prepared.message(&fee_payer)?, &signers).await?;
+        let signature = native
+            .sender
+            .send_transaction(&transaction, prepared.last_valid_block_height)
+            .await
+            .context("submit multisig_create_v2")?
+            .into_result()?;
+        raw_response["signature"] = json!(signature.to_string());
+
+        Ok(CreateSafeResult {
+            request_id,
+            safe_address,
+            transaction_url: Some(explorer_tx_url(&signature, &input.cluster)),
+            cluster: input.cluster.clone(),
+            threshold: input.threshold,
+            members,
+            raw_response,
+            mode: self.current_mode(),
+            status_url: None,
+            creation_signature: Some(signature.to_string()),
+        })
+    }
+
     fn create_safe_local(
         &self,
         input: &CreateSafeInput,
@@ -769,6 +1090,7 @@ impl SquadsService {
             raw_response,
             mode: self.current_mode(),
             status_url: None,
+            creation_signature: None,
         }
     }
 
@@ -856,6 +1178,7 @@ impl SquadsService {
                     raw_response,
                     mode: self.current_mode(),
                     status_url,
+                    creation_signature: None,
                 });
             }
 
@@ -884,6 +1207,7 @@ impl SquadsService {
         match self.inner.mode {
             SquadsMode::Local => SquadsModeKind::Local,
             SquadsMode::Http(_) => SquadsModeKind::Http,
+            SquadsMode::Native(_) => SquadsModeKind::Native,
         }
     }
 }
@@ -966,6 +1290,63 @@ fn extract_status(value: &Value) -> Option<String> {
     .map(|raw| raw.to_lowercase())
 }
 
+/// Derives the multisig create key from a KMS signature over the idempotency
+/// key. Ed25519 signatures are deterministic, so retries reuse the same key,
+/// while nobody without the attn key can claim the multisig address first.
+async fn derive_create_key<C: KmsClient>(
+    attn_signer: &KmsSigner<C>,
+    idempotency_key: &str,
+) -> Result<Keypair> {
+    let message = format!("attn-squads-create-key:{idempotency_key}");
+    let signature = attn_signer.sign(message.as_bytes()).await?;
+    let seed: [u8; 32] = Sha256::digest(signature.as_ref()).into();
+    Ok(Keypair::new_from_array(seed))
+}
+
+/// `multisig_create_v2` for a creator safe: the creator and attn wallets with
+/// every permission, no config authority or time lock, paid for by `attn`.
+fn creator_safe_instruction(
+    treasury: Pubkey,
+    create_key: Pubkey,
+    creator: Pubkey,
+    attn: Pubkey,
+    threshold: u8,
+    memo: Option<String>,
+) -> Result<Instruction> {
+    build_multisig_create_v2_ix(
+        treasury,
+        create_key,
+        attn,
+        &MultisigCreateArgsV2 {
+            config_authority: None,
+            threshold: u16::from(threshold),
+            members: [creator, attn]
+                .into_iter()
+                .map(|key| Member {
+                    key,
+                    permissions: Permissions {
+                        mask: Permissions::ALL,
+                    },
+                })
+                .collect(),
+            time_lock: 0,
+            rent_collector: None,
+            memo,
+        },
+    )
+}
+
+fn explorer_tx_url(signature: &Signature, cluster: &str) -> String {
+    if cluster == "mainnet-beta" {
+        format!("https://explorer.solana.com/tx/{}", signature)
+    } else {
+        format!(
+            "https://explorer.solana.com/tx/{}?cluster={}",
+            signature, cluster
+        )
+    }
+}
+
 fn generate_deterministic_address(creator: &str, attn: &str, cluster: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(b"attn-squads-local");
@@ -1120,6 +1501,7 @@ pub struct SafeRequestRecord {
     pub status_last_response: Option<Value>,
     pub status_last_response_hash: Option<String>,
     pub status_sync_error: Option<String>,
+    pub creation_signature: Option<String>,
     pub request_payload: Value,
     pub requester_api_key: Option<String>,
     pub requester_wallet: String,
@@ -1422,19 +1804,29 @@ impl SquadsSafeRepository {
         Ok(row_to_request(updated_row))
     }
 
+    /// Stores the upstream result. The request only becomes `ready` when a
+    /// safe address came back and `verification_error` is `None`; otherwise
+    /// it stays `submitted` with the error in `status_sync_error`.
     pub async fn update_submission(
         &self,
         request_id: Uuid,
         upstream: &CreateSafeResult,
         backoff: ChronoDuration,
+        verification_error: Option<&str>,
     ) -> Result<SafeRequestRecord> {
         let raw_response = upstream.raw_response.clone();
         let hash = hash_json(&raw_response);
-        let status = if upstream.safe_address.is_empty() {
+        let status = if upstream.safe_address.is_empty() || verification_error.is_some() {
             SafeStatus::Submitted
         } else {
             SafeStatus::Ready
         };
+        let verification_error = verification_error.map(|message| {
+            message
+                .chars()
+                .take(STATUS_ERROR_MAX_LEN)
+                .collect::<String>()
+        });
         let next_retry_at = if status == SafeStatus::Submitted {
             Some(Utc::now() + backoff)
         } else {
@@ -1461,7 +1853,8 @@ impl SquadsSafeRepository {
                 status_last_checked_at = now(),
                 status_last_response = $10,
                 status_last_response_hash = $11,
-                status_sync_error = null,
+                status_sync_error = $13,
+                creation_signature = coalesce($12, creation_signature),
                 updated_at = now()
              where id = $1
              returning *",
@@ -1481,6 +1874,8 @@ impl SquadsSafeRepository {
         .bind(upstream.status_url.clone())
         .bind(raw_response)
         .bind(hash)
+        .bind(upstream.creation_signature.clone())
+        .bind(verification_error)
         .fetch_one(&self.pool)
         .await?;
 
@@ -1653,32 +2048,6 @@ impl SquadsSafeRepository {
                 $15,
                 $15
             )
-            on conflict on constraint squads_safe_requests_uniqueness_idx
-            do update set
-                threshold = excluded.threshold,
-                safe_name = excluded.safe_name,
-                contact_email = excluded.contact_email,
-                note = excluded.note,
-                status = excluded.status,
-                safe_address = excluded.safe_address,
-                transaction_url = excluded.transaction_url,
-                members = excluded.members,
-                raw_response = excluded.raw_response,
-                raw_response_hash = excluded.raw_response_hash,
-                request_payload = excluded.request_payload,
-                creator_signature = excluded.creator_signature,
-                nonce = excluded.nonce,
-                last_attempt_at = excluded.last_attempt_at,
-                next_retry_at = excluded.next_retry_at,
-                status_url = excluded.status_url,
-                status_last_checked_at = excluded.status_last_checked_at,
-                status_last_response = excluded.status_last_response,
-                status_last_response_hash = excluded.status_last_response_hash,
-                status_sync_error = excluded.status_sync_error,
-                import_source = excluded.import_source,
-                import_metadata = excluded.import_metadata,
-                imported_at = excluded.imported_at,
-                updated_at = excluded.updated_at
             returning *",
             )
             .bind(Uuid::new_v4())
@@ -2058,6 +2427,75 @@ mod tests {
     use super::*;
     use httpmock::prelude::*;
     use serde_json::json;
+    use solana_sdk::message::{Message, VersionedMessage};
+
+    #[derive(Debug)]
+    struct KeypairKmsClient(Keypair);
+
+    #[async_trait::async_trait]
+    impl KmsClient for KeypairKmsClient {
+        async fn asymmetric_sign(&self, _resource_name: &str, message: &[u8]) -> Result<Vec<u8>> {
+            Ok(self.0.sign_message(message).as_ref().to_vec())
+        }
+    }
+
+    fn kms_keypair(seed: u8) -> (Pubkey, KmsSigner<KeypairKmsClient>) {
+        let keypair = Keypair::new_from_array([seed; 32]);
+        let pubkey = keypair.pubkey();
+        (
+            pubkey,
+            KmsSigner::new("resource", pubkey, KeypairKmsClient(keypair)),
+        )
+    }
+
+    #[tokio::test]
+    async fn native_create_transaction_is_fully_signed() {
+        let (attn, attn_signer) = kms_keypair(1);
+        let (payer, payer_signer) = kms_keypair(2);
+        let creator = Pubkey::new_unique();
+        let create_key = derive_create_key(&attn_signer, "idem-1").await.unwrap();
+        let again = derive_create_key(&attn_signer, "idem-1").await.unwrap();
+        assert_eq!(create_key.pubkey(), again.pubkey());
+        let other = derive_create_key(&attn_signer, "idem-2").await.unwrap();
+        assert_ne!(create_key.pubkey(), other.pubkey());
+
+        let instruction = creator_safe_instruction(
+            Pubkey::new_unique(),
+            create_key.pubkey(),
+            creator,
+            attn,
+            2,
+            None,
+        )
+        .unwrap();
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &[instruction],
+            Some(&payer),
+            &solana_sdk::hash::Hash::new_unique(),
+        ));
+        assert_eq!(message.header().num_required_signatures, 3);
+        let transaction =
+            sign_transaction(message.clone(), &[&attn_signer, &payer_signer, &create_key])
+                .await
+                .unwrap();
+        assert_eq!(transaction.message.static_account_keys()[0], payer);
+        assert!(transaction
+            .verify_with_results()
+            .into_iter()
+            .all(|valid| valid));
+
+        let err = sign_transaction(message, &[&attn_signer, &create_key])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no signer configured"));
+    }
+
+    #[test]
+    fn explorer_urls_include_non_mainnet_cluster() {
+        let signature = Signature::default();
+        assert!(!explorer_tx_url(&signature, "mainnet-beta").contains("cluster"));
+        assert!(explorer_tx_url(&signature, "devnet").ends_with("?cluster=devnet"));
+    }
 
     #[test]
     fn validates_base58_addresses() {
@@ -2118,6 +2556,7 @@ mod tests {
             allow_invalid_certs: false,
             create_paths,
             mode_override: None,
+            priority_fee: FeeStrategy::None,
         };
         let service = SquadsService::new(config).await.unwrap();
 
@@ -2192,6 +2631,7 @@ mod tests {
             allow_invalid_certs: false,
             create_paths,
             mode_override: None,
+            priority_fee: FeeStrategy::None,
         };
         let service = SquadsService::new(config).await.unwrap();
 
@@ -2218,6 +2658,204 @@ mod tests {
             Some("https://status/fallback")
         );
     }
+
+    const CREATOR: Pubkey = Pubkey::new_from_array([1; 32]);
+    const ATTN: Pubkey = Pubkey::new_from_array([2; 32]);
+
+    /// `Multisig` account bytes as laid out on chain by the Squads v4 program.
+    fn multisig_fixture(
+        config_authority: Pubkey,
+        threshold: u16,
+        members: &[(Pubkey, u8)],
+    ) -> Vec<u8> {
+        let mut data = vec![224, 116, 121, 186, 68, 161, 79, 236];
+        data.extend_from_slice(&[3; 32]);
+        data.extend_from_slice(config_authority.as_ref());
+        data.extend_from_slice(&threshold.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.push(0);
+        data.push(254);
+        data.extend_from_slice(&(members.len() as u32).to_le_bytes());
+        for (key, permissions) in members {
+            data.extend_from_slice(key.as_ref());
+            data.push(*permissions);
+        }
+        data
+    }
+
+    fn expected_creator_safe() -> ExpectedSafe {
+        ExpectedSafe {
+            creator_wallet: CREATOR,
+            attn_wallet: ATTN,
+            threshold: 2,
+        }
+    }
+
+    #[test]
+    fn check_multisig_account_accepts_creator_safe() {
+        let data = multisig_fixture(
+            Pubkey::default(),
+            2,
+            &[(CREATOR, Permissions::ALL), (ATTN, Permissions::ALL)],
+        );
+        assert!(
+            check_multisig_account(&SQUADS_PROGRAM_ID, &data, &expected_creator_safe()).is_empty()
+        );
+    }
+
+    #[test]
+    fn check_multisig_account_rejects_foreign_or_truncated_accounts() {
+        let data = multisig_fixture(Pubkey::default(), 2, &[(CREATOR, 7), (ATTN, 7)]);
+        let problems =
+            check_multisig_account(&Pubkey::new_unique(), &data, &expected_creator_safe());
+        assert!(
+            problems[0].contains("not the Squads v4 program"),
+            "{problems:?}"
+        );
+
+        let problems = check_multisig_account(
+            &SQUADS_PROGRAM_ID,
+            &data[..data.len() - 10],
+            &expected_creator_safe(),
+        );
+        assert!(
+            problems[0].contains("not a Squads v4 multisig"),
+            "{problems:?}"
+        );
+
+        let mut wrong = data.clone();
+        wrong[0] ^= 0xff;
+        let problems = check_multisig_account(&SQUADS_PROGRAM_ID, &wrong, &expected_creator_safe());
+        assert!(
+            problems[0].contains("not a Squads Multisig"),
+            "{problems:?}"
+        );
+    }
+
+    #[test]
+    fn check_multisig_account_reports_every_mismatch() {
+        // Creator is sole signer with a config authority that can rewrite members.
+        let data = multisig_fixture(
+            CREATOR,
+            1,
+            &[(CREATOR, Permissions::ALL), (ATTN, Permissions::VOTE)],
+        );
+        let problems = check_multisig_account(&SQUADS_PROGRAM_ID, &data, &expected_creator_safe());
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].starts_with("attn wallet"));
+        assert!(problems[1].contains("threshold is 1, expected 2"));
+        assert!(problems[2].contains("config authority"));
+
+        let data = multisig_fixture(Pubkey::default(), 2, &[(CREATOR, Permissions::ALL)]);
+        let problems = check_multisig_account(&SQUADS_PROGRAM_ID, &data, &expected_creator_safe());
+        assert_eq!(
+            problems,
+            vec![format!("attn wallet {ATTN} is not a member")]
+        );
+    }
+
+    /// Runs the native create transaction against the Squads v4 program
+    /// dumped to `tests/fixtures/squads_multisig_program.so` by
+    /// `scripts/dump-squads-program.sh` and checked against the hash pinned
+    /// next to it.
+    #[cfg(feature = "program-test")]
+    mod program_test {
+        use super::*;
+        use attn_client::squads::account_discriminator;
+        use solana_program_test::ProgramTest;
+        use solana_sdk::{account::Account, native_token::LAMPORTS_PER_SOL};
+
+        /// `ProgramConfig` with no creation fee, as on devnet.
+        fn program_config_account(treasury: &Pubkey) -> Account {
+            let mut data = account_discriminator("ProgramConfig").to_vec();
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+            data.extend_from_slice(&0u64.to_le_bytes());
+            data.extend_from_slice(treasury.as_ref());
+            data.extend_from_slice(&[0; 64]);
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data,
+                owner: SQUADS_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            }
+        }
+
+        #[tokio::test]
+        async fn native_create_transaction_creates_a_verified_multisig() {
+            let (attn, attn_signer) = kms_keypair(1);
+            let (payer, payer_signer) = kms_keypair(2);
+            let creator = Pubkey::new_unique();
+            let treasury = Pubkey::new_unique();
+
+            let mut program_test = ProgramTest::default();
+            program_test.prefer_bpf(true);
+            program_test.add_program("squads_multisig_program", SQUADS_PROGRAM_ID, None);
+            program_test.add_account(program_config_pda().0, program_config_account(&treasury));
+            // The system program id is all zeros.
+            for key in [attn, payer] {
+                program_test.add_account(
+                    key,
+                    Account::new(10 * LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+                );
+            }
+            let (banks, _, blockhash) = program_test.start().await;
+
+            let create_key = derive_create_key(&attn_signer, "idem-1").await.unwrap();
+            let instruction = creator_safe_instruction(
+                treasury,
+                create_key.pubkey(),
+                creator,
+                attn,
+                2,
+                Some("CreatorVault-test".to_string()),
+            )
+            .unwrap();
+            let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+                std::slice::from_ref(&instruction),
+                Some(&payer),
+                &blockhash,
+            ));
+            let transaction =
+                sign_transaction(message, &[&attn_signer, &payer_signer, &create_key])
+                    .await
+                    .unwrap();
+            banks.process_transaction(transaction).await.unwrap();
+
+            let (multisig, _) = multisig_pda(&create_key.pubkey());
+            let account = banks
+                .get_account(multisig)
+                .await
+                .unwrap()
+                .expect("multisig created");
+            let expected = ExpectedSafe {
+                creator_wallet: creator,
+                attn_wallet: attn,
+                threshold: 2,
+            };
+            assert_eq!(
+                check_multisig_account(&account.owner, &account.data, &expected),
+                Vec::<String>::new()
+            );
+            let decoded = MultisigAccount::from_account_data(&account.data).unwrap();
+            assert_eq!(decoded.create_key, create_key.pubkey());
+            assert!(decoded.is_autonomous());
+
+            // The create key seeds the address, so a replay cannot claim it again.
+            let blockhash = banks.get_latest_blockhash().await.unwrap();
+            let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+                &[instruction],
+                Some(&attn),
+                &blockhash,
+            ));
+            let replay = sign_transaction(message, &[&attn_signer, &create_key])
+                .await
+                .unwrap();
+            assert!(banks.process_transaction(replay).await.is_err());
+        }
+    }
 }
 
 fn row_to_request(row: sqlx::postgres::PgRow) -> SafeRequestRecord {
@@ -2247,6 +2885,7 @@ fn row_to_request(row: sqlx::postgres::PgRow) -> SafeRequestRecord {
         status_last_response: row.get("status_last_response"),
         status_last_response_hash: row.get("status_last_response_hash"),
         status_sync_error: row.get("status_sync_error"),
+        creation_signature: row.get("creation_signature"),
         request_payload: row.get("request_payload"),
         requester_api_key: row.get("requester_api_key"),
         requester_wallet: row.get("requester_wallet"),
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::time::Duration;
+
+use attn_indexer::stream::{StreamUpdate, Topic, STREAM_CHANNEL};
+use axum::response::sse::Event;
+use futures::stream::{self, Stream};
+use serde_json::json;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+/// Updates buffered per subscriber before it is reported as lagged.
+const STREAM_BUFFER: usize = 1024;
+/// Upper bound on topics a single `/v1/stream` connection may subscribe to.
+pub const MAX_STREAM_TOPICS: usize = 20;
+const LISTENER_RETRY_SECS: u64 = 5;
+
+/// In-process fan-out of [`StreamUpdate`]s to connected `/v1/stream` clients.
+#[derive(Clone)]
+pub struct StreamHub {
+    sender: broadcast::Sender<StreamUpdate>,
+}
+
+impl Default for StreamHub {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(STREAM_BUFFER);
+        Self { sender }
+    }
+}
+
+impl StreamHub {
+    pub fn publish(&self, update: StreamUpdate) {
+        // No receivers just means nobody is connected.
+        let _ = self.sender.send(update);
+    }
+
+    /// SSE events for the updates matching `topics`. A subscriber that falls
+    /// behind receives a `lagged` event and should refetch over REST.
+    pub fn events(
+        &self,
+        topics: HashSet<Topic>,
+    ) -> impl Stream<Item = Result<Event, Infallible>> + Send + 'static {
+        let receiver = self.sender.subscribe();
+        stream::unfold((receiver, topics), |(mut receiver, topics)| async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(update) if topics.contains(&update.topic) => update_event(&update),
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => Event::default()
+                        .event("lagged")
+                        .data(json!({ "skipped": skipped }).to_string()),
+                    Err(RecvError::Closed) => return None,
+                };
+                return Some((Ok(event), (receiver, topics)));
+            }
+        })
+    }
+}
+
+fn update_event(update: &StreamUpdate) -> Event {
+    let event = Event::default().event("update");
+    let event = match update.signature.as_deref() {
+        Some(signature) => event.id(signature),
+        None => event,
+    };
+    event.data(serde_json::to_string(update).unwrap_or_default())
+}
+
+/// Parses a comma separated `topics` query parameter.
+pub fn parse_topics(raw: &str) -> Result<HashSet<Topic>, String> {
+    let topics = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|topic| !topic.is_empty())
+        .map(str::parse)
+        .collect::<Result<HashSet<Topic>, String>>()?;
+    if topics.is_empty() {
+        return Err("at least one topic is required".to_string());
+    }
+    if topics.len() > MAX_STREAM_TOPICS {
+        return Err(format!("at most {MAX_STREAM_TOPICS} topics per stream"));
+    }
+    Ok(topics)
+}
+
+/// Relays `NOTIFY` payloads on [`STREAM_CHANNEL`] into `hub`, reconnecting
+/// after failures. Notifications sent while disconnected are lost.
+pub fn spawn_listener(pool: PgPool, hub: StreamHub) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = listen(&pool, &hub).await {
+                error!(error = ?err, "stream listener failed");
+            }
+            sleep(Duration::from_secs(LISTENER_RETRY_SECS)).await;
+        }
+    });
+}
+
+async fn listen(pool: &PgPool, hub: &StreamHub) -> anyhow::Result<()> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(STREAM_CHANNEL).await?;
+    info!(channel = STREAM_CHANNEL, "stream listener started");
+    loop {
+        let notification = listener.recv().await?;
+        match serde_json::from_str::<StreamUpdate>(notification.payload()) {
+            Ok(update) => hub.publish(update),
+            Err(err) => warn!(error = ?err, "dropping malformed stream notification"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_topics_validates_input() {
+        let topics = parse_topics("market:M1, attnusd,market:M1").unwrap();
+        assert_eq!(topics.len(), 2);
+        assert!(topics.contains(&Topic::AttnUsd));
+        assert!(parse_topics(" , ").is_err());
+        assert!(parse_topics("vault:V1").is_err());
+        let many = (0..=MAX_STREAM_TOPICS)
+            .map(|idx| format!("market:M{idx}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(parse_topics(&many).is_err());
+    }
+}
@@ -0,0 +1,894 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use attn_webhook::{
+    self as webhook, sign_payload, DELIVERY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use chrono::{DateTime, Duration, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Event types integrators can subscribe to.
+pub const WEBHOOK_EVENT_TYPES: [&str; 6] = [
+    "rfq.filled",
+    "squads_safe.ready",
+    "squads_safe.failed",
+    "squads_safe.governance_linked",
+    "vault.pause_changed",
+    "vault.lock_changed",
+];
+pub const MAX_ENDPOINTS_PER_KEY: usize = 20;
+
+pub const EVENT_HEADER: &str = "x-attn-event";
+
+const RETRY_BASE: StdDuration = StdDuration::from_secs(15);
+const RETRY_MAX: StdDuration = StdDuration::from_secs(6 * 3_600);
+/// Attempts before a delivery is marked `failed` (about a day of retries).
+const MAX_ATTEMPTS: i32 = 12;
+/// Claimed deliveries are hidden from other API instances for this long.
+const CLAIM_LEASE_SECS: i64 = 120;
+const DELIVERY_TIMEOUT_SECS: u64 = 10;
+/// Characters of the response body kept in `last_error`.
+const ERROR_BODY_MAX_LEN: usize = 512;
+
+/// A registered endpoint. The signing secret is only returned when it is
+/// created or rotated.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub description: Option<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip)]
+    pub api_key_id: String,
+    #[serde(skip)]
+    pub secret: String,
+}
+
+impl WebhookEndpoint {
+    fn accepts(&self, event_type: &str) -> bool {
+        self.active
+            && (self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWebhookEndpoint {
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WebhookEndpointUpdate {
+    pub url: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+    /// Replaces the signing secret when set.
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookEvent {
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    pub data: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub event_id: i64,
+    pub event_type: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Which past events a replay re-sends.
+#[derive(Debug, Clone)]
+pub enum ReplayTarget {
+    Event(i64),
+    Since(DateTime<Utc>),
+}
+
+/// A claimed outbox row ready to send.
+#[derive(Debug, Clone)]
+pub struct PendingDelivery {
+    pub id: i64,
+    pub attempts: i32,
+    pub endpoint: WebhookEndpoint,
+    pub event: WebhookEvent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeliveryOutcome {
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl DeliveryOutcome {
+    fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Endpoint registry and delivery outbox. Endpoints are scoped to the API
+/// key that registered them.
+#[async_trait]
+pub trait WebhookStore: Send + Sync {
+    async fn create_endpoint(
+        &self,
+        api_key_id: &str,
+        endpoint: NewWebhookEndpoint,
+        secret: String,
+    ) -> Result<WebhookEndpoint>;
+
+    async fn list_endpoints(&self, api_key_id: &str) -> Result<Vec<WebhookEndpoint>>;
+
+    async fn endpoint(&self, api_key_id: &str, id: Uuid) -> Result<Option<WebhookEndpoint>>;
+
+    async fn update_endpoint(
+        &self,
+        api_key_id: &str,
+        id: Uuid,
+        update: WebhookEndpointUpdate,
+    ) -> Result<Option<WebhookEndpoint>>;
+
+    async fn delete_endpoint(&self, api_key_id: &str, id: Uuid) -> Result<bool>;
+
+    async fn deliveries(&self, endpoint_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>>;
+
+    /// Queues fresh deliveries of past events to one endpoint, ignoring its
+    /// type filter for an explicit event id. Returns the number queued.
+    async fn replay(&self, endpoint: &WebhookEndpoint, target: ReplayTarget) -> Result<u64>;
+
+    async fn claim_due(&self, limit: i64) -> Result<Vec<PendingDelivery>>;
+
+    async fn record_attempt(
+        &self,
+        delivery_id: i64,
+        attempts: i32,
+        outcome: &DeliveryOutcome,
+    ) -> Result<()>;
+}
+
+pub fn generate_secret() -> String {
+    let token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+    format!("whsec_{token}")
+}
+
+/// Backoff after `attempts` failed deliveries: 15s doubling up to 6h.
+pub fn retry_delay(attempts: i32) -> StdDuration {
+    webhook::retry_delay(attempts, RETRY_BASE, RETRY_MAX)
+}
+
+#[derive(Default)]
+struct MemoryBook {
+    endpoints: HashMap<Uuid, WebhookEndpoint>,
+    events: Vec<WebhookEvent>,
+    deliveries: Vec<(Uuid, WebhookDelivery)>,
+    last_delivery_id: i64,
+}
+
+impl MemoryBook {
+    fn queue(&mut self, endpoint_id: Uuid, event: &WebhookEvent, now: DateTime<Utc>) {
+        self.last_delivery_id += 1;
+        self.deliveries.push((
+            endpoint_id,
+            WebhookDelivery {
+                id: self.last_delivery_id,
+                event_id: event.id,
+                event_type: event.event_type.clone(),
+                status: "pending".to_string(),
+                attempts: 0,
+                next_attempt_at: now,
+                last_status_code: None,
+                last_error: None,
+                created_at: now,
+                delivered_at: None,
+            },
+        ));
+    }
+}
+
+/// Process-local store used when the API runs without Postgres.
+#[derive(Default)]
+pub struct InMemoryWebhookStore {
+    book: RwLock<MemoryBook>,
+}
+
+impl InMemoryWebhookStore {
+    /// Records an event and queues it for every matching endpoint, mirroring
+    /// `enqueue_webhook_event` in Postgres.
+    #[cfg(test)]
+    pub async fn enqueue(&self, event_type: &str, data: Value) -> Result<i64> {
+        let now = Utc::now();
+        let mut book = self.book.write().await;
+        let event = WebhookEvent {
+            id: book.events.len() as i64 + 1,
+            event_type: event_type.to_string(),
+            created_at: now,
+            data,
+        };
+        let targets: Vec<Uuid> = book
+            .endpoints
+            .values()
+            .filter(|endpoint| endpoint.accepts(event_type))
+            .map(|endpoint| endpoint.id)
+            .collect();
+        for endpoint_id in targets {
+            book.queue(endpoint_id, &event, now);
+        }
+        book.events.push(event.clone());
+        Ok(event.id)
+    }
+}
+
+#[async_trait]
+impl WebhookStore for InMemoryWebhookStore {
+    async fn create_endpoint(
+        &self,
+        api_key_id: &str,
+        endpoint: NewWebhookEndpoint,
+        secret: String,
+    ) -> Result<WebhookEndpoint> {
+        let now = Utc::now();
+        let endpoint = WebhookEndpoint {
+            id: Uuid::new_v4(),
+            url: endpoint.url,
+            event_types: endpoint.event_types,
+            description: endpoint.description,
+            active: true,
+            created_at: now,
+            updated_at: now,
+            api_key_id: api_key_id.to_string(),
+            secret,
+        };
+        let mut book = self.book.write().await;
+        book.endpoints.insert(endpoint.id, endpoint.clone());
+        Ok(endpoint)
+    }
+
+    async fn list_endpoints(&self, api_key_id: &str) -> Result<Vec<WebhookEndpoint>> {
+        let book = self.book.read().await;
+        let mut endpoints: Vec<_> = book
+            .endpoints
+            .values()
+            .filter(|endpoint| endpoint.api_key_id == api_key_id)
+            .cloned()
+            .collect();
+        endpoints.sort_by_key(|endpoint| endpoint.created_at);
+        Ok(endpoints)
+    }
+
+    async fn endpoint(&self, api_key_id: &str, id: Uuid) -> Result<Option<WebhookEndpoint>> {
+        let book = self.book.read().await;
+        Ok(book
+            .endpoints
+            .get(&id)
+            .filter(|endpoint| endpoint.api_key_id == api_key_id)
+            .cloned())
+    }
+
+    async fn update_endpoint(
+        &self,
+        api_key_id: &str,
+        id: Uuid,
+        update: WebhookEndpointUpdate,
+    ) -> Result<Option<WebhookEndpoint>> {
+        let mut book = self.book.write().await;
+        let Some(endpoint) = book
+            .endpoints
+            .get_mut(&id)
+            .filter(|endpoint| endpoint.api_key_id == api_key_id)
+        else {
+            return Ok(None);
+        };
+        if let Some(url) = update.url {
+            endpoint.url = url;
+        }
+        if let Some(event_types) = update.event_types {
+            endpoint.event_types = event_types;
+        }
+        if let Some(description) = update.description {
+            endpoint.description = Some(description);
+        }
+        if let Some(active) = update.active {
+            endpoint.active = active;
+        }
+        if let Some(secret) = update.secret {
+            endpoint.secret = secret;
+        }
+        endpoint.updated_at = Utc::now();
+        Ok(Some(endpoint.clone()))
+    }
+
+    async fn delete_endpoint(&self, api_key_id: &str, id: Uuid) -> Result<bool> {
+        let mut book = self.book.write().await;
+        let owned = book
+            .endpoints
+            .get(&id)
+            .is_some_and(|endpoint| endpoint.api_key_id == api_key_id);
+        if owned {
+            book.endpoints.remove(&id);
+            book.deliveries
+                .retain(|(endpoint_id, _)| *endpoint_id != id);
+        }
+        Ok(owned)
+    }
+
+    async fn deliveries(&self, endpoint_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        let book = self.book.read().await;
+        Ok(book
+            .deliveries
+            .iter()
+            .rev()
+            .filter(|(id, _)| *id == endpoint_id)
+            .take(limit.max(0) as usize)
+            .map(|(_, delivery)| delivery.clone())
+            .collect())
+    }
+
+    async fn replay(&self, endpoint: &WebhookEndpoint, target: ReplayTarget) -> Result<u64> {
+        let now = Utc::now();
+        let mut book = self.book.write().await;
+        let events: Vec<WebhookEvent> = book
+            .events
+            .iter()
+            .filter(|event| match target {
+                ReplayTarget::Event(id) => event.id == id,
+                ReplayTarget::Since(since) => {
+                    event.created_at >= since && endpoint.accepts(&event.event_type)
+                }
+            })
+            .cloned()
+            .collect();
+        for event in &events {
+            book.queue(endpoint.id, event, now);
+        }
+        Ok(events.len() as u64)
+    }
+
+    async fn claim_due(&self, limit: i64) -> Result<Vec<PendingDelivery>> {
+        let now = Utc::now();
+        let mut book = self.book.write().await;
+        let MemoryBook {
+            endpoints,
+            events,
+            deliveries,
+            ..
+        } = &mut *book;
+        let mut claimed = Vec::new();
+        for (endpoint_id, delivery) in deliveries.iter_mut() {
+            if claimed.len() as i64 >= limit {
+                break;
+            }
+            if delivery.status != "pending" || delivery.next_attempt_at > now {
+                continue;
+            }
+            let (Some(endpoint), Some(event)) = (
+                endpoints.get(endpoint_id),
+                events.iter().find(|event| event.id == delivery.event_id),
+            ) else {
+                continue;
+            };
+            delivery.next_attempt_at = now + Duration::seconds(CLAIM_LEASE_SECS);
+            claimed.push(PendingDelivery {
+                id: delivery.id,
+                attempts: delivery.attempts,
+                endpoint: endpoint.clone(),
+                event: event.clone(),
+            });
+        }
+        Ok(claimed)
+    }
+
+    async fn record_attempt(
+        &self,
+        delivery_id: i64,
+        attempts: i32,
+        outcome: &DeliveryOutcome,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let mut book = self.book.write().await;
+        if let Some((_, delivery)) = book
+            .deliveries
+            .iter_mut()
+            .find(|(_, delivery)| delivery.id == delivery_id)
+        {
+            delivery.attempts = attempts;
+            delivery.last_status_code = outcome.status_code.map(i32::from);
+            delivery.last_error = outcome.error.clone();
+            if outcome.succeeded() {
+                delivery.status = "delivered".to_string();
+                delivery.delivered_at = Some(now);
+            } else if attempts >= MAX_ATTEMPTS {
+                delivery.status = "failed".to_string();
+            } else {
+                delivery.next_attempt_at =
+                    now + Duration::seconds(retry_delay(attempts).as_secs() as i64);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Postgres-backed store; events are enqueued by the triggers in
+/// `020_webhooks.sql`.
+#[derive(Clone)]
+pub struct PgWebhookStore {
+    pool: PgPool,
+}
+
+impl PgWebhookStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_endpoint(row: &PgRow) -> WebhookEndpoint {
+    WebhookEndpoint {
+        id: row.get("id"),
+        url: row.get("url"),
+        event_types: row.get("event_types"),
+        description: row.get("description"),
+        active: row.get("active"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        api_key_id: row.get("api_key_id"),
+        secret: row.get("secret"),
+    }
+}
+
+#[async_trait]
+impl WebhookStore for PgWebhookStore {
+    async fn create_endpoint(
+        &self,
+        api_key_id: &str,
+        endpoint: NewWebhookEndpoint,
+        secret: String,
+    ) -> Result<WebhookEndpoint> {
+        let row = sqlx::query(
+            "insert into webhook_endpoints (id, api_key_id, url, secret, event_types, description)
+             values ($1, $2, $3, $4, $5, $6)
+             returning *",
+        )
+        .bind(Uuid::new_v4())
+        .bind(api_key_id)
+        .bind(&endpoint.url)
+        .bind(&secret)
+        .bind(&endpoint.event_types)
+        .bind(&endpoint.description)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row_to_endpoint(&row))
+    }
+
+    async fn list_endpoints(&self, api_key_id: &str) -> Result<Vec<WebhookEndpoint>> {
+        let rows = sqlx::query(
+            "select * from webhook_endpoints where api_key_id = $1 order by created_at asc",
+        )
+        .bind(api_key_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(row_to_endpoint).collect())
+    }
+
+    async fn endpoint(&self, api_key_id: &str, id: Uuid) -> Result<Option<WebhookEndpoint>> {
+        let row = sqlx::query("select * from webhook_endpoints where id = $1 and api_key_id = $2")
+            .bind(id)
+            .bind(api_key_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(row_to_endpoint))
+    }
+
+    async fn update_endpoint(
+        &self,
+        api_key_id: &str,
+        id: Uuid,
+        update: WebhookEndpointUpdate,
+    ) -> Result<Option<WebhookEndpoint>> {
+        let row = sqlx::query(
+            "update webhook_endpoints set
+                url = coalesce($3, url),
+                event_types = coalesce($4, event_types),
+                description = coalesce($5, description),
+                active = coalesce($6, active),
+                secret = coalesce($7, secret),
+                updated_at = now()
+             where id = $1 and api_key_id = $2
+             returning *",
+        )
+        .bind(id)
+        .bind(api_key_id)
+        .bind(update.url)
+        .bind(update.event_types)
+        .bind(update.description)
+        .bind(update.active)
+        .bind(update.secret)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.as_ref().map(row_to_endpoint))
+    }
+
+    async fn delete_endpoint(&self, api_key_id: &str, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("delete from webhook_endpoints where id = $1 and api_key_id = $2")
+            .bind(id)
+            .bind(api_key_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn deliveries(&self, endpoint_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        let rows = sqlx::query(
+            "select o.*, e.event_type
+             from webhook_outbox o
+             join webhook_events e on e.id = o.event_id
+             where o.endpoint_id = $1
+             order by o.id desc
+             limit $2",
+        )
+        .bind(endpoint_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| WebhookDelivery {
+                id: row.get("id"),
+                event_id: row.get("event_id"),
+                event_type: row.get("event_type"),
+                status: row.get("status"),
+                attempts: row.get("attempts"),
+                next_attempt_at: row.get("next_attempt_at"),
+                last_status_code: row.get("last_status_code"),
+                last_error: row.get("last_error"),
+                created_at: row.get("created_at"),
+                delivered_at: row.get("delivered_at"),
+            })
+            .collect())
+    }
+
+    async fn replay(&self, endpoint: &WebhookEndpoint, target: ReplayTarget) -> Result<u64> {
+        let result = match target {
+            ReplayTarget::Event(event_id) => {
+                sqlx::query(
+                    "insert into webhook_outbox (endpoint_id, event_id)
+                     select $1, id from webhook_events where id = $2",
+                )
+                .bind(endpoint.id)
+                .bind(event_id)
+                .execute(&self.pool)
+                .await?
+            }
+            ReplayTarget::Since(since) => {
+                sqlx::query(
+                    "insert into webhook_outbox (endpoint_id, event_id)
+                     select $1, id from webhook_events
+                     where created_at >= $2
+                       and (cardinality($3::text[]) = 0 or event_type = any($3))
+                     order by id",
+                )
+                .bind(endpoint.id)
+                .bind(since)
+                .bind(&endpoint.event_types)
+                .execute(&self.pool)
+                .await?
+            }
+        };
+        Ok(result.rows_affected())
+    }
+
+    async fn claim_due(&self, limit: i64) -> Result<Vec<PendingDelivery>> {
+        let rows = sqlx::query(
+            "with due as (
+                select id from webhook_outbox
+                where status = 'pending' and next_attempt_at <= now()
+                order by next_attempt_at asc
+                limit $1
+                for update skip locked
+             ),
+             claimed as (
+                update webhook_outbox o
+                set next_attempt_at = now() + make_interval(secs => $2)
+                from due
+                where o.id = due.id
+                returning o.id as delivery_id, o.attempts, o.endpoint_id, o.event_id
+             )
+             select c.delivery_id, c.attempts,
+                    w.*,
+                    e.id as event_id, e.event_type, e.payload, e.created_at as event_created_at
+             from claimed c
+             join webhook_endpoints w on w.id = c.endpoint_id
+             join webhook_events e on e.id = c.event_id",
+        )
+        .bind(limit)
+        .bind(CLAIM_LEASE_SECS as f64)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| PendingDelivery {
+                id: row.get("delivery_id"),
+                attempts: row.get("attempts"),
+                endpoint: row_to_endpoint(&row),
+                event: WebhookEvent {
+                    id: row.get("event_id"),
+                    event_type: row.get("event_type"),
+                    created_at: row.get("event_created_at"),
+                    data: row.get("payload"),
+                },
+            })
+            .collect())
+    }
+
+    async fn record_attempt(
+        &self,
+        delivery_id: i64,
+        attempts: i32,
+        outcome: &DeliveryOutcome,
+    ) -> Result<()> {
+        let status = if outcome.succeeded() {
+            "delivered"
+        } else if attempts >= MAX_ATTEMPTS {
+            "failed"
+        } else {
+            "pending"
+        };
+        sqlx::query(
+            "update webhook_outbox set
+                status = $2,
+                attempts = $3,
+                last_status_code = $4,
+                last_error = $5,
+                next_attempt_at = case when $2 = 'pending'
+                    then now() + make_interval(secs => $6) else next_attempt_at end,
+                delivered_at = case when $2 = 'delivered' then now() else delivered_at end
+             where id = $1",
+        )
+        .bind(delivery_id)
+        .bind(status)
+        .bind(attempts)
+        .bind(outcome.status_code.map(i32::from))
+        .bind(&outcome.error)
+        .bind(retry_delay(attempts).as_secs_f64())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Drains the outbox, POSTing signed event envelopes to endpoints. Endpoint
+/// URLs are checked again at delivery and hostnames must still resolve to
+/// public addresses, unless `allow_private` is set.
+pub struct WebhookDispatcher {
+    store: Arc<dyn WebhookStore>,
+    client: reqwest::Client,
+    interval: StdDuration,
+    batch_size: i64,
+    allow_private: bool,
+}
+
+impl WebhookDispatcher {
+    pub fn new(
+        store: Arc<dyn WebhookStore>,
+        interval: StdDuration,
+        allow_private: bool,
+    ) -> Result<Self> {
+        let client = webhook::client(StdDuration::from_secs(DELIVERY_TIMEOUT_SECS), allow_private)?;
+        Ok(Self {
+            store,
+            client,
+            interval,
+            batch_size: 50,
+            allow_private,
+        })
+    }
+
+    pub fn spawn(self) {
+        tokio::spawn(async move {
+            info!(
+                interval_secs = self.interval.as_secs(),
+                "webhook dispatcher started"
+            );
+            let mut ticker = tokio::time::interval(self.interval);
+            loop {
+                ticker.tick().await;
+                if let Err(err) = self.drain().await {
+                    warn!(error = ?err, "webhook dispatch failed");
+                }
+            }
+        });
+    }
+
+    /// Sends every due delivery once; returns how many were attempted.
+    pub async fn drain(&self) -> Result<usize> {
+        let due = self.store.claim_due(self.batch_size).await?;
+        for delivery in &due {
+            let outcome = self.send(delivery).await;
+            if let Some(error) = &outcome.error {
+                warn!(
+                    delivery_id = delivery.id,
+                    endpoint = %delivery.endpoint.id,
+                    attempts = delivery.attempts + 1,
+                    error = %error,
+                    "webhook delivery failed"
+                );
+            }
+            self.store
+                .record_attempt(delivery.id, delivery.attempts + 1, &outcome)
+                .await?;
+        }
+        Ok(due.len())
+    }
+
+    async fn send(&self, delivery: &PendingDelivery) -> DeliveryOutcome {
+        let body = match serde_json::to_vec(&delivery.event) {
+            Ok(body) => body,
+            Err(err) => {
+                return DeliveryOutcome {
+                    status_code: None,
+                    error: Some(err.to_string()),
+                }
+            }
+        };
+        let url = match webhook::check_url(&delivery.endpoint.url, self.allow_private) {
+            Ok(url) => url,
+            Err(message) => {
+                return DeliveryOutcome {
+                    status_code: None,
+                    error: Some(message),
+                }
+            }
+        };
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_payload(&delivery.endpoint.secret, timestamp, &body);
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, format!("v1={signature}"))
+            .header(EVENT_HEADER, &delivery.event.event_type)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .body(body)
+            .send()
+            .await;
+        match response {
+            Ok(response) if response.status().is_success() => DeliveryOutcome {
+                status_code: Some(response.status().as_u16()),
+                error: None,
+            },
+            Ok(response) => {
+                let status = response.status();
+                let body: String = response
+                    .text()
+                    .await
+                    .unwrap_or_default()
+                    .chars()
+                    .take(ERROR_BODY_MAX_LEN)
+                    .collect();
+                DeliveryOutcome {
+                    status_code: Some(status.as_u16()),
+                    error: Some(format!("endpoint returned {status}: {body}")),
+                }
+            }
+            Err(err) => DeliveryOutcome {
+                status_code: None,
+                error: Some(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn retry_delay_doubles_and_caps() {
+        assert_eq!(retry_delay(1), StdDuration::from_secs(15));
+        assert_eq!(retry_delay(3), StdDuration::from_secs(60));
+        assert_eq!(retry_delay(MAX_ATTEMPTS), RETRY_MAX);
+        assert!(generate_secret().starts_with("whsec_"));
+    }
+
+    #[tokio::test]
+    async fn dispatcher_signs_deliveries_and_backs_off_on_failure() {
+        let server = MockServer::start_async().await;
+        let ok = server.mock(|when, then| {
+            when.method(POST)
+                .path("/ok")
+                .header(EVENT_HEADER, "rfq.filled")
+                .header_exists(SIGNATURE_HEADER)
+                .header_exists(TIMESTAMP_HEADER);
+            then.status(200);
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/down");
+            then.status(500).body("boom");
+        });
+
+        let store = Arc::new(InMemoryWebhookStore::default());
+        let endpoint = |path: &str, types: &[&str]| NewWebhookEndpoint {
+            url: server.url(path),
+            event_types: types.iter().map(|t| t.to_string()).collect(),
+            description: None,
+        };
+        let good = store
+            .create_endpoint("key", endpoint("/ok", &["rfq.filled"]), generate_secret())
+            .await
+            .unwrap();
+        let bad = store
+            .create_endpoint("key", endpoint("/down", &[]), generate_secret())
+            .await
+            .unwrap();
+        store
+            .enqueue("rfq.filled", json!({ "quote_id": "q1" }))
+            .await
+            .unwrap();
+        store
+            .enqueue("vault.pause_changed", json!({ "paused": true }))
+            .await
+            .unwrap();
+
+        // The mock server listens on loopback.
+        let dispatcher =
+            WebhookDispatcher::new(store.clone(), StdDuration::from_secs(1), true).unwrap();
+        assert_eq!(dispatcher.drain().await.unwrap(), 3);
+        ok.assert_hits(1);
+
+        let delivered = store.deliveries(good.id, 10).await.unwrap();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].status, "delivered");
+        let failed = store.deliveries(bad.id, 10).await.unwrap();
+        assert_eq!(failed.len(), 2);
+        assert!(failed
+            .iter()
+            .all(|d| d.status == "pending" && d.attempts == 1));
+        assert_eq!(failed[0].last_status_code, Some(500));
+        // Backed off, so nothing is due on the next pass.
+        assert_eq!(dispatcher.drain().await.unwrap(), 0);
+
+        assert_eq!(
+            store.replay(&good, ReplayTarget::Event(2)).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            store
+                .replay(&good, ReplayTarget::Since(Utc::now() - Duration::hours(1)))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(dispatcher.drain().await.unwrap(), 2);
+    }
+}
@@ -1,18 +1,40 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    io::{IsTerminal, Write},
+    str::FromStr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anchor_client::{Client, Cluster, Program};
 use anyhow::{anyhow, Context, Result};
-use attn_client::{creator, rewards, splitter as splitter_client, stable};
-use clap::{Parser, Subcommand};
-use shellexpand;
+use attn_client::{
+    alt, creator,
+    errors::ProgramError,
+    rewards, splitter as splitter_client, squads, stable,
+    tx::{self, FeeStrategy, SimulationFailed, TxSender},
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use clap::{Args, Parser, Subcommand};
+use output::{
+    note, OutputFormat, OutputMode, Report, SentTx, TxDetails, TxEncoding, TxOutcome, UnsignedTx,
+};
+use serde_json::json;
+use signing::{CliSigner, NonceConfig, SigningConfig};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient as AsyncRpcClient, rpc_config::RpcSimulateTransactionConfig,
+};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
+    hash::Hash,
     instruction::{AccountMeta, Instruction},
     pubkey,
     pubkey::Pubkey,
-    signature::{read_keypair_file, Keypair, Signature, Signer},
+    signature::{Keypair, Signature, Signer},
 };
-use tracing::{info, Level};
+use tracing::{warn, Level};
+
+mod output;
+mod signing;
 
 #[derive(Parser)]
 #[command(author, version, about = "attn.markets CLI", long_about = None)]
@@ -20,13 +42,59 @@ struct Cli {
     /// RPC URL to use
     #[arg(long = "url", global = true, default_value = "http://127.0.0.1:8899")]
     rpc_url: String,
-    /// Keypair used for signing transactions
+    /// Keypair used for signing transactions, or a pubkey whose signature is passed with --signer
     #[arg(
         long = "keypair",
         global = true,
         default_value = "~/.config/solana/id.json"
     )]
     keypair: String,
+    /// Skip confirmation prompts for admin actions
+    #[arg(long, short = 'y', global = true)]
+    yes: bool,
+    /// Format for command results
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+    /// Simulate transactions and report compute units, logs and errors instead of sending
+    #[arg(long, global = true, conflicts_with = "print_tx")]
+    dry_run: bool,
+    /// Print the transaction, signed by every local signer except the fee payer, instead of sending
+    #[arg(long, global = true, value_enum, value_name = "ENCODING")]
+    print_tx: Option<TxEncoding>,
+    /// Sign with the local keypairs and print PUBKEY=SIGNATURE pairs instead of sending
+    #[arg(
+        long,
+        global = true,
+        requires = "blockhash",
+        conflicts_with_all = ["dry_run", "print_tx"]
+    )]
+    sign_only: bool,
+    /// Blockhash to sign with; with --nonce, the value stored in the nonce account
+    #[arg(long, global = true, value_parser = signing::parse_hash)]
+    blockhash: Option<Hash>,
+    /// Signature collected with --sign-only; repeat for each offline signer
+    #[arg(
+        long = "signer",
+        global = true,
+        value_name = "PUBKEY=SIGNATURE",
+        value_parser = signing::parse_presigner
+    )]
+    presigners: Vec<(Pubkey, Signature)>,
+    /// Durable nonce account to use instead of a recent blockhash
+    #[arg(long, global = true, value_parser = parse_pubkey)]
+    nonce: Option<Pubkey>,
+    /// Nonce authority keypair or pubkey; defaults to --keypair
+    #[arg(long, global = true, requires = "nonce")]
+    nonce_authority: Option<String>,
+    /// Compute-unit price: none, micro-lamports, or pNN of recent fees [default: p50]
+    #[arg(long, global = true, value_name = "FEE")]
+    priority_fee: Option<FeeStrategy>,
+    /// Compute-unit limit to request instead of estimating it by simulation
+    #[arg(long, global = true, value_name = "UNITS")]
+    compute_unit_limit: Option<u32>,
+    /// Address lookup table to compile a v0 transaction against; repeatable
+    #[arg(long = "lookup-table", global = true, value_parser = parse_pubkey)]
+    lookup_tables: Vec<Pubkey>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -61,6 +129,16 @@ enum Commands {
         #[arg(long)]
         amount: u64,
     },
+    /// Splitter market lifecycle helpers
+    Market {
+        #[command(subcommand)]
+        command: MarketCommands,
+    },
+    /// Address lookup tables for v0 transactions (use with --lookup-table)
+    Alt {
+        #[command(subcommand)]
+        command: AltCommands,
+    },
     /// Split SY into PT & YT for a given market
     Split {
         #[arg(long = "market", value_parser = parse_pubkey)]
@@ -87,6 +165,11 @@ enum Commands {
         #[command(subcommand)]
         command: CreatorCommands,
     },
+    /// Squads multisig proposals for vault admin actions
+    Squads {
+        #[command(subcommand)]
+        command: SquadsCommands,
+    },
 }
 
 #[derive(Subcommand)]
@@ -100,6 +183,198 @@ enum StableVaultCommands {
         #[arg(long = "stable-mint", value_parser = parse_pubkey)]
         stable_mint: Pubkey,
     },
+    /// Show StableVault state, balances and share price
+    Show {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+    },
+    /// Initialize a StableVault seeded by the payer, who becomes the keeper
+    Initialize {
+        #[arg(long = "stable-mint", value_parser = parse_pubkey)]
+        stable_mint: Pubkey,
+        /// Additional accepted mints; the primary stable mint is always included
+        #[arg(long = "accepted-mint", value_parser = parse_pubkey)]
+        accepted_mints: Vec<Pubkey>,
+        #[arg(long = "admin", value_parser = parse_pubkey)]
+        admin: Option<Pubkey>,
+    },
+    /// Deposit the primary stable mint and receive attnUSD
+    Deposit {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// The vault's primary stable mint; required with --sign-only, which skips reading the vault
+        #[arg(long = "stable-mint", value_parser = parse_pubkey)]
+        stable_mint: Option<Pubkey>,
+    },
+    /// Burn attnUSD and receive the primary stable mint
+    Redeem {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long)]
+        shares: u64,
+        /// The vault's primary stable mint; required with --sign-only, which skips reading the vault
+        #[arg(long = "stable-mint", value_parser = parse_pubkey)]
+        stable_mint: Option<Pubkey>,
+    },
+    /// Split swept SOL fees between rewards and the conversion buffer; requires keeper signer
+    SweepFees {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long = "operation-id")]
+        operation_id: u64,
+        /// Keypair of the rewards pool allowed funder; defaults to --keypair
+        #[arg(long = "allowed-funder-keypair")]
+        allowed_funder_keypair: Option<String>,
+        /// The vault's primary stable mint; required with --sign-only, which skips reading the vault
+        #[arg(long = "stable-mint", value_parser = parse_pubkey)]
+        stable_mint: Option<Pubkey>,
+    },
+    /// Settle a SOL to stable conversion into the treasury; requires keeper signer
+    ProcessConversion {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "amount-stable")]
+        amount_stable: u64,
+        #[arg(long = "sol-spent")]
+        sol_spent: u64,
+        #[arg(long = "operation-id")]
+        operation_id: u64,
+        /// Keypair that owns the converted stables; defaults to --keypair
+        #[arg(long = "conversion-authority-keypair")]
+        conversion_authority_keypair: Option<String>,
+        /// The vault's primary stable mint; required with --sign-only, which skips reading the vault
+        #[arg(long = "stable-mint", value_parser = parse_pubkey)]
+        stable_mint: Option<Pubkey>,
+    },
+    /// Hand StableVault admin to a new key; requires admin signer
+    UpdateAdmin {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "new-admin", value_parser = parse_pubkey)]
+        new_admin: Pubkey,
+    },
+    /// Rotate the keeper authority; requires admin or emergency admin signer
+    UpdateKeeper {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "new-keeper", value_parser = parse_pubkey)]
+        new_keeper: Pubkey,
+    },
+    /// Set or clear the emergency admin; requires admin signer
+    UpdateEmergencyAdmin {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(
+            long = "emergency-admin",
+            value_parser = parse_pubkey,
+            required_unless_present = "clear"
+        )]
+        emergency_admin: Option<Pubkey>,
+        /// Remove the emergency admin instead of setting one
+        #[arg(long, conflicts_with = "emergency_admin")]
+        clear: bool,
+    },
+    /// Pause or resume the vault; resuming requires the admin signer
+    SetPause {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long, action = clap::ArgAction::Set)]
+        paused: bool,
+    },
+    /// Accept an additional stable mint; requires admin or emergency admin signer
+    AddMint {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "mint", value_parser = parse_pubkey)]
+        mint: Pubkey,
+    },
+    /// Stop accepting a stable mint; requires admin or emergency admin signer
+    RemoveMint {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "mint", value_parser = parse_pubkey)]
+        mint: Pubkey,
+    },
+    /// Withdraw SOL held above the pending conversion buffer
+    WithdrawDust {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long = "destination", value_parser = parse_pubkey)]
+        destination: Option<Pubkey>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MarketCommands {
+    /// Create a PT/YT market backed by a CreatorVault; the payer funds rent
+    Create {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        /// Unix timestamp at which PT becomes redeemable
+        #[arg(long = "maturity-ts")]
+        maturity_ts: i64,
+    },
+    /// Close a market with no PT/YT outstanding; requires CreatorVault authority and admin signers
+    Close {
+        #[arg(long = "market", value_parser = parse_pubkey)]
+        market: Pubkey,
+        /// Keypair of the CreatorVault admin; defaults to --keypair
+        #[arg(long = "admin-keypair")]
+        admin_keypair: Option<String>,
+    },
+    /// Show a market and a user's position in it
+    Show {
+        #[arg(long = "market", value_parser = parse_pubkey)]
+        market: Pubkey,
+        /// Position owner; defaults to the payer
+        #[arg(long = "user", value_parser = parse_pubkey)]
+        user: Option<Pubkey>,
+    },
+    /// List markets, optionally only those backed by one CreatorVault
+    List {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Option<Pubkey>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AltCommands {
+    /// Create a lookup table owned by the payer, seeded with a CreatorVault's protocol accounts
+    Create {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        /// StableVault sweeping this CreatorVault; adds its PDAs
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Option<Pubkey>,
+        /// Slot deriving the table address; pass the same value to --sign-only and the online run
+        #[arg(long = "recent-slot")]
+        recent_slot: Option<u64>,
+    },
+    /// Add a CreatorVault's missing protocol accounts, or explicit addresses, to a table
+    Extend {
+        #[arg(long = "table", value_parser = parse_pubkey)]
+        table: Pubkey,
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Option<Pubkey>,
+        #[arg(long = "stable-vault", value_parser = parse_pubkey, requires = "creator_vault")]
+        stable_vault: Option<Pubkey>,
+        /// Extra address to add, e.g. a market; repeatable
+        #[arg(long = "address", value_parser = parse_pubkey)]
+        addresses: Vec<Pubkey>,
+    },
+    /// Show a lookup table's authority, status and addresses
+    Show {
+        #[arg(long = "table", value_parser = parse_pubkey)]
+        table: Pubkey,
+    },
 }
 
 #[derive(Subcommand)]
@@ -202,6 +477,30 @@ enum CreatorCommands {
         #[arg(long = "creator-vault", value_parser = parse_pubkey)]
         creator_vault: Pubkey,
     },
+    /// Allowlist a sweeper delegate with fee and rate limits; requires CreatorVault admin signer
+    AllowSweeper {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "delegate", value_parser = parse_pubkey)]
+        delegate: Pubkey,
+        #[command(flatten)]
+        limits: SweeperLimitArgs,
+    },
+    /// Remove a delegate from the sweeper allowlist; requires CreatorVault admin signer
+    RevokeSweeper {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "delegate", value_parser = parse_pubkey)]
+        delegate: Pubkey,
+    },
+    /// Claim accrued Pump.fun creator fees into the vault's wSOL fee vault
+    CollectFees {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        /// Keep cranking every N seconds instead of claiming once
+        #[arg(long = "interval-secs")]
+        interval_secs: Option<u64>,
+    },
     /// Sweep fees as the configured delegate
     DelegateSweep {
         #[arg(long = "creator-vault", value_parser = parse_pubkey)]
@@ -215,31 +514,466 @@ enum CreatorCommands {
     },
 }
 
+#[derive(Args, Clone, Copy)]
+struct SweeperLimitArgs {
+    /// Highest fee the authority may grant the delegate
+    #[arg(long = "max-fee-bps")]
+    max_fee_bps: u16,
+    /// Gross amount the delegate may sweep per window
+    #[arg(long = "max-amount-per-window")]
+    max_amount_per_window: u64,
+    #[arg(long = "window-secs", default_value_t = 86_400)]
+    window_secs: i64,
+    /// Minimum seconds between two sweeps
+    #[arg(long = "min-interval-secs", default_value_t = 3_600)]
+    min_interval_secs: i64,
+}
+
+impl From<SweeperLimitArgs> for creator::SweeperLimits {
+    fn from(args: SweeperLimitArgs) -> Self {
+        creator::SweeperLimits {
+            max_fee_bps: args.max_fee_bps,
+            max_amount_per_window: args.max_amount_per_window,
+            window_secs: args.window_secs,
+            min_interval_secs: args.min_interval_secs,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum SquadsCommands {
+    /// Propose an admin action signed by the multisig vault
+    Propose {
+        #[arg(long = "multisig", value_parser = parse_pubkey)]
+        multisig: Pubkey,
+        #[arg(long = "vault-index", default_value_t = 0)]
+        vault_index: u8,
+        #[arg(long)]
+        memo: Option<String>,
+        /// Skip the proposer's own approval
+        #[arg(long = "no-approve")]
+        no_approve: bool,
+        #[command(subcommand)]
+        action: AdminAction,
+    },
+    /// Approve a pending proposal as a multisig member
+    Approve {
+        #[arg(long = "multisig", value_parser = parse_pubkey)]
+        multisig: Pubkey,
+        #[arg(long = "index")]
+        index: u64,
+        #[arg(long)]
+        memo: Option<String>,
+    },
+    /// Execute an approved proposal
+    Execute {
+        #[arg(long = "multisig", value_parser = parse_pubkey)]
+        multisig: Pubkey,
+        #[arg(long = "index")]
+        index: u64,
+    },
+    /// List the most recent proposals and their approvals
+    ListProposals {
+        #[arg(long = "multisig", value_parser = parse_pubkey)]
+        multisig: Pubkey,
+        #[arg(long, default_value_t = 10)]
+        limit: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum AdminAction {
+    /// Pause or resume a CreatorVault
+    CreatorSetPause {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long, action = clap::ArgAction::Set)]
+        paused: bool,
+    },
+    /// Hand CreatorVault admin to a new key
+    CreatorUpdateAdmin {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "new-admin", value_parser = parse_pubkey)]
+        new_admin: Pubkey,
+    },
+    /// Allowlist a CreatorVault sweeper delegate with fee and rate limits
+    CreatorAllowSweeper {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "delegate", value_parser = parse_pubkey)]
+        delegate: Pubkey,
+        #[command(flatten)]
+        limits: SweeperLimitArgs,
+    },
+    /// Remove a delegate from a CreatorVault sweeper allowlist
+    CreatorRevokeSweeper {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "delegate", value_parser = parse_pubkey)]
+        delegate: Pubkey,
+    },
+    /// Set the share of SOL fees routed to rewards
+    CreatorSetRewardsSplit {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "sol-rewards-bps")]
+        sol_rewards_bps: u16,
+    },
+    /// Pause or resume the rewards pool of a CreatorVault
+    RewardsSetPause {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long, action = clap::ArgAction::Set)]
+        paused: bool,
+    },
+    /// Hand rewards pool admin to a new key
+    RewardsUpdateAdmin {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "new-admin", value_parser = parse_pubkey)]
+        new_admin: Pubkey,
+    },
+    /// Update the rewards pool reward bps
+    RewardsUpdateRewardBps {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "reward-bps")]
+        reward_bps: u16,
+    },
+    /// Update the key allowed to fund the rewards pool
+    RewardsUpdateAllowedFunder {
+        #[arg(long = "creator-vault", value_parser = parse_pubkey)]
+        creator_vault: Pubkey,
+        #[arg(long = "allowed-funder", value_parser = parse_pubkey)]
+        allowed_funder: Pubkey,
+    },
+    /// Pause or resume the StableVault
+    StableSetPause {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long, action = clap::ArgAction::Set)]
+        paused: bool,
+    },
+    /// Hand StableVault admin to a new key
+    StableUpdateAdmin {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "new-admin", value_parser = parse_pubkey)]
+        new_admin: Pubkey,
+    },
+    /// Rotate the StableVault keeper authority
+    StableUpdateKeeper {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "new-keeper", value_parser = parse_pubkey)]
+        new_keeper: Pubkey,
+    },
+    /// Set or clear the StableVault emergency admin
+    StableUpdateEmergencyAdmin {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        /// Omit to clear the emergency admin
+        #[arg(long = "emergency-admin", value_parser = parse_pubkey)]
+        emergency_admin: Option<Pubkey>,
+    },
+    /// Accept an additional stable mint on the StableVault
+    StableAddMint {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "mint", value_parser = parse_pubkey)]
+        mint: Pubkey,
+    },
+    /// Stop accepting a stable mint on the StableVault
+    StableRemoveMint {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long = "mint", value_parser = parse_pubkey)]
+        mint: Pubkey,
+    },
+    /// Withdraw StableVault SOL dust to a destination
+    StableWithdrawDust {
+        #[arg(long = "stable-vault", value_parser = parse_pubkey)]
+        stable_vault: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long = "destination", value_parser = parse_pubkey)]
+        destination: Pubkey,
+    },
+}
+
+impl AdminAction {
+    /// Builds the admin instruction with `admin` (the multisig vault) as signer.
+    fn instruction(&self, admin: Pubkey) -> Instruction {
+        match *self {
+            AdminAction::CreatorSetPause {
+                creator_vault,
+                paused,
+            } => creator::build_set_pause_ix(creator_vault, admin, paused),
+            AdminAction::CreatorUpdateAdmin {
+                creator_vault,
+                new_admin,
+            } => creator::build_update_admin_ix(creator_vault, admin, new_admin),
+            AdminAction::CreatorAllowSweeper {
+                creator_vault,
+                delegate,
+                limits,
+            } => creator::build_allow_sweeper_delegate_ix(
+                creator_vault,
+                admin,
+                delegate,
+                limits.into(),
+            ),
+            AdminAction::CreatorRevokeSweeper {
+                creator_vault,
+                delegate,
+            } => creator::build_revoke_sweeper_delegate_ix(creator_vault, admin, delegate),
+            AdminAction::CreatorSetRewardsSplit {
+                creator_vault,
+                sol_rewards_bps,
+            } => creator::build_set_rewards_split_ix(creator_vault, admin, sol_rewards_bps),
+            AdminAction::RewardsSetPause {
+                creator_vault,
+                paused,
+            } => rewards::build_set_pause_ix(
+                rewards::rewards_pool_pda(&creator_vault).0,
+                admin,
+                paused,
+            ),
+            AdminAction::RewardsUpdateAdmin {
+                creator_vault,
+                new_admin,
+            } => rewards::build_update_admin_ix(
+                rewards::rewards_pool_pda(&creator_vault).0,
+                admin,
+                new_admin,
+            ),
+            AdminAction::RewardsUpdateRewardBps {
+                creator_vault,
+                reward_bps,
+            } => rewards::build_update_reward_bps_ix(
+                rewards::rewards_pool_pda(&creator_vault).0,
+                admin,
+                reward_bps,
+            ),
+            AdminAction::RewardsUpdateAllowedFunder {
+                creator_vault,
+                allowed_funder,
+            } => rewards::build_update_allowed_funder_ix(
+                rewards::rewards_pool_pda(&creator_vault).0,
+                admin,
+                allowed_funder,
+            ),
+            AdminAction::StableSetPause {
+                stable_vault,
+                paused,
+            } => stable::build_set_pause_ix(stable_vault, admin, paused),
+            AdminAction::StableUpdateAdmin {
+                stable_vault,
+                new_admin,
+            } => stable::build_update_admin_ix(stable_vault, admin, new_admin),
+            AdminAction::StableUpdateKeeper {
+                stable_vault,
+                new_keeper,
+            } => stable::build_update_keeper_authority_ix(stable_vault, admin, new_keeper),
+            AdminAction::StableUpdateEmergencyAdmin {
+                stable_vault,
+                emergency_admin,
+            } => stable::build_update_emergency_admin_ix(stable_vault, admin, emergency_admin),
+            AdminAction::StableAddMint { stable_vault, mint } => {
+                stable::build_add_accepted_mint_ix(stable_vault, admin, mint)
+            }
+            AdminAction::StableRemoveMint { stable_vault, mint } => {
+                stable::build_remove_accepted_mint_ix(stable_vault, admin, mint)
+            }
+            AdminAction::StableWithdrawDust {
+                stable_vault,
+                amount,
+                destination,
+            } => stable::build_withdraw_sol_dust_ix(stable_vault, admin, destination, amount),
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+    run().await.map_err(with_program_error)
+}
+
+/// Leads with the decoded program error when a transaction failed on-chain or in
+/// preflight; the RPC error chain follows as its cause.
+fn with_program_error(err: anyhow::Error) -> anyhow::Error {
+    match ProgramError::find(&err) {
+        Some(program_error) => err.context(program_error.to_string()),
+        None => err,
+    }
+}
+
+async fn run() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_writer(std::io::stderr)
+        .init();
     let cli = Cli::parse();
-    let keypair_path = shellexpand::tilde(&cli.keypair).into_owned();
-    let payer = Arc::new(
-        read_keypair_file(&keypair_path)
-            .map_err(|err| anyhow!(err.to_string()))
-            .context("failed to read keypair from path")?,
-    );
+    output::init(OutputMode {
+        format: cli.output,
+        dry_run: cli.dry_run,
+        print_tx: cli.print_tx,
+        sign_only: cli.sign_only,
+    });
+    let payer = Arc::new(CliSigner::load(&cli.keypair)?);
+    let nonce = match cli.nonce {
+        Some(account) => Some(NonceConfig {
+            account,
+            authority: load_optional_keypair(cli.nonce_authority.clone(), &payer)?,
+        }),
+        None => None,
+    };
+    signing::init(SigningConfig {
+        payer: payer.clone(),
+        blockhash: cli.blockhash,
+        presigners: cli.presigners.clone(),
+        nonce,
+        priority_fee: cli.priority_fee,
+        compute_unit_limit: cli.compute_unit_limit,
+        lookup_tables: cli.lookup_tables.clone(),
+    });
     let cluster = Cluster::Custom(cli.rpc_url.clone(), cli.rpc_url.clone());
     let client = Client::new_with_options(cluster, payer.clone(), CommitmentConfig::confirmed());
 
     match cli.command {
-        Commands::Ping => info!("pong"),
+        Commands::Ping => Report::new("ping").message("pong").emit()?,
         Commands::StableVault { command } => match command {
             StableVaultCommands::Derive {
                 authority,
                 stable_mint,
             } => {
                 let pdas = stable::derive_pdas(&authority, &stable_mint);
-                println!("StableVault PDA: {}", pdas.stable_vault);
-                println!("Treasury PDA: {}", pdas.treasury);
-                println!("attnUSD mint PDA: {}", pdas.share_mint);
-                println!("SOL vault PDA: {}", pdas.sol_vault);
+                stable_pdas_report(Report::new("stable-vault derive"), &pdas).emit()?
+            }
+            StableVaultCommands::Show { stable_vault } => {
+                stable_show(&client, stable_vault).await?
+            }
+            StableVaultCommands::Initialize {
+                stable_mint,
+                accepted_mints,
+                admin,
+            } => {
+                stable_initialize(
+                    &client,
+                    payer.clone(),
+                    stable_mint,
+                    accepted_mints,
+                    admin,
+                    cli.yes,
+                )
+                .await?
+            }
+            StableVaultCommands::Deposit {
+                stable_vault,
+                amount,
+                stable_mint,
+            } => stable_deposit(&client, payer.clone(), stable_vault, stable_mint, amount).await?,
+            StableVaultCommands::Redeem {
+                stable_vault,
+                shares,
+                stable_mint,
+            } => stable_redeem(&client, payer.clone(), stable_vault, stable_mint, shares).await?,
+            StableVaultCommands::SweepFees {
+                stable_vault,
+                creator_vault,
+                amount,
+                operation_id,
+                allowed_funder_keypair,
+                stable_mint,
+            } => {
+                let allowed_funder = load_optional_keypair(allowed_funder_keypair, &payer)?;
+                stable_sweep_fees(
+                    &client,
+                    payer.clone(),
+                    allowed_funder,
+                    stable_vault,
+                    stable_mint,
+                    creator_vault,
+                    amount,
+                    operation_id,
+                )
+                .await?
+            }
+            StableVaultCommands::ProcessConversion {
+                stable_vault,
+                amount_stable,
+                sol_spent,
+                operation_id,
+                conversion_authority_keypair,
+                stable_mint,
+            } => {
+                let conversion_authority =
+                    load_optional_keypair(conversion_authority_keypair, &payer)?;
+                stable_process_conversion(
+                    &client,
+                    payer.clone(),
+                    conversion_authority,
+                    stable_vault,
+                    stable_mint,
+                    amount_stable,
+                    sol_spent,
+                    operation_id,
+                )
+                .await?
+            }
+            StableVaultCommands::UpdateAdmin {
+                stable_vault,
+                new_admin,
+            } => {
+                stable_update_admin(&client, payer.clone(), stable_vault, new_admin, cli.yes)
+                    .await?
+            }
+            StableVaultCommands::UpdateKeeper {
+                stable_vault,
+                new_keeper,
+            } => {
+                stable_update_keeper(&client, payer.clone(), stable_vault, new_keeper, cli.yes)
+                    .await?
+            }
+            StableVaultCommands::UpdateEmergencyAdmin {
+                stable_vault,
+                emergency_admin,
+                clear: _,
+            } => {
+                stable_update_emergency_admin(
+                    &client,
+                    payer.clone(),
+                    stable_vault,
+                    emergency_admin,
+                    cli.yes,
+                )
+                .await?
+            }
+            StableVaultCommands::SetPause {
+                stable_vault,
+                paused,
+            } => stable_set_pause(&client, payer.clone(), stable_vault, paused, cli.yes).await?,
+            StableVaultCommands::AddMint { stable_vault, mint } => {
+                stable_add_mint(&client, payer.clone(), stable_vault, mint, cli.yes).await?
+            }
+            StableVaultCommands::RemoveMint { stable_vault, mint } => {
+                stable_remove_mint(&client, payer.clone(), stable_vault, mint, cli.yes).await?
+            }
+            StableVaultCommands::WithdrawDust {
+                stable_vault,
+                amount,
+                destination,
+            } => {
+                stable_withdraw_dust(
+                    &client,
+                    payer.clone(),
+                    stable_vault,
+                    amount,
+                    destination,
+                    cli.yes,
+                )
+                .await?
             }
         },
         Commands::Rewards { command } => match command {
@@ -282,14 +1016,64 @@ async fn main() -> Result<()> {
         Commands::Wrap { pump_mint, amount } => {
             wrap(&client, payer.clone(), pump_mint, amount).await?
         }
-        Commands::Split { market, amount } => split(&client, payer.clone(), market, amount).await?,
-        Commands::RedeemYt {
-            market,
-            new_fee_index,
-        } => redeem_yield(&client, payer.clone(), market, new_fee_index).await?,
-        Commands::RedeemPt { market, amount } => {
-            redeem_principal(&client, payer.clone(), market, amount).await?
-        }
+        Commands::Market { command } => match command {
+            MarketCommands::Create {
+                creator_vault,
+                maturity_ts,
+            } => market_create(&client, payer.clone(), creator_vault, maturity_ts).await?,
+            MarketCommands::Close {
+                market,
+                admin_keypair,
+            } => {
+                let admin = load_optional_keypair(admin_keypair, &payer)?;
+                market_close(&client, payer.clone(), admin, market, cli.yes).await?
+            }
+            MarketCommands::Show { market, user } => {
+                market_show(&client, payer.clone(), market, user).await?
+            }
+            MarketCommands::List { creator_vault } => market_list(&client, creator_vault).await?,
+        },
+        Commands::Alt { command } => match command {
+            AltCommands::Create {
+                creator_vault,
+                stable_vault,
+                recent_slot,
+            } => {
+                alt_create(
+                    &client,
+                    payer.clone(),
+                    creator_vault,
+                    stable_vault,
+                    recent_slot,
+                )
+                .await?
+            }
+            AltCommands::Extend {
+                table,
+                creator_vault,
+                stable_vault,
+                addresses,
+            } => {
+                alt_extend(
+                    &client,
+                    payer.clone(),
+                    table,
+                    creator_vault,
+                    stable_vault,
+                    addresses,
+                )
+                .await?
+            }
+            AltCommands::Show { table } => alt_show(&client, table)?,
+        },
+        Commands::Split { market, amount } => split(&client, payer.clone(), market, amount).await?,
+        Commands::RedeemYt {
+            market,
+            new_fee_index,
+        } => redeem_yield(&client, payer.clone(), market, new_fee_index).await?,
+        Commands::RedeemPt { market, amount } => {
+            redeem_principal(&client, payer.clone(), market, amount).await?
+        }
         Commands::Creator { command } => match command {
             CreatorCommands::Initialize {
                 pump_creator,
@@ -333,6 +1117,22 @@ async fn main() -> Result<()> {
             CreatorCommands::ClearSweeper { creator_vault } => {
                 creator_clear_sweeper(&client, payer.clone(), creator_vault).await?
             }
+            CreatorCommands::AllowSweeper {
+                creator_vault,
+                delegate,
+                limits,
+            } => {
+                creator_allow_sweeper(&client, payer.clone(), creator_vault, delegate, limits)
+                    .await?
+            }
+            CreatorCommands::RevokeSweeper {
+                creator_vault,
+                delegate,
+            } => creator_revoke_sweeper(&client, payer.clone(), creator_vault, delegate).await?,
+            CreatorCommands::CollectFees {
+                creator_vault,
+                interval_secs,
+            } => creator_collect_fees(&client, payer.clone(), creator_vault, interval_secs).await?,
             CreatorCommands::DelegateSweep {
                 creator_vault,
                 amount,
@@ -355,14 +1155,963 @@ async fn main() -> Result<()> {
             amount,
             operation_id,
         } => rewards_fund(&client, payer.clone(), creator_vault, amount, operation_id).await?,
+        Commands::Squads { command } => match command {
+            SquadsCommands::Propose {
+                multisig,
+                vault_index,
+                memo,
+                no_approve,
+                action,
+            } => {
+                squads_propose(
+                    &client,
+                    payer.clone(),
+                    multisig,
+                    vault_index,
+                    memo,
+                    !no_approve,
+                    action,
+                )
+                .await?
+            }
+            SquadsCommands::Approve {
+                multisig,
+                index,
+                memo,
+            } => squads_approve(&client, payer.clone(), multisig, index, memo).await?,
+            SquadsCommands::Execute { multisig, index } => {
+                squads_execute(&client, payer.clone(), multisig, index).await?
+            }
+            SquadsCommands::ListProposals { multisig, limit } => {
+                squads_list_proposals(&client, multisig, limit)?
+            }
+        },
+    }
+
+    Ok(())
+}
+
+async fn stable_show(client: &Client<Arc<CliSigner>>, stable_vault: Pubkey) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = fetch_stable_vault(&program, stable_vault).await?;
+    let rpc = program.rpc();
+    let treasury_balance = token_balance(&program, &vault.treasury);
+    let sol_vault_lamports = rpc
+        .get_balance(&vault.sol_vault)
+        .context("failed to fetch SOL vault balance")?;
+    let accepted = vault
+        .accepted_mints
+        .iter()
+        .map(Pubkey::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let price = format_price(vault.price_per_share());
+    let dust = sol_vault_lamports.saturating_sub(vault.pending_sol);
+
+    Report::new("stable-vault show")
+        .line(format!("StableVault {}", stable_vault))
+        .field("stable_vault", stable_vault.to_string())
+        .line(format!("  Authority seed: {}", vault.authority_seed))
+        .field("authority_seed", vault.authority_seed.to_string())
+        .detail("  Admin", "admin", vault.admin)
+        .line(format!(
+            "  Emergency admin: {}",
+            display_optional_key(vault.emergency_admin)
+        ))
+        .field(
+            "emergency_admin",
+            vault.emergency_admin.map(|key| key.to_string()),
+        )
+        .detail("  Keeper", "keeper_authority", vault.keeper_authority)
+        .line(format!("  Paused: {}", vault.paused))
+        .field("paused", vault.paused)
+        .detail("  Stable mint", "stable_mint", vault.stable_mint)
+        .line(format!("  Accepted mints: {}", accepted))
+        .field(
+            "accepted_mints",
+            vault
+                .accepted_mints
+                .iter()
+                .map(Pubkey::to_string)
+                .collect::<Vec<_>>(),
+        )
+        .detail("  attnUSD mint", "share_mint", vault.share_mint)
+        .line(format!(
+            "  Treasury: {} (balance {})",
+            vault.treasury, treasury_balance
+        ))
+        .field("treasury", vault.treasury.to_string())
+        .field("treasury_balance", treasury_balance)
+        .line(format!("  Total assets: {}", vault.total_assets))
+        .field("total_assets", vault.total_assets)
+        .line(format!("  Total shares: {}", vault.total_shares))
+        .field("total_shares", vault.total_shares)
+        .detail("  Price per share", "price_per_share", price)
+        .line(format!(
+            "  SOL vault: {} ({} lamports, {} pending conversion, {} withdrawable dust)",
+            vault.sol_vault, sol_vault_lamports, vault.pending_sol, dust
+        ))
+        .field(
+            "sol_vault",
+            json!({
+                "address": vault.sol_vault.to_string(),
+                "lamports": sol_vault_lamports,
+                "pending_sol": vault.pending_sol,
+                "withdrawable_dust": dust,
+            }),
+        )
+        .line(format!("  Last sweep id: {}", vault.last_sweep_id))
+        .field("last_sweep_id", vault.last_sweep_id)
+        .line(format!(
+            "  Last conversion id: {}",
+            vault.last_conversion_id
+        ))
+        .field("last_conversion_id", vault.last_conversion_id)
+        .emit()
+}
+
+async fn stable_initialize(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_mint: Pubkey,
+    accepted_mints: Vec<Pubkey>,
+    admin: Option<Pubkey>,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let authority = payer.pubkey();
+    let admin = admin.unwrap_or(authority);
+    let mut mints = vec![stable_mint];
+    for mint in accepted_mints {
+        if !mints.contains(&mint) {
+            mints.push(mint);
+        }
+    }
+    if mints.len() > stable_vault::StableVault::MAX_ACCEPTED_MINTS {
+        return Err(anyhow!(
+            "StableVault accepts at most {} mints, got {}",
+            stable_vault::StableVault::MAX_ACCEPTED_MINTS,
+            mints.len()
+        ));
+    }
+
+    let (ix, pdas) =
+        stable::build_initialize_vault_ix(authority, stable_mint, mints.clone(), admin);
+    if program.rpc().get_account(&pdas.stable_vault).is_ok() {
+        return Err(anyhow!(
+            "StableVault {} already exists for authority {}",
+            pdas.stable_vault,
+            authority
+        ));
+    }
+
+    let summary = format!(
+        "Initialize StableVault {} with authority/keeper {}, admin {} and accepted mints [{}]",
+        pdas.stable_vault,
+        authority,
+        admin,
+        mints
+            .iter()
+            .map(Pubkey::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault initialize", "Aborted").emit();
+    }
+
+    let outcome = send_instructions(program, vec![ix]).await?;
+    stable_pdas_report(Report::new("stable-vault initialize"), &pdas)
+        .message("StableVault initialized")
+        .tx(outcome)
+        .emit()
+}
+
+fn stable_pdas_report(report: Report, pdas: &stable::StableVaultPdas) -> Report {
+    report
+        .detail("StableVault PDA", "stable_vault", pdas.stable_vault)
+        .detail("Treasury PDA", "treasury", pdas.treasury)
+        .detail("attnUSD mint PDA", "share_mint", pdas.share_mint)
+        .detail("SOL vault PDA", "sol_vault", pdas.sol_vault)
+}
+
+async fn stable_deposit(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    stable_mint: Option<Pubkey>,
+    amount: u64,
+) -> Result<()> {
+    require_offline_stable_mint(stable_vault, stable_mint)?;
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let (stable_mint, pdas) = stable_vault_keys(stable_vault, vault.as_ref(), stable_mint)?;
+    let user = payer.pubkey();
+    let user_stable_ata = associated_token_address(&user, &stable_mint);
+    let user_share_ata = associated_token_address(&user, &pdas.share_mint);
+
+    let mut shares = None;
+    if let Some(vault) = &vault {
+        if vault.paused {
+            return Err(anyhow!("StableVault {} is paused", stable_vault));
+        }
+        let balance = token_balance(&program, &user_stable_ata);
+        if balance < amount {
+            return Err(anyhow!(
+                "payer {} holds {} of stable mint {}, deposit needs {}",
+                user,
+                balance,
+                stable_mint,
+                amount
+            ));
+        }
+
+        let expected = vault.preview_deposit(amount)?;
+        note!(
+            "Depositing {} of {} for {} attnUSD at price per share {}",
+            amount,
+            stable_mint,
+            expected,
+            format_price(vault.price_per_share())
+        );
+        shares = Some(expected);
+    }
+
+    let instructions = vec![
+        create_associated_token_account_idempotent_ix(&user, &user, &stable_mint),
+        create_associated_token_account_idempotent_ix(&user, &user, &pdas.share_mint),
+        stable::build_deposit_stable_ix(
+            &pdas,
+            stable_mint,
+            user,
+            user_stable_ata,
+            user_share_ata,
+            amount,
+        ),
+    ];
+    let outcome = send_instructions(program, instructions).await?;
+    Report::new("stable-vault deposit")
+        .field("stable_vault", stable_vault.to_string())
+        .field("amount", amount)
+        .field("expected_shares", shares)
+        .message("StableVault deposit confirmed")
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_redeem(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    stable_mint: Option<Pubkey>,
+    shares: u64,
+) -> Result<()> {
+    require_offline_stable_mint(stable_vault, stable_mint)?;
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let (stable_mint, pdas) = stable_vault_keys(stable_vault, vault.as_ref(), stable_mint)?;
+    let user = payer.pubkey();
+    let user_stable_ata = associated_token_address(&user, &stable_mint);
+    let user_share_ata = associated_token_address(&user, &pdas.share_mint);
+
+    let mut assets = None;
+    if let Some(vault) = &vault {
+        if vault.paused {
+            return Err(anyhow!("StableVault {} is paused", stable_vault));
+        }
+        let balance = token_balance(&program, &user_share_ata);
+        if balance < shares {
+            return Err(anyhow!(
+                "payer {} holds {} attnUSD, redeem needs {}",
+                user,
+                balance,
+                shares
+            ));
+        }
+
+        let expected = vault.preview_redeem(shares)?;
+        note!(
+            "Redeeming {} attnUSD for {} of {} at price per share {}",
+            shares,
+            expected,
+            stable_mint,
+            format_price(vault.price_per_share())
+        );
+        assets = Some(expected);
+    }
+
+    let instructions = vec![
+        create_associated_token_account_idempotent_ix(&user, &user, &stable_mint),
+        stable::build_redeem_attnusd_ix(
+            &pdas,
+            stable_mint,
+            user,
+            user_stable_ata,
+            user_share_ata,
+            shares,
+        ),
+    ];
+    let outcome = send_instructions(program, instructions).await?;
+    Report::new("stable-vault redeem")
+        .field("stable_vault", stable_vault.to_string())
+        .field("shares", shares)
+        .field("expected_assets", assets)
+        .message("StableVault redeem confirmed")
+        .tx(outcome)
+        .emit()
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn stable_sweep_fees(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    allowed_funder: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    stable_mint: Option<Pubkey>,
+    creator_vault: Pubkey,
+    amount: u64,
+    operation_id: u64,
+) -> Result<()> {
+    require_offline_stable_mint(stable_vault, stable_mint)?;
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let (_, pdas) = stable_vault_keys(stable_vault, vault.as_ref(), stable_mint)?;
+    let keeper = payer.pubkey();
+    let rewards_pdas = rewards::derive_pdas(&creator_vault);
+
+    let mut split = None;
+    if let Some(vault) = &vault {
+        if vault.keeper_authority != keeper {
+            return Err(anyhow!(
+                "payer {} must match StableVault keeper {}",
+                keeper,
+                vault.keeper_authority
+            ));
+        }
+        if vault.paused {
+            return Err(anyhow!("StableVault {} is paused", stable_vault));
+        }
+        if operation_id == vault.last_sweep_id {
+            return Report::skipped(
+                "stable-vault sweep-fees",
+                format!(
+                    "Sweep operation {} already applied to StableVault {}; skipping transaction",
+                    operation_id, stable_vault
+                ),
+            )
+            .emit();
+        }
+        if operation_id < vault.last_sweep_id {
+            return Err(anyhow!(
+                "operation id {} is behind last sweep id {}",
+                operation_id,
+                vault.last_sweep_id
+            ));
+        }
+
+        let creator_program = client.program(creator_vault::ID)?;
+        let creator = creator::fetch_account(&creator_program, creator_vault)
+            .await
+            .context("failed to fetch creator vault account")?;
+        if creator.paused {
+            return Err(anyhow!("CreatorVault {} is paused", creator_vault));
+        }
+
+        let rewards_program = client.program(rewards_vault::ID)?;
+        let pool = rewards::fetch_pool(&rewards_program, rewards_pdas.rewards_pool)
+            .await
+            .context("failed to fetch rewards pool account")?;
+        if pool.allowed_funder != allowed_funder.pubkey() {
+            return Err(anyhow!(
+                "allowed funder {} must match rewards pool funder {}",
+                allowed_funder.pubkey(),
+                pool.allowed_funder
+            ));
+        }
+
+        let rewards_amount = (amount as u128 * creator.sol_rewards_bps as u128 / 10_000) as u64;
+        note!(
+            "Sweeping {} lamports from {}: {} to rewards ({} bps), {} to the conversion buffer",
+            amount,
+            allowed_funder.pubkey(),
+            rewards_amount,
+            creator.sol_rewards_bps,
+            amount - rewards_amount
+        );
+        split = Some((rewards_amount, amount - rewards_amount));
+    }
+
+    let ix = stable::build_sweep_creator_fees_ix(
+        &pdas,
+        keeper,
+        allowed_funder.pubkey(),
+        creator_vault,
+        rewards_pdas.rewards_pool,
+        rewards_pdas.sol_treasury,
+        amount,
+        operation_id,
+    );
+    let outcome = send_instructions_with_signers(program, vec![ix], vec![allowed_funder]).await?;
+    Report::new("stable-vault sweep-fees")
+        .field("stable_vault", stable_vault.to_string())
+        .field("operation_id", operation_id)
+        .field("rewards_amount", split.map(|(rewards, _)| rewards))
+        .field("conversion_amount", split.map(|(_, conversion)| conversion))
+        .message(format!("StableVault sweep {} confirmed", operation_id))
+        .tx(outcome)
+        .emit()
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn stable_process_conversion(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    conversion_authority: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    stable_mint: Option<Pubkey>,
+    amount_stable: u64,
+    sol_spent: u64,
+    operation_id: u64,
+) -> Result<()> {
+    require_offline_stable_mint(stable_vault, stable_mint)?;
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let (stable_mint, pdas) = stable_vault_keys(stable_vault, vault.as_ref(), stable_mint)?;
+    let keeper = payer.pubkey();
+    let conversion_source = associated_token_address(&conversion_authority.pubkey(), &stable_mint);
+
+    if let Some(vault) = &vault {
+        if vault.keeper_authority != keeper {
+            return Err(anyhow!(
+                "payer {} must match StableVault keeper {}",
+                keeper,
+                vault.keeper_authority
+            ));
+        }
+        if vault.paused {
+            return Err(anyhow!("StableVault {} is paused", stable_vault));
+        }
+        if operation_id == vault.last_conversion_id {
+            return Report::skipped(
+                "stable-vault process-conversion",
+                format!(
+                    "Conversion operation {} already applied to StableVault {}; skipping transaction",
+                    operation_id, stable_vault
+                ),
+            )
+            .emit();
+        }
+        if operation_id < vault.last_conversion_id {
+            return Err(anyhow!(
+                "operation id {} is behind last conversion id {}",
+                operation_id,
+                vault.last_conversion_id
+            ));
+        }
+        if sol_spent > vault.pending_sol {
+            return Err(anyhow!(
+                "sol spent {} exceeds pending SOL {}",
+                sol_spent,
+                vault.pending_sol
+            ));
+        }
+
+        let balance = token_balance(&program, &conversion_source);
+        if balance < amount_stable {
+            return Err(anyhow!(
+                "conversion source {} holds {} of {}, conversion needs {}",
+                conversion_source,
+                balance,
+                stable_mint,
+                amount_stable
+            ));
+        }
+
+        let new_assets = vault
+            .total_assets
+            .checked_add(amount_stable)
+            .ok_or_else(|| anyhow!("total assets overflow"))?;
+        note!(
+            "Converting {} lamports into {} of {}: pending SOL {} -> {}, price per share {} -> {}",
+            sol_spent,
+            amount_stable,
+            stable_mint,
+            vault.pending_sol,
+            vault.pending_sol - sol_spent,
+            format_price(vault.price_per_share()),
+            format_price(
+                stable::StableVaultAccount {
+                    total_assets: new_assets,
+                    ..vault.clone()
+                }
+                .price_per_share()
+            )
+        );
+    }
+
+    let ix = stable::build_process_conversion_ix(
+        &pdas,
+        keeper,
+        conversion_authority.pubkey(),
+        stable_mint,
+        conversion_source,
+        amount_stable,
+        sol_spent,
+        operation_id,
+    );
+    let outcome =
+        send_instructions_with_signers(program, vec![ix], vec![conversion_authority]).await?;
+    Report::new("stable-vault process-conversion")
+        .field("stable_vault", stable_vault.to_string())
+        .field("operation_id", operation_id)
+        .message(format!("StableVault conversion {} confirmed", operation_id))
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_update_admin(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    new_admin: Pubkey,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let admin = payer.pubkey();
+    if let Some(vault) = &vault {
+        ensure_stable_admin(vault, &admin, false)?;
+    }
+
+    let summary = format!(
+        "Hand StableVault {} admin from {} to {}",
+        stable_vault, admin, new_admin
+    );
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault update-admin", "Aborted").emit();
+    }
+
+    let ix = stable::build_update_admin_ix(stable_vault, admin, new_admin);
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("stable-vault update-admin")
+        .message(format!(
+            "StableVault {} admin updated to {}",
+            stable_vault, new_admin
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_update_keeper(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    new_keeper: Pubkey,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let authority = payer.pubkey();
+    let summary = match &vault {
+        Some(vault) => {
+            ensure_stable_admin(vault, &authority, true)?;
+            format!(
+                "Rotate StableVault {} keeper from {} to {}",
+                stable_vault, vault.keeper_authority, new_keeper
+            )
+        }
+        None => format!(
+            "Rotate StableVault {} keeper to {}",
+            stable_vault, new_keeper
+        ),
+    };
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault update-keeper", "Aborted").emit();
+    }
+
+    let ix = stable::build_update_keeper_authority_ix(stable_vault, authority, new_keeper);
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("stable-vault update-keeper")
+        .message(format!(
+            "StableVault {} keeper updated to {}",
+            stable_vault, new_keeper
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_update_emergency_admin(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    emergency_admin: Option<Pubkey>,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let admin = payer.pubkey();
+    let summary = match &vault {
+        Some(vault) => {
+            ensure_stable_admin(vault, &admin, false)?;
+            format!(
+                "Change StableVault {} emergency admin from {} to {}",
+                stable_vault,
+                display_optional_key(vault.emergency_admin),
+                display_optional_key(emergency_admin)
+            )
+        }
+        None => format!(
+            "Set StableVault {} emergency admin to {}",
+            stable_vault,
+            display_optional_key(emergency_admin)
+        ),
+    };
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault update-emergency-admin", "Aborted").emit();
+    }
+
+    let ix = stable::build_update_emergency_admin_ix(stable_vault, admin, emergency_admin);
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("stable-vault update-emergency-admin")
+        .message(format!(
+            "StableVault {} emergency admin set to {}",
+            stable_vault,
+            display_optional_key(emergency_admin)
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_set_pause(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    paused: bool,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let authority = payer.pubkey();
+    if let Some(vault) = &vault {
+        ensure_stable_admin(vault, &authority, paused)?;
+        if vault.paused == paused {
+            return Report::skipped(
+                "stable-vault set-pause",
+                format!(
+                    "StableVault {} already {}; skipping transaction",
+                    stable_vault,
+                    if paused { "paused" } else { "active" }
+                ),
+            )
+            .emit();
+        }
+    }
+
+    let summary = format!(
+        "{} StableVault {}",
+        if paused { "Pause" } else { "Resume" },
+        stable_vault
+    );
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault set-pause", "Aborted").emit();
+    }
+
+    let ix = stable::build_set_pause_ix(stable_vault, authority, paused);
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("stable-vault set-pause")
+        .message(format!(
+            "StableVault {} {}",
+            stable_vault,
+            if paused { "paused" } else { "resumed" }
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_add_mint(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    mint: Pubkey,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let authority = payer.pubkey();
+    if let Some(vault) = &vault {
+        ensure_stable_admin(vault, &authority, true)?;
+        if vault.accepted_mints.contains(&mint) {
+            return Err(anyhow!(
+                "mint {} is already accepted by StableVault {}",
+                mint,
+                stable_vault
+            ));
+        }
+        if vault.accepted_mints.len() >= stable_vault::StableVault::MAX_ACCEPTED_MINTS {
+            return Err(anyhow!(
+                "StableVault {} already accepts the maximum of {} mints",
+                stable_vault,
+                stable_vault::StableVault::MAX_ACCEPTED_MINTS
+            ));
+        }
+    }
+
+    let summary = format!("Accept mint {} on StableVault {}", mint, stable_vault);
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault add-mint", "Aborted").emit();
+    }
+
+    let ix = stable::build_add_accepted_mint_ix(stable_vault, authority, mint);
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("stable-vault add-mint")
+        .message(format!(
+            "Mint {} accepted on StableVault {}",
+            mint, stable_vault
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_remove_mint(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    mint: Pubkey,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let authority = payer.pubkey();
+    if let Some(vault) = &vault {
+        ensure_stable_admin(vault, &authority, true)?;
+        if mint == vault.stable_mint {
+            return Err(anyhow!(
+                "cannot remove primary stable mint {} from StableVault {}",
+                mint,
+                stable_vault
+            ));
+        }
+        if !vault.accepted_mints.contains(&mint) {
+            return Err(anyhow!(
+                "mint {} is not accepted by StableVault {}",
+                mint,
+                stable_vault
+            ));
+        }
+    }
+
+    let summary = format!(
+        "Stop accepting mint {} on StableVault {}",
+        mint, stable_vault
+    );
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault remove-mint", "Aborted").emit();
+    }
+
+    let ix = stable::build_remove_accepted_mint_ix(stable_vault, authority, mint);
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("stable-vault remove-mint")
+        .message(format!(
+            "Mint {} removed from StableVault {}",
+            mint, stable_vault
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn stable_withdraw_dust(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    stable_vault: Pubkey,
+    amount: u64,
+    destination: Option<Pubkey>,
+    yes: bool,
+) -> Result<()> {
+    let program = client.program(stable_vault::ID)?;
+    let vault = load_stable_vault(&program, stable_vault).await?;
+    let authority = payer.pubkey();
+    let destination = destination.unwrap_or(authority);
+    let summary = match &vault {
+        Some(vault) => {
+            ensure_stable_admin(vault, &authority, true)?;
+            let sol_vault_lamports = program
+                .rpc()
+                .get_balance(&vault.sol_vault)
+                .context("failed to fetch SOL vault balance")?;
+            let available = sol_vault_lamports.saturating_sub(vault.pending_sol);
+            if amount > available {
+                return Err(anyhow!(
+                    "only {} lamports of dust available above pending SOL {}",
+                    available,
+                    vault.pending_sol
+                ));
+            }
+            format!(
+                "Withdraw {} of {} lamports of SOL dust from StableVault {} to {}",
+                amount, available, stable_vault, destination
+            )
+        }
+        None => format!(
+            "Withdraw {} lamports of SOL dust from StableVault {} to {}",
+            amount, stable_vault, destination
+        ),
+    };
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("stable-vault withdraw-dust", "Aborted").emit();
     }
 
+    let ix = stable::build_withdraw_sol_dust_ix(stable_vault, authority, destination, amount);
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("stable-vault withdraw-dust")
+        .message(format!(
+            "Withdrew {} lamports of SOL dust to {}",
+            amount, destination
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn fetch_stable_vault(
+    program: &Program<Arc<CliSigner>>,
+    address: Pubkey,
+) -> Result<stable::StableVaultAccount> {
+    stable::fetch_vault(program, address)
+        .await
+        .context("failed to fetch StableVault account")
+}
+
+/// The StableVault account, or `None` under `--sign-only`, which runs without RPC;
+/// callers then skip the checks and previews that need its state.
+async fn load_stable_vault(
+    program: &Program<Arc<CliSigner>>,
+    address: Pubkey,
+) -> Result<Option<stable::StableVaultAccount>> {
+    if output::mode().sign_only {
+        return Ok(None);
+    }
+    fetch_stable_vault(program, address).await.map(Some)
+}
+
+/// The vault's primary stable mint and PDAs: from the account when it was loaded,
+/// else derived from the vault address and `--stable-mint`.
+fn stable_vault_keys(
+    stable_vault: Pubkey,
+    vault: Option<&stable::StableVaultAccount>,
+    stable_mint: Option<Pubkey>,
+) -> Result<(Pubkey, stable::StableVaultPdas)> {
+    match (vault, stable_mint) {
+        (Some(vault), Some(mint)) if mint != vault.stable_mint => Err(anyhow!(
+            "--stable-mint {} does not match StableVault {} stable mint {}",
+            mint,
+            stable_vault,
+            vault.stable_mint
+        )),
+        (Some(vault), _) => Ok((vault.stable_mint, vault.pdas(stable_vault))),
+        (None, Some(mint)) => Ok((mint, stable::vault_pdas(stable_vault, &mint))),
+        (None, None) => Err(missing_stable_mint(stable_vault)),
+    }
+}
+
+/// Fails a `--sign-only` run without `--stable-mint` before an RPC client exists.
+fn require_offline_stable_mint(stable_vault: Pubkey, stable_mint: Option<Pubkey>) -> Result<()> {
+    if output::mode().sign_only && stable_mint.is_none() {
+        return Err(missing_stable_mint(stable_vault));
+    }
     Ok(())
 }
 
+fn missing_stable_mint(stable_vault: Pubkey) -> anyhow::Error {
+    anyhow!(
+        "--sign-only does not read StableVault {}; pass its --stable-mint",
+        stable_vault
+    )
+}
+
+fn ensure_stable_admin(
+    vault: &stable::StableVaultAccount,
+    signer: &Pubkey,
+    allow_emergency: bool,
+) -> Result<()> {
+    if vault.admin == *signer || (allow_emergency && vault.emergency_admin == Some(*signer)) {
+        return Ok(());
+    }
+    if allow_emergency {
+        Err(anyhow!(
+            "payer {} must match StableVault admin {} or emergency admin {}",
+            signer,
+            vault.admin,
+            display_optional_key(vault.emergency_admin)
+        ))
+    } else {
+        Err(anyhow!(
+            "payer {} must match StableVault admin {}",
+            signer,
+            vault.admin
+        ))
+    }
+}
+
+fn format_price(scaled: u64) -> String {
+    let scale = stable_vault::PRICE_SCALE as u64;
+    format!("{}.{:09}", scaled / scale, scaled % scale)
+}
+
+fn token_balance<C>(program: &Program<C>, token_account: &Pubkey) -> u64
+where
+    C: std::ops::Deref + Clone,
+    C::Target: Signer + Sized,
+{
+    program
+        .rpc()
+        .get_token_account_balance(token_account)
+        .ok()
+        .and_then(|balance| balance.amount.parse().ok())
+        .unwrap_or(0)
+}
+
+fn display_optional_key(key: Option<Pubkey>) -> String {
+    key.map(|key| key.to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
+
+/// Prints `summary` and asks for confirmation unless `--yes` was passed or nothing
+/// will be sent (`--dry-run`, `--print-tx`).
+fn confirm_admin_action(yes: bool, summary: &str) -> Result<bool> {
+    note!("{summary}");
+    if yes || !output::mode().sends() {
+        return Ok(true);
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "stdin is not a terminal; pass --yes to confirm admin actions non-interactively"
+        ));
+    }
+    if output::mode().notes_to_stderr() {
+        eprint!("Proceed? [y/N] ");
+        std::io::stderr().flush()?;
+    } else {
+        print!("Proceed? [y/N] ");
+        std::io::stdout().flush()?;
+    }
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+fn load_optional_keypair(path: Option<String>, default: &Arc<CliSigner>) -> Result<Arc<CliSigner>> {
+    match path {
+        Some(path) => Ok(Arc::new(CliSigner::load(&path)?)),
+        None => Ok(default.clone()),
+    }
+}
+
 async fn rewards_initialize(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault: Pubkey,
     attn_mint: Pubkey,
     reward_bps: u16,
@@ -370,32 +2119,33 @@ async fn rewards_initialize(
 ) -> Result<()> {
     let program = client.program(rewards_vault::ID)?;
     let allowed = allowed_funder.unwrap_or(creator_vault);
-    let payer_clone = payer.clone();
+    let pdas = rewards::derive_pdas(&creator_vault);
+    let ix = rewards::build_initialize_pool_ix(
+        payer.pubkey(),
+        payer.pubkey(),
+        creator_vault,
+        attn_mint,
+        reward_bps,
+        allowed,
+        &pdas,
+    );
 
-    let pdas = tokio::task::spawn_blocking(move || {
-        let rewards_client = rewards::RewardsVaultClient::new(&program);
-        rewards_client.initialize_pool(
-            payer_clone.as_ref(),
-            payer_clone.as_ref(),
-            creator_vault,
-            attn_mint,
-            reward_bps,
-            allowed,
-        )
-    })
-    .await
-    .map_err(|err| anyhow!("blocking initialize failed: {err}"))?
-    .context("failed to initialize rewards pool")?;
-    println!("Rewards pool: {}", pdas.rewards_pool);
-    println!("sAttnUSD mint: {}", pdas.s_attn_mint);
-    println!("attnUSD vault: {}", pdas.attn_vault);
-    println!("SOL treasury: {}", pdas.sol_treasury);
-    Ok(())
+    let outcome = send_instructions(program, vec![ix])
+        .await
+        .context("failed to initialize rewards pool")?;
+    Report::new("rewards initialize")
+        .detail("Rewards pool", "rewards_pool", pdas.rewards_pool)
+        .detail("sAttnUSD mint", "s_attn_mint", pdas.s_attn_mint)
+        .detail("attnUSD vault", "attn_vault", pdas.attn_vault)
+        .detail("SOL treasury", "sol_treasury", pdas.sol_treasury)
+        .message("Rewards pool initialized")
+        .tx(outcome)
+        .emit()
 }
 
 async fn rewards_stake(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault: Pubkey,
     attn_mint: Pubkey,
     amount: u64,
@@ -427,14 +2177,16 @@ async fn rewards_stake(
     );
     instructions.push(stake_ix);
 
-    let sig = send_instructions(program, instructions).await?;
-    println!("Rewards stake transaction signature: {}", sig);
-    Ok(())
+    let outcome = send_instructions(program, instructions).await?;
+    Report::new("rewards stake")
+        .message("Rewards stake confirmed")
+        .tx(outcome)
+        .emit()
 }
 
 async fn rewards_unstake(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault: Pubkey,
     attn_mint: Pubkey,
     amount: u64,
@@ -464,115 +2216,570 @@ async fn rewards_unstake(
         pdas.sol_treasury,
         amount,
     );
-    instructions.push(unstake_ix);
+    instructions.push(unstake_ix);
+
+    let outcome = send_instructions(program, instructions).await?;
+    Report::new("rewards unstake")
+        .message("Rewards unstake confirmed")
+        .tx(outcome)
+        .emit()
+}
+
+async fn rewards_claim(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    creator_vault: Pubkey,
+) -> Result<()> {
+    let program = client.program(rewards_vault::ID)?;
+    let pdas = rewards::derive_pdas(&creator_vault);
+    let user = payer.pubkey();
+    let (stake_position, _) = rewards::stake_position_pda(&pdas.rewards_pool, &user);
+
+    let claim_ix =
+        rewards::build_claim_rewards_ix(pdas.rewards_pool, user, stake_position, pdas.sol_treasury);
+    let outcome = send_instructions(program, vec![claim_ix]).await?;
+    Report::new("rewards claim")
+        .message("Rewards claim confirmed")
+        .tx(outcome)
+        .emit()
+}
+
+async fn rewards_fund(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    creator_vault: Pubkey,
+    amount: u64,
+    operation_id: u64,
+) -> Result<()> {
+    let program = client.program(rewards_vault::ID)?;
+    let pdas = rewards::derive_pdas(&creator_vault);
+    let ix = rewards::build_fund_rewards_ix(
+        creator_vault,
+        pdas.rewards_pool,
+        payer.pubkey(),
+        pdas.sol_treasury,
+        amount,
+        operation_id,
+    );
+
+    let outcome = send_instructions(program, vec![ix])
+        .await
+        .context("failed to fund rewards")?;
+    Report::new("rewards fund")
+        .field("operation_id", operation_id)
+        .message("Rewards fund submitted")
+        .tx(outcome)
+        .emit()
+}
+
+fn rewards_derive(_client: &Client<Arc<CliSigner>>, creator_vault: Pubkey) -> Result<()> {
+    let pdas = rewards::derive_pdas(&creator_vault);
+    Report::new("rewards derive")
+        .line(format!("Rewards pool PDAs for {}:", creator_vault))
+        .field("creator_vault", creator_vault.to_string())
+        .detail("  Pool", "rewards_pool", pdas.rewards_pool)
+        .detail("  Authority", "rewards_authority", pdas.rewards_authority)
+        .detail("  sAttn mint", "s_attn_mint", pdas.s_attn_mint)
+        .detail("  attn vault", "attn_vault", pdas.attn_vault)
+        .detail("  SOL treasury", "sol_treasury", pdas.sol_treasury)
+        .emit()
+}
+
+async fn wrap(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    pump_mint: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let program = client.program(creator_vault::ID)?;
+    let (creator_vault_addr, _) = creator::creator_vault_pda(&pump_mint);
+    let creator_state = creator::fetch_account(&program, creator_vault_addr)
+        .await
+        .context("failed to fetch creator vault account")?;
+
+    let pdas = creator::derive_pdas(&pump_mint);
+    let user = payer.pubkey();
+    let user_quote_ata = associated_token_address(&user, &creator_state.quote_mint);
+    let user_sy_ata = associated_token_address(&user, &pdas.sy_mint);
+
+    let instructions = vec![
+        create_associated_token_account_idempotent_ix(
+            &payer.pubkey(),
+            &user,
+            &creator_state.quote_mint,
+        ),
+        create_associated_token_account_idempotent_ix(&payer.pubkey(), &user, &pdas.sy_mint),
+        creator::build_wrap_fees_ix(
+            pump_mint,
+            creator_state.quote_mint,
+            user,
+            user_quote_ata,
+            user_sy_ata,
+            amount,
+        ),
+    ];
+
+    let outcome = send_instructions(program, instructions).await?;
+    Report::new("wrap")
+        .message("Wrap confirmed")
+        .tx(outcome)
+        .emit()
+}
+
+async fn market_create(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    creator_vault: Pubkey,
+    maturity_ts: i64,
+) -> Result<()> {
+    let creator_program = client.program(creator_vault::ID)?;
+    let vault = creator::fetch_account(&creator_program, creator_vault)
+        .await
+        .context("failed to fetch creator vault account")?;
+    if vault.splitter_program != splitter::ID {
+        return Err(anyhow!(
+            "CreatorVault {} is wired to splitter program {}, expected {}",
+            creator_vault,
+            vault.splitter_program,
+            splitter::ID
+        ));
+    }
+    if maturity_ts <= 0 {
+        return Err(anyhow!("maturity timestamp must be positive"));
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    if maturity_ts <= now {
+        warn!(maturity_ts, now, "market maturity is already in the past");
+    }
+
+    let splitter_program = client.program(splitter::ID)?;
+    let (splitter_authority, _) = splitter_client::splitter_authority_pda(&creator_vault);
+    if splitter_program
+        .rpc()
+        .get_account(&splitter_authority)
+        .is_ok()
+    {
+        return Err(anyhow!(
+            "splitter authority {} already exists; CreatorVault {} already backs a market",
+            splitter_authority,
+            creator_vault
+        ));
+    }
+
+    let market = Arc::new(CliSigner::from(Keypair::new()));
+    let pt_mint = Arc::new(CliSigner::from(Keypair::new()));
+    let yt_mint = Arc::new(CliSigner::from(Keypair::new()));
+    let ix = splitter_client::build_create_market_ix(
+        &splitter_client::CreateMarketAccounts {
+            authority: payer.pubkey(),
+            creator_vault,
+            pump_mint: vault.pump_mint,
+            sy_mint: vault.sy_mint,
+            market: market.pubkey(),
+            pt_mint: pt_mint.pubkey(),
+            yt_mint: yt_mint.pubkey(),
+        },
+        maturity_ts,
+    );
+    let outcome = send_instructions_with_signers(
+        splitter_program,
+        vec![ix],
+        vec![market.clone(), pt_mint.clone(), yt_mint.clone()],
+    )
+    .await?;
+    Report::new("market create")
+        .detail("Market", "market", market.pubkey())
+        .detail("PT mint", "pt_mint", pt_mint.pubkey())
+        .detail("YT mint", "yt_mint", yt_mint.pubkey())
+        .detail(
+            "Splitter authority",
+            "splitter_authority",
+            splitter_authority,
+        )
+        .message("Market created")
+        .tx(outcome)
+        .emit()
+}
+
+async fn market_close(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    admin: Arc<CliSigner>,
+    market_pubkey: Pubkey,
+    yes: bool,
+) -> Result<()> {
+    let splitter_program = client.program(splitter::ID)?;
+    let market = splitter_client::fetch_market(&splitter_program, market_pubkey)
+        .await
+        .context("failed to fetch market account")?;
+    let creator_program = client.program(creator_vault::ID)?;
+    let vault = creator::fetch_account(&creator_program, market.creator_vault)
+        .await
+        .context("failed to fetch creator vault account")?;
+
+    let creator_authority = payer.pubkey();
+    if vault.authority != creator_authority {
+        return Err(anyhow!(
+            "payer {} must match CreatorVault authority {}",
+            creator_authority,
+            vault.authority
+        ));
+    }
+    if vault.admin != admin.pubkey() {
+        return Err(anyhow!(
+            "admin signer {} must match CreatorVault admin {}",
+            admin.pubkey(),
+            vault.admin
+        ));
+    }
+    if market.is_closed {
+        return Report::skipped(
+            "market close",
+            format!(
+                "Market {} already closed; skipping transaction",
+                market_pubkey
+            ),
+        )
+        .emit();
+    }
+    if market.total_pt_issued != 0 || market.total_yt_issued != 0 {
+        return Err(anyhow!(
+            "market {} still has {} PT and {} YT outstanding",
+            market_pubkey,
+            market.total_pt_issued,
+            market.total_yt_issued
+        ));
+    }
+
+    let summary = format!(
+        "Close market {} on CreatorVault {}; rent returns to {}",
+        market_pubkey, market.creator_vault, creator_authority
+    );
+    if !confirm_admin_action(yes, &summary)? {
+        return Report::skipped("market close", "Aborted").emit();
+    }
+
+    let ix = splitter_client::build_close_market_ix(
+        creator_authority,
+        admin.pubkey(),
+        market.creator_vault,
+        market_pubkey,
+        market.pt_mint,
+        market.yt_mint,
+    );
+    let outcome = send_instructions_with_signers(splitter_program, vec![ix], vec![admin]).await?;
+    Report::new("market close")
+        .message(format!("Market {} closed", market_pubkey))
+        .tx(outcome)
+        .emit()
+}
+
+async fn market_show(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    market_pubkey: Pubkey,
+    user: Option<Pubkey>,
+) -> Result<()> {
+    let splitter_program = client.program(splitter::ID)?;
+    let market = splitter_client::fetch_market(&splitter_program, market_pubkey)
+        .await
+        .context("failed to fetch market account")?;
+    let mut report = Report::new("market show");
+    for line in market_lines(market_pubkey, &market)? {
+        report = report.line(line);
+    }
+    report = report.field("market", market_json(market_pubkey, &market));
+
+    let user = user.unwrap_or_else(|| payer.pubkey());
+    let position = splitter_client::fetch_user_position(&splitter_program, market_pubkey, user)
+        .await
+        .context("failed to fetch user position")?;
+    match position {
+        Some(position) => {
+            let (address, _) = splitter_client::user_position_pda(&market_pubkey, &user);
+            let pending_yield = position.pending_yield_scaled / splitter::FEE_INDEX_SCALE;
+            report
+                .line(format!("Position {} for {}", address, user))
+                .line(format!("  Last fee index: {}", position.last_fee_index))
+                .line(format!(
+                    "  Pending yield: {} (scaled {})",
+                    pending_yield, position.pending_yield_scaled
+                ))
+                .field(
+                    "position",
+                    json!({
+                        "address": address.to_string(),
+                        "user": user.to_string(),
+                        "last_fee_index": position.last_fee_index.to_string(),
+                        "pending_yield": pending_yield.to_string(),
+                        "pending_yield_scaled": position.pending_yield_scaled.to_string(),
+                    }),
+                )
+                .emit()
+        }
+        None => report
+            .line(format!("No position for {}", user))
+            .field("position", None::<()>)
+            .emit(),
+    }
+}
+
+async fn market_list(client: &Client<Arc<CliSigner>>, creator_vault: Option<Pubkey>) -> Result<()> {
+    let splitter_program = client.program(splitter::ID)?;
+    let mut markets = splitter_client::fetch_markets(&splitter_program, creator_vault)
+        .await
+        .context("failed to list markets")?;
+    markets.sort_by_key(|(_, market)| market.maturity_ts);
+    let mut report = Report::new("market list").field(
+        "markets",
+        markets
+            .iter()
+            .map(|(address, market)| market_json(*address, market))
+            .collect::<Vec<_>>(),
+    );
+    if markets.is_empty() {
+        report = report.line("No markets found");
+    }
+    for (address, market) in &markets {
+        for line in market_lines(*address, market)? {
+            report = report.line(line);
+        }
+    }
+    report.emit()
+}
 
-    let sig = send_instructions(program, instructions).await?;
-    println!("Rewards unstake transaction signature: {}", sig);
-    Ok(())
+fn market_lines(address: Pubkey, market: &splitter_client::MarketAccount) -> Result<Vec<String>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let status = if market.is_closed {
+        "closed".to_string()
+    } else if market.maturity_ts <= now {
+        "matured".to_string()
+    } else {
+        format!("matures in {}s", market.maturity_ts - now)
+    };
+    Ok(vec![
+        format!("Market {} ({})", address, status),
+        format!("  CreatorVault: {}", market.creator_vault),
+        format!("  Pump mint: {}", market.pump_mint),
+        format!("  SY mint: {}", market.sy_mint),
+        format!("  PT mint: {}", market.pt_mint),
+        format!("  YT mint: {}", market.yt_mint),
+        format!("  Maturity: {}", market.maturity_ts),
+        format!("  Fee index: {}", market.fee_index),
+        format!("  PT issued: {}", market.total_pt_issued),
+        format!("  YT issued: {}", market.total_yt_issued),
+    ])
 }
 
-async fn rewards_claim(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+fn market_json(address: Pubkey, market: &splitter_client::MarketAccount) -> serde_json::Value {
+    json!({
+        "address": address.to_string(),
+        "creator_vault": market.creator_vault.to_string(),
+        "pump_mint": market.pump_mint.to_string(),
+        "sy_mint": market.sy_mint.to_string(),
+        "pt_mint": market.pt_mint.to_string(),
+        "yt_mint": market.yt_mint.to_string(),
+        "maturity_ts": market.maturity_ts,
+        "fee_index": market.fee_index.to_string(),
+        "total_pt_issued": market.total_pt_issued,
+        "total_yt_issued": market.total_yt_issued,
+        "is_closed": market.is_closed,
+    })
+}
+
+async fn alt_create(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault: Pubkey,
+    stable_vault: Option<Pubkey>,
+    recent_slot: Option<u64>,
 ) -> Result<()> {
-    let program = client.program(rewards_vault::ID)?;
-    let pdas = rewards::derive_pdas(&creator_vault);
-    let user = payer.pubkey();
-    let (stake_position, _) = rewards::stake_position_pda(&pdas.rewards_pool, &user);
-
-    let claim_ix =
-        rewards::build_claim_rewards_ix(pdas.rewards_pool, user, stake_position, pdas.sol_treasury);
-    let sig = send_instructions(program, vec![claim_ix]).await?;
-    println!("Rewards claim transaction signature: {}", sig);
-    Ok(())
+    let creator_program = client.program(creator_vault::ID)?;
+    let vault = creator::fetch_account(&creator_program, creator_vault)
+        .await
+        .context("failed to fetch creator vault account")?;
+    let addresses = alt::protocol_addresses(&creator_vault, &vault, stable_vault.as_ref());
+    let recent_slot = match recent_slot {
+        Some(slot) => slot,
+        None => creator_program
+            .rpc()
+            .get_slot_with_commitment(CommitmentConfig::finalized())
+            .context("failed to fetch recent slot")?,
+    };
+    let (create_ix, table) =
+        alt::build_create_table_ix(payer.pubkey(), payer.pubkey(), recent_slot);
+    let (first, rest) = addresses.split_at(addresses.len().min(alt::EXTEND_CHUNK));
+    let extend_ix =
+        alt::build_extend_table_ix(table, payer.pubkey(), payer.pubkey(), first.to_vec());
+    let outcome = send_instructions(creator_program, vec![create_ix, extend_ix]).await?;
+    let mut report = Report::new("alt create")
+        .detail("Lookup table", "lookup_table", table)
+        .detail("Recent slot", "recent_slot", recent_slot)
+        .field("addresses", pubkey_strings(first))
+        .line(format!("Addresses: {}", first.len()));
+    if !rest.is_empty() {
+        report = report
+            .line(format!(
+                "{} addresses left; run alt extend --table {} --creator-vault {}",
+                rest.len(),
+                table,
+                creator_vault
+            ))
+            .field("remaining", rest.len());
+    }
+    report
+        .message(format!(
+            "Created lookup table {} with {} addresses",
+            table,
+            first.len()
+        ))
+        .tx(outcome)
+        .emit()
 }
 
-async fn rewards_fund(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
-    creator_vault: Pubkey,
-    amount: u64,
-    operation_id: u64,
+async fn alt_extend(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    table: Pubkey,
+    creator_vault: Option<Pubkey>,
+    stable_vault: Option<Pubkey>,
+    extra: Vec<Pubkey>,
 ) -> Result<()> {
-    let program = client.program(rewards_vault::ID)?;
-    let pdas = rewards::derive_pdas(&creator_vault);
-    let pdas_clone = pdas.clone();
-    let payer_clone = payer.clone();
+    let creator_program = client.program(creator_vault::ID)?;
+    let lookup = fetch_lookup_table(&creator_program, table)?;
+    if !lookup.is_active() {
+        return Err(anyhow!(
+            "lookup table {} was deactivated at slot {}",
+            table,
+            lookup.deactivation_slot
+        ));
+    }
+    if lookup.authority != Some(payer.pubkey()) {
+        return Err(anyhow!(
+            "lookup table {} is owned by {}; extend it with that --keypair",
+            table,
+            lookup
+                .authority
+                .map(|authority| authority.to_string())
+                .unwrap_or_else(|| "nobody (frozen)".to_string())
+        ));
+    }
 
-    tokio::task::spawn_blocking(move || {
-        let rewards_client = rewards::RewardsVaultClient::new(&program);
-        rewards_client.fund_rewards(
-            creator_vault,
-            &pdas_clone,
-            payer_clone.as_ref(),
-            amount,
-            operation_id,
-        )
-    })
-    .await
-    .map_err(|err| anyhow!("blocking fund failed: {err}"))?
-    .context("failed to fund rewards")?;
-    println!("Rewards fund submitted");
-    Ok(())
-}
+    let mut wanted = Vec::new();
+    if let Some(creator_vault) = creator_vault {
+        let vault = creator::fetch_account(&creator_program, creator_vault)
+            .await
+            .context("failed to fetch creator vault account")?;
+        wanted.extend(alt::protocol_addresses(
+            &creator_vault,
+            &vault,
+            stable_vault.as_ref(),
+        ));
+    }
+    wanted.extend(extra);
+    if wanted.is_empty() {
+        return Err(anyhow!("pass --creator-vault or at least one --address"));
+    }
+    let missing = lookup.missing(&wanted);
+    if missing.is_empty() {
+        return Report::skipped("alt extend", "Lookup table already holds every address")
+            .field("lookup_table", table.to_string())
+            .emit();
+    }
+    if lookup.addresses.len() + missing.len() > alt::MAX_ADDRESSES {
+        return Err(anyhow!(
+            "lookup table {} holds {} addresses; adding {} would exceed {}",
+            table,
+            lookup.addresses.len(),
+            missing.len(),
+            alt::MAX_ADDRESSES
+        ));
+    }
 
-fn rewards_derive(_client: &Client<Arc<Keypair>>, creator_vault: Pubkey) -> Result<()> {
-    let pdas = rewards::derive_pdas(&creator_vault);
-    println!(
-        "Rewards pool PDAs for {}:\n  Pool: {}\n  Authority: {}\n  sAttn mint: {}\n  attn vault: {}\n  SOL treasury: {}",
-        creator_vault,
-        pdas.rewards_pool,
-        pdas.rewards_authority,
-        pdas.s_attn_mint,
-        pdas.attn_vault,
-        pdas.sol_treasury
-    );
-    Ok(())
+    let (chunk, rest) = missing.split_at(missing.len().min(alt::EXTEND_CHUNK));
+    let ix = alt::build_extend_table_ix(table, payer.pubkey(), payer.pubkey(), chunk.to_vec());
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    let mut report = Report::new("alt extend")
+        .detail("Lookup table", "lookup_table", table)
+        .field("added", pubkey_strings(chunk))
+        .line(format!("Adding: {}", chunk.len()));
+    if !rest.is_empty() {
+        report = report
+            .line(format!(
+                "{} addresses left; run alt extend again",
+                rest.len()
+            ))
+            .field("remaining", rest.len());
+    }
+    report
+        .message(format!(
+            "Extended lookup table {} with {} addresses",
+            table,
+            chunk.len()
+        ))
+        .tx(outcome)
+        .emit()
 }
 
-async fn wrap(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
-    pump_mint: Pubkey,
-    amount: u64,
-) -> Result<()> {
+fn alt_show(client: &Client<Arc<CliSigner>>, table: Pubkey) -> Result<()> {
     let program = client.program(creator_vault::ID)?;
-    let (creator_vault_addr, _) = creator::creator_vault_pda(&pump_mint);
-    let creator_state = creator::fetch_account(&program, creator_vault_addr)
-        .await
-        .context("failed to fetch creator vault account")?;
-
-    let pdas = creator::derive_pdas(&pump_mint);
-    let user = payer.pubkey();
-    let user_quote_ata = associated_token_address(&user, &creator_state.quote_mint);
-    let user_sy_ata = associated_token_address(&user, &pdas.sy_mint);
+    let lookup = fetch_lookup_table(&program, table)?;
+    let status = if lookup.is_active() {
+        "active".to_string()
+    } else {
+        format!("deactivated at slot {}", lookup.deactivation_slot)
+    };
+    let authority = lookup
+        .authority
+        .map(|authority| authority.to_string())
+        .unwrap_or_else(|| "none (frozen)".to_string());
+    let mut report = Report::new("alt show")
+        .line(format!("Lookup table {} ({})", table, status))
+        .line(format!("  Authority: {}", authority))
+        .line(format!(
+            "  Last extended slot: {}",
+            lookup.last_extended_slot
+        ))
+        .line(format!("  Addresses: {}", lookup.addresses.len()))
+        .field(
+            "lookup_table",
+            json!({
+                "address": table.to_string(),
+                "authority": lookup.authority.map(|authority| authority.to_string()),
+                "active": lookup.is_active(),
+                "deactivation_slot": lookup.deactivation_slot.to_string(),
+                "last_extended_slot": lookup.last_extended_slot,
+                "addresses": pubkey_strings(&lookup.addresses),
+            }),
+        );
+    for (index, address) in lookup.addresses.iter().enumerate() {
+        report = report.line(format!("  [{index:>3}] {address}"));
+    }
+    report.emit()
+}
 
-    let instructions = vec![
-        create_associated_token_account_idempotent_ix(
-            &payer.pubkey(),
-            &user,
-            &creator_state.quote_mint,
-        ),
-        create_associated_token_account_idempotent_ix(&payer.pubkey(), &user, &pdas.sy_mint),
-        creator::build_wrap_fees_ix(
-            pump_mint,
-            creator_state.quote_mint,
-            user,
-            user_quote_ata,
-            user_sy_ata,
-            amount,
-        ),
-    ];
+fn fetch_lookup_table<C>(program: &Program<C>, table: Pubkey) -> Result<alt::LookupTable>
+where
+    C: std::ops::Deref + Clone,
+    C::Target: Signer + Sized,
+{
+    let account = program
+        .rpc()
+        .get_account(&table)
+        .with_context(|| format!("failed to fetch lookup table {table}"))?;
+    if account.owner != alt::ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        return Err(anyhow!("{table} is not an address lookup table"));
+    }
+    alt::LookupTable::from_account_data(table, &account.data)
+}
 
-    let sig = send_instructions(program, instructions).await?;
-    println!("Wrap transaction signature: {}", sig);
-    Ok(())
+fn pubkey_strings(pubkeys: &[Pubkey]) -> Vec<String> {
+    pubkeys.iter().map(Pubkey::to_string).collect()
 }
 
 async fn split(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     market_pubkey: Pubkey,
     amount: u64,
 ) -> Result<()> {
@@ -613,17 +2820,22 @@ async fn split(
     );
     instructions.push(mint_ix);
 
-    let sig = send_instructions(splitter_program, instructions).await?;
-    println!(
-        "Split transaction signature: {}\nSplitter authority: {}\nUser position: {}",
-        sig, pdas.splitter_authority, pdas.user_position
-    );
-    Ok(())
+    let outcome = send_instructions(splitter_program, instructions).await?;
+    Report::new("split")
+        .detail(
+            "Splitter authority",
+            "splitter_authority",
+            pdas.splitter_authority,
+        )
+        .detail("User position", "user_position", pdas.user_position)
+        .message("Split confirmed")
+        .tx(outcome)
+        .emit()
 }
 
 async fn redeem_yield(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     market_pubkey: Pubkey,
     new_fee_index: Option<u128>,
 ) -> Result<()> {
@@ -663,14 +2875,16 @@ async fn redeem_yield(
     );
     instructions.push(ix);
 
-    let sig = send_instructions(splitter_program, instructions).await?;
-    println!("Redeem yield transaction signature: {}", sig);
-    Ok(())
+    let outcome = send_instructions(splitter_program, instructions).await?;
+    Report::new("redeem-yt")
+        .message("Redeem yield confirmed")
+        .tx(outcome)
+        .emit()
 }
 
 async fn redeem_principal(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     market_pubkey: Pubkey,
     amount: u64,
 ) -> Result<()> {
@@ -711,14 +2925,16 @@ async fn redeem_principal(
     );
     instructions.push(ix);
 
-    let sig = send_instructions(splitter_program, instructions).await?;
-    println!("Redeem principal transaction signature: {}", sig);
-    Ok(())
+    let outcome = send_instructions(splitter_program, instructions).await?;
+    Report::new("redeem-pt")
+        .message("Redeem principal confirmed")
+        .tx(outcome)
+        .emit()
 }
 
 async fn creator_lock(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     market_pubkey: Pubkey,
 ) -> Result<()> {
     let splitter_program = client.program(splitter::ID)?;
@@ -742,17 +2958,19 @@ async fn creator_lock(
 
     let ix =
         creator::build_lock_collateral_ix(market.creator_vault, admin, Some(market.maturity_ts));
-    let sig = send_instructions(creator_program, vec![ix]).await?;
-    println!(
-        "CreatorVault {} locked until maturity {} (tx: {})",
-        market.creator_vault, market.maturity_ts, sig
-    );
-    Ok(())
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    Report::new("creator lock")
+        .message(format!(
+            "CreatorVault {} locked until maturity {}",
+            market.creator_vault, market.maturity_ts
+        ))
+        .tx(outcome)
+        .emit()
 }
 
 async fn creator_unlock(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     market_pubkey: Pubkey,
 ) -> Result<()> {
     let splitter_program = client.program(splitter::ID)?;
@@ -775,25 +2993,27 @@ async fn creator_unlock(
     }
 
     if !vault.locked && vault.lock_expires_at == 0 {
-        println!(
-            "CreatorVault {} already unlocked; skipping transaction",
-            market.creator_vault
-        );
-        return Ok(());
+        return Report::skipped(
+            "creator unlock",
+            format!(
+                "CreatorVault {} already unlocked; skipping transaction",
+                market.creator_vault
+            ),
+        )
+        .emit();
     }
 
     let ix = creator::build_unlock_collateral_ix(market.creator_vault, admin);
-    let sig = send_instructions(creator_program, vec![ix]).await?;
-    println!(
-        "CreatorVault {} unlocked (tx: {})",
-        market.creator_vault, sig
-    );
-    Ok(())
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    Report::new("creator unlock")
+        .message(format!("CreatorVault {} unlocked", market.creator_vault))
+        .tx(outcome)
+        .emit()
 }
 
 async fn creator_withdraw(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault_pubkey: Pubkey,
     destination: Option<Pubkey>,
     amount: u64,
@@ -845,17 +3065,19 @@ async fn creator_withdraw(
     );
     instructions.push(withdraw_ix);
 
-    let sig = send_instructions(creator_program, instructions).await?;
-    println!(
-        "Withdrawn {} units from CreatorVault {} into {} (tx: {})",
-        amount, creator_vault_pubkey, destination, sig
-    );
-    Ok(())
+    let outcome = send_instructions(creator_program, instructions).await?;
+    Report::new("creator withdraw")
+        .message(format!(
+            "Withdrawn {} units from CreatorVault {} into {}",
+            amount, creator_vault_pubkey, destination
+        ))
+        .tx(outcome)
+        .emit()
 }
 
 async fn creator_set_sweeper(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault: Pubkey,
     delegate: Pubkey,
     fee_bps: u16,
@@ -874,18 +3096,42 @@ async fn creator_set_sweeper(
         ));
     }
 
+    let Some(allowance) =
+        creator::fetch_sweeper_allowance(&creator_program, creator_vault, delegate).await?
+    else {
+        return Err(anyhow!(
+            "delegate {} is not allowlisted; the CreatorVault admin must run `creator allow-sweeper` first",
+            delegate
+        ));
+    };
+    if allowance.revoked {
+        return Err(anyhow!(
+            "delegate {} was revoked; the CreatorVault admin must run `creator allow-sweeper` again",
+            delegate
+        ));
+    }
+    if fee_bps > allowance.limits.max_fee_bps {
+        return Err(anyhow!(
+            "fee {} bps exceeds the delegate's allowlisted maximum of {} bps",
+            fee_bps,
+            allowance.limits.max_fee_bps
+        ));
+    }
+
     let ix = creator::build_set_sweeper_delegate_ix(creator_vault, authority, delegate, fee_bps);
-    let sig = send_instructions(creator_program, vec![ix]).await?;
-    println!(
-        "Sweeper delegate {} set on CreatorVault {} with fee {} bps (tx: {})",
-        delegate, creator_vault, fee_bps, sig
-    );
-    Ok(())
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    Report::new("creator set-sweeper")
+        .message(format!(
+            "Sweeper delegate {} set on CreatorVault {} with fee {} bps",
+            delegate, creator_vault, fee_bps
+        ))
+        .tx(outcome)
+        .emit()
 }
 
 async fn creator_clear_sweeper(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault: Pubkey,
 ) -> Result<()> {
     let creator_program = client.program(creator_vault::ID)?;
@@ -903,17 +3149,77 @@ async fn creator_clear_sweeper(
     }
 
     let ix = creator::build_clear_sweeper_delegate_ix(creator_vault, authority);
-    let sig = send_instructions(creator_program, vec![ix]).await?;
-    println!(
-        "Cleared sweeper delegate for CreatorVault {} (tx: {})",
-        creator_vault, sig
-    );
-    Ok(())
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    Report::new("creator clear-sweeper")
+        .message(format!(
+            "Cleared sweeper delegate for CreatorVault {}",
+            creator_vault
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn creator_allow_sweeper(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    creator_vault: Pubkey,
+    delegate: Pubkey,
+    limits: SweeperLimitArgs,
+) -> Result<()> {
+    let creator_program = client.program(creator_vault::ID)?;
+    let vault = creator::fetch_account(&creator_program, creator_vault)
+        .await
+        .context("failed to fetch creator vault account")?;
+
+    let admin = payer.pubkey();
+    if vault.admin != admin {
+        return Err(anyhow!(
+            "payer {} must match CreatorVault admin {}",
+            admin,
+            vault.admin
+        ));
+    }
+
+    let ix =
+        creator::build_allow_sweeper_delegate_ix(creator_vault, admin, delegate, limits.into());
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    Report::new("creator allow-sweeper").message(format!("Sweeper delegate {} allowlisted on CreatorVault {}: max fee {} bps, {} units per {}s, {}s between sweeps", delegate, creator_vault, limits.max_fee_bps, limits.max_amount_per_window, limits.window_secs, limits.min_interval_secs)).tx(outcome).emit()
+}
+
+async fn creator_revoke_sweeper(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    creator_vault: Pubkey,
+    delegate: Pubkey,
+) -> Result<()> {
+    let creator_program = client.program(creator_vault::ID)?;
+    let vault = creator::fetch_account(&creator_program, creator_vault)
+        .await
+        .context("failed to fetch creator vault account")?;
+
+    let admin = payer.pubkey();
+    if vault.admin != admin {
+        return Err(anyhow!(
+            "payer {} must match CreatorVault admin {}",
+            admin,
+            vault.admin
+        ));
+    }
+
+    let ix = creator::build_revoke_sweeper_delegate_ix(creator_vault, admin, delegate);
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    Report::new("creator revoke-sweeper")
+        .message(format!(
+            "Revoked sweeper delegate {} on CreatorVault {}",
+            delegate, creator_vault
+        ))
+        .tx(outcome)
+        .emit()
 }
 
 async fn creator_delegate_sweep(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     creator_vault: Pubkey,
     amount: u64,
     destination: Option<Pubkey>,
@@ -940,6 +3246,39 @@ async fn creator_delegate_sweep(
         ));
     }
 
+    let Some(allowance) =
+        creator::fetch_sweeper_allowance(&creator_program, creator_vault, delegate).await?
+    else {
+        return Err(anyhow!(
+            "delegate {} is not on the CreatorVault sweeper allowlist",
+            delegate
+        ));
+    };
+    if allowance.revoked {
+        return Err(anyhow!(
+            "delegate {} was revoked from the CreatorVault sweeper allowlist",
+            delegate
+        ));
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let next_sweep_at = allowance
+        .last_sweep_ts
+        .saturating_add(allowance.limits.min_interval_secs);
+    if allowance.last_sweep_ts > 0 && now < next_sweep_at {
+        return Err(anyhow!(
+            "next delegate sweep allowed in {}s",
+            next_sweep_at - now
+        ));
+    }
+    let remaining = allowance.remaining_in_window(now);
+    if amount > remaining {
+        return Err(anyhow!(
+            "amount {} exceeds the {} units left in the current sweep window",
+            amount,
+            remaining
+        ));
+    }
+
     let creator_destination = destination
         .unwrap_or_else(|| associated_token_address(&vault.authority, &vault.quote_mint));
 
@@ -960,17 +3299,79 @@ async fn creator_delegate_sweep(
         amount,
         fee_destination,
     );
-    let sig = send_instructions(creator_program, vec![ix]).await?;
-    println!(
-        "Delegate {} swept {} units from CreatorVault {} (tx: {})",
-        delegate, amount, creator_vault, sig
+    let outcome = send_instructions(creator_program, vec![ix]).await?;
+    Report::new("creator delegate-sweep")
+        .message(format!(
+            "Delegate {} swept {} units from CreatorVault {}",
+            delegate, amount, creator_vault
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn creator_collect_fees(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    creator_vault: Pubkey,
+    interval_secs: Option<u64>,
+) -> Result<()> {
+    let creator_program = client.program(creator_vault::ID)?;
+    let vault = creator::fetch_account(&creator_program, creator_vault)
+        .await
+        .context("failed to fetch creator vault account")?;
+    if vault.source_kind != creator::RevenueSourceKind::PumpFun {
+        return Err(anyhow!(
+            "CreatorVault {} is fed by {:?}, not Pump.fun",
+            creator_vault,
+            vault.source_kind
+        ));
+    }
+    if vault.quote_mint != creator::WSOL_MINT {
+        return Err(anyhow!(
+            "CreatorVault {} quotes in {}; Pump.fun fees need a wSOL fee vault",
+            creator_vault,
+            vault.quote_mint
+        ));
+    }
+    let with_revenue_source = creator::fetch_revenue_source(&creator_program, creator_vault)
+        .await?
+        .is_some();
+    let ix = creator::build_collect_creator_fees_ix(
+        vault.pump_mint,
+        payer.pubkey(),
+        with_revenue_source,
     );
-    Ok(())
+
+    let report = |outcome| {
+        Report::new("creator collect-fees")
+            .message(format!(
+                "Collected Pump.fun creator fees into CreatorVault {}",
+                creator_vault
+            ))
+            .tx(outcome)
+    };
+    let Some(interval_secs) = interval_secs else {
+        let outcome = send_instructions(client.program(creator_vault::ID)?, vec![ix]).await?;
+        return report(outcome).emit();
+    };
+
+    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs.max(1)));
+    loop {
+        ticker.tick().await;
+        let result =
+            match send_instructions(client.program(creator_vault::ID)?, vec![ix.clone()]).await {
+                Ok(outcome) => report(outcome).emit(),
+                Err(err) => Err(err),
+            };
+        if let Err(err) = result {
+            warn!(%creator_vault, error = ?err, "fee collection failed");
+        }
+    }
 }
 
 async fn creator_initialize(
-    client: &Client<Arc<Keypair>>,
-    payer: Arc<Keypair>,
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
     pump_creator: Pubkey,
     pump_mint: Pubkey,
     quote_mint: Pubkey,
@@ -988,34 +3389,297 @@ async fn creator_initialize(
         admin_key,
     );
 
-    let sig = send_instructions(program, vec![ix]).await?;
-    println!(
-        "Creator vault initialized.\n  Vault: {}\n  Fee vault: {}\n  SY mint: {}\n  Signature: {}",
-        pdas.creator_vault, pdas.fee_vault, pdas.sy_mint, sig
-    );
-    Ok(())
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("creator initialize")
+        .detail("Vault", "creator_vault", pdas.creator_vault)
+        .detail("Fee vault", "fee_vault", pdas.fee_vault)
+        .detail("SY mint", "sy_mint", pdas.sy_mint)
+        .message("Creator vault initialized")
+        .tx(outcome)
+        .emit()
+}
+
+async fn squads_propose(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    multisig: Pubkey,
+    vault_index: u8,
+    memo: Option<String>,
+    approve: bool,
+    action: AdminAction,
+) -> Result<()> {
+    let program = client.program(squads::SQUADS_PROGRAM_ID)?;
+    let state = squads::fetch_multisig(&program.rpc(), &multisig)?;
+    let (vault, _) = squads::vault_pda(&multisig, vault_index);
+    let inner = action.instruction(vault);
+    let plan = squads::build_proposal(
+        multisig,
+        &state,
+        payer.pubkey(),
+        vault_index,
+        &[inner],
+        memo,
+        approve,
+    )?;
+
+    let outcome = send_instructions(program, plan.instructions).await?;
+    Report::new("squads propose")
+        .field("multisig", multisig.to_string())
+        .field("transaction_index", plan.transaction_index)
+        .detail("Vault", "vault", plan.vault)
+        .detail("Proposal", "proposal", plan.proposal)
+        .detail("Threshold", "threshold", state.threshold)
+        .message(format!(
+            "Proposal #{} created for multisig {}",
+            plan.transaction_index, multisig
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn squads_approve(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    multisig: Pubkey,
+    index: u64,
+    memo: Option<String>,
+) -> Result<()> {
+    let program = client.program(squads::SQUADS_PROGRAM_ID)?;
+    let ix = squads::build_proposal_approve_ix(multisig, index, payer.pubkey(), memo)?;
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("squads approve")
+        .message(format!(
+            "Approved proposal #{} on multisig {} as {}",
+            index,
+            multisig,
+            payer.pubkey()
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+async fn squads_execute(
+    client: &Client<Arc<CliSigner>>,
+    payer: Arc<CliSigner>,
+    multisig: Pubkey,
+    index: u64,
+) -> Result<()> {
+    let program = client.program(squads::SQUADS_PROGRAM_ID)?;
+    let rpc = program.rpc();
+    let proposal = squads::fetch_proposal(&rpc, &multisig, index)?;
+    if !matches!(proposal.status, squads::ProposalStatus::Approved { .. }) {
+        return Err(anyhow!(
+            "proposal #{} is {}, not approved",
+            index,
+            proposal.status
+        ));
+    }
+    let transaction = squads::fetch_vault_transaction(&rpc, &multisig, index)?;
+    let ix =
+        squads::build_vault_transaction_execute_ix(multisig, index, payer.pubkey(), &transaction)?;
+    let outcome = send_instructions(program, vec![ix]).await?;
+    Report::new("squads execute")
+        .message(format!(
+            "Executed proposal #{} on multisig {}",
+            index, multisig
+        ))
+        .tx(outcome)
+        .emit()
+}
+
+fn squads_list_proposals(
+    client: &Client<Arc<CliSigner>>,
+    multisig: Pubkey,
+    limit: u64,
+) -> Result<()> {
+    let program = client.program(squads::SQUADS_PROGRAM_ID)?;
+    let rpc = program.rpc();
+    let state = squads::fetch_multisig(&rpc, &multisig)?;
+    let mut report = Report::new("squads list-proposals")
+        .line(format!(
+            "Multisig {}: threshold {} of {} members, transaction index {}",
+            multisig,
+            state.threshold,
+            state.members.len(),
+            state.transaction_index
+        ))
+        .field("multisig", multisig.to_string())
+        .field("threshold", state.threshold)
+        .field("members", state.members.len())
+        .field("transaction_index", state.transaction_index);
+    let mut proposals = Vec::new();
+    for summary in squads::list_proposals(&rpc, &multisig, &state, limit)? {
+        let proposal = &summary.proposal;
+        proposals.push(json!({
+            "transaction_index": summary.transaction_index,
+            "address": summary.address.to_string(),
+            "status": proposal.status.to_string(),
+            "stale": summary.stale,
+            "approved": proposal.approved.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+            "rejected": proposal.rejected.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+        }));
+        report = report.line(format!(
+            "  #{} {}{} approvals {}/{} rejections {} ({})",
+            summary.transaction_index,
+            proposal.status,
+            if summary.stale { " (stale)" } else { "" },
+            proposal.approved.len(),
+            state.threshold,
+            proposal.rejected.len(),
+            summary.address
+        ));
+    }
+    report.field("proposals", proposals).emit()
 }
 
 async fn send_instructions<C>(
     program: Program<C>,
     instructions: Vec<Instruction>,
-) -> Result<Signature>
+) -> Result<TxOutcome>
+where
+    C: std::ops::Deref + Clone + Send + 'static,
+    C::Target: Signer + Sized,
+{
+    send_instructions_with_signers(program, instructions, Vec::new()).await
+}
+
+/// Like [`send_instructions`], with extra signers beyond the payer.
+///
+/// Honours the global output and signing modes: `--dry-run` simulates without
+/// signatures, `--print-tx` returns the encoded transaction signed by everyone but the
+/// payer, `--sign-only` returns the local signatures, and `--signer`, `--blockhash` and
+/// `--nonce` shape the transaction that is signed or sent. Online transactions get an
+/// estimated compute budget and priority fee and are rebroadcast until they confirm or
+/// their blockhash expires.
+async fn send_instructions_with_signers<C>(
+    program: Program<C>,
+    instructions: Vec<Instruction>,
+    signers: Vec<Arc<CliSigner>>,
+) -> Result<TxOutcome>
 where
     C: std::ops::Deref + Clone + Send + 'static,
     C::Target: Signer + Sized,
 {
-    let handle = tokio::task::spawn_blocking(move || {
-        let mut builder = program.request();
-        for ix in instructions {
-            builder = builder.instruction(ix);
+    let mode = output::mode();
+    let signing = signing::config();
+    let runtime = tokio::runtime::Handle::current();
+    tokio::task::spawn_blocking(move || {
+        let rpc = program.rpc();
+        let payer = program.payer();
+        let signers = signing.signers(signers);
+        let sender = TxSender::new(
+            Arc::new(AsyncRpcClient::new_with_commitment(
+                rpc.url(),
+                rpc.commitment(),
+            )),
+            signing.send_config(),
+        );
+        let lookup_tables: Vec<_> = if signing.lookup_tables.is_empty() {
+            Vec::new()
+        } else {
+            runtime
+                .block_on(alt::fetch_lookup_tables(sender.rpc(), &signing.lookup_tables))?
+                .iter()
+                .map(alt::LookupTable::account)
+                .collect()
+        };
+        let budget = if signing.offline() {
+            signing.offline_budget()?
+        } else if mode.dry_run {
+            sender.fixed_budget()
+        } else {
+            match runtime.block_on(sender.compute_budget(&instructions, &payer, &lookup_tables)) {
+                Ok(budget) => budget,
+                Err(err) => {
+                    return match err.downcast::<SimulationFailed>() {
+                        Ok(failed) => Ok(TxOutcome::Simulated(failed.result.into())),
+                        Err(err) => Err(err),
+                    }
+                }
+            }
+        };
+        let instructions = signing.with_nonce(budget.apply(instructions));
+        let fixed_blockhash = signing.fixed_blockhash(&rpc)?;
+        let compile = |blockhash| {
+            tx::compile_message(&payer, &instructions, &lookup_tables, blockhash)
+                .map(tx::unsigned_transaction)
+        };
+
+        if mode.dry_run {
+            let tx = compile(fixed_blockhash.unwrap_or_default())?;
+            let config = RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: fixed_blockhash.is_none(),
+                commitment: Some(rpc.commitment()),
+                ..RpcSimulateTransactionConfig::default()
+            };
+            let result = rpc
+                .simulate_transaction_with_config(&tx, config)
+                .context("failed to simulate transaction")?;
+            return Ok(TxOutcome::Simulated(result.value.into()));
         }
-        builder.send()
+
+        let (blockhash, last_valid_block_height) = match fixed_blockhash {
+            Some(blockhash) => (blockhash, None),
+            None => {
+                let (blockhash, height) = rpc
+                    .get_latest_blockhash_with_commitment(rpc.commitment())
+                    .context("failed to fetch recent blockhash")?;
+                (blockhash, Some(height))
+            }
+        };
+
+        let mut tx = compile(blockhash)?;
+        if mode.print_tx.is_some() {
+            signing.sign(&mut tx, &signers, Some(payer))?;
+            return Ok(TxOutcome::Unsigned(UnsignedTx {
+                encoding: "base64",
+                transaction: STANDARD.encode(bincode::serialize(&tx)?),
+                missing_signers: signing::absent_signers(&tx)
+                    .iter()
+                    .map(Pubkey::to_string)
+                    .collect(),
+            }));
+        }
+
+        signing.sign(&mut tx, &signers, None)?;
+        if mode.sign_only {
+            return Ok(TxOutcome::Signed(signing::signed_tx(&tx)));
+        }
+        let absent = signing::absent_signers(&tx);
+        if !absent.is_empty() {
+            return Err(anyhow!(
+                "missing signatures from {}; collect them with --sign-only and pass --signer PUBKEY=SIGNATURE",
+                absent
+                    .iter()
+                    .map(Pubkey::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        // A fixed blockhash has no known expiry height and a nonce never expires, so
+        // those go through the RPC client's own confirmation loop.
+        let signature = match last_valid_block_height {
+            Some(height) => runtime
+                .block_on(sender.send_transaction(&tx, height))?
+                .into_result()?,
+            None => rpc.send_and_confirm_transaction(&tx)?,
+        };
+        let details = if mode.json() {
+            TxDetails::fetch(&rpc, &signature, tx.message.static_account_keys())
+                .map_err(|err| warn!(%signature, error = ?err, "failed to fetch transaction details"))
+                .ok()
+        } else {
+            None
+        };
+        Ok(TxOutcome::Sent(SentTx {
+            signature: signature.to_string(),
+            details,
+        }))
     })
     .await
-    .map_err(|err| anyhow!("blocking send failed: {err}"))?;
-
-    let sig = handle?;
-    Ok(sig)
+    .map_err(|err| anyhow!("blocking send failed: {err}"))?
 }
 
 fn parse_pubkey(value: &str) -> Result<Pubkey, String> {
@@ -0,0 +1,439 @@
+//! Result rendering for the global `--output`, `--dry-run` and `--print-tx` flags.
+//!
+//! Handlers describe what they did with a [`Report`]; in text mode it prints the
+//! familiar human-readable lines, in JSON mode a single object on stdout. Progress
+//! and preview lines go through [`note!`] so they never mix with JSON or with an
+//! encoded transaction on stdout.
+
+use std::{fmt::Display, sync::OnceLock};
+
+use anyhow::{anyhow, Result};
+use attn_client::{
+    errors::ProgramError,
+    events::{self, DecodedEvent},
+};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use solana_client::{
+    rpc_client::RpcClient, rpc_config::RpcTransactionConfig,
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_transaction_status_client_types::UiLoadedAddresses;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TxEncoding {
+    Base64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OutputMode {
+    pub format: OutputFormat,
+    pub dry_run: bool,
+    pub print_tx: Option<TxEncoding>,
+    pub sign_only: bool,
+}
+
+impl OutputMode {
+    pub fn json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    /// Whether transactions are actually submitted to the cluster.
+    pub fn sends(&self) -> bool {
+        !self.dry_run && self.print_tx.is_none() && !self.sign_only
+    }
+
+    /// Stdout is reserved for the JSON document, the encoded transaction or the
+    /// offline signatures.
+    pub fn notes_to_stderr(&self) -> bool {
+        self.json() || self.print_tx.is_some() || self.sign_only
+    }
+}
+
+static MODE: OnceLock<OutputMode> = OnceLock::new();
+
+pub fn init(mode: OutputMode) {
+    let _ = MODE.set(mode);
+}
+
+pub fn mode() -> OutputMode {
+    MODE.get().copied().unwrap_or_default()
+}
+
+/// `println!` for progress and preview lines; moves to stderr when stdout carries
+/// machine-readable output.
+macro_rules! note {
+    ($($arg:tt)*) => {
+        if $crate::output::mode().notes_to_stderr() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use note;
+
+/// What happened to the transaction a command built.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TxOutcome {
+    Sent(SentTx),
+    Simulated(Simulation),
+    Unsigned(UnsignedTx),
+    Signed(SignedTx),
+}
+
+#[derive(Debug, Serialize)]
+pub struct SentTx {
+    pub signature: String,
+    /// Only fetched in JSON mode; text output just shows the signature.
+    #[serde(flatten)]
+    pub details: Option<TxDetails>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TxDetails {
+    pub slot: u64,
+    pub fee: u64,
+    pub balances: Vec<BalanceChange>,
+    pub token_balances: Vec<TokenBalanceChange>,
+    pub events: Vec<DecodedEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BalanceChange {
+    pub account: String,
+    pub pre: u64,
+    pub post: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenBalanceChange {
+    pub account: String,
+    pub mint: String,
+    pub owner: Option<String>,
+    pub pre: u64,
+    pub post: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Simulation {
+    pub err: Option<String>,
+    pub program_error: Option<ProgramError>,
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    pub events: Vec<DecodedEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnsignedTx {
+    pub encoding: &'static str,
+    pub transaction: String,
+    /// Signers whose signature slot is still empty.
+    pub missing_signers: Vec<String>,
+}
+
+/// Output of `--sign-only`, laid out like the Solana CLI's.
+#[derive(Debug, Serialize)]
+pub struct SignedTx {
+    pub blockhash: String,
+    /// `pubkey=signature`, ready to pass back as `--signer`.
+    pub signers: Vec<String>,
+    pub absent: Vec<String>,
+}
+
+impl TxDetails {
+    /// Loads balances and events of a confirmed transaction. `account_keys` are the
+    /// static keys of the sent message; with lookup tables the loaded writable and
+    /// readonly addresses follow them in the balance arrays.
+    pub fn fetch(rpc: &RpcClient, signature: &Signature, account_keys: &[Pubkey]) -> Result<Self> {
+        let tx = rpc.get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                commitment: Some(rpc.commitment()),
+                max_supported_transaction_version: Some(0),
+                ..RpcTransactionConfig::default()
+            },
+        )?;
+        let meta = tx
+            .transaction
+            .meta
+            .ok_or_else(|| anyhow!("transaction {signature} has no status meta"))?;
+        let mut keys: Vec<String> = account_keys.iter().map(Pubkey::to_string).collect();
+        if let Some(loaded) = Option::<UiLoadedAddresses>::from(meta.loaded_addresses.clone()) {
+            keys.extend(loaded.writable);
+            keys.extend(loaded.readonly);
+        }
+        let key = |index: usize| {
+            keys.get(index)
+                .cloned()
+                .unwrap_or_else(|| format!("#{index}"))
+        };
+
+        let balances = meta
+            .pre_balances
+            .iter()
+            .zip(&meta.post_balances)
+            .enumerate()
+            .map(|(index, (pre, post))| BalanceChange {
+                account: key(index),
+                pre: *pre,
+                post: *post,
+            })
+            .collect();
+
+        let pre_tokens: Vec<_> = Option::from(meta.pre_token_balances).unwrap_or_default();
+        let post_tokens: Vec<_> = Option::from(meta.post_token_balances).unwrap_or_default();
+        let amount = |amount: &str| amount.parse::<u64>().unwrap_or(0);
+        let mut token_balances: Vec<TokenBalanceChange> = post_tokens
+            .iter()
+            .map(|balance| TokenBalanceChange {
+                account: key(balance.account_index as usize),
+                mint: balance.mint.clone(),
+                owner: Option::from(balance.owner.clone()),
+                pre: 0,
+                post: amount(&balance.ui_token_amount.amount),
+            })
+            .collect();
+        for balance in pre_tokens {
+            let account = key(balance.account_index as usize);
+            let pre = amount(&balance.ui_token_amount.amount);
+            match token_balances
+                .iter_mut()
+                .find(|entry| entry.account == account)
+            {
+                Some(entry) => entry.pre = pre,
+                None => token_balances.push(TokenBalanceChange {
+                    account,
+                    mint: balance.mint,
+                    owner: Option::from(balance.owner),
+                    pre,
+                    post: 0,
+                }),
+            }
+        }
+
+        let logs: Vec<String> = Option::from(meta.log_messages).unwrap_or_default();
+        Ok(Self {
+            slot: tx.slot,
+            fee: meta.fee,
+            balances,
+            token_balances,
+            events: events::decode_logs(&logs),
+        })
+    }
+}
+
+impl From<RpcSimulateTransactionResult> for Simulation {
+    fn from(result: RpcSimulateTransactionResult) -> Self {
+        let logs = result.logs.unwrap_or_default();
+        Self {
+            err: result.err.map(|err| err.to_string()),
+            program_error: ProgramError::from_logs(&logs),
+            units_consumed: result.units_consumed,
+            events: events::decode_logs(&logs),
+            logs,
+        }
+    }
+}
+
+/// Result of one command: text lines for humans, fields for `--output json`.
+pub struct Report {
+    command: &'static str,
+    message: Option<String>,
+    lines: Vec<String>,
+    fields: Map<String, Value>,
+    tx: Option<TxOutcome>,
+}
+
+impl Report {
+    pub fn new(command: &'static str) -> Self {
+        Self {
+            command,
+            message: None,
+            lines: Vec::new(),
+            fields: Map::new(),
+            tx: None,
+        }
+    }
+
+    /// A command that had nothing to do, e.g. an operation id that already landed.
+    pub fn skipped(command: &'static str, message: impl Into<String>) -> Self {
+        Self::new(command).message(message).field("skipped", true)
+    }
+
+    /// Closing line; followed by `(tx: ...)` when a transaction was sent.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Text-only line.
+    pub fn line(mut self, line: impl Into<String>) -> Self {
+        self.lines.push(line.into());
+        self
+    }
+
+    /// JSON-only field.
+    pub fn field(mut self, key: &str, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        self.fields.insert(key.to_string(), value);
+        self
+    }
+
+    /// A `label: value` text line that is also a JSON field.
+    pub fn detail<T: Serialize + Display>(self, label: &str, key: &str, value: T) -> Self {
+        let line = format!("{label}: {value}");
+        self.line(line).field(key, value.to_string())
+    }
+
+    pub fn tx(mut self, outcome: TxOutcome) -> Self {
+        self.tx = Some(outcome);
+        self
+    }
+
+    /// Prints the report. Fails after printing when a dry run simulated an error, so
+    /// scripts see a non-zero exit status.
+    pub fn emit(self) -> Result<()> {
+        let mode = mode();
+        let failure = match &self.tx {
+            Some(TxOutcome::Simulated(simulation)) => match &simulation.program_error {
+                Some(error) => Some(error.to_string()),
+                None => simulation.err.clone(),
+            },
+            _ => None,
+        };
+        if mode.json() {
+            self.print_json()?;
+        } else {
+            self.print_text();
+        }
+        match failure {
+            Some(err) => Err(anyhow!("transaction simulation failed: {err}")),
+            None => Ok(()),
+        }
+    }
+
+    fn print_json(self) -> Result<()> {
+        let mut object = Map::new();
+        object.insert("command".to_string(), Value::from(self.command));
+        // Handlers word the message for a sent transaction.
+        let sent = matches!(self.tx, None | Some(TxOutcome::Sent(_)));
+        if let Some(message) = self.message.filter(|_| sent) {
+            object.insert("message".to_string(), Value::from(message));
+        }
+        object.extend(self.fields);
+        if let Some(tx) = self.tx {
+            object.insert("transaction".to_string(), serde_json::to_value(tx)?);
+        }
+        println!("{}", serde_json::to_string_pretty(&Value::Object(object))?);
+        Ok(())
+    }
+
+    fn print_text(self) {
+        for line in &self.lines {
+            note!("{line}");
+        }
+        match (self.tx, self.message) {
+            (Some(TxOutcome::Sent(sent)), Some(message)) => {
+                println!("{message} (tx: {})", sent.signature)
+            }
+            (Some(TxOutcome::Sent(sent)), None) => println!("Signature: {}", sent.signature),
+            (Some(TxOutcome::Simulated(simulation)), _) => print_simulation(&simulation),
+            (Some(TxOutcome::Unsigned(unsigned)), _) => {
+                if unsigned.missing_signers.is_empty() {
+                    eprintln!("Transaction is fully signed");
+                } else {
+                    eprintln!(
+                        "Unsigned transaction; missing signatures from: {}",
+                        unsigned.missing_signers.join(", ")
+                    );
+                }
+                println!("{}", unsigned.transaction);
+            }
+            (Some(TxOutcome::Signed(signed)), _) => {
+                println!("Blockhash: {}", signed.blockhash);
+                println!("Signers (Pubkey=Signature):");
+                for signer in &signed.signers {
+                    println!("  {signer}");
+                }
+                if !signed.absent.is_empty() {
+                    println!("Absent Signers (Pubkey):");
+                    for pubkey in &signed.absent {
+                        println!("  {pubkey}");
+                    }
+                }
+            }
+            (None, Some(message)) => note!("{message}"),
+            (None, None) => {}
+        }
+    }
+}
+
+fn print_simulation(simulation: &Simulation) {
+    let units = simulation
+        .units_consumed
+        .map(|units| format!("{units} compute units"))
+        .unwrap_or_else(|| "compute units unknown".to_string());
+    match &simulation.err {
+        None => println!("Simulation succeeded ({units})"),
+        Some(err) => println!("Simulation failed: {err} ({units})"),
+    }
+    if let Some(error) = &simulation.program_error {
+        println!("  Program error: {error}");
+    }
+    for event in &simulation.events {
+        println!("  Event {}::{} {}", event.program, event.name, event.data);
+    }
+    println!("  Logs:");
+    for log in &simulation.logs {
+        println!("    {log}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+    #[test]
+    fn simulation_decodes_the_program_error() {
+        let stable_vault = stable_vault::ID.to_string();
+        let result = RpcSimulateTransactionResult {
+            err: Some(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(6001),
+            )),
+            logs: Some(vec![
+                format!("Program {stable_vault} invoke [1]"),
+                "Program log: AnchorError thrown in programs/stable_vault/src/lib.rs:88. Error Code: VaultPaused. Error Number: 6001. Error Message: Vault is paused.".to_string(),
+                format!("Program {stable_vault} failed: custom program error: 0x1771"),
+            ]),
+            accounts: None,
+            units_consumed: Some(4_200),
+            loaded_accounts_data_size: None,
+            return_data: None,
+            inner_instructions: None,
+            replacement_blockhash: None,
+        };
+        let simulation = Simulation::from(result);
+        let error = simulation.program_error.expect("program error");
+        assert_eq!(
+            error.to_string(),
+            "stable_vault::VaultPaused (6001): Vault is paused"
+        );
+        assert_eq!(
+            serde_json::to_value(&error).unwrap()["name"],
+            Value::from("VaultPaused")
+        );
+    }
+}
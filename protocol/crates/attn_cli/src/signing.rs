@@ -0,0 +1,452 @@
+//! Offline signing for the global `--sign-only`, `--blockhash`, `--signer`, `--nonce`
+//! and `--nonce-authority` flags.
+//!
+//! A command is run twice with the same arguments: once with `--sign-only` on the
+//! machine holding a key, which prints `pubkey=signature` pairs, and once online with
+//! those pairs passed back as `--signer`. Both runs must agree on the blockhash, so
+//! signing offline needs `--blockhash`; a durable nonce keeps it valid for hours.
+//! For the same reason the compute budget of an offline message comes only from
+//! `--compute-unit-limit` and a fixed `--priority-fee`, never from a simulation.
+
+use std::{str::FromStr, sync::Arc, sync::OnceLock};
+
+use anyhow::{anyhow, Context, Result};
+use attn_client::tx::{ComputeBudget, FeeStrategy, SendConfig};
+use solana_client::{nonce_utils, rpc_client::RpcClient};
+use solana_derivation_path::DerivationPath;
+use solana_remote_wallet::{
+    locator::Locator,
+    remote_keypair::{generate_remote_keypair, RemoteKeypair},
+    remote_wallet::initialize_wallet_manager,
+};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer, SignerError},
+    transaction::VersionedTransaction,
+};
+
+use crate::output::SignedTx;
+
+/// A keypair file, a Ledger, or only the public key of a signer that signs elsewhere.
+pub enum CliSigner {
+    Keypair(Keypair),
+    Pubkey(Pubkey),
+    /// A `usb://ledger` path. The device handle can't cross threads, so it is
+    /// opened again for each signature.
+    Remote {
+        locator: Locator,
+        derivation_path: DerivationPath,
+        pubkey: Pubkey,
+    },
+}
+
+impl CliSigner {
+    /// Accepts a keypair path, a `usb://ledger[/<wallet>][?key=<account>[/<change>]]`
+    /// path or a base58 public key.
+    pub fn load(value: &str) -> Result<Self> {
+        if let Ok(pubkey) = Pubkey::from_str(value) {
+            return Ok(Self::Pubkey(pubkey));
+        }
+        if value.starts_with("usb://") {
+            let (locator, derivation_path) = parse_remote_path(value)?;
+            let pubkey = open_remote(&locator, &derivation_path)?.pubkey;
+            return Ok(Self::Remote {
+                locator,
+                derivation_path,
+                pubkey,
+            });
+        }
+        let path = shellexpand::tilde(value).into_owned();
+        let keypair = read_keypair_file(&path)
+            .map_err(|err| anyhow!(err.to_string()))
+            .with_context(|| format!("failed to read keypair from {path}"))?;
+        Ok(Self::Keypair(keypair))
+    }
+
+    pub fn can_sign(&self) -> bool {
+        !matches!(self, Self::Pubkey(_))
+    }
+}
+
+/// Splits a `usb://` keypair path into the device and the `?key=` derivation path,
+/// `m/44'/501'` when absent.
+fn parse_remote_path(path: &str) -> Result<(Locator, DerivationPath)> {
+    let (device, query) = path.split_once('?').unwrap_or((path, ""));
+    let locator = Locator::new_from_path(device)
+        .map_err(|err| anyhow!("invalid remote wallet path {path}: {err}"))?;
+    let derivation_path = match query.strip_prefix("key=") {
+        Some(key) => DerivationPath::from_key_str(key)
+            .with_context(|| format!("invalid remote wallet path {path}"))?,
+        None if query.is_empty() => DerivationPath::default(),
+        None => {
+            return Err(anyhow!(
+                "invalid remote wallet path {path}: expected ?key=<account>[/<change>]"
+            ))
+        }
+    };
+    Ok((locator, derivation_path))
+}
+
+/// Opens the Ledger at `locator`.
+fn open_remote(locator: &Locator, derivation_path: &DerivationPath) -> Result<RemoteKeypair> {
+    let manager = initialize_wallet_manager().map_err(|err| {
+        anyhow!("{err}; usb:// signers need attn_cli built with --features ledger")
+    })?;
+    manager
+        .update_devices()
+        .map_err(|err| anyhow!("failed to list USB wallets: {err}"))?;
+    generate_remote_keypair(
+        locator.clone(),
+        derivation_path.clone(),
+        &manager,
+        false,
+        "keypair",
+    )
+    .map_err(|err| anyhow!("failed to open remote wallet {locator}: {err}"))
+}
+
+impl From<Keypair> for CliSigner {
+    fn from(keypair: Keypair) -> Self {
+        Self::Keypair(keypair)
+    }
+}
+
+impl Signer for CliSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        match self {
+            Self::Keypair(keypair) => keypair.try_pubkey(),
+            Self::Pubkey(pubkey) | Self::Remote { pubkey, .. } => Ok(*pubkey),
+        }
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        match self {
+            Self::Keypair(keypair) => keypair.try_sign_message(message),
+            Self::Pubkey(pubkey) => Err(SignerError::Custom(format!(
+                "no keypair for {pubkey}; pass --signer {pubkey}=<signature>"
+            ))),
+            Self::Remote {
+                locator,
+                derivation_path,
+                pubkey,
+            } => {
+                let keypair = open_remote(locator, derivation_path)
+                    .map_err(|err| SignerError::Connection(format!("{err:#}")))?;
+                if keypair.pubkey != *pubkey {
+                    return Err(SignerError::Custom(format!(
+                        "remote wallet {locator} now holds {}, expected {pubkey}",
+                        keypair.pubkey
+                    )));
+                }
+                keypair.try_sign_message(message)
+            }
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        matches!(self, Self::Remote { .. })
+    }
+}
+
+pub struct NonceConfig {
+    pub account: Pubkey,
+    pub authority: Arc<CliSigner>,
+}
+
+pub struct SigningConfig {
+    pub payer: Arc<CliSigner>,
+    pub blockhash: Option<Hash>,
+    pub presigners: Vec<(Pubkey, Signature)>,
+    pub nonce: Option<NonceConfig>,
+    pub priority_fee: Option<FeeStrategy>,
+    pub compute_unit_limit: Option<u32>,
+    /// `--lookup-table` accounts; messages compile to v0 when non-empty.
+    pub lookup_tables: Vec<Pubkey>,
+}
+
+static CONFIG: OnceLock<SigningConfig> = OnceLock::new();
+
+pub fn init(config: SigningConfig) {
+    let _ = CONFIG.set(config);
+}
+
+pub fn config() -> &'static SigningConfig {
+    CONFIG.get().expect("signing config initialised in main")
+}
+
+/// Parses `--signer PUBKEY=SIGNATURE`.
+pub fn parse_presigner(value: &str) -> Result<(Pubkey, Signature), String> {
+    let (pubkey, signature) = value
+        .split_once('=')
+        .ok_or_else(|| "expected PUBKEY=SIGNATURE".to_string())?;
+    let pubkey = Pubkey::from_str(pubkey).map_err(|err| format!("invalid pubkey: {err}"))?;
+    let signature =
+        Signature::from_str(signature).map_err(|err| format!("invalid signature: {err}"))?;
+    Ok((pubkey, signature))
+}
+
+/// Priority fee when `--priority-fee` is not given: the median recent fee paid for
+/// the transaction's writable accounts.
+const DEFAULT_PRIORITY_FEE: FeeStrategy = FeeStrategy::Percentile(50);
+
+pub fn parse_hash(value: &str) -> Result<Hash, String> {
+    Hash::from_str(value).map_err(|err| err.to_string())
+}
+
+impl SigningConfig {
+    /// Whether the message has to be reproducible on another machine.
+    pub fn offline(&self) -> bool {
+        self.blockhash.is_some() || self.nonce.is_some() || !self.presigners.is_empty()
+    }
+
+    pub fn send_config(&self) -> SendConfig {
+        SendConfig {
+            fee: self.priority_fee.unwrap_or(DEFAULT_PRIORITY_FEE),
+            compute_unit_limit: self.compute_unit_limit,
+            ..SendConfig::default()
+        }
+    }
+
+    /// The budget of an offline message, from explicit flags only.
+    pub fn offline_budget(&self) -> Result<ComputeBudget> {
+        let unit_price = match self.priority_fee {
+            None | Some(FeeStrategy::None) => None,
+            Some(FeeStrategy::Fixed(price)) => Some(price),
+            Some(strategy @ FeeStrategy::Percentile(_)) => {
+                return Err(anyhow!(
+                    "--priority-fee {strategy} depends on live fee data; pass a fixed \
+                     micro-lamport price when signing offline"
+                ))
+            }
+        };
+        Ok(ComputeBudget {
+            unit_limit: self.compute_unit_limit,
+            unit_price,
+        })
+    }
+
+    /// Prepends `advance_nonce_account` when a durable nonce is in use.
+    pub fn with_nonce(&self, mut instructions: Vec<Instruction>) -> Vec<Instruction> {
+        if let Some(nonce) = &self.nonce {
+            instructions.insert(
+                0,
+                solana_system_interface::instruction::advance_nonce_account(
+                    &nonce.account,
+                    &nonce.authority.pubkey(),
+                ),
+            );
+        }
+        instructions
+    }
+
+    /// Every signer the transaction may need: payer, command signers, nonce authority.
+    pub fn signers(&self, extra: Vec<Arc<CliSigner>>) -> Vec<Arc<CliSigner>> {
+        let mut signers = vec![self.payer.clone()];
+        signers.extend(extra);
+        if let Some(nonce) = &self.nonce {
+            signers.push(nonce.authority.clone());
+        }
+        let mut seen = Vec::new();
+        signers.retain(|signer| {
+            let pubkey = signer.pubkey();
+            let first = !seen.contains(&pubkey);
+            seen.push(pubkey);
+            first
+        });
+        signers
+    }
+
+    /// `--blockhash`, else the value stored in the nonce account, else `None` for a
+    /// fresh blockhash.
+    pub fn fixed_blockhash(&self, rpc: &RpcClient) -> Result<Option<Hash>> {
+        if let Some(blockhash) = self.blockhash {
+            return Ok(Some(blockhash));
+        }
+        let Some(nonce) = &self.nonce else {
+            return Ok(None);
+        };
+        let account =
+            nonce_utils::get_account_with_commitment(rpc, &nonce.account, rpc.commitment())
+                .with_context(|| format!("failed to fetch nonce account {}", nonce.account))?;
+        let data = nonce_utils::data_from_account(&account)
+            .with_context(|| format!("{} is not an initialized nonce account", nonce.account))?;
+        if data.authority != nonce.authority.pubkey() {
+            return Err(anyhow!(
+                "nonce authority {} does not match {} stored in nonce account {}",
+                nonce.authority.pubkey(),
+                data.authority,
+                nonce.account
+            ));
+        }
+        Ok(Some(data.blockhash()))
+    }
+
+    /// Signs with every local keypair (minus `skip`) and attaches `--signer` signatures.
+    pub fn sign(
+        &self,
+        tx: &mut VersionedTransaction,
+        signers: &[Arc<CliSigner>],
+        skip: Option<Pubkey>,
+    ) -> Result<()> {
+        let message = tx.message.serialize();
+        let required = usize::from(tx.message.header().num_required_signatures);
+        let keys = tx.message.static_account_keys()[..required].to_vec();
+        let slot = |pubkey: &Pubkey| keys.iter().position(|key| key == pubkey);
+        for signer in signers
+            .iter()
+            .filter(|signer| signer.can_sign() && Some(signer.pubkey()) != skip)
+        {
+            let pubkey = signer.pubkey();
+            let index = slot(&pubkey)
+                .ok_or_else(|| anyhow!("{pubkey} is not a signer of this transaction"))?;
+            tx.signatures[index] = signer
+                .try_sign_message(&message)
+                .context("failed to sign transaction")?;
+        }
+        for (pubkey, signature) in &self.presigners {
+            let index = slot(pubkey)
+                .ok_or_else(|| anyhow!("--signer {pubkey} is not a signer of this transaction"))?;
+            if !signature.verify(pubkey.as_ref(), &message) {
+                return Err(anyhow!(
+                    "--signer signature for {pubkey} does not match this transaction; \
+                     rerun --sign-only with the same arguments and blockhash"
+                ));
+            }
+            tx.signatures[index] = *signature;
+        }
+        Ok(())
+    }
+}
+
+/// Signer keys whose signature slot is still empty.
+pub fn absent_signers(tx: &VersionedTransaction) -> Vec<Pubkey> {
+    tx.message
+        .static_account_keys()
+        .iter()
+        .zip(&tx.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+/// The `--sign-only` result: present signatures and the signers still missing.
+pub fn signed_tx(tx: &VersionedTransaction) -> SignedTx {
+    let required = usize::from(tx.message.header().num_required_signatures);
+    let signers = tx.message.static_account_keys()[..required]
+        .iter()
+        .zip(&tx.signatures)
+        .filter(|(_, signature)| **signature != Signature::default())
+        .map(|(key, signature)| format!("{key}={signature}"))
+        .collect();
+    SignedTx {
+        blockhash: tx.message.recent_blockhash().to_string(),
+        signers,
+        absent: absent_signers(tx).iter().map(Pubkey::to_string).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use attn_client::tx::{compile_message, unsigned_transaction};
+    use solana_sdk::{instruction::AccountMeta, signature::Keypair};
+
+    fn config(payer: Arc<CliSigner>, presigners: Vec<(Pubkey, Signature)>) -> SigningConfig {
+        SigningConfig {
+            payer,
+            blockhash: None,
+            presigners,
+            nonce: None,
+            priority_fee: None,
+            compute_unit_limit: None,
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn offline_signatures_complete_an_online_transaction() {
+        let admin = Keypair::new();
+        let admin_pubkey = admin.pubkey();
+        let ix = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![AccountMeta::new_readonly(admin_pubkey, true)],
+            data: vec![1, 2, 3],
+        };
+        let blockhash = Hash::new_unique();
+        let transaction = |blockhash| {
+            let message =
+                compile_message(&admin_pubkey, std::slice::from_ref(&ix), &[], blockhash).unwrap();
+            unsigned_transaction(message)
+        };
+
+        let offline = config(Arc::new(admin.into()), Vec::new());
+        let mut tx = transaction(blockhash);
+        offline
+            .sign(&mut tx, &offline.signers(Vec::new()), None)
+            .unwrap();
+        let signed = signed_tx(&tx);
+        assert!(signed.absent.is_empty());
+        let presigner = parse_presigner(&signed.signers[0]).unwrap();
+
+        let online = config(Arc::new(CliSigner::Pubkey(admin_pubkey)), vec![presigner]);
+        let mut tx = transaction(blockhash);
+        online
+            .sign(&mut tx, &online.signers(Vec::new()), None)
+            .unwrap();
+        assert!(absent_signers(&tx).is_empty());
+        assert!(tx.verify_with_results().into_iter().all(|valid| valid));
+
+        let mut tx = transaction(Hash::new_unique());
+        let err = online
+            .sign(&mut tx, &online.signers(Vec::new()), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn durable_nonce_advances_first_and_signs_with_authority() {
+        let payer = Arc::new(CliSigner::from(Keypair::new()));
+        let authority = Arc::new(CliSigner::from(Keypair::new()));
+        let mut config = config(payer, Vec::new());
+        config.nonce = Some(NonceConfig {
+            account: Pubkey::new_unique(),
+            authority: authority.clone(),
+        });
+
+        let ix = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: Vec::new(),
+            data: Vec::new(),
+        };
+        let instructions = config.with_nonce(vec![ix]);
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions[0].program_id,
+            solana_system_interface::program::ID
+        );
+        let signers = config.signers(vec![authority.clone()]);
+        assert_eq!(signers.len(), 2);
+    }
+
+    #[test]
+    fn remote_wallet_paths_carry_the_derivation_path() {
+        let wallet = Pubkey::new_unique();
+        let (locator, derivation_path) =
+            parse_remote_path(&format!("usb://ledger/{wallet}?key=1/0")).unwrap();
+        assert_eq!(locator.pubkey, Some(wallet));
+        assert_eq!(
+            derivation_path,
+            DerivationPath::from_key_str("1/0").unwrap()
+        );
+
+        let (locator, derivation_path) = parse_remote_path("usb://ledger").unwrap();
+        assert_eq!(locator.pubkey, None);
+        assert_eq!(derivation_path, DerivationPath::default());
+
+        assert!(parse_remote_path("usb://trezor").is_err());
+        assert!(parse_remote_path("usb://ledger?account=1").is_err());
+        assert!(parse_remote_path("usb://ledger?key=1/0/0").is_err());
+    }
+}
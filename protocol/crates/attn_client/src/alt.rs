@@ -0,0 +1,247 @@
+//! Address lookup tables for v0 transactions.
+//!
+//! `stable_vault::sweep_creator_fees` and `splitter::redeem_yield` each pull in a
+//! creator vault, rewards pool, treasuries and the programs they CPI into. Combined
+//! with ATA creation or a swap they overflow a legacy transaction. A table per creator
+//! vault holding its static protocol accounts lets [`crate::tx::TxSender`] compile the
+//! same instructions into a v0 message that fits.
+
+use anchor_lang::system_program;
+use anchor_spl::{associated_token, token};
+use anyhow::{anyhow, Result};
+use solana_address_lookup_table_interface::{instruction as alt_ix, state::AddressLookupTable};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account, clock::Slot, instruction::Instruction, message::AddressLookupTableAccount,
+    pubkey::Pubkey, sysvar,
+};
+
+use crate::{creator, rewards, splitter, stable};
+
+pub use solana_address_lookup_table_interface::program::ID as ADDRESS_LOOKUP_TABLE_PROGRAM_ID;
+
+/// Most addresses one extend instruction carries while the create-and-extend
+/// transaction, with compute budget and a nonce advance, stays under the packet size.
+pub const EXTEND_CHUNK: usize = 24;
+/// Largest number of addresses a lookup table holds.
+pub const MAX_ADDRESSES: usize = 256;
+
+/// Static protocol accounts used by a creator vault's multi-CPI instructions: the
+/// attn programs and the SPL/system programs they call, the vault's PDAs and mints,
+/// its rewards pool PDAs and, when given, the StableVault that sweeps it.
+pub fn protocol_addresses(
+    creator_vault: &Pubkey,
+    vault: &creator::CreatorVaultAccount,
+    stable_vault: Option<&Pubkey>,
+) -> Vec<Pubkey> {
+    let rewards = rewards::derive_pdas(creator_vault);
+    let mut addresses = vec![
+        creator_vault::ID,
+        ::splitter::ID,
+        rewards_vault::ID,
+        stable_vault::ID,
+        token::ID,
+        associated_token::ID,
+        system_program::ID,
+        sysvar::rent::ID,
+        *creator_vault,
+        creator::fee_vault_pda(&vault.pump_mint).0,
+        vault.sy_mint,
+        vault.pump_mint,
+        vault.quote_mint,
+        splitter::splitter_authority_pda(creator_vault).0,
+        creator::sweeper_pda(creator_vault).0,
+        creator::revenue_source_pda(creator_vault).0,
+        rewards.rewards_pool,
+        rewards.rewards_authority,
+        rewards.s_attn_mint,
+        rewards.attn_vault,
+        rewards.sol_treasury,
+    ];
+    if let Some(stable_vault) = stable_vault {
+        addresses.extend([
+            *stable_vault,
+            stable::sol_vault_pda(stable_vault).0,
+            stable::share_mint_pda(stable_vault).0,
+        ]);
+    }
+    dedup(addresses)
+}
+
+fn dedup(addresses: Vec<Pubkey>) -> Vec<Pubkey> {
+    let mut unique = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        if !unique.contains(&address) {
+            unique.push(address);
+        }
+    }
+    unique
+}
+
+/// Creates a table owned by `authority`; `recent_slot` seeds the table address.
+pub fn build_create_table_ix(
+    authority: Pubkey,
+    payer: Pubkey,
+    recent_slot: Slot,
+) -> (Instruction, Pubkey) {
+    alt_ix::create_lookup_table(authority, payer, recent_slot)
+}
+
+pub fn build_extend_table_ix(
+    table: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    addresses: Vec<Pubkey>,
+) -> Instruction {
+    alt_ix::extend_lookup_table(table, authority, Some(payer), addresses)
+}
+
+/// A decoded lookup table account.
+#[derive(Debug, Clone)]
+pub struct LookupTable {
+    pub address: Pubkey,
+    pub authority: Option<Pubkey>,
+    /// `u64::MAX` while the table is active.
+    pub deactivation_slot: Slot,
+    pub last_extended_slot: Slot,
+    pub addresses: Vec<Pubkey>,
+}
+
+impl LookupTable {
+    pub fn from_account_data(address: Pubkey, data: &[u8]) -> Result<Self> {
+        let table = AddressLookupTable::deserialize(data)
+            .map_err(|err| anyhow!("{address} is not an address lookup table: {err}"))?;
+        Ok(Self {
+            address,
+            authority: table.meta.authority,
+            deactivation_slot: table.meta.deactivation_slot,
+            last_extended_slot: table.meta.last_extended_slot,
+            addresses: table.addresses.into_owned(),
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.deactivation_slot == Slot::MAX
+    }
+
+    /// `wanted` addresses the table does not hold yet, in order and without repeats.
+    pub fn missing(&self, wanted: &[Pubkey]) -> Vec<Pubkey> {
+        dedup(
+            wanted
+                .iter()
+                .filter(|address| !self.addresses.contains(address))
+                .copied()
+                .collect(),
+        )
+    }
+
+    /// The form v0 message compilation takes.
+    pub fn account(&self) -> AddressLookupTableAccount {
+        AddressLookupTableAccount {
+            key: self.address,
+            addresses: self.addresses.clone(),
+        }
+    }
+}
+
+/// Fetches one table, active or not.
+pub async fn fetch_lookup_table(rpc: &RpcClient, address: Pubkey) -> Result<LookupTable> {
+    let account = rpc.get_multiple_accounts(&[address]).await?.remove(0);
+    decode_table(address, account)
+}
+
+/// Fetches the tables to compile a message against, in order. A deactivated table
+/// is an error since lookups through it stop resolving.
+pub async fn fetch_lookup_tables(
+    rpc: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<LookupTable>> {
+    let accounts = rpc.get_multiple_accounts(addresses).await?;
+    addresses
+        .iter()
+        .zip(accounts)
+        .map(|(address, account)| {
+            let table = decode_table(*address, account)?;
+            if !table.is_active() {
+                return Err(anyhow!("address lookup table {address} is deactivated"));
+            }
+            Ok(table)
+        })
+        .collect()
+}
+
+fn decode_table(address: Pubkey, account: Option<Account>) -> Result<LookupTable> {
+    let account = account.ok_or_else(|| anyhow!("address lookup table {address} not found"))?;
+    if account.owner != ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        return Err(anyhow!("{address} is not an address lookup table"));
+    }
+    LookupTable::from_account_data(address, &account.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::{compile_message, unsigned_transaction};
+    use solana_sdk::{hash::Hash, packet::PACKET_DATA_SIZE};
+
+    #[test]
+    fn protocol_table_shrinks_a_sweep_below_the_packet_size() {
+        let pump_mint = Pubkey::new_unique();
+        let creator_vault = creator::creator_vault_pda(&pump_mint).0;
+        let vault = creator::CreatorVaultAccount {
+            bump: 0,
+            fee_vault_bump: 0,
+            sy_mint_bump: 0,
+            authority: Pubkey::new_unique(),
+            pump_creator: Pubkey::new_unique(),
+            pump_mint,
+            quote_mint: creator::WSOL_MINT,
+            sy_mint: creator::sy_mint_pda(&pump_mint).0,
+            splitter_program: ::splitter::ID,
+            total_fees_collected: 0,
+            total_sy_minted: 0,
+            admin: Pubkey::new_unique(),
+            sol_rewards_bps: 0,
+            paused: false,
+            locked: false,
+            lock_expires_at: 0,
+            source_kind: Default::default(),
+        };
+        let stable = stable::derive_pdas(&Pubkey::new_unique(), &Pubkey::new_unique());
+        let addresses = protocol_addresses(&creator_vault, &vault, Some(&stable.stable_vault));
+        assert!(addresses.len() <= EXTEND_CHUNK);
+
+        let table = LookupTable {
+            address: Pubkey::new_unique(),
+            authority: None,
+            deactivation_slot: Slot::MAX,
+            last_extended_slot: 0,
+            addresses: addresses.clone(),
+        };
+        assert!(table.missing(&addresses).is_empty());
+
+        let rewards = rewards::derive_pdas(&creator_vault);
+        let keeper = Pubkey::new_unique();
+        let sweep = stable::build_sweep_creator_fees_ix(
+            &stable,
+            keeper,
+            stable.stable_vault,
+            creator_vault,
+            rewards.rewards_pool,
+            rewards.sol_treasury,
+            1,
+            1,
+        );
+        let instructions = vec![sweep.clone(), sweep];
+        let size = |tables: &[AddressLookupTableAccount]| {
+            let message = compile_message(&keeper, &instructions, tables, Hash::default()).unwrap();
+            let tx = unsigned_transaction(message);
+            // Compact-u16 signature count, signatures, message.
+            1 + tx.signatures.len() * 64 + tx.message.serialize().len()
+        };
+        let legacy = size(&[]);
+        let v0 = size(&[table.account()]);
+        assert!(v0 < legacy);
+        assert!(v0 <= PACKET_DATA_SIZE);
+    }
+}
@@ -0,0 +1,495 @@
+//! Decodes custom program errors raised by the attn programs.
+//!
+//! A failed instruction only reaches the client as `custom program error: 0x1770`;
+//! the code is ambiguous until paired with the program that raised it, and every
+//! Anchor program numbers its errors from 6000. [`ProgramError`] recovers both from
+//! simulation logs, RPC preflight failures or a confirmed transaction's status, and
+//! maps codes of the four attn programs onto their typed error enums. Codes of other
+//! Anchor programs (Squads, the framework's own constraint errors) keep the name and
+//! message from the `AnchorError` log line when one is available.
+
+use std::fmt;
+
+use anchor_client::ClientError as AnchorClientError;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+
+use crate::tx::{SimulationFailed, TransactionFailed};
+
+/// A typed error of one of the attn programs.
+#[derive(Debug, Clone, Copy)]
+pub enum AttnProgramError {
+    CreatorVault(::creator_vault::AttnError),
+    StableVault(::stable_vault::AttnError),
+    Splitter(::splitter::SplitterError),
+    Rewards(::rewards_vault::RewardsError),
+}
+
+/// Finds the variant of `$program::$error` whose code is `$code`. Variants are
+/// listed in declaration order; the tests check each list against the program's
+/// `#[error_code]` enum in its source.
+macro_rules! find_variant {
+    ($code:expr, $wrap:ident, $program:ident :: $error:ident { $($variant:ident),* $(,)? }) => {
+        [$(::$program::$error::$variant),*]
+            .into_iter()
+            .find(|variant| u32::from(*variant) == $code)
+            .map(AttnProgramError::$wrap)
+    };
+}
+
+impl AttnProgramError {
+    /// The typed error for `code` raised by `program_id`, if it is an attn program.
+    pub fn decode(program_id: &Pubkey, code: u32) -> Option<Self> {
+        if *program_id == ::creator_vault::ID {
+            find_variant!(
+                code,
+                CreatorVault,
+                creator_vault::AttnError {
+                    InvalidAmount,
+                    MathOverflow,
+                    SplitterProgramUnset,
+                    UnauthorizedSplitter,
+                    InvalidFeeVault,
+                    UnauthorizedAdmin,
+                    InvalidAdmin,
+                    InvalidBps,
+                    VaultPaused,
+                    AdminSignatureRequired,
+                    InsufficientVaultBalance,
+                    InvalidWithdrawalDestination,
+                    InvalidWithdrawalMint,
+                    InvalidLockExpiry,
+                    InvalidSweeperDelegate,
+                    InvalidSweeperFee,
+                    VaultLockedForDelegate,
+                    UnauthorizedSweeper,
+                    DelegateFeeDestinationRequired,
+                    InvalidDelegateFeeDestination,
+                    InvalidRevenueSource,
+                    SourceClaimUnsupported,
+                    SourceDepositUnsupported,
+                    InvalidSourceProgram,
+                    RevenueSourceMismatch,
+                    UnauthorizedDepositor,
+                    QuoteMintNotNative,
+                    InvalidSweeperLimits,
+                    SweeperFeeAboveLimit,
+                    SweepTooFrequent,
+                    SweepWindowLimitExceeded,
+                    InvalidClaimAccounts,
+                    NothingClaimed,
+                }
+            )
+        } else if *program_id == ::stable_vault::ID {
+            find_variant!(
+                code,
+                StableVault,
+                stable_vault::AttnError {
+                    InvalidAmount,
+                    VaultPaused,
+                    InvalidBps,
+                    MathOverflow,
+                    InvalidVaultState,
+                    AmountTooSmall,
+                    UnsupportedMint,
+                    NoAcceptedMints,
+                    TooManyAcceptedMints,
+                    MintAlreadyAccepted,
+                    MintNotAccepted,
+                    OperationOutOfOrder,
+                    InsufficientDustBalance,
+                    Unauthorized,
+                    UnauthorizedKeeper,
+                    UnauthorizedAdmin,
+                    InvalidAdmin,
+                    InvalidKeeper,
+                    InsufficientShares,
+                    InsufficientPendingSol,
+                    CreatorVaultPaused,
+                    CannotRemovePrimaryMint,
+                    InvalidMint,
+                    InvalidStableVaultPda,
+                    InvalidTreasuryPda,
+                    InvalidShareMintPda,
+                    InvalidSolVaultPda,
+                }
+            )
+        } else if *program_id == ::splitter::ID {
+            find_variant!(
+                code,
+                Splitter,
+                splitter::SplitterError {
+                    InvalidMaturity,
+                    InvalidAmount,
+                    MathOverflow,
+                    InsufficientSyBalance,
+                    SplitterProgramMismatch,
+                    InvalidSplitterAuthority,
+                    FeeIndexRegression,
+                    NoYieldPosition,
+                    InsufficientYieldLiquidity,
+                    InvalidFeeVault,
+                    MarketNotMatured,
+                    InsufficientPtBalance,
+                    InsufficientYieldTokens,
+                    OutstandingPrincipal,
+                    OutstandingYield,
+                    InvalidUserPosition,
+                    InvalidTokenProgram,
+                    MintDecimalsMismatch,
+                    YieldSupplyMismatch,
+                    MarketClosed,
+                }
+            )
+        } else if *program_id == ::rewards_vault::ID {
+            find_variant!(
+                code,
+                Rewards,
+                rewards_vault::RewardsError {
+                    InvalidAmount,
+                    MathOverflow,
+                    InvalidBps,
+                    PositionPoolMismatch,
+                    PositionOwnerMismatch,
+                    InsufficientStake,
+                    UnauthorizedCreatorVault,
+                    InsufficientTreasury,
+                    UnauthorizedFunder,
+                    TreasuryBalanceRegression,
+                    InvalidTreasuryOwner,
+                    InvalidAllowedFunder,
+                    InvalidTreasuryAccount,
+                    UnauthorizedAdmin,
+                    InvalidAdmin,
+                    PoolPaused,
+                    IndexInvariant,
+                    PendingRewardsInvariant,
+                    OperationOutOfOrder,
+                }
+            )
+        } else {
+            None
+        }
+    }
+
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::CreatorVault(_) => "creator_vault",
+            Self::StableVault(_) => "stable_vault",
+            Self::Splitter(_) => "splitter",
+            Self::Rewards(_) => "rewards_vault",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::CreatorVault(error) => error.name(),
+            Self::StableVault(error) => error.name(),
+            Self::Splitter(error) => error.name(),
+            Self::Rewards(error) => error.name(),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match *self {
+            Self::CreatorVault(error) => error.into(),
+            Self::StableVault(error) => error.into(),
+            Self::Splitter(error) => error.into(),
+            Self::Rewards(error) => error.into(),
+        }
+    }
+}
+
+/// The `#[msg]` text.
+impl fmt::Display for AttnProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreatorVault(error) => error.fmt(f),
+            Self::StableVault(error) => error.fmt(f),
+            Self::Splitter(error) => error.fmt(f),
+            Self::Rewards(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for AttnProgramError {}
+
+/// Name of an attn program by id.
+pub fn program_name(program_id: &Pubkey) -> Option<&'static str> {
+    [
+        (::creator_vault::ID, "creator_vault"),
+        (::stable_vault::ID, "stable_vault"),
+        (::splitter::ID, "splitter"),
+        (::rewards_vault::ID, "rewards_vault"),
+    ]
+    .into_iter()
+    .find(|(id, _)| id == program_id)
+    .map(|(_, name)| name)
+}
+
+/// A custom error raised by a program.
+#[derive(Debug, Clone)]
+pub struct ProgramError {
+    /// Unknown when only the transaction status was available: a CPI failure is
+    /// reported against the top-level instruction, so its program id would mislead.
+    pub program_id: Option<Pubkey>,
+    pub code: u32,
+    pub name: Option<String>,
+    pub message: Option<String>,
+    /// Set when `program_id` is an attn program that declares `code`.
+    pub attn: Option<AttnProgramError>,
+}
+
+impl ProgramError {
+    pub fn new(program_id: Option<Pubkey>, code: u32) -> Self {
+        let attn = program_id.and_then(|id| AttnProgramError::decode(&id, code));
+        Self {
+            program_id,
+            code,
+            name: attn.map(|error| error.name()),
+            message: attn.map(|error| error.to_string()),
+            attn,
+        }
+    }
+
+    /// The attn program name, or the program id for anything else.
+    pub fn program(&self) -> Option<String> {
+        let id = self.program_id?;
+        Some(
+            program_name(&id)
+                .map(str::to_string)
+                .unwrap_or_else(|| id.to_string()),
+        )
+    }
+
+    /// The innermost program that failed with a custom error, from a transaction's
+    /// log messages.
+    pub fn from_logs<S: AsRef<str>>(logs: &[S]) -> Option<Self> {
+        let mut anchor_log = None;
+        for log in logs {
+            let log = log.as_ref();
+            if let Some(parsed) = AnchorErrorLog::parse(log) {
+                anchor_log = Some(parsed);
+            } else if let Some((program_id, code)) = parse_failed_log(log) {
+                let mut error = Self::new(Some(program_id), code);
+                if let Some(parsed) = anchor_log.filter(|parsed| parsed.number == code) {
+                    error.name.get_or_insert(parsed.name);
+                    error.message.get_or_insert(parsed.message);
+                }
+                return Some(error);
+            }
+        }
+        anchor_log.map(|parsed| Self {
+            program_id: None,
+            code: parsed.number,
+            name: Some(parsed.name),
+            message: Some(parsed.message),
+            attn: None,
+        })
+    }
+
+    /// The custom error code of a transaction status; the program is not known.
+    pub fn from_transaction_error(error: &TransactionError) -> Option<Self> {
+        match error {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                Some(Self::new(None, *code))
+            }
+            _ => None,
+        }
+    }
+
+    /// Preflight failures carry the simulation logs; other RPC errors only the status.
+    pub fn from_client_error(error: &ClientError) -> Option<Self> {
+        if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) = error.kind()
+        {
+            if let Some(error) = result.logs.as_deref().and_then(Self::from_logs) {
+                return Some(error);
+            }
+        }
+        Self::from_transaction_error(&error.get_transaction_error()?)
+    }
+
+    /// Searches an error chain for a program error: a simulation, preflight or
+    /// confirmed transaction failure from [`crate::tx`] or the RPC clients.
+    pub fn find(error: &anyhow::Error) -> Option<Self> {
+        error.chain().find_map(|cause| {
+            if let Some(error) = cause.downcast_ref::<ProgramError>() {
+                Some(error.clone())
+            } else if let Some(failed) = cause.downcast_ref::<SimulationFailed>() {
+                let logs = failed.result.logs.as_deref().unwrap_or_default();
+                Self::from_logs(logs)
+                    .or_else(|| Self::from_transaction_error(failed.result.err.as_ref()?))
+            } else if let Some(failed) = cause.downcast_ref::<TransactionFailed>() {
+                Self::from_transaction_error(&failed.error)
+            } else if let Some(error) = cause.downcast_ref::<ClientError>() {
+                Self::from_client_error(error)
+            } else if let Some(AnchorClientError::SolanaClientError(error)) =
+                cause.downcast_ref::<AnchorClientError>()
+            {
+                Self::from_client_error(error)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// `creator_vault::VaultPaused (6008): Vault is paused`, or the bare code when the
+/// error could not be named.
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let program = self.program().unwrap_or_else(|| "program".to_string());
+        match (&self.name, &self.message) {
+            (Some(name), Some(message)) => {
+                write!(f, "{program}::{name} ({}): {message}", self.code)
+            }
+            (Some(name), None) => write!(f, "{program}::{name} ({})", self.code),
+            _ => write!(f, "{program} custom error {} ({:#x})", self.code, self.code),
+        }
+    }
+}
+
+impl std::error::Error for ProgramError {}
+
+impl Serialize for ProgramError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ProgramError", 5)?;
+        state.serialize_field("program_id", &self.program_id.map(|id| id.to_string()))?;
+        state.serialize_field("program", &self.program())?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("message", &self.message)?;
+        state.end()
+    }
+}
+
+/// `AnchorError ... Error Code: X. Error Number: N. Error Message: M.`
+#[derive(Debug, Clone, PartialEq)]
+struct AnchorErrorLog {
+    name: String,
+    number: u32,
+    message: String,
+}
+
+impl AnchorErrorLog {
+    fn parse(log: &str) -> Option<Self> {
+        let rest = log.split_once("Error Code: ")?.1;
+        let (name, rest) = rest.split_once(". Error Number: ")?;
+        let (number, message) = rest.split_once(". Error Message: ")?;
+        Some(Self {
+            name: name.to_string(),
+            number: number.parse().ok()?,
+            message: message.trim_end_matches('.').to_string(),
+        })
+    }
+}
+
+/// `Program <id> failed: custom program error: 0x1770`
+fn parse_failed_log(log: &str) -> Option<(Pubkey, u32)> {
+    let rest = log.strip_prefix("Program ")?;
+    let (program_id, code) = rest.split_once(" failed: custom program error: 0x")?;
+    Some((
+        program_id.parse().ok()?,
+        u32::from_str_radix(code.trim(), 16).ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::error::ERROR_CODE_OFFSET;
+
+    /// Variant names of the `#[error_code]` enum in a program's source, in order.
+    fn declared_variants(source: &str) -> Vec<&str> {
+        let (_, rest) = source
+            .split_once("#[error_code]")
+            .expect("no #[error_code] enum");
+        let (_, body) = rest.split_once('{').expect("error enum body");
+        let (body, _) = body.split_once("\n}").expect("error enum end");
+        body.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//"))
+            .map(|line| line.trim_end_matches(','))
+            .collect()
+    }
+
+    #[test]
+    fn variant_lists_match_the_declared_error_enums() {
+        for (program_id, source) in [
+            (
+                ::creator_vault::ID,
+                include_str!("../../../programs/creator_vault/src/lib.rs"),
+            ),
+            (
+                ::stable_vault::ID,
+                include_str!("../../../programs/stable_vault/src/lib.rs"),
+            ),
+            (
+                ::splitter::ID,
+                include_str!("../../../programs/splitter/src/lib.rs"),
+            ),
+            (
+                ::rewards_vault::ID,
+                include_str!("../../../programs/rewards_vault/src/lib.rs"),
+            ),
+        ] {
+            let declared = declared_variants(source);
+            assert!(!declared.is_empty());
+            for (code, name) in (ERROR_CODE_OFFSET..).zip(&declared) {
+                let error = AttnProgramError::decode(&program_id, code)
+                    .unwrap_or_else(|| panic!("{program_id} is missing {name} ({code})"));
+                assert_eq!((error.name().as_str(), error.code()), (*name, code));
+            }
+            let past_last = ERROR_CODE_OFFSET + declared.len() as u32;
+            assert!(AttnProgramError::decode(&program_id, past_last).is_none());
+        }
+    }
+
+    #[test]
+    fn decodes_the_innermost_failure_from_cpi_logs() {
+        let logs = [
+            format!("Program {} invoke [1]", ::stable_vault::ID),
+            "Program log: Instruction: SweepCreatorFees".to_string(),
+            format!("Program {} invoke [2]", ::creator_vault::ID),
+            "Program log: AnchorError thrown in programs/creator_vault/src/lib.rs:412. Error Code: VaultPaused. Error Number: 6008. Error Message: Vault is paused.".to_string(),
+            format!("Program {} failed: custom program error: 0x1778", ::creator_vault::ID),
+            format!("Program {} failed: custom program error: 0x1778", ::stable_vault::ID),
+        ];
+        let error = ProgramError::from_logs(&logs).unwrap();
+        assert!(matches!(
+            error.attn,
+            Some(AttnProgramError::CreatorVault(
+                ::creator_vault::AttnError::VaultPaused
+            ))
+        ));
+        assert_eq!(
+            error.to_string(),
+            "creator_vault::VaultPaused (6008): Vault is paused"
+        );
+
+        let failed = TransactionFailed {
+            signature: Default::default(),
+            error: TransactionError::InstructionError(0, InstructionError::Custom(2006)),
+        };
+        let chained = anyhow::Error::new(failed).context("submit sweep");
+        let error = ProgramError::find(&chained).unwrap();
+        assert_eq!(error.code, 2006);
+        assert!(error.program_id.is_none());
+        assert_eq!(error.to_string(), "program custom error 2006 (0x7d6)");
+
+        let foreign = [
+            "Program log: AnchorError caused by account: multisig. Error Code: InvalidThreshold. Error Number: 6004. Error Message: Invalid threshold.",
+            "Program SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf failed: custom program error: 0x1774",
+        ];
+        let error = ProgramError::from_logs(&foreign).unwrap();
+        assert!(error.attn.is_none());
+        assert_eq!(error.name.as_deref(), Some("InvalidThreshold"));
+    }
+}
@@ -0,0 +1,261 @@
+//! Decodes Anchor events emitted by the attn programs.
+//!
+//! Anchor `emit!` writes `Program data: <base64>` log lines holding the event
+//! discriminator followed by its Borsh payload. Events are matched against every
+//! attn program, so callers can pass the full log of a transaction.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use creator_vault::{RevenueSourceConfig, RevenueSourceKind, SweeperLimits};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use solana_sdk::pubkey::Pubkey;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DecodedEvent {
+    pub program: &'static str,
+    pub name: &'static str,
+    pub data: Value,
+}
+
+/// Decodes every attn event found in a transaction's log messages, in order.
+pub fn decode_logs<S: AsRef<str>>(logs: &[S]) -> Vec<DecodedEvent> {
+    logs.iter()
+        .filter_map(|log| log.as_ref().strip_prefix(PROGRAM_DATA_PREFIX))
+        .filter_map(|encoded| STANDARD.decode(encoded.trim()).ok())
+        .filter_map(|data| decode_event(&data))
+        .collect()
+}
+
+/// Decodes one event payload (discriminator plus Borsh body).
+pub fn decode_event(data: &[u8]) -> Option<DecodedEvent> {
+    decode_creator_vault(data)
+        .or_else(|| decode_splitter(data))
+        .or_else(|| decode_stable_vault(data))
+        .or_else(|| decode_rewards_vault(data))
+}
+
+trait EventField {
+    fn to_value(&self) -> Value;
+}
+
+impl EventField for Pubkey {
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl EventField for Option<Pubkey> {
+    fn to_value(&self) -> Value {
+        self.map(|key| key.to_value()).unwrap_or(Value::Null)
+    }
+}
+
+macro_rules! json_event_field {
+    ($($ty:ty),*) => {
+        $(impl EventField for $ty {
+            fn to_value(&self) -> Value {
+                json!(self)
+            }
+        })*
+    };
+}
+
+json_event_field!(bool, u16, u64, i64);
+
+/// Rendered as a string; fee indices overflow JSON-safe integers.
+impl EventField for u128 {
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl EventField for SweeperLimits {
+    fn to_value(&self) -> Value {
+        json!({
+            "max_fee_bps": self.max_fee_bps,
+            "max_amount_per_window": self.max_amount_per_window,
+            "window_secs": self.window_secs,
+            "min_interval_secs": self.min_interval_secs,
+        })
+    }
+}
+
+impl EventField for RevenueSourceKind {
+    fn to_value(&self) -> Value {
+        Value::String(format!("{self:?}"))
+    }
+}
+
+impl EventField for RevenueSourceConfig {
+    fn to_value(&self) -> Value {
+        Value::String(format!("{self:?}"))
+    }
+}
+
+fn event<T: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<T> {
+    let mut rest = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut rest).ok()
+}
+
+macro_rules! decode_events {
+    ($data:expr, $program:ident { $($event:ident { $($field:ident),* $(,)? }),* $(,)? }) => {{
+        $(
+            if let Some(event) = event::<::$program::$event>($data) {
+                let mut fields = Map::new();
+                $(fields.insert(stringify!($field).to_string(), event.$field.to_value());)*
+                return Some(DecodedEvent {
+                    program: stringify!($program),
+                    name: stringify!($event),
+                    data: Value::Object(fields),
+                });
+            }
+        )*
+        None
+    }};
+}
+
+fn decode_creator_vault(data: &[u8]) -> Option<DecodedEvent> {
+    decode_events!(data, creator_vault {
+        VaultInitialized { creator_vault, pump_mint, quote_mint, sy_mint, authority, admin },
+        RevenueSourceConfigured { creator_vault, source_id, config },
+        SourceRevenueCollected { creator_vault, kind, amount, total_collected },
+        CreatorFeesCollected { creator_vault, cranker, amount, total_fees_collected },
+        SyMinted { user, pump_mint, amount },
+        FeesWithdrawn { creator_vault, authority, destination, amount, locked },
+        VaultLockStatusChanged { creator_vault, locked, lock_expires_at, is_auto },
+        SweeperDelegateUpdated { creator_vault, delegate, fee_bps },
+        SweeperDelegateCleared { creator_vault },
+        SweeperDelegateAllowed { creator_vault, delegate, limits },
+        SweeperDelegateRevoked { creator_vault, delegate },
+        DelegatedFeesSwept { creator_vault, delegate, destination, amount, fee_amount },
+        RewardsSplitUpdated { creator_vault, sol_rewards_bps },
+        AdminUpdated { creator_vault, previous_admin, new_admin },
+        VaultPauseToggled { creator_vault, paused },
+    })
+}
+
+fn decode_splitter(data: &[u8]) -> Option<DecodedEvent> {
+    decode_events!(data, splitter {
+        MarketCreated { market, pump_mint, maturity_ts },
+        PtYtMinted { market, user, amount },
+        YieldRedeemed { market, user, claimed_amount, fee_index, market_delta },
+        PrincipalRedeemed { market, user, amount },
+        MarketClosed { market, creator_authority, admin },
+    })
+}
+
+fn decode_stable_vault(data: &[u8]) -> Option<DecodedEvent> {
+    decode_events!(data, stable_vault {
+        StableVaultInitialized { stable_vault, authority_seed, share_mint, stable_mint, admin },
+        AttnUsdMinted { user, deposited_amount, minted_shares, price_per_share },
+        AttnUsdRedeemed { user, redeemed_shares, returned_amount, price_per_share },
+        CreatorFeesSwept {
+            stable_vault,
+            keeper_authority,
+            amount_lamports,
+            sol_rewards_bps,
+            sol_rewards_lamports,
+            converted_lamports,
+            pending_sol,
+            operation_id,
+        },
+        ConversionProcessed {
+            stable_vault,
+            executor,
+            stable_received,
+            sol_spent,
+            pending_sol,
+            operation_id,
+        },
+        StableVaultAdminUpdated { stable_vault, previous_admin, new_admin },
+        KeeperAuthorityUpdated { stable_vault, keeper_authority },
+        EmergencyAdminUpdated { stable_vault, previous_emergency_admin, new_emergency_admin },
+        StableVaultPauseToggled { stable_vault, authority, is_paused },
+        AcceptedMintAdded { stable_vault, mint },
+        AcceptedMintRemoved { stable_vault, mint },
+        SolDustWithdrawn { stable_vault, authority, amount, remaining_dust },
+    })
+}
+
+fn decode_rewards_vault(data: &[u8]) -> Option<DecodedEvent> {
+    decode_events!(data, rewards_vault {
+        RewardsPoolInitialized {
+            pool,
+            creator_vault,
+            attn_mint,
+            s_attn_mint,
+            reward_bps,
+            admin,
+            allowed_funder,
+        },
+        RewardsFunded {
+            pool,
+            amount,
+            source_amount,
+            sol_per_share,
+            treasury_balance,
+            operation_id,
+        },
+        Staked { pool, user, amount, total_staked, claimed },
+        Unstaked { pool, user, amount, total_staked, claimed },
+        RewardsClaimed { pool, user, amount },
+        AllowedFunderUpdated { pool, allowed_funder },
+        RewardBpsUpdated { pool, reward_bps },
+        RewardsAdminUpdated { pool, previous_admin, new_admin },
+        RewardsPoolPaused { pool, paused },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorSerialize;
+
+    fn program_data<T: Discriminator + AnchorSerialize>(event: &T) -> String {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        event.serialize(&mut data).unwrap();
+        format!("{PROGRAM_DATA_PREFIX}{}", STANDARD.encode(data))
+    }
+
+    #[test]
+    fn decodes_events_from_program_logs() {
+        let stable_vault = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let logs = vec![
+            "Program 98jhX2iz4cec2evPKhLwA1HriVEbUAsMBo61bQpSef5Z invoke [1]".to_string(),
+            "Program log: Instruction: UpdateEmergencyAdmin".to_string(),
+            program_data(&::stable_vault::EmergencyAdminUpdated {
+                stable_vault,
+                previous_emergency_admin: None,
+                new_emergency_admin: Some(admin),
+            }),
+            program_data(&::splitter::YieldRedeemed {
+                market: stable_vault,
+                user: admin,
+                claimed_amount: 7,
+                fee_index: u128::MAX,
+                market_delta: 1,
+            }),
+            "Program data: not-an-event".to_string(),
+        ];
+
+        let events = decode_logs(&logs);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].program, "stable_vault");
+        assert_eq!(events[0].name, "EmergencyAdminUpdated");
+        assert_eq!(
+            events[0].data,
+            json!({
+                "stable_vault": stable_vault.to_string(),
+                "previous_emergency_admin": null,
+                "new_emergency_admin": admin.to_string(),
+            })
+        );
+        assert_eq!(events[1].name, "YieldRedeemed");
+        assert_eq!(events[1].data["claimed_amount"], json!(7));
+        assert_eq!(events[1].data["fee_index"], json!(u128::MAX.to_string()));
+    }
+}
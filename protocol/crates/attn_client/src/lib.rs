@@ -1,12 +1,24 @@
 use anchor_client::Program;
 use anchor_lang::{AnchorDeserialize, AnchorSerialize, InstructionData, ToAccountMetas};
 use anyhow::{bail, Result};
+use solana_client::{
+    rpc_config::RpcProgramAccountsConfig,
+    rpc_filter::{Memcmp, RpcFilterType},
+};
 use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signature::Signer;
 use std::ops::Deref;
 
+pub mod alt;
+pub mod errors;
+pub mod events;
+pub mod nonblocking;
+pub mod sources;
+pub mod squads;
+pub mod tx;
+
 pub struct AttnClient<C>
 where
     C: Deref + Clone,
@@ -15,7 +27,8 @@ where
     program: Program<C>,
 }
 
-fn decode_account<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
+/// Decodes an Anchor account's data, skipping its 8-byte discriminator.
+pub fn decode_account<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
     if data.len() < 8 {
         bail!("account data too short");
     }
@@ -23,6 +36,55 @@ fn decode_account<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
     T::deserialize(&mut slice).map_err(|err| err.into())
 }
 
+/// `getProgramAccounts` config selecting accounts with `discriminator`, optionally
+/// holding `pubkey` at a byte offset.
+fn program_accounts_config(
+    discriminator: &[u8],
+    pubkey_at: Option<(usize, Pubkey)>,
+) -> RpcProgramAccountsConfig {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+        0,
+        discriminator.to_vec(),
+    ))];
+    if let Some((offset, pubkey)) = pubkey_at {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            offset,
+            pubkey.to_bytes().to_vec(),
+        )));
+    }
+    RpcProgramAccountsConfig {
+        filters: Some(filters),
+        ..Default::default()
+    }
+}
+
+fn decode_accounts<T: AnchorDeserialize>(
+    accounts: Vec<(Pubkey, solana_sdk::account::Account)>,
+) -> Result<Vec<(Pubkey, T)>> {
+    accounts
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode_account(&account.data)?)))
+        .collect()
+}
+
+fn scan_accounts<C, T: AnchorDeserialize>(
+    program: &Program<C>,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+    pubkey_at: Option<(usize, Pubkey)>,
+) -> Result<Vec<(Pubkey, T)>>
+where
+    C: Deref + Clone,
+    C::Target: Signer + Sized,
+{
+    let config = program_accounts_config(discriminator, pubkey_at);
+    decode_accounts(
+        program
+            .rpc()
+            .get_program_accounts_with_config(program_id, config)?,
+    )
+}
+
 impl<C> AttnClient<C>
 where
     C: Deref + Clone,
@@ -51,11 +113,15 @@ where
 
 pub mod stable {
     use super::*;
+    use crate::errors::AttnProgramError;
+    use anchor_lang::prelude::borsh;
+    use anchor_lang::Discriminator;
     use anchor_spl::token;
     use solana_sdk::pubkey::Pubkey;
     use solana_sdk::{signature::Signer, system_program, sysvar};
     use stable_vault::accounts as stable_accounts;
     use stable_vault::instruction as stable_ix;
+    use stable_vault::AttnError as StableError;
 
     #[derive(Debug, Clone)]
     pub struct StableVaultPdas {
@@ -90,6 +156,11 @@ pub mod stable {
 
     pub fn derive_pdas(authority: &Pubkey, stable_mint: &Pubkey) -> StableVaultPdas {
         let (stable_vault, _) = stable_vault_pda(authority);
+        vault_pdas(stable_vault, stable_mint)
+    }
+
+    /// PDAs of an existing vault, without reading it.
+    pub fn vault_pdas(stable_vault: Pubkey, stable_mint: &Pubkey) -> StableVaultPdas {
         let (treasury, _) = treasury_pda(&stable_vault, stable_mint);
         let (share_mint, _) = share_mint_pda(&stable_vault);
         let (sol_vault, _) = sol_vault_pda(&stable_vault);
@@ -101,6 +172,124 @@ pub mod stable {
         }
     }
 
+    /// Mirror of `stable_vault::StableVault`.
+    #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+    pub struct StableVaultAccount {
+        pub bump: u8,
+        pub treasury_bump: u8,
+        pub sol_vault_bump: u8,
+        pub share_mint_bump: u8,
+        pub authority_seed: Pubkey,
+        pub keeper_authority: Pubkey,
+        pub admin: Pubkey,
+        pub emergency_admin: Option<Pubkey>,
+        pub share_mint: Pubkey,
+        pub stable_mint: Pubkey,
+        pub treasury: Pubkey,
+        pub sol_vault: Pubkey,
+        pub total_assets: u64,
+        pub total_shares: u64,
+        pub pending_sol: u64,
+        pub last_sweep_id: u64,
+        pub last_conversion_id: u64,
+        pub paused: bool,
+        pub accepted_mints: Vec<Pubkey>,
+        pub padding: [u8; 32],
+    }
+
+    /// Byte offset of `StableVault::admin`, for `getProgramAccounts` filters.
+    pub const STABLE_VAULT_ADMIN_OFFSET: usize = 8 + 4 + 32 + 32;
+
+    impl StableVaultAccount {
+        pub fn pdas(&self, address: Pubkey) -> StableVaultPdas {
+            StableVaultPdas {
+                stable_vault: address,
+                treasury: self.treasury,
+                share_mint: self.share_mint,
+                sol_vault: self.sol_vault,
+            }
+        }
+
+        /// Assets per share scaled by `stable_vault::PRICE_SCALE`.
+        pub fn price_per_share(&self) -> u64 {
+            if self.total_shares == 0 {
+                return stable_vault::PRICE_SCALE as u64;
+            }
+            (self.total_assets as u128)
+                .saturating_mul(stable_vault::PRICE_SCALE)
+                .checked_div(self.total_shares as u128)
+                .unwrap_or(stable_vault::PRICE_SCALE) as u64
+        }
+
+        /// Shares `deposit_stable` mints for `amount`, failing as the program would.
+        pub fn preview_deposit(&self, amount: u64) -> Result<u64, AttnProgramError> {
+            let error = AttnProgramError::StableVault;
+            if amount == 0 {
+                return Err(error(StableError::InvalidAmount));
+            }
+            if self.total_shares == 0 || self.total_assets == 0 {
+                return Ok(amount);
+            }
+            let shares = (amount as u128)
+                .checked_mul(self.total_shares as u128)
+                .ok_or(error(StableError::MathOverflow))?
+                / self.total_assets as u128;
+            if shares == 0 {
+                return Err(error(StableError::AmountTooSmall));
+            }
+            u64::try_from(shares).map_err(|_| error(StableError::MathOverflow))
+        }
+
+        /// Assets `redeem_attnusd` returns for `shares`, failing as the program would.
+        pub fn preview_redeem(&self, shares: u64) -> Result<u64, AttnProgramError> {
+            let error = AttnProgramError::StableVault;
+            if shares == 0 {
+                return Err(error(StableError::InvalidAmount));
+            }
+            if shares > self.total_shares {
+                return Err(error(StableError::InsufficientShares));
+            }
+            if shares == self.total_shares {
+                return Ok(self.total_assets);
+            }
+            let assets = (shares as u128)
+                .checked_mul(self.total_assets as u128)
+                .ok_or(error(StableError::MathOverflow))?
+                / self.total_shares as u128;
+            if assets == 0 {
+                return Err(error(StableError::AmountTooSmall));
+            }
+            u64::try_from(assets).map_err(|_| error(StableError::MathOverflow))
+        }
+    }
+
+    pub async fn fetch_vault<C>(program: &Program<C>, address: Pubkey) -> Result<StableVaultAccount>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let account = program.rpc().get_account(&address)?;
+        decode_account::<StableVaultAccount>(&account.data)
+    }
+
+    /// Scans the program for StableVaults, optionally only those administered by `admin`.
+    pub async fn fetch_vaults<C>(
+        program: &Program<C>,
+        admin: Option<Pubkey>,
+    ) -> Result<Vec<(Pubkey, StableVaultAccount)>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let admin = admin.map(|admin| (STABLE_VAULT_ADMIN_OFFSET, admin));
+        scan_accounts(
+            program,
+            &stable_vault::ID,
+            stable_vault::StableVault::DISCRIMINATOR,
+            admin,
+        )
+    }
+
     pub fn build_initialize_vault_ix(
         authority: Pubkey,
         stable_mint: Pubkey,
@@ -251,6 +440,131 @@ pub mod stable {
         }
     }
 
+    pub fn build_update_admin_ix(
+        stable_vault: Pubkey,
+        admin: Pubkey,
+        new_admin: Pubkey,
+    ) -> Instruction {
+        let accounts = stable_accounts::UpdateAdmin {
+            stable_vault,
+            admin,
+        };
+        let data = stable_ix::UpdateAdmin { new_admin }.data();
+        Instruction {
+            program_id: stable_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    pub fn build_update_keeper_authority_ix(
+        stable_vault: Pubkey,
+        authority: Pubkey,
+        new_keeper: Pubkey,
+    ) -> Instruction {
+        let accounts = stable_accounts::UpdateKeeperAuthority {
+            stable_vault,
+            authority,
+        };
+        let data = stable_ix::UpdateKeeperAuthority { new_keeper }.data();
+        Instruction {
+            program_id: stable_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    pub fn build_set_pause_ix(
+        stable_vault: Pubkey,
+        authority: Pubkey,
+        is_paused: bool,
+    ) -> Instruction {
+        let accounts = stable_accounts::SetStableVaultPause {
+            stable_vault,
+            authority,
+        };
+        let data = stable_ix::SetPause { is_paused }.data();
+        Instruction {
+            program_id: stable_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    pub fn build_update_emergency_admin_ix(
+        stable_vault: Pubkey,
+        admin: Pubkey,
+        new_emergency_admin: Option<Pubkey>,
+    ) -> Instruction {
+        let accounts = stable_accounts::UpdateEmergencyAdmin {
+            stable_vault,
+            admin,
+        };
+        let data = stable_ix::UpdateEmergencyAdmin {
+            new_emergency_admin,
+        }
+        .data();
+        Instruction {
+            program_id: stable_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    pub fn build_add_accepted_mint_ix(
+        stable_vault: Pubkey,
+        authority: Pubkey,
+        mint: Pubkey,
+    ) -> Instruction {
+        let accounts = stable_accounts::ManageAcceptedMint {
+            stable_vault,
+            authority,
+        };
+        let data = stable_ix::AddAcceptedMint { mint }.data();
+        Instruction {
+            program_id: stable_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    pub fn build_remove_accepted_mint_ix(
+        stable_vault: Pubkey,
+        authority: Pubkey,
+        mint: Pubkey,
+    ) -> Instruction {
+        let accounts = stable_accounts::ManageAcceptedMint {
+            stable_vault,
+            authority,
+        };
+        let data = stable_ix::RemoveAcceptedMint { mint }.data();
+        Instruction {
+            program_id: stable_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    pub fn build_withdraw_sol_dust_ix(
+        stable_vault: Pubkey,
+        authority: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let accounts = stable_accounts::WithdrawSolDust {
+            stable_vault,
+            authority,
+            sol_vault: sol_vault_pda(&stable_vault).0,
+            destination,
+        };
+        let data = stable_ix::WithdrawSolDust { amount }.data();
+        Instruction {
+            program_id: stable_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
     pub struct StableVaultClient<'a, C>
     where
         C: Deref + Clone,
@@ -445,19 +759,11 @@ pub mod stable {
             pdas: &StableVaultPdas,
             new_emergency_admin: Option<Pubkey>,
         ) -> Result<()> {
-            let accounts = stable_accounts::UpdateEmergencyAdmin {
-                stable_vault: pdas.stable_vault,
-                admin: admin.pubkey(),
-            };
-            let data = stable_ix::UpdateEmergencyAdmin {
+            let ix = build_update_emergency_admin_ix(
+                pdas.stable_vault,
+                admin.pubkey(),
                 new_emergency_admin,
-            }
-            .data();
-            let ix = Instruction {
-                program_id: stable_vault::ID,
-                accounts: accounts.to_account_metas(None),
-                data,
-            };
+            );
             self.program
                 .request()
                 .instruction(ix)
@@ -496,16 +802,7 @@ pub mod stable {
             pdas: &StableVaultPdas,
             mint: Pubkey,
         ) -> Result<()> {
-            let accounts = stable_accounts::ManageAcceptedMint {
-                stable_vault: pdas.stable_vault,
-                authority: authority.pubkey(),
-            };
-            let data = stable_ix::AddAcceptedMint { mint }.data();
-            let ix = Instruction {
-                program_id: stable_vault::ID,
-                accounts: accounts.to_account_metas(None),
-                data,
-            };
+            let ix = build_add_accepted_mint_ix(pdas.stable_vault, authority.pubkey(), mint);
             self.program
                 .request()
                 .instruction(ix)
@@ -520,16 +817,7 @@ pub mod stable {
             pdas: &StableVaultPdas,
             mint: Pubkey,
         ) -> Result<()> {
-            let accounts = stable_accounts::ManageAcceptedMint {
-                stable_vault: pdas.stable_vault,
-                authority: authority.pubkey(),
-            };
-            let data = stable_ix::RemoveAcceptedMint { mint }.data();
-            let ix = Instruction {
-                program_id: stable_vault::ID,
-                accounts: accounts.to_account_metas(None),
-                data,
-            };
+            let ix = build_remove_accepted_mint_ix(pdas.stable_vault, authority.pubkey(), mint);
             self.program
                 .request()
                 .instruction(ix)
@@ -545,18 +833,12 @@ pub mod stable {
             destination: Pubkey,
             amount: u64,
         ) -> Result<()> {
-            let accounts = stable_accounts::WithdrawSolDust {
-                stable_vault: pdas.stable_vault,
-                authority: authority.pubkey(),
-                sol_vault: pdas.sol_vault,
+            let ix = build_withdraw_sol_dust_ix(
+                pdas.stable_vault,
+                authority.pubkey(),
                 destination,
-            };
-            let data = stable_ix::WithdrawSolDust { amount }.data();
-            let ix = Instruction {
-                program_id: stable_vault::ID,
-                accounts: accounts.to_account_metas(None),
-                data,
-            };
+                amount,
+            );
             self.program
                 .request()
                 .instruction(ix)
@@ -569,6 +851,9 @@ pub mod stable {
 
 pub mod rewards {
     use super::*;
+    use crate::errors::AttnProgramError;
+    use anchor_lang::prelude::borsh;
+    use anchor_lang::Discriminator;
     use anchor_spl::token;
     use rewards_vault::accounts as rewards_accounts;
     use rewards_vault::instruction as rewards_ix;
@@ -634,6 +919,156 @@ pub mod rewards {
         }
     }
 
+    /// Mirror of `rewards_vault::RewardsPool`.
+    #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+    pub struct RewardsPoolAccount {
+        pub bump: u8,
+        pub authority_bump: u8,
+        pub treasury_bump: u8,
+        pub admin: Pubkey,
+        pub creator_vault: Pubkey,
+        pub attn_mint: Pubkey,
+        pub s_attn_mint: Pubkey,
+        pub attn_vault: Pubkey,
+        pub total_staked: u64,
+        pub sol_per_share: u128,
+        pub pending_rewards: u64,
+        pub reward_bps: u16,
+        pub allowed_funder: Pubkey,
+        pub last_treasury_balance: u64,
+        pub last_fund_id: u64,
+        pub is_paused: bool,
+        pub padding: [u8; 5],
+    }
+
+    /// Mirror of `rewards_vault::StakePosition`.
+    #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+    pub struct StakePositionAccount {
+        pub bump: u8,
+        pub pool: Pubkey,
+        pub user: Pubkey,
+        pub staked_amount: u64,
+        pub reward_debt: u128,
+        pub padding: [u8; 7],
+    }
+
+    /// Byte offsets for `getProgramAccounts` filters.
+    pub const REWARDS_POOL_ADMIN_OFFSET: usize = 8 + 3;
+    pub const STAKE_POSITION_USER_OFFSET: usize = 8 + 1 + 32;
+
+    impl RewardsPoolAccount {
+        /// Folds rewards funded while nothing was staked into `sol_per_share`, as
+        /// every stake, unstake and claim does first.
+        pub fn distribute_pending(&mut self) -> Result<(), AttnProgramError> {
+            if self.total_staked > 0 && self.pending_rewards > 0 {
+                self.sol_per_share = self
+                    .sol_per_share
+                    .checked_add(index_increment(self.pending_rewards, self.total_staked)?)
+                    .ok_or(overflow())?;
+                self.pending_rewards = 0;
+            }
+            Ok(())
+        }
+
+        /// Lamports owed to `position` at the current index.
+        pub fn pending_amount(
+            &self,
+            position: &StakePositionAccount,
+        ) -> Result<u64, AttnProgramError> {
+            let accrued = (position.staked_amount as u128)
+                .checked_mul(self.sol_per_share)
+                .ok_or(overflow())?
+                / rewards_vault::INDEX_SCALE;
+            Ok(accrued.saturating_sub(position.reward_debt) as u64)
+        }
+
+        /// Lamports `settle_rewards` would pay `position` on its next stake, unstake
+        /// or claim.
+        pub fn settle_rewards(
+            &self,
+            position: &StakePositionAccount,
+        ) -> Result<u64, AttnProgramError> {
+            let mut pool = self.clone();
+            pool.distribute_pending()?;
+            pool.pending_amount(position)
+        }
+    }
+
+    fn index_increment(amount: u64, total_staked: u64) -> Result<u128, AttnProgramError> {
+        Ok((amount as u128)
+            .checked_mul(rewards_vault::INDEX_SCALE)
+            .ok_or(overflow())?
+            / total_staked as u128)
+    }
+
+    fn overflow() -> AttnProgramError {
+        AttnProgramError::Rewards(rewards_vault::RewardsError::MathOverflow)
+    }
+
+    pub async fn fetch_pool<C>(program: &Program<C>, address: Pubkey) -> Result<RewardsPoolAccount>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let account = program.rpc().get_account(&address)?;
+        decode_account::<RewardsPoolAccount>(&account.data)
+    }
+
+    /// Returns `None` when `user` has never staked in the pool.
+    pub async fn fetch_stake_position<C>(
+        program: &Program<C>,
+        rewards_pool: Pubkey,
+        user: Pubkey,
+    ) -> Result<Option<StakePositionAccount>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let (address, _) = stake_position_pda(&rewards_pool, &user);
+        let rpc = program.rpc();
+        let account = rpc
+            .get_account_with_commitment(&address, rpc.commitment())?
+            .value;
+        account
+            .map(|account| decode_account::<StakePositionAccount>(&account.data))
+            .transpose()
+    }
+
+    /// Scans the program for rewards pools, optionally only those administered by `admin`.
+    pub async fn fetch_pools<C>(
+        program: &Program<C>,
+        admin: Option<Pubkey>,
+    ) -> Result<Vec<(Pubkey, RewardsPoolAccount)>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let admin = admin.map(|admin| (REWARDS_POOL_ADMIN_OFFSET, admin));
+        scan_accounts(
+            program,
+            &rewards_vault::ID,
+            rewards_vault::RewardsPool::DISCRIMINATOR,
+            admin,
+        )
+    }
+
+    /// Every stake position `user` holds, across pools.
+    pub async fn fetch_stake_positions<C>(
+        program: &Program<C>,
+        user: Pubkey,
+    ) -> Result<Vec<(Pubkey, StakePositionAccount)>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        scan_accounts(
+            program,
+            &rewards_vault::ID,
+            rewards_vault::StakePosition::DISCRIMINATOR,
+            Some((STAKE_POSITION_USER_OFFSET, user)),
+        )
+    }
+
     pub fn build_initialize_pool_ix(
         payer: Pubkey,
         admin: Pubkey,
@@ -1083,6 +1518,12 @@ pub mod creator {
     use creator_vault::instruction as creator_ix;
     use solana_sdk::{instruction::AccountMeta, system_program, sysvar};
 
+    pub use creator_vault::{
+        RevenueSourceConfig, RevenueSourceKind, SweeperLimits, PUMP_PROGRAM_ID,
+    };
+
+    pub const WSOL_MINT: Pubkey = token::spl_token::native_mint::ID;
+
     #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
     pub struct CreatorVaultAccount {
         pub bump: u8,
@@ -1101,7 +1542,23 @@ pub mod creator {
         pub paused: bool,
         pub locked: bool,
         pub lock_expires_at: i64,
-        pub padding: [u8; 1],
+        pub source_kind: RevenueSourceKind,
+    }
+
+    impl CreatorVaultAccount {
+        pub fn from_account_data(data: &[u8]) -> Result<Self> {
+            decode_account(data)
+        }
+    }
+
+    #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+    pub struct RevenueSourceAccount {
+        pub bump: u8,
+        pub creator_vault: Pubkey,
+        pub config: RevenueSourceConfig,
+        pub total_collected: u64,
+        pub last_collected_at: i64,
+        pub padding: [u8; 32],
     }
 
     #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -1114,6 +1571,33 @@ pub mod creator {
         pub padding: [u8; 5],
     }
 
+    #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+    pub struct SweeperAllowanceAccount {
+        pub bump: u8,
+        pub creator_vault: Pubkey,
+        pub delegate: Pubkey,
+        pub limits: SweeperLimits,
+        pub window_start_ts: i64,
+        pub window_swept: u64,
+        pub revoked: bool,
+        pub last_sweep_ts: i64,
+        pub padding: [u8; 7],
+    }
+
+    impl SweeperAllowanceAccount {
+        /// Amount the delegate may still sweep at `now`, accounting for an
+        /// expired window resetting on the next sweep.
+        pub fn remaining_in_window(&self, now: i64) -> u64 {
+            if now >= self.window_start_ts.saturating_add(self.limits.window_secs) {
+                self.limits.max_amount_per_window
+            } else {
+                self.limits
+                    .max_amount_per_window
+                    .saturating_sub(self.window_swept)
+            }
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub struct CreatorVaultPdas {
         pub creator_vault: Pubkey,
@@ -1137,6 +1621,56 @@ pub mod creator {
         Pubkey::find_program_address(&[b"sweeper", creator_vault.as_ref()], &creator_vault::ID)
     }
 
+    pub fn sweeper_allowance_pda(creator_vault: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"sweeper-allowance",
+                creator_vault.as_ref(),
+                delegate.as_ref(),
+            ],
+            &creator_vault::ID,
+        )
+    }
+
+    pub fn revenue_source_pda(creator_vault: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"revenue-source", creator_vault.as_ref()],
+            &creator_vault::ID,
+        )
+    }
+
+    /// Pump.fun PDA accruing creator fees for `creator`.
+    pub fn pump_creator_fee_pda(creator: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"creator-vault", creator.as_ref()], &PUMP_PROGRAM_ID)
+    }
+
+    pub fn pump_bonding_curve_pda(pump_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"bonding-curve", pump_mint.as_ref()], &PUMP_PROGRAM_ID)
+    }
+
+    /// Creator-fee recipient recorded on a Pump.fun bonding curve: five u64
+    /// reserves and the `complete` flag follow the discriminator.
+    pub fn pump_bonding_curve_creator(data: &[u8]) -> Option<Pubkey> {
+        const CREATOR_OFFSET: usize = 8 + 5 * 8 + 1;
+        let bytes = data.get(CREATOR_OFFSET..CREATOR_OFFSET + 32)?;
+        Some(Pubkey::new_from_array(bytes.try_into().ok()?))
+    }
+
+    /// Creator quote-side trading fees not yet claimed from a Meteora DBC
+    /// `VirtualPool`: the volatility tracker, five pubkeys, six reserve and fee
+    /// u64s, the sqrt price, activation point, eight flags, pool metrics,
+    /// finish timestamp and creator base fee follow the discriminator.
+    pub fn dbc_pool_creator_quote_fee(data: &[u8]) -> Option<u64> {
+        const CREATOR_QUOTE_FEE_OFFSET: usize =
+            8 + 64 + 5 * 32 + 6 * 8 + 16 + 8 + 8 + 4 * 8 + 8 + 8;
+        let bytes = data.get(CREATOR_QUOTE_FEE_OFFSET..CREATOR_QUOTE_FEE_OFFSET + 8)?;
+        Some(u64::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    pub fn pump_event_authority_pda() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"__event_authority"], &PUMP_PROGRAM_ID)
+    }
+
     pub fn derive_pdas(pump_mint: &Pubkey) -> CreatorVaultPdas {
         let (creator_vault, _) = creator_vault_pda(pump_mint);
         let (fee_vault, _) = fee_vault_pda(pump_mint);
@@ -1183,6 +1717,51 @@ pub mod creator {
         }
     }
 
+    pub async fn fetch_sweeper_allowance<C>(
+        program: &Program<C>,
+        creator_vault: Pubkey,
+        delegate: Pubkey,
+    ) -> Result<Option<SweeperAllowanceAccount>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let (allowance, _) = sweeper_allowance_pda(&creator_vault, &delegate);
+        match program.rpc().get_account(&allowance) {
+            Ok(account) => Ok(Some(decode_account::<SweeperAllowanceAccount>(
+                &account.data,
+            )?)),
+            Err(err) => {
+                if err.to_string().contains("AccountNotFound") {
+                    Ok(None)
+                } else {
+                    Err(err.into())
+                }
+            }
+        }
+    }
+
+    pub async fn fetch_revenue_source<C>(
+        program: &Program<C>,
+        creator_vault: Pubkey,
+    ) -> Result<Option<RevenueSourceAccount>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let (revenue_source, _) = revenue_source_pda(&creator_vault);
+        match program.rpc().get_account(&revenue_source) {
+            Ok(account) => Ok(Some(decode_account::<RevenueSourceAccount>(&account.data)?)),
+            Err(err) => {
+                if err.to_string().contains("AccountNotFound") {
+                    Ok(None)
+                } else {
+                    Err(err.into())
+                }
+            }
+        }
+    }
+
     pub fn build_initialize_vault_ix(
         authority: Pubkey,
         pump_creator: Pubkey,
@@ -1219,6 +1798,127 @@ pub mod creator {
         )
     }
 
+    pub fn build_initialize_source_vault_ix(
+        authority: Pubkey,
+        source_id: Pubkey,
+        quote_mint: Pubkey,
+        config: RevenueSourceConfig,
+        splitter_program: Pubkey,
+        admin: Pubkey,
+    ) -> (Instruction, CreatorVaultPdas) {
+        let pdas = derive_pdas(&source_id);
+        let (revenue_source, _) = revenue_source_pda(&pdas.creator_vault);
+        let accounts = creator_accounts::InitializeSourceVault {
+            authority,
+            source_id,
+            quote_mint,
+            creator_vault: pdas.creator_vault,
+            fee_vault: pdas.fee_vault,
+            sy_mint: pdas.sy_mint,
+            revenue_source,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+        };
+        let data = creator_ix::InitializeSourceVault {
+            config,
+            splitter_program,
+            admin,
+        }
+        .data();
+        (
+            Instruction {
+                program_id: creator_vault::ID,
+                accounts: accounts.to_account_metas(None),
+                data,
+            },
+            pdas,
+        )
+    }
+
+    /// `claim_accounts` are forwarded, in order, to the source program's
+    /// claim instruction; the program rejects any layout that would route
+    /// fees away from the vault.
+    pub fn build_claim_source_fees_ix(
+        source_id: Pubkey,
+        source_program: Pubkey,
+        cranker: Pubkey,
+        max_quote_amount: u64,
+        claim_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let pdas = derive_pdas(&source_id);
+        let (revenue_source, _) = revenue_source_pda(&pdas.creator_vault);
+        let mut accounts = creator_accounts::ClaimSourceFees {
+            creator_vault: pdas.creator_vault,
+            revenue_source,
+            fee_vault: pdas.fee_vault,
+            source_program,
+            cranker,
+        }
+        .to_account_metas(None);
+        accounts.extend(claim_accounts);
+        let data = creator_ix::ClaimSourceFees { max_quote_amount }.data();
+        Instruction {
+            program_id: creator_vault::ID,
+            accounts,
+            data,
+        }
+    }
+
+    pub fn build_deposit_source_revenue_ix(
+        source_id: Pubkey,
+        quote_mint: Pubkey,
+        depositor: Pubkey,
+        depositor_token_account: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let pdas = derive_pdas(&source_id);
+        let (revenue_source, _) = revenue_source_pda(&pdas.creator_vault);
+        let accounts = creator_accounts::DepositSourceRevenue {
+            creator_vault: pdas.creator_vault,
+            revenue_source,
+            quote_mint,
+            fee_vault: pdas.fee_vault,
+            depositor,
+            depositor_token_account,
+            token_program: token::ID,
+        };
+        let data = creator_ix::DepositSourceRevenue { amount }.data();
+        Instruction {
+            program_id: creator_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    /// Set `with_revenue_source` for vaults created by `initialize_source_vault`
+    /// so the source totals are updated alongside the vault.
+    pub fn build_collect_creator_fees_ix(
+        pump_mint: Pubkey,
+        cranker: Pubkey,
+        with_revenue_source: bool,
+    ) -> Instruction {
+        let pdas = derive_pdas(&pump_mint);
+        let revenue_source = with_revenue_source.then(|| revenue_source_pda(&pdas.creator_vault).0);
+        let accounts = creator_accounts::CollectCreatorFees {
+            creator_vault: pdas.creator_vault,
+            revenue_source,
+            fee_vault: pdas.fee_vault,
+            pump_creator_vault: pump_creator_fee_pda(&pdas.creator_vault).0,
+            pump_event_authority: pump_event_authority_pda().0,
+            pump_program: PUMP_PROGRAM_ID,
+            cranker,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        };
+        let data = creator_ix::CollectCreatorFees {}.data();
+        Instruction {
+            program_id: creator_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
     pub fn build_set_rewards_split_ix(
         creator_vault: Pubkey,
         admin: Pubkey,
@@ -1243,10 +1943,12 @@ pub mod creator {
         fee_bps: u16,
     ) -> Instruction {
         let (sweeper, _) = sweeper_pda(&creator_vault);
+        let (allowance, _) = sweeper_allowance_pda(&creator_vault, &delegate);
         let accounts = creator_accounts::SetSweeperDelegate {
             creator_vault,
             authority,
             sweeper,
+            allowance,
             system_program: system_program::ID,
         };
         let data = creator_ix::SetSweeperDelegate { delegate, fee_bps }.data();
@@ -1275,6 +1977,46 @@ pub mod creator {
         }
     }
 
+    pub fn build_allow_sweeper_delegate_ix(
+        creator_vault: Pubkey,
+        admin: Pubkey,
+        delegate: Pubkey,
+        limits: SweeperLimits,
+    ) -> Instruction {
+        let (allowance, _) = sweeper_allowance_pda(&creator_vault, &delegate);
+        let accounts = creator_accounts::AllowSweeperDelegate {
+            creator_vault,
+            admin,
+            allowance,
+            system_program: system_program::ID,
+        };
+        let data = creator_ix::AllowSweeperDelegate { delegate, limits }.data();
+        Instruction {
+            program_id: creator_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    pub fn build_revoke_sweeper_delegate_ix(
+        creator_vault: Pubkey,
+        admin: Pubkey,
+        delegate: Pubkey,
+    ) -> Instruction {
+        let (allowance, _) = sweeper_allowance_pda(&creator_vault, &delegate);
+        let accounts = creator_accounts::RevokeSweeperDelegate {
+            creator_vault,
+            admin,
+            allowance,
+        };
+        let data = creator_ix::RevokeSweeperDelegate {}.data();
+        Instruction {
+            program_id: creator_vault::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
     pub fn build_delegate_sweep_ix(
         creator_vault: Pubkey,
         pump_mint: Pubkey,
@@ -1284,10 +2026,12 @@ pub mod creator {
         delegate_fee_destination: Option<Pubkey>,
     ) -> Instruction {
         let (sweeper, _) = sweeper_pda(&creator_vault);
+        let (allowance, _) = sweeper_allowance_pda(&creator_vault, &delegate);
         let (fee_vault, _) = fee_vault_pda(&pump_mint);
         let mut accounts = vec![
             AccountMeta::new(creator_vault, false),
             AccountMeta::new(sweeper, false),
+            AccountMeta::new(allowance, false),
             AccountMeta::new_readonly(delegate, true),
             AccountMeta::new(fee_vault, false),
             AccountMeta::new(destination, false),
@@ -1432,8 +2176,9 @@ pub mod splitter {
     use ::splitter::accounts as splitter_accounts;
     use ::splitter::instruction as splitter_ix;
     use anchor_lang::prelude::borsh;
+    use anchor_lang::Discriminator;
     use anchor_spl::token;
-    use solana_sdk::system_program;
+    use solana_sdk::{system_program, sysvar};
 
     #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
     pub struct MarketAccount {
@@ -1452,9 +2197,9 @@ pub mod splitter {
 
     #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
     pub struct UserPositionAccount {
-        pub bump: u8,
         pub market: Pubkey,
         pub user: Pubkey,
+        pub bump: u8,
         pub last_fee_index: u128,
         pub pending_yield_scaled: u128,
     }
@@ -1488,6 +2233,111 @@ pub mod splitter {
         decode_account::<MarketAccount>(&account.data)
     }
 
+    /// Scans the splitter for markets, optionally only those backed by `creator_vault`.
+    pub async fn fetch_markets<C>(
+        program: &Program<C>,
+        creator_vault: Option<Pubkey>,
+    ) -> Result<Vec<(Pubkey, MarketAccount)>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let creator_vault = creator_vault.map(|creator_vault| (8, creator_vault));
+        scan_accounts(
+            program,
+            &::splitter::ID,
+            ::splitter::Market::DISCRIMINATOR,
+            creator_vault,
+        )
+    }
+
+    /// Returns `None` when the user has never minted in the market.
+    pub async fn fetch_user_position<C>(
+        program: &Program<C>,
+        market: Pubkey,
+        user: Pubkey,
+    ) -> Result<Option<UserPositionAccount>>
+    where
+        C: Deref + Clone,
+        C::Target: Signer + Sized,
+    {
+        let (address, _) = user_position_pda(&market, &user);
+        let rpc = program.rpc();
+        let account = rpc
+            .get_account_with_commitment(&address, rpc.commitment())?
+            .value;
+        account
+            .map(|account| decode_account::<UserPositionAccount>(&account.data))
+            .transpose()
+    }
+
+    /// Caller-supplied accounts for `create_market`. `market`, `pt_mint` and `yt_mint` are
+    /// fresh keypairs that must co-sign.
+    #[derive(Debug, Clone)]
+    pub struct CreateMarketAccounts {
+        pub authority: Pubkey,
+        pub creator_vault: Pubkey,
+        pub pump_mint: Pubkey,
+        pub sy_mint: Pubkey,
+        pub market: Pubkey,
+        pub pt_mint: Pubkey,
+        pub yt_mint: Pubkey,
+    }
+
+    /// The program creates both mints with the SY mint's decimals and the CreatorVault as
+    /// mint authority.
+    pub fn build_create_market_ix(
+        accounts: &CreateMarketAccounts,
+        maturity_ts: i64,
+    ) -> Instruction {
+        let (splitter_authority, _) = splitter_authority_pda(&accounts.creator_vault);
+        let accounts = splitter_accounts::CreateMarket {
+            authority: accounts.authority,
+            creator_vault: accounts.creator_vault,
+            splitter_authority,
+            pump_mint: accounts.pump_mint,
+            sy_mint: accounts.sy_mint,
+            market: accounts.market,
+            pt_mint: accounts.pt_mint,
+            yt_mint: accounts.yt_mint,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+        };
+        let data = splitter_ix::CreateMarket { maturity_ts }.data();
+        Instruction {
+            program_id: ::splitter::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
+    /// Both the CreatorVault authority (who receives the market rent) and the
+    /// CreatorVault admin must sign; they may be the same key.
+    pub fn build_close_market_ix(
+        creator_authority: Pubkey,
+        admin: Pubkey,
+        creator_vault: Pubkey,
+        market: Pubkey,
+        pt_mint: Pubkey,
+        yt_mint: Pubkey,
+    ) -> Instruction {
+        let accounts = splitter_accounts::CloseMarket {
+            creator_authority,
+            admin,
+            creator_vault,
+            market,
+            pt_mint,
+            yt_mint,
+        };
+        let data = splitter_ix::CloseMarket {}.data();
+        Instruction {
+            program_id: ::splitter::ID,
+            accounts: accounts.to_account_metas(None),
+            data,
+        }
+    }
+
     pub fn build_mint_pt_yt_ix(
         market: Pubkey,
         creator_vault: Pubkey,
@@ -1598,3 +2448,233 @@ pub mod splitter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::AttnProgramError;
+    use proptest::prelude::*;
+    use rewards::{RewardsPoolAccount, StakePositionAccount};
+    use stable::StableVaultAccount;
+
+    fn stable_vault(total_assets: u64, total_shares: u64) -> StableVaultAccount {
+        StableVaultAccount {
+            bump: 1,
+            treasury_bump: 2,
+            sol_vault_bump: 3,
+            share_mint_bump: 4,
+            authority_seed: Pubkey::new_unique(),
+            keeper_authority: Pubkey::new_unique(),
+            admin: Pubkey::new_unique(),
+            emergency_admin: Some(Pubkey::new_unique()),
+            share_mint: Pubkey::new_unique(),
+            stable_mint: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            sol_vault: Pubkey::new_unique(),
+            total_assets,
+            total_shares,
+            pending_sol: 5,
+            last_sweep_id: 6,
+            last_conversion_id: 7,
+            paused: false,
+            accepted_mints: vec![Pubkey::new_unique()],
+            padding: [0; 32],
+        }
+    }
+
+    fn program_vault(vault: &StableVaultAccount) -> ::stable_vault::StableVault {
+        ::stable_vault::StableVault {
+            bump: vault.bump,
+            treasury_bump: vault.treasury_bump,
+            sol_vault_bump: vault.sol_vault_bump,
+            share_mint_bump: vault.share_mint_bump,
+            authority_seed: vault.authority_seed,
+            keeper_authority: vault.keeper_authority,
+            admin: vault.admin,
+            emergency_admin: vault.emergency_admin,
+            share_mint: vault.share_mint,
+            stable_mint: vault.stable_mint,
+            treasury: vault.treasury,
+            sol_vault: vault.sol_vault,
+            total_assets: vault.total_assets,
+            total_shares: vault.total_shares,
+            pending_sol: vault.pending_sol,
+            last_sweep_id: vault.last_sweep_id,
+            last_conversion_id: vault.last_conversion_id,
+            paused: vault.paused,
+            accepted_mints: vault.accepted_mints.clone(),
+            padding: vault.padding,
+        }
+    }
+
+    fn rewards_pool(total_staked: u64, sol_per_share: u128, pending: u64) -> RewardsPoolAccount {
+        RewardsPoolAccount {
+            bump: 1,
+            authority_bump: 2,
+            treasury_bump: 3,
+            admin: Pubkey::new_unique(),
+            creator_vault: Pubkey::new_unique(),
+            attn_mint: Pubkey::new_unique(),
+            s_attn_mint: Pubkey::new_unique(),
+            attn_vault: Pubkey::new_unique(),
+            total_staked,
+            sol_per_share,
+            pending_rewards: pending,
+            reward_bps: 500,
+            allowed_funder: Pubkey::new_unique(),
+            last_treasury_balance: 8,
+            last_fund_id: 9,
+            is_paused: false,
+            padding: [0; 5],
+        }
+    }
+
+    fn program_pool(pool: &RewardsPoolAccount) -> ::rewards_vault::RewardsPool {
+        ::rewards_vault::RewardsPool {
+            bump: pool.bump,
+            authority_bump: pool.authority_bump,
+            treasury_bump: pool.treasury_bump,
+            admin: pool.admin,
+            creator_vault: pool.creator_vault,
+            attn_mint: pool.attn_mint,
+            s_attn_mint: pool.s_attn_mint,
+            attn_vault: pool.attn_vault,
+            total_staked: pool.total_staked,
+            sol_per_share: pool.sol_per_share,
+            pending_rewards: pool.pending_rewards,
+            reward_bps: pool.reward_bps,
+            allowed_funder: pool.allowed_funder,
+            last_treasury_balance: pool.last_treasury_balance,
+            last_fund_id: pool.last_fund_id,
+            is_paused: pool.is_paused,
+            padding: pool.padding,
+        }
+    }
+
+    fn stake_position(staked_amount: u64, reward_debt: u128) -> StakePositionAccount {
+        StakePositionAccount {
+            bump: 1,
+            pool: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            staked_amount,
+            reward_debt,
+            padding: [0; 7],
+        }
+    }
+
+    fn program_position(position: &StakePositionAccount) -> ::rewards_vault::StakePosition {
+        ::rewards_vault::StakePosition {
+            bump: position.bump,
+            pool: position.pool,
+            user: position.user,
+            staked_amount: position.staked_amount,
+            reward_debt: position.reward_debt,
+            padding: position.padding,
+        }
+    }
+
+    fn codes<T>(
+        mirror: std::result::Result<T, AttnProgramError>,
+        program: anchor_lang::Result<T>,
+    ) -> (std::result::Result<T, u32>, std::result::Result<T, u32>) {
+        let program = program.map_err(|err| match err {
+            anchor_lang::error::Error::AnchorError(err) => err.error_code_number,
+            other => panic!("unexpected program error {other:?}"),
+        });
+        (mirror.map_err(|err| err.code()), program)
+    }
+
+    fn account_data<T: AnchorSerialize>(discriminator: &[u8], account: &T) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        account.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn mirrors_match_program_layouts_and_filter_offsets() {
+        use anchor_lang::Discriminator;
+
+        let vault = stable_vault(10, 20);
+        let data = account_data(::stable_vault::StableVault::DISCRIMINATOR, &vault);
+        assert_eq!(
+            data,
+            account_data(
+                ::stable_vault::StableVault::DISCRIMINATOR,
+                &program_vault(&vault)
+            )
+        );
+        let admin = stable::STABLE_VAULT_ADMIN_OFFSET;
+        assert_eq!(&data[admin..admin + 32], vault.admin.as_ref());
+
+        let pool = rewards_pool(1, 2, 3);
+        let data = account_data(::rewards_vault::RewardsPool::DISCRIMINATOR, &pool);
+        assert_eq!(
+            data,
+            account_data(
+                ::rewards_vault::RewardsPool::DISCRIMINATOR,
+                &program_pool(&pool)
+            )
+        );
+        assert_eq!(data.len(), ::rewards_vault::RewardsPool::SPACE);
+        let admin = rewards::REWARDS_POOL_ADMIN_OFFSET;
+        assert_eq!(&data[admin..admin + 32], pool.admin.as_ref());
+
+        let position = stake_position(4, 5);
+        let data = account_data(::rewards_vault::StakePosition::DISCRIMINATOR, &position);
+        assert_eq!(
+            decode_account::<StakePositionAccount>(&data)
+                .unwrap()
+                .reward_debt,
+            5
+        );
+        assert_eq!(data.len(), ::rewards_vault::StakePosition::SPACE);
+        let user = rewards::STAKE_POSITION_USER_OFFSET;
+        assert_eq!(&data[user..user + 32], position.user.as_ref());
+    }
+
+    fn amount() -> impl Strategy<Value = u64> {
+        prop_oneof![0u64..1_000, 0u64..1_000_000_000_000, any::<u64>()]
+    }
+
+    proptest! {
+        #[test]
+        fn stable_previews_match_program(
+            total_assets in amount(),
+            total_shares in amount(),
+            value in amount(),
+        ) {
+            let vault = stable_vault(total_assets, total_shares);
+            let program = program_vault(&vault);
+            prop_assert_eq!(vault.price_per_share(), program.price_per_share_scaled());
+            let (mirror, expected) = codes(vault.preview_deposit(value), program.preview_deposit(value));
+            prop_assert_eq!(mirror, expected);
+            let (mirror, expected) = codes(vault.preview_redeem(value), program.preview_redeem(value));
+            prop_assert_eq!(mirror, expected);
+        }
+
+        #[test]
+        fn settle_rewards_matches_program(
+            total_staked in amount(),
+            sol_per_share in prop_oneof![0u128..1_000_000_000_000_000, any::<u128>()],
+            pending_rewards in amount(),
+            staked_amount in amount(),
+            reward_debt in prop_oneof![0u128..1_000_000_000_000, any::<u128>()],
+        ) {
+            let pool = rewards_pool(total_staked, sol_per_share, pending_rewards);
+            let position = stake_position(staked_amount, reward_debt);
+
+            let mut program = program_pool(&pool);
+            let expected = ::rewards_vault::distribute_pending(&mut program).and_then(|()| {
+                ::rewards_vault::pending_amount(&program, &program_position(&position))
+            });
+            let (mirror, expected) = codes(pool.settle_rewards(&position), expected);
+            prop_assert_eq!(mirror, expected);
+
+            let mut settled = pool.clone();
+            if settled.distribute_pending().is_ok() {
+                prop_assert_eq!(settled.sol_per_share, program.sol_per_share);
+                prop_assert_eq!(settled.pending_rewards, program.pending_rewards);
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Async client on the nonblocking `RpcClient`.
+//!
+//! The `Program<C>`-based helpers in this crate block on RPC, so services running on
+//! tokio would have to park a thread for every call. [`AttnAsyncClient`] fetches the
+//! same account mirrors and sends instructions from the `build_*_ix` builders without
+//! blocking. Signing goes through [`TransactionSigner`], so a KMS-held key signs the
+//! same way a local [`Keypair`] does.
+
+use std::{fmt, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    message::{AddressLookupTableAccount, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+
+use crate::{
+    creator::CreatorVaultAccount,
+    decode_account, decode_accounts, program_accounts_config,
+    rewards::{self, RewardsPoolAccount, StakePositionAccount},
+    splitter::{self, MarketAccount, UserPositionAccount},
+    stable::{self, StableVaultAccount},
+    tx::{SendConfig, TxSender},
+};
+
+/// Signs transaction messages; may call out to a remote key service.
+#[async_trait]
+pub trait TransactionSigner: Send + Sync {
+    fn pubkey(&self) -> Pubkey;
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature>;
+}
+
+#[async_trait]
+impl TransactionSigner for Keypair {
+    fn pubkey(&self) -> Pubkey {
+        Signer::pubkey(self)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        Ok(Signer::sign_message(self, message))
+    }
+}
+
+#[async_trait]
+impl<T: TransactionSigner + ?Sized> TransactionSigner for Arc<T> {
+    fn pubkey(&self) -> Pubkey {
+        (**self).pubkey()
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        (**self).sign_message(message).await
+    }
+}
+
+/// Signs `message` with every signer it requires; each must be among `signers`.
+pub async fn sign_transaction(
+    message: VersionedMessage,
+    signers: &[&dyn TransactionSigner],
+) -> Result<VersionedTransaction> {
+    let message_bytes = message.serialize();
+    let required = usize::from(message.header().num_required_signatures);
+    let mut signatures = Vec::with_capacity(required);
+    for key in &message.static_account_keys()[..required] {
+        let signer = signers
+            .iter()
+            .find(|signer| signer.pubkey() == *key)
+            .ok_or_else(|| anyhow!("no signer configured for {key}"))?;
+        signatures.push(signer.sign_message(&message_bytes).await?);
+    }
+    Ok(VersionedTransaction {
+        signatures,
+        message,
+    })
+}
+
+#[derive(Clone)]
+pub struct AttnAsyncClient {
+    sender: TxSender,
+    payer: Arc<dyn TransactionSigner>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl fmt::Debug for AttnAsyncClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttnAsyncClient")
+            .field("payer", &self.payer.pubkey())
+            .field("rpc", &self.sender.rpc().url())
+            .finish()
+    }
+}
+
+impl AttnAsyncClient {
+    pub fn new(rpc: Arc<RpcClient>, payer: Arc<dyn TransactionSigner>) -> Self {
+        Self::with_config(rpc, payer, SendConfig::default())
+    }
+
+    pub fn with_config(
+        rpc: Arc<RpcClient>,
+        payer: Arc<dyn TransactionSigner>,
+        config: SendConfig,
+    ) -> Self {
+        Self {
+            sender: TxSender::new(rpc, config),
+            payer,
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    /// Compiles every transaction as v0 against these tables (see [`crate::alt`]).
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    pub fn rpc(&self) -> &Arc<RpcClient> {
+        self.sender.rpc()
+    }
+
+    pub fn sender(&self) -> &TxSender {
+        &self.sender
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Budgets, signs and sends `instructions`, paid for by the client's payer;
+    /// `signers` covers every other key the instructions require.
+    pub async fn send(
+        &self,
+        instructions: Vec<Instruction>,
+        signers: &[&dyn TransactionSigner],
+    ) -> Result<Signature> {
+        let payer = self.payer.pubkey();
+        let prepared = self
+            .sender
+            .prepare(instructions, &payer, &self.lookup_tables)
+            .await?;
+        let mut all_signers: Vec<&dyn TransactionSigner> = vec![self.payer.as_ref()];
+        all_signers.extend_from_slice(signers);
+        let transaction = sign_transaction(prepared.message(&payer)?, &all_signers).await?;
+        self.sender
+            .send_transaction(&transaction, prepared.last_valid_block_height)
+            .await?
+            .into_result()
+    }
+
+    async fn account<T: anchor_lang::AnchorDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let account = self.rpc().get_account(address).await?;
+        decode_account(&account.data)
+    }
+
+    async fn optional_account<T: anchor_lang::AnchorDeserialize>(
+        &self,
+        address: &Pubkey,
+    ) -> Result<Option<T>> {
+        let rpc = self.rpc();
+        rpc.get_account_with_commitment(address, rpc.commitment())
+            .await?
+            .value
+            .map(|account| decode_account(&account.data))
+            .transpose()
+    }
+
+    async fn scan<T: anchor_lang::AnchorDeserialize>(
+        &self,
+        program_id: &Pubkey,
+        discriminator: &[u8],
+        pubkey_at: Option<(usize, Pubkey)>,
+    ) -> Result<Vec<(Pubkey, T)>> {
+        let config = program_accounts_config(discriminator, pubkey_at);
+        decode_accounts(
+            self.rpc()
+                .get_program_accounts_with_config(program_id, config)
+                .await?,
+        )
+    }
+
+    pub async fn creator_vault(&self, address: Pubkey) -> Result<CreatorVaultAccount> {
+        self.account(&address).await
+    }
+
+    pub async fn stable_vault(&self, address: Pubkey) -> Result<StableVaultAccount> {
+        self.account(&address).await
+    }
+
+    pub async fn rewards_pool(&self, address: Pubkey) -> Result<RewardsPoolAccount> {
+        self.account(&address).await
+    }
+
+    /// `None` when `user` has never staked in the pool.
+    pub async fn stake_position(
+        &self,
+        rewards_pool: Pubkey,
+        user: Pubkey,
+    ) -> Result<Option<StakePositionAccount>> {
+        let (address, _) = rewards::stake_position_pda(&rewards_pool, &user);
+        self.optional_account(&address).await
+    }
+
+    pub async fn market(&self, address: Pubkey) -> Result<MarketAccount> {
+        self.account(&address).await
+    }
+
+    /// `None` when `user` has never minted in the market.
+    pub async fn user_position(
+        &self,
+        market: Pubkey,
+        user: Pubkey,
+    ) -> Result<Option<UserPositionAccount>> {
+        let (address, _) = splitter::user_position_pda(&market, &user);
+        self.optional_account(&address).await
+    }
+
+    /// See [`stable::fetch_vaults`].
+    pub async fn stable_vaults(
+        &self,
+        admin: Option<Pubkey>,
+    ) -> Result<Vec<(Pubkey, StableVaultAccount)>> {
+        use anchor_lang::Discriminator;
+        let admin = admin.map(|admin| (stable::STABLE_VAULT_ADMIN_OFFSET, admin));
+        self.scan(
+            &::stable_vault::ID,
+            ::stable_vault::StableVault::DISCRIMINATOR,
+            admin,
+        )
+        .await
+    }
+
+    /// See [`rewards::fetch_pools`].
+    pub async fn rewards_pools(
+        &self,
+        admin: Option<Pubkey>,
+    ) -> Result<Vec<(Pubkey, RewardsPoolAccount)>> {
+        use anchor_lang::Discriminator;
+        let admin = admin.map(|admin| (rewards::REWARDS_POOL_ADMIN_OFFSET, admin));
+        self.scan(
+            &::rewards_vault::ID,
+            ::rewards_vault::RewardsPool::DISCRIMINATOR,
+            admin,
+        )
+        .await
+    }
+
+    /// See [`rewards::fetch_stake_positions`].
+    pub async fn stake_positions(
+        &self,
+        user: Pubkey,
+    ) -> Result<Vec<(Pubkey, StakePositionAccount)>> {
+        use anchor_lang::Discriminator;
+        self.scan(
+            &::rewards_vault::ID,
+            ::rewards_vault::StakePosition::DISCRIMINATOR,
+            Some((rewards::STAKE_POSITION_USER_OFFSET, user)),
+        )
+        .await
+    }
+
+    /// See [`splitter::fetch_markets`].
+    pub async fn markets(
+        &self,
+        creator_vault: Option<Pubkey>,
+    ) -> Result<Vec<(Pubkey, MarketAccount)>> {
+        use anchor_lang::Discriminator;
+        let creator_vault = creator_vault.map(|creator_vault| (8, creator_vault));
+        self.scan(
+            &::splitter::ID,
+            ::splitter::Market::DISCRIMINATOR,
+            creator_vault,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::{compile_message, unsigned_transaction};
+    use solana_sdk::{hash::Hash, instruction::AccountMeta};
+
+    #[tokio::test]
+    async fn signs_with_every_required_signer() {
+        let payer = Keypair::new();
+        let shared: Arc<dyn TransactionSigner> = Arc::new(Keypair::new());
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![AccountMeta::new(shared.pubkey(), true)],
+        );
+        let message = compile_message(
+            &Signer::pubkey(&payer),
+            &[instruction],
+            &[],
+            Hash::new_unique(),
+        )
+        .unwrap();
+
+        let signers: [&dyn TransactionSigner; 2] = [&shared, &payer];
+        let transaction = sign_transaction(message.clone(), &signers).await.unwrap();
+        assert!(transaction
+            .verify_with_results()
+            .into_iter()
+            .all(|valid| valid));
+        assert_eq!(
+            transaction.signatures.len(),
+            unsigned_transaction(message.clone()).signatures.len()
+        );
+
+        let err = sign_transaction(message, &[&payer]).await.unwrap_err();
+        assert!(err.to_string().contains("no signer configured"));
+    }
+}
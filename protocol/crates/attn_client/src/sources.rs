@@ -0,0 +1,226 @@
+//! Revenue source adapters for `CreatorVault`.
+//!
+//! A vault is keyed by a source id (the launched token's mint) and fed by one
+//! revenue source. Each adapter knows the on-chain config recorded for its
+//! source and how to move accrued revenue into the vault's fee vault.
+
+use crate::creator::{self, CreatorVaultPdas, RevenueSourceConfig, RevenueSourceKind};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token,
+};
+use anyhow::{bail, Result};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+pub trait RevenueSourceAdapter {
+    fn kind(&self) -> RevenueSourceKind;
+
+    /// Mint seeding the creator-vault, fee-vault and SY mint PDAs.
+    fn source_id(&self) -> Pubkey;
+
+    fn quote_mint(&self) -> Pubkey;
+
+    /// Config stored in the vault's `RevenueSource` account.
+    fn config(&self) -> RevenueSourceConfig;
+
+    /// Instructions moving up to `amount` of accrued revenue into the fee
+    /// vault, signed by `operator`.
+    fn collect_ixs(&self, operator: Pubkey, amount: u64) -> Result<Vec<Instruction>>;
+
+    fn pdas(&self) -> CreatorVaultPdas {
+        creator::derive_pdas(&self.source_id())
+    }
+
+    fn initialize_ix(
+        &self,
+        authority: Pubkey,
+        splitter_program: Pubkey,
+        admin: Pubkey,
+    ) -> (Instruction, CreatorVaultPdas) {
+        creator::build_initialize_source_vault_ix(
+            authority,
+            self.source_id(),
+            self.quote_mint(),
+            self.config(),
+            splitter_program,
+            admin,
+        )
+    }
+}
+
+/// Pump.fun creator rewards with the vault PDA as coin creator. Collection
+/// claims the whole accrual into the wSOL fee vault, so `amount` is ignored.
+#[derive(Debug, Clone)]
+pub struct PumpFunSource {
+    pub pump_mint: Pubkey,
+    pub pump_creator: Pubkey,
+    pub quote_mint: Pubkey,
+}
+
+impl RevenueSourceAdapter for PumpFunSource {
+    fn kind(&self) -> RevenueSourceKind {
+        RevenueSourceKind::PumpFun
+    }
+
+    fn source_id(&self) -> Pubkey {
+        self.pump_mint
+    }
+
+    fn quote_mint(&self) -> Pubkey {
+        self.quote_mint
+    }
+
+    fn config(&self) -> RevenueSourceConfig {
+        RevenueSourceConfig::PumpFun {
+            creator: self.pump_creator,
+        }
+    }
+
+    fn collect_ixs(&self, operator: Pubkey, _amount: u64) -> Result<Vec<Instruction>> {
+        if self.quote_mint != creator::WSOL_MINT {
+            bail!("Pump.fun fees can only be collected into a wSOL fee vault");
+        }
+        Ok(vec![creator::build_collect_creator_fees_ix(
+            self.pump_mint,
+            operator,
+            true,
+        )])
+    }
+}
+
+/// Meteora Dynamic Bonding Curve-style pool with the creator vault PDA as
+/// pool creator. Quote-side creator trading fees are claimed straight into the
+/// fee vault; base-side fees are left in the pool.
+#[derive(Debug, Clone)]
+pub struct MeteoraDbcSource {
+    pub program: Pubkey,
+    pub pool: Pubkey,
+    pub pool_authority: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub event_authority: Pubkey,
+}
+
+impl MeteoraDbcSource {
+    /// Vault-owned account receiving base-side fees when they are claimed.
+    pub fn base_fee_account(&self) -> Pubkey {
+        get_associated_token_address(&self.pdas().creator_vault, &self.base_mint)
+    }
+
+    /// Accounts of `claim_creator_trading_fee`, in program order.
+    pub fn claim_accounts(&self) -> Vec<AccountMeta> {
+        let pdas = self.pdas();
+        vec![
+            AccountMeta::new_readonly(self.pool_authority, false),
+            AccountMeta::new(self.pool, false),
+            AccountMeta::new(self.base_fee_account(), false),
+            AccountMeta::new(pdas.fee_vault, false),
+            AccountMeta::new(self.base_vault, false),
+            AccountMeta::new(self.quote_vault, false),
+            AccountMeta::new_readonly(self.base_mint, false),
+            AccountMeta::new_readonly(self.quote_mint, false),
+            AccountMeta::new_readonly(pdas.creator_vault, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(self.event_authority, false),
+            AccountMeta::new_readonly(self.program, false),
+        ]
+    }
+}
+
+impl RevenueSourceAdapter for MeteoraDbcSource {
+    fn kind(&self) -> RevenueSourceKind {
+        RevenueSourceKind::MeteoraDbc
+    }
+
+    fn source_id(&self) -> Pubkey {
+        self.base_mint
+    }
+
+    fn quote_mint(&self) -> Pubkey {
+        self.quote_mint
+    }
+
+    fn config(&self) -> RevenueSourceConfig {
+        RevenueSourceConfig::MeteoraDbc {
+            program: self.program,
+            pool: self.pool,
+        }
+    }
+
+    fn collect_ixs(&self, operator: Pubkey, amount: u64) -> Result<Vec<Instruction>> {
+        // The program requires the vault-owned base account to exist even
+        // though base fees are left in the pool.
+        let base_fee_account = create_associated_token_account_idempotent(
+            &operator,
+            &self.pdas().creator_vault,
+            &self.base_mint,
+            &token::ID,
+        );
+        Ok(vec![
+            base_fee_account,
+            creator::build_claim_source_fees_ix(
+                self.base_mint,
+                self.program,
+                operator,
+                amount,
+                self.claim_accounts(),
+            ),
+        ])
+    }
+}
+
+/// Revenue paid as plain SPL transfers of the quote mint into the fee vault,
+/// optionally restricted to a single depositor.
+#[derive(Debug, Clone)]
+pub struct SplTransferSource {
+    pub source_id: Pubkey,
+    pub quote_mint: Pubkey,
+    pub depositor: Option<Pubkey>,
+}
+
+impl RevenueSourceAdapter for SplTransferSource {
+    fn kind(&self) -> RevenueSourceKind {
+        RevenueSourceKind::SplTransfer
+    }
+
+    fn source_id(&self) -> Pubkey {
+        self.source_id
+    }
+
+    fn quote_mint(&self) -> Pubkey {
+        self.quote_mint
+    }
+
+    fn config(&self) -> RevenueSourceConfig {
+        RevenueSourceConfig::SplTransfer {
+            depositor: self.depositor,
+        }
+    }
+
+    fn collect_ixs(&self, operator: Pubkey, amount: u64) -> Result<Vec<Instruction>> {
+        if amount == 0 {
+            bail!("deposit amount must be greater than zero");
+        }
+        if let Some(depositor) = self.depositor {
+            if depositor != operator {
+                bail!("{operator} is not the configured depositor {depositor}");
+            }
+        }
+        Ok(vec![creator::build_deposit_source_revenue_ix(
+            self.source_id,
+            self.quote_mint,
+            operator,
+            get_associated_token_address(&operator, &self.quote_mint),
+            amount,
+        )])
+    }
+}
@@ -0,0 +1,1188 @@
+//! Squads v4 proposal flow for attn admin instructions.
+//!
+//! Vault admins (`CreatorVault.admin`, `StableVault.admin`, `RewardsPool.admin`)
+//! are expected to be the vault PDA of a Squads multisig. Admin instructions
+//! are built with that vault as the signer, wrapped into a vault transaction
+//! plus proposal, approved by members and executed once the threshold is met.
+//!
+//! Creator safes themselves are created with [`build_multisig_create_v2_ix`].
+
+use std::fmt;
+
+use super::decode_account;
+use anchor_lang::prelude::borsh;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize, Discriminator};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey,
+    pubkey::Pubkey,
+};
+
+pub const SQUADS_PROGRAM_ID: Pubkey = pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+
+const SEED_PREFIX: &[u8] = b"multisig";
+const SEED_MULTISIG: &[u8] = b"multisig";
+const SEED_VAULT: &[u8] = b"vault";
+const SEED_TRANSACTION: &[u8] = b"transaction";
+const SEED_PROPOSAL: &[u8] = b"proposal";
+const SEED_PROGRAM_CONFIG: &[u8] = b"program_config";
+
+/// Largest number of proposals fetched per `getMultipleAccounts` call.
+const MULTIPLE_ACCOUNTS_CHUNK: usize = 100;
+
+pub fn multisig_pda(create_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_PREFIX, SEED_MULTISIG, create_key.as_ref()],
+        &SQUADS_PROGRAM_ID,
+    )
+}
+
+pub fn program_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_PREFIX, SEED_PROGRAM_CONFIG], &SQUADS_PROGRAM_ID)
+}
+
+pub fn vault_pda(multisig: &Pubkey, vault_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_PREFIX, multisig.as_ref(), SEED_VAULT, &[vault_index]],
+        &SQUADS_PROGRAM_ID,
+    )
+}
+
+pub fn transaction_pda(multisig: &Pubkey, transaction_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SEED_PREFIX,
+            multisig.as_ref(),
+            SEED_TRANSACTION,
+            &transaction_index.to_le_bytes(),
+        ],
+        &SQUADS_PROGRAM_ID,
+    )
+}
+
+pub fn proposal_pda(multisig: &Pubkey, transaction_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SEED_PREFIX,
+            multisig.as_ref(),
+            SEED_TRANSACTION,
+            &transaction_index.to_le_bytes(),
+            SEED_PROPOSAL,
+        ],
+        &SQUADS_PROGRAM_ID,
+    )
+}
+
+/// Anchor account discriminator: the first 8 bytes of `sha256("account:<Name>")`.
+pub fn account_discriminator(name: &str) -> [u8; 8] {
+    let preimage = format!("account:{name}");
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hashv(&[preimage.as_bytes()]).to_bytes()[..8]);
+    discriminator
+}
+
+fn instruction_data(name: &str, args: Option<&impl AnchorSerialize>) -> Result<Vec<u8>> {
+    let preimage = format!("global:{name}");
+    let mut data = hashv(&[preimage.as_bytes()]).to_bytes()[..8].to_vec();
+    if let Some(args) = args {
+        args.serialize(&mut data)?;
+    }
+    Ok(data)
+}
+
+/// Decodes a Squads account after checking its discriminator, so another
+/// account type stored at the same address is rejected.
+fn decode_squads_account<T: AnchorDeserialize>(name: &str, data: &[u8]) -> Result<T> {
+    if data.get(..8) != Some(&account_discriminator(name)[..]) {
+        bail!("account is not a Squads {name}");
+    }
+    decode_account(data)
+}
+
+fn ensure_squads_owner(address: &Pubkey, owner: &Pubkey) -> Result<()> {
+    if owner != &SQUADS_PROGRAM_ID {
+        bail!("{address} is not owned by the Squads v4 program");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct Permissions {
+    pub mask: u8,
+}
+
+impl Permissions {
+    pub const INITIATE: u8 = 1 << 0;
+    pub const VOTE: u8 = 1 << 1;
+    pub const EXECUTE: u8 = 1 << 2;
+    pub const ALL: u8 = Self::INITIATE | Self::VOTE | Self::EXECUTE;
+
+    pub fn has(&self, permission: u8) -> bool {
+        self.mask & permission == permission
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct Member {
+    pub key: Pubkey,
+    pub permissions: Permissions,
+}
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MultisigAccount {
+    pub create_key: Pubkey,
+    /// `Pubkey::default()` for autonomous multisigs, where config changes
+    /// must go through a config transaction approved by the members.
+    pub config_authority: Pubkey,
+    pub threshold: u16,
+    pub time_lock: u32,
+    pub transaction_index: u64,
+    pub stale_transaction_index: u64,
+    pub rent_collector: Option<Pubkey>,
+    pub bump: u8,
+    pub members: Vec<Member>,
+}
+
+impl MultisigAccount {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_squads_account("Multisig", data)
+    }
+
+    pub fn member(&self, key: &Pubkey) -> Option<&Member> {
+        self.members.iter().find(|member| &member.key == key)
+    }
+
+    pub fn is_autonomous(&self) -> bool {
+        self.config_authority == Pubkey::default()
+    }
+}
+
+/// `ProgramConfig`; the reserved tail is not decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ProgramConfig {
+    pub authority: Pubkey,
+    pub multisig_creation_fee: u64,
+    /// Receives the multisig creation fee.
+    pub treasury: Pubkey,
+}
+
+impl ProgramConfig {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_squads_account("ProgramConfig", data)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum ProposalStatus {
+    Draft { timestamp: i64 },
+    Active { timestamp: i64 },
+    Rejected { timestamp: i64 },
+    Approved { timestamp: i64 },
+    Executing,
+    Executed { timestamp: i64 },
+    Cancelled { timestamp: i64 },
+}
+
+impl ProposalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalStatus::Draft { .. } => "draft",
+            ProposalStatus::Active { .. } => "active",
+            ProposalStatus::Rejected { .. } => "rejected",
+            ProposalStatus::Approved { .. } => "approved",
+            ProposalStatus::Executing => "executing",
+            ProposalStatus::Executed { .. } => "executed",
+            ProposalStatus::Cancelled { .. } => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ProposalAccount {
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub status: ProposalStatus,
+    pub bump: u8,
+    pub approved: Vec<Pubkey>,
+    pub rejected: Vec<Pubkey>,
+    pub cancelled: Vec<Pubkey>,
+}
+
+impl ProposalAccount {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_squads_account("Proposal", data)
+    }
+}
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub account_indexes: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MessageAddressTableLookup {
+    pub account_key: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct VaultTransactionMessage {
+    pub num_signers: u8,
+    pub num_writable_signers: u8,
+    pub num_writable_non_signers: u8,
+    pub account_keys: Vec<Pubkey>,
+    pub instructions: Vec<CompiledInstruction>,
+    pub address_table_lookups: Vec<MessageAddressTableLookup>,
+}
+
+impl VaultTransactionMessage {
+    pub fn is_writable(&self, index: usize) -> bool {
+        let num_signers = usize::from(self.num_signers);
+        if index < num_signers {
+            index < usize::from(self.num_writable_signers)
+        } else {
+            index - num_signers < usize::from(self.num_writable_non_signers)
+        }
+    }
+
+    /// Decompiles back into instructions, e.g. to describe a pending proposal.
+    pub fn instructions(&self) -> Result<Vec<Instruction>> {
+        if !self.address_table_lookups.is_empty() {
+            bail!("vault transactions with address lookup tables are not supported");
+        }
+        let key = |index: u8| {
+            self.account_keys
+                .get(usize::from(index))
+                .copied()
+                .ok_or_else(|| anyhow!("account index {index} out of range"))
+        };
+        self.instructions
+            .iter()
+            .map(|ix| {
+                let accounts = ix
+                    .account_indexes
+                    .iter()
+                    .map(|index| {
+                        Ok(AccountMeta {
+                            pubkey: key(*index)?,
+                            is_signer: usize::from(*index) < usize::from(self.num_signers),
+                            is_writable: self.is_writable(usize::from(*index)),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Instruction {
+                    program_id: key(ix.program_id_index)?,
+                    accounts,
+                    data: ix.data.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct VaultTransactionAccount {
+    pub multisig: Pubkey,
+    pub creator: Pubkey,
+    pub index: u64,
+    pub bump: u8,
+    pub vault_index: u8,
+    pub vault_bump: u8,
+    pub ephemeral_signer_bumps: Vec<u8>,
+    pub message: VaultTransactionMessage,
+}
+
+impl VaultTransactionAccount {
+    /// Whether `data` holds a vault transaction rather than a config or batch
+    /// transaction sharing the same PDA.
+    pub fn is_vault_transaction(data: &[u8]) -> bool {
+        data.len() >= 8 && data[..8] == account_discriminator("VaultTransaction")
+    }
+
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_squads_account("VaultTransaction", data)
+    }
+}
+
+/// `MultisigCreateArgsV2`.
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize)]
+pub struct MultisigCreateArgsV2 {
+    pub config_authority: Option<Pubkey>,
+    pub threshold: u16,
+    pub members: Vec<Member>,
+    pub time_lock: u32,
+    pub rent_collector: Option<Pubkey>,
+    pub memo: Option<String>,
+}
+
+#[derive(AnchorSerialize)]
+struct VaultTransactionCreateArgs {
+    vault_index: u8,
+    ephemeral_signers: u8,
+    transaction_message: Vec<u8>,
+    memo: Option<String>,
+}
+
+#[derive(AnchorSerialize)]
+struct ProposalCreateArgs {
+    transaction_index: u64,
+    draft: bool,
+}
+
+#[derive(AnchorSerialize)]
+struct ProposalVoteArgs {
+    memo: Option<String>,
+}
+
+/// Serializes `instructions` into the compact `TransactionMessage` layout
+/// `vault_transaction_create` expects (u8/u16 length prefixes), with the vault
+/// as the only signer.
+pub fn compile_transaction_message(
+    vault: &Pubkey,
+    instructions: &[Instruction],
+) -> Result<Vec<u8>> {
+    let message = Message::new(instructions, Some(vault));
+    let header = message.header;
+    if header.num_required_signatures != 1 {
+        bail!(
+            "inner instructions require {} signers; only the multisig vault can sign",
+            header.num_required_signatures
+        );
+    }
+    let num_keys = message.account_keys.len();
+    let num_signers = header.num_required_signatures;
+    let num_writable_signers = num_signers - header.num_readonly_signed_accounts;
+    let num_writable_non_signers =
+        num_keys - usize::from(num_signers) - usize::from(header.num_readonly_unsigned_accounts);
+
+    let mut out = vec![
+        num_signers,
+        num_writable_signers,
+        u8::try_from(num_writable_non_signers)?,
+        u8::try_from(num_keys).context("too many accounts for a vault transaction")?,
+    ];
+    for key in &message.account_keys {
+        out.extend_from_slice(key.as_ref());
+    }
+    out.push(u8::try_from(message.instructions.len()).context("too many instructions")?);
+    for ix in &message.instructions {
+        out.push(ix.program_id_index);
+        out.push(u8::try_from(ix.accounts.len())?);
+        out.extend_from_slice(&ix.accounts);
+        out.extend_from_slice(&u16::try_from(ix.data.len())?.to_le_bytes());
+        out.extend_from_slice(&ix.data);
+    }
+    // No address table lookups.
+    out.push(0);
+    Ok(out)
+}
+
+/// `multisig_create_v2`. `creator` pays rent and the creation fee to
+/// `treasury`; `create_key` seeds the multisig PDA and must sign so nobody
+/// else can claim the address.
+pub fn build_multisig_create_v2_ix(
+    treasury: Pubkey,
+    create_key: Pubkey,
+    creator: Pubkey,
+    args: &MultisigCreateArgsV2,
+) -> Result<Instruction> {
+    let (multisig, _) = multisig_pda(&create_key);
+    let (program_config, _) = program_config_pda();
+    Ok(Instruction {
+        program_id: SQUADS_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(program_config, false),
+            AccountMeta::new(treasury, false),
+            AccountMeta::new(multisig, false),
+            AccountMeta::new_readonly(create_key, true),
+            AccountMeta::new(creator, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: instruction_data("multisig_create_v2", Some(args))?,
+    })
+}
+
+pub fn build_vault_transaction_create_ix(
+    multisig: Pubkey,
+    transaction_index: u64,
+    creator: Pubkey,
+    rent_payer: Pubkey,
+    vault_index: u8,
+    transaction_message: Vec<u8>,
+    memo: Option<String>,
+) -> Result<Instruction> {
+    let (transaction, _) = transaction_pda(&multisig, transaction_index);
+    let args = VaultTransactionCreateArgs {
+        vault_index,
+        ephemeral_signers: 0,
+        transaction_message,
+        memo,
+    };
+    Ok(Instruction {
+        program_id: SQUADS_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(multisig, false),
+            AccountMeta::new(transaction, false),
+            AccountMeta::new_readonly(creator, true),
+            AccountMeta::new(rent_payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: instruction_data("vault_transaction_create", Some(&args))?,
+    })
+}
+
+pub fn build_proposal_create_ix(
+    multisig: Pubkey,
+    transaction_index: u64,
+    creator: Pubkey,
+    rent_payer: Pubkey,
+) -> Result<Instruction> {
+    let (proposal, _) = proposal_pda(&multisig, transaction_index);
+    let args = ProposalCreateArgs {
+        transaction_index,
+        draft: false,
+    };
+    Ok(Instruction {
+        program_id: SQUADS_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(multisig, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new_readonly(creator, true),
+            AccountMeta::new(rent_payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: instruction_data("proposal_create", Some(&args))?,
+    })
+}
+
+pub fn build_proposal_approve_ix(
+    multisig: Pubkey,
+    transaction_index: u64,
+    member: Pubkey,
+    memo: Option<String>,
+) -> Result<Instruction> {
+    let (proposal, _) = proposal_pda(&multisig, transaction_index);
+    Ok(Instruction {
+        program_id: SQUADS_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(multisig, false),
+            AccountMeta::new(member, true),
+            AccountMeta::new(proposal, false),
+        ],
+        data: instruction_data("proposal_approve", Some(&ProposalVoteArgs { memo }))?,
+    })
+}
+
+/// Executes an approved vault transaction. The inner message's accounts are
+/// appended as remaining accounts; the vault signs inside the program.
+pub fn build_vault_transaction_execute_ix(
+    multisig: Pubkey,
+    transaction_index: u64,
+    member: Pubkey,
+    transaction: &VaultTransactionAccount,
+) -> Result<Instruction> {
+    if !transaction.message.address_table_lookups.is_empty() {
+        bail!("vault transactions with address lookup tables are not supported");
+    }
+    if !transaction.ephemeral_signer_bumps.is_empty() {
+        bail!("vault transactions with ephemeral signers are not supported");
+    }
+    let (proposal, _) = proposal_pda(&multisig, transaction_index);
+    let (transaction_address, _) = transaction_pda(&multisig, transaction_index);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(multisig, false),
+        AccountMeta::new(proposal, false),
+        AccountMeta::new_readonly(transaction_address, false),
+        AccountMeta::new_readonly(member, true),
+    ];
+    accounts.extend(
+        transaction
+            .message
+            .account_keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| AccountMeta {
+                pubkey: *key,
+                is_signer: false,
+                is_writable: transaction.message.is_writable(index),
+            }),
+    );
+    Ok(Instruction {
+        program_id: SQUADS_PROGRAM_ID,
+        accounts,
+        data: instruction_data("vault_transaction_execute", None::<&ProposalVoteArgs>)?,
+    })
+}
+
+/// Instructions proposing `inner` (built with the vault as admin signer) as
+/// the next transaction of `multisig`.
+#[derive(Debug, Clone)]
+pub struct ProposalPlan {
+    pub transaction_index: u64,
+    pub vault: Pubkey,
+    pub transaction: Pubkey,
+    pub proposal: Pubkey,
+    pub instructions: Vec<Instruction>,
+}
+
+/// Builds `vault_transaction_create` + `proposal_create`, plus the proposer's
+/// own approval when `approve` is set, so a 2-of-2 needs one more signature.
+pub fn build_proposal(
+    multisig: Pubkey,
+    state: &MultisigAccount,
+    proposer: Pubkey,
+    vault_index: u8,
+    inner: &[Instruction],
+    memo: Option<String>,
+    approve: bool,
+) -> Result<ProposalPlan> {
+    let member = state
+        .member(&proposer)
+        .ok_or_else(|| anyhow!("{proposer} is not a member of multisig {multisig}"))?;
+    if !member.permissions.has(Permissions::INITIATE) {
+        bail!("{proposer} lacks the initiate permission on multisig {multisig}");
+    }
+    let transaction_index = state
+        .transaction_index
+        .checked_add(1)
+        .ok_or_else(|| anyhow!("multisig transaction index overflow"))?;
+    let (vault, _) = vault_pda(&multisig, vault_index);
+    let message = compile_transaction_message(&vault, inner)?;
+    let mut instructions = vec![
+        build_vault_transaction_create_ix(
+            multisig,
+            transaction_index,
+            proposer,
+            proposer,
+            vault_index,
+            message,
+            memo,
+        )?,
+        build_proposal_create_ix(multisig, transaction_index, proposer, proposer)?,
+    ];
+    if approve && member.permissions.has(Permissions::VOTE) {
+        instructions.push(build_proposal_approve_ix(
+            multisig,
+            transaction_index,
+            proposer,
+            None,
+        )?);
+    }
+    Ok(ProposalPlan {
+        transaction_index,
+        vault,
+        transaction: transaction_pda(&multisig, transaction_index).0,
+        proposal: proposal_pda(&multisig, transaction_index).0,
+        instructions,
+    })
+}
+
+pub fn fetch_multisig(rpc: &RpcClient, multisig: &Pubkey) -> Result<MultisigAccount> {
+    let account = rpc
+        .get_account(multisig)
+        .with_context(|| format!("fetch multisig {multisig}"))?;
+    ensure_squads_owner(multisig, &account.owner)?;
+    MultisigAccount::from_account_data(&account.data)
+}
+
+pub fn fetch_proposal(
+    rpc: &RpcClient,
+    multisig: &Pubkey,
+    transaction_index: u64,
+) -> Result<ProposalAccount> {
+    let (address, _) = proposal_pda(multisig, transaction_index);
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("fetch proposal {transaction_index}"))?;
+    ensure_squads_owner(&address, &account.owner)?;
+    ProposalAccount::from_account_data(&account.data)
+}
+
+pub fn fetch_vault_transaction(
+    rpc: &RpcClient,
+    multisig: &Pubkey,
+    transaction_index: u64,
+) -> Result<VaultTransactionAccount> {
+    let (address, _) = transaction_pda(multisig, transaction_index);
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("fetch vault transaction {transaction_index}"))?;
+    ensure_squads_owner(&address, &account.owner)?;
+    VaultTransactionAccount::from_account_data(&account.data)
+}
+
+#[derive(Debug, Clone)]
+pub struct ProposalSummary {
+    pub transaction_index: u64,
+    pub address: Pubkey,
+    pub proposal: ProposalAccount,
+    /// Set when a config change invalidated the proposal before it executed.
+    pub stale: bool,
+}
+
+/// Proposals for the most recent `limit` transaction indices, newest first.
+pub fn list_proposals(
+    rpc: &RpcClient,
+    multisig: &Pubkey,
+    state: &MultisigAccount,
+    limit: u64,
+) -> Result<Vec<ProposalSummary>> {
+    let newest = state.transaction_index;
+    let oldest = newest.saturating_sub(limit.saturating_sub(1)).max(1);
+    let indices: Vec<u64> = (oldest..=newest).rev().collect();
+    let mut summaries = Vec::new();
+    for chunk in indices.chunks(MULTIPLE_ACCOUNTS_CHUNK) {
+        let addresses: Vec<Pubkey> = chunk
+            .iter()
+            .map(|index| proposal_pda(multisig, *index).0)
+            .collect();
+        let accounts = rpc.get_multiple_accounts(&addresses)?;
+        for ((index, address), account) in chunk.iter().zip(addresses).zip(accounts) {
+            let Some(account) = account else {
+                continue;
+            };
+            ensure_squads_owner(&address, &account.owner)?;
+            summaries.push(ProposalSummary {
+                transaction_index: *index,
+                address,
+                proposal: ProposalAccount::from_account_data(&account.data)?,
+                stale: *index <= state.stale_transaction_index,
+            });
+        }
+    }
+    Ok(summaries)
+}
+
+/// An instruction found inside a vault transaction, decoded against the attn
+/// program IDLs where the program is known.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedAction {
+    pub program: String,
+    pub instruction: String,
+    /// First account of the instruction; for attn admin instructions, the
+    /// vault or pool being governed.
+    pub target: Option<String>,
+    pub args: Value,
+    /// e.g. `set_pause(true) on creator vault <pubkey>`.
+    pub summary: String,
+}
+
+type DecodedArgs = (&'static str, Vec<(&'static str, Value)>);
+
+pub fn describe_vault_transaction(
+    transaction: &VaultTransactionAccount,
+) -> Result<Vec<ProposedAction>> {
+    Ok(transaction
+        .message
+        .instructions()?
+        .iter()
+        .map(describe_instruction)
+        .collect())
+}
+
+pub fn describe_instruction(ix: &Instruction) -> ProposedAction {
+    let (program, label, decoded) = if ix.program_id == creator_vault::ID {
+        (
+            "creator_vault",
+            "creator vault",
+            decode_creator_vault(&ix.data),
+        )
+    } else if ix.program_id == rewards_vault::ID {
+        (
+            "rewards_vault",
+            "rewards pool",
+            decode_rewards_vault(&ix.data),
+        )
+    } else if ix.program_id == stable_vault::ID {
+        (
+            "stable_vault",
+            "stable vault",
+            decode_stable_vault(&ix.data),
+        )
+    } else {
+        let target = ix.accounts.first().map(|meta| meta.pubkey.to_string());
+        return ProposedAction {
+            program: ix.program_id.to_string(),
+            instruction: "unknown".to_string(),
+            target,
+            args: Value::Null,
+            summary: format!("unknown instruction for program {}", ix.program_id),
+        };
+    };
+    let target = ix.accounts.first().map(|meta| meta.pubkey.to_string());
+    let on_target = target
+        .as_deref()
+        .map(|target| format!(" on {label} {target}"))
+        .unwrap_or_default();
+    let Some((name, args)) = decoded else {
+        return ProposedAction {
+            program: program.to_string(),
+            instruction: "unknown".to_string(),
+            target,
+            args: Value::Null,
+            summary: format!("unrecognised {program} instruction{on_target}"),
+        };
+    };
+    let rendered = args
+        .iter()
+        .map(|(_, value)| match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let summary = format!("{name}({rendered}){on_target}");
+    ProposedAction {
+        program: program.to_string(),
+        instruction: name.to_string(),
+        target,
+        args: Value::Object(
+            args.into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        ),
+        summary,
+    }
+}
+
+fn args<T: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<T> {
+    let mut rest = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut rest).ok()
+}
+
+fn key(pubkey: Pubkey) -> Value {
+    Value::String(pubkey.to_string())
+}
+
+fn decode_creator_vault(data: &[u8]) -> Option<DecodedArgs> {
+    use creator_vault::instruction as ix;
+    if let Some(a) = args::<ix::SetPause>(data) {
+        return Some(("set_pause", vec![("paused", json!(a.paused))]));
+    }
+    if let Some(a) = args::<ix::UpdateAdmin>(data) {
+        return Some(("update_admin", vec![("new_admin", key(a.new_admin))]));
+    }
+    if let Some(a) = args::<ix::SetRewardsSplit>(data) {
+        return Some((
+            "set_rewards_split",
+            vec![("sol_rewards_bps", json!(a.sol_rewards_bps))],
+        ));
+    }
+    if let Some(a) = args::<ix::SetSweeperDelegate>(data) {
+        return Some((
+            "set_sweeper_delegate",
+            vec![("delegate", key(a.delegate)), ("fee_bps", json!(a.fee_bps))],
+        ));
+    }
+    if args::<ix::ClearSweeperDelegate>(data).is_some() {
+        return Some(("clear_sweeper_delegate", Vec::new()));
+    }
+    if let Some(a) = args::<ix::AllowSweeperDelegate>(data) {
+        return Some((
+            "allow_sweeper_delegate",
+            vec![
+                ("delegate", key(a.delegate)),
+                ("max_fee_bps", json!(a.limits.max_fee_bps)),
+                (
+                    "max_amount_per_window",
+                    json!(a.limits.max_amount_per_window),
+                ),
+                ("window_secs", json!(a.limits.window_secs)),
+                ("min_interval_secs", json!(a.limits.min_interval_secs)),
+            ],
+        ));
+    }
+    if args::<ix::RevokeSweeperDelegate>(data).is_some() {
+        return Some(("revoke_sweeper_delegate", Vec::new()));
+    }
+    if let Some(a) = args::<ix::LockCollateral>(data) {
+        return Some((
+            "lock_collateral",
+            vec![("lock_expires_at", json!(a.lock_expires_at))],
+        ));
+    }
+    if args::<ix::UnlockCollateral>(data).is_some() {
+        return Some(("unlock_collateral", Vec::new()));
+    }
+    if let Some(a) = args::<ix::WithdrawFees>(data) {
+        return Some(("withdraw_fees", vec![("amount", json!(a.amount))]));
+    }
+    None
+}
+
+fn decode_rewards_vault(data: &[u8]) -> Option<DecodedArgs> {
+    use rewards_vault::instruction as ix;
+    if let Some(a) = args::<ix::SetPause>(data) {
+        return Some(("set_pause", vec![("paused", json!(a.paused))]));
+    }
+    if let Some(a) = args::<ix::UpdateAdmin>(data) {
+        return Some(("update_admin", vec![("new_admin", key(a.new_admin))]));
+    }
+    if let Some(a) = args::<ix::UpdateRewardBps>(data) {
+        return Some((
+            "update_reward_bps",
+            vec![("new_reward_bps", json!(a.new_reward_bps))],
+        ));
+    }
+    if let Some(a) = args::<ix::UpdateAllowedFunder>(data) {
+        return Some((
+            "update_allowed_funder",
+            vec![("new_allowed_funder", key(a.new_allowed_funder))],
+        ));
+    }
+    None
+}
+
+fn decode_stable_vault(data: &[u8]) -> Option<DecodedArgs> {
+    use stable_vault::instruction as ix;
+    if let Some(a) = args::<ix::SetPause>(data) {
+        return Some(("set_pause", vec![("is_paused", json!(a.is_paused))]));
+    }
+    if let Some(a) = args::<ix::UpdateAdmin>(data) {
+        return Some(("update_admin", vec![("new_admin", key(a.new_admin))]));
+    }
+    if let Some(a) = args::<ix::UpdateKeeperAuthority>(data) {
+        return Some((
+            "update_keeper_authority",
+            vec![("new_keeper", key(a.new_keeper))],
+        ));
+    }
+    if let Some(a) = args::<ix::UpdateEmergencyAdmin>(data) {
+        return Some((
+            "update_emergency_admin",
+            vec![(
+                "new_emergency_admin",
+                a.new_emergency_admin.map(key).unwrap_or(Value::Null),
+            )],
+        ));
+    }
+    if let Some(a) = args::<ix::AddAcceptedMint>(data) {
+        return Some(("add_accepted_mint", vec![("mint", key(a.mint))]));
+    }
+    if let Some(a) = args::<ix::RemoveAcceptedMint>(data) {
+        return Some(("remove_accepted_mint", vec![("mint", key(a.mint))]));
+    }
+    if let Some(a) = args::<ix::WithdrawSolDust>(data) {
+        return Some(("withdraw_sol_dust", vec![("amount", json!(a.amount))]));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::InstructionData;
+
+    const CREATE_KEY: Pubkey = Pubkey::new_from_array([3; 32]);
+    const MEMBER: Pubkey = Pubkey::new_from_array([1; 32]);
+
+    fn multisig() -> Pubkey {
+        multisig_pda(&CREATE_KEY).0
+    }
+
+    fn account_data(name: &str, body: impl AnchorSerialize) -> Vec<u8> {
+        let mut data = account_discriminator(name).to_vec();
+        body.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn pdas_follow_squads_seeds() {
+        // Mainnet program config; the rest are pinned for a fixed create key.
+        assert_eq!(
+            program_config_pda().0.to_string(),
+            "BSTq9w3kZwNwpBXJEvTZz2G9ZTNyKBvoSeXMvwb4cNZr"
+        );
+        assert_eq!(
+            multisig().to_string(),
+            "3Lgdd8UPN9SKXmMLNg5nonKNekb3krg42VKNZwomLaxy"
+        );
+        assert_eq!(
+            vault_pda(&multisig(), 0).0.to_string(),
+            "BEd9tMFMxy2uZJsdbag3sVkqbSP66KSptaGwubteZkA1"
+        );
+        assert_eq!(
+            transaction_pda(&multisig(), 1).0.to_string(),
+            "KmrDNdVniuTYSbMTYuzokwB9Wn3URnBE7VHNGoM3fUX"
+        );
+        assert_eq!(
+            proposal_pda(&multisig(), 1).0.to_string(),
+            "4LQEGHt6EvxA7q1F2xPEi6azStD74BL2dzSYraMMNEyi"
+        );
+        assert_eq!(
+            account_discriminator("Multisig"),
+            [224, 116, 121, 186, 68, 161, 79, 236]
+        );
+    }
+
+    #[test]
+    fn compiles_transaction_message_with_compact_prefixes() {
+        let vault = vault_pda(&multisig(), 0).0;
+        let program = Pubkey::new_from_array([7; 32]);
+        let writable = Pubkey::new_from_array([8; 32]);
+        let readonly = Pubkey::new_from_array([9; 32]);
+        let ix = Instruction {
+            program_id: program,
+            accounts: vec![
+                AccountMeta::new_readonly(vault, true),
+                AccountMeta::new(writable, false),
+                AccountMeta::new_readonly(readonly, false),
+            ],
+            data: vec![0xaa, 0xbb],
+        };
+        let message = compile_transaction_message(&vault, &[ix]).unwrap();
+
+        // The vault pays, so it is the only (writable) signer.
+        // Read-only keys are sorted, so the program precedes `readonly`.
+        let mut expected = vec![1, 1, 1, 4];
+        for key in [vault, writable, program, readonly] {
+            expected.extend_from_slice(key.as_ref());
+        }
+        expected.extend_from_slice(&[1, 2, 3, 0, 1, 3, 2, 0, 0xaa, 0xbb, 0]);
+        assert_eq!(message, expected);
+
+        let foreign = Instruction {
+            program_id: program,
+            accounts: vec![AccountMeta::new(MEMBER, true)],
+            data: Vec::new(),
+        };
+        let err = compile_transaction_message(&vault, &[foreign]).unwrap_err();
+        assert!(err.to_string().contains("2 signers"), "{err}");
+    }
+
+    fn vault_transaction(message: VaultTransactionMessage) -> VaultTransactionAccount {
+        VaultTransactionAccount {
+            multisig: multisig(),
+            creator: MEMBER,
+            index: 1,
+            bump: 255,
+            vault_index: 0,
+            vault_bump: 254,
+            ephemeral_signer_bumps: Vec::new(),
+            message,
+        }
+    }
+
+    /// `set_pause` as the vault would propose it. The vault pays for the
+    /// message, so it is (and decompiles as) a writable signer.
+    fn admin_message() -> (Instruction, VaultTransactionMessage) {
+        let vault = vault_pda(&multisig(), 0).0;
+        let ix = Instruction {
+            program_id: creator_vault::ID,
+            accounts: vec![
+                AccountMeta::new(Pubkey::new_from_array([5; 32]), false),
+                AccountMeta::new(vault, true),
+                AccountMeta::new_readonly(Pubkey::new_from_array([6; 32]), false),
+            ],
+            data: creator_vault::instruction::SetPause { paused: true }.data(),
+        };
+        let compiled = Message::new(std::slice::from_ref(&ix), Some(&vault));
+        let header = compiled.header;
+        let message = VaultTransactionMessage {
+            num_signers: header.num_required_signatures,
+            num_writable_signers: header.num_required_signatures
+                - header.num_readonly_signed_accounts,
+            num_writable_non_signers: (compiled.account_keys.len()
+                - usize::from(header.num_required_signatures)
+                - usize::from(header.num_readonly_unsigned_accounts))
+                as u8,
+            account_keys: compiled.account_keys.clone(),
+            instructions: compiled
+                .instructions
+                .iter()
+                .map(|ix| CompiledInstruction {
+                    program_id_index: ix.program_id_index,
+                    account_indexes: ix.accounts.clone(),
+                    data: ix.data.clone(),
+                })
+                .collect(),
+            address_table_lookups: Vec::new(),
+        };
+        (ix, message)
+    }
+
+    #[test]
+    fn execute_appends_message_accounts_in_key_order() {
+        let (_, message) = admin_message();
+        let transaction = vault_transaction(message.clone());
+        let ix = build_vault_transaction_execute_ix(multisig(), 1, MEMBER, &transaction).unwrap();
+
+        let metas: Vec<_> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect();
+        let keys = &message.account_keys;
+        assert_eq!(
+            metas,
+            vec![
+                (multisig(), false, false),
+                (proposal_pda(&multisig(), 1).0, false, true),
+                (transaction_pda(&multisig(), 1).0, false, false),
+                (MEMBER, true, false),
+                // The vault signs inside the program, never on the outer transaction.
+                (keys[0], false, true),
+                (keys[1], false, true),
+                (keys[2], false, false),
+                (keys[3], false, false),
+            ]
+        );
+        assert_eq!(ix.data, [194, 8, 161, 87, 153, 164, 25, 171]);
+
+        let mut with_lookup = message;
+        with_lookup
+            .address_table_lookups
+            .push(MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![0],
+                readonly_indexes: Vec::new(),
+            });
+        let transaction = vault_transaction(with_lookup);
+        assert!(build_vault_transaction_execute_ix(multisig(), 1, MEMBER, &transaction).is_err());
+    }
+
+    #[test]
+    fn decodes_account_fixtures_and_rejects_other_types() {
+        let mut multisig_bytes = vec![224, 116, 121, 186, 68, 161, 79, 236];
+        multisig_bytes.extend_from_slice(CREATE_KEY.as_ref());
+        multisig_bytes.extend_from_slice(&[0; 32]);
+        multisig_bytes.extend_from_slice(&2u16.to_le_bytes());
+        multisig_bytes.extend_from_slice(&0u32.to_le_bytes());
+        multisig_bytes.extend_from_slice(&3u64.to_le_bytes());
+        multisig_bytes.extend_from_slice(&1u64.to_le_bytes());
+        multisig_bytes.extend_from_slice(&[0, 254]);
+        multisig_bytes.extend_from_slice(&1u32.to_le_bytes());
+        multisig_bytes.extend_from_slice(MEMBER.as_ref());
+        multisig_bytes.push(Permissions::ALL);
+        let state = MultisigAccount::from_account_data(&multisig_bytes).unwrap();
+        assert_eq!(state.create_key, CREATE_KEY);
+        assert!(state.is_autonomous());
+        assert_eq!((state.threshold, state.transaction_index), (2, 3));
+        assert!(state
+            .member(&MEMBER)
+            .unwrap()
+            .permissions
+            .has(Permissions::ALL));
+
+        let mut proposal_bytes = vec![26, 94, 189, 187, 116, 136, 53, 33];
+        proposal_bytes.extend_from_slice(multisig().as_ref());
+        proposal_bytes.extend_from_slice(&4u64.to_le_bytes());
+        proposal_bytes.push(3);
+        proposal_bytes.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        proposal_bytes.push(253);
+        proposal_bytes.extend_from_slice(&1u32.to_le_bytes());
+        proposal_bytes.extend_from_slice(MEMBER.as_ref());
+        proposal_bytes.extend_from_slice(&[0; 8]);
+        let proposal = ProposalAccount::from_account_data(&proposal_bytes).unwrap();
+        assert_eq!(proposal.transaction_index, 4);
+        assert_eq!(
+            proposal.status,
+            ProposalStatus::Approved {
+                timestamp: 1_700_000_000
+            }
+        );
+        assert_eq!(proposal.approved, vec![MEMBER]);
+
+        let (ix, message) = admin_message();
+        let transaction_bytes = account_data("VaultTransaction", vault_transaction(message));
+        assert_eq!(
+            transaction_bytes[..8],
+            [168, 250, 162, 100, 81, 14, 162, 207]
+        );
+        let transaction = VaultTransactionAccount::from_account_data(&transaction_bytes).unwrap();
+        assert_eq!(transaction.message.instructions().unwrap(), vec![ix]);
+
+        let config_bytes = account_data(
+            "ProgramConfig",
+            (
+                ProgramConfig {
+                    authority: MEMBER,
+                    multisig_creation_fee: 1_000_000,
+                    treasury: Pubkey::new_from_array([9; 32]),
+                },
+                [0u8; 64],
+            ),
+        );
+        let config = ProgramConfig::from_account_data(&config_bytes).unwrap();
+        assert_eq!(config.treasury, Pubkey::new_from_array([9; 32]));
+
+        // Each decoder refuses the others' bytes, and anything too short.
+        assert!(MultisigAccount::from_account_data(&proposal_bytes).is_err());
+        assert!(ProposalAccount::from_account_data(&transaction_bytes).is_err());
+        assert!(VaultTransactionAccount::from_account_data(&proposal_bytes).is_err());
+        assert!(ProgramConfig::from_account_data(&multisig_bytes).is_err());
+        assert!(ProposalAccount::from_account_data(&proposal_bytes[..6]).is_err());
+    }
+
+    #[test]
+    fn multisig_create_v2_encodes_args_and_accounts() {
+        let treasury = Pubkey::new_from_array([9; 32]);
+        let attn = Pubkey::new_from_array([2; 32]);
+        let args = MultisigCreateArgsV2 {
+            config_authority: None,
+            threshold: 2,
+            members: [MEMBER, attn]
+                .into_iter()
+                .map(|key| Member {
+                    key,
+                    permissions: Permissions {
+                        mask: Permissions::ALL,
+                    },
+                })
+                .collect(),
+            time_lock: 0,
+            rent_collector: None,
+            memo: Some("CreatorVault-abc".to_string()),
+        };
+        let ix = build_multisig_create_v2_ix(treasury, CREATE_KEY, attn, &args).unwrap();
+
+        assert_eq!(ix.program_id, SQUADS_PROGRAM_ID);
+        assert_eq!(&ix.data[..8], &[50, 221, 199, 93, 40, 245, 139, 233]);
+        let mut expected = vec![0u8];
+        expected.extend_from_slice(&2u16.to_le_bytes());
+        expected.extend_from_slice(&2u32.to_le_bytes());
+        expected.extend_from_slice(MEMBER.as_ref());
+        expected.push(7);
+        expected.extend_from_slice(attn.as_ref());
+        expected.push(7);
+        expected.extend_from_slice(&0u32.to_le_bytes());
+        expected.push(0);
+        expected.push(1);
+        expected.extend_from_slice(&16u32.to_le_bytes());
+        expected.extend_from_slice(b"CreatorVault-abc");
+        assert_eq!(&ix.data[8..], expected.as_slice());
+
+        let keys: Vec<_> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (program_config_pda().0, false, false),
+                (treasury, false, true),
+                (multisig(), false, true),
+                (CREATE_KEY, true, false),
+                (attn, true, true),
+                (SYSTEM_PROGRAM_ID, false, false),
+            ]
+        );
+    }
+}
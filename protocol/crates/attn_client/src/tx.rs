@@ -0,0 +1,731 @@
+//! Transaction sending with compute budget, priority fees and rebroadcasting.
+//!
+//! [`TxSender`] sizes the compute-unit limit from a simulation, prices the
+//! transaction from a [`FeeStrategy`], and resends the signed transaction until it
+//! reaches the configured commitment or its blockhash expires. Signing stays with the
+//! caller so keypairs, offline signatures and KMS keys all go through the same path:
+//! [`TxSender::prepare`], sign the message, then [`TxSender::send_transaction`].
+//! Given address lookup tables (see [`crate::alt`]) messages compile to v0.
+
+use std::{fmt, str::FromStr, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::SerializableTransaction,
+    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    signers::Signers,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use tokio::time::{sleep, Instant};
+
+/// Largest compute-unit limit a transaction may request.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+/// Most accounts `getRecentPrioritizationFees` accepts in one call.
+const MAX_FEE_ACCOUNTS: usize = 128;
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How the compute-unit price (micro-lamports per CU) is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeStrategy {
+    /// No `SetComputeUnitPrice` instruction.
+    #[default]
+    None,
+    /// A fixed price.
+    Fixed(u64),
+    /// A percentile (0-100) of recent prioritization fees paid for the writable
+    /// accounts of the transaction.
+    Percentile(u8),
+}
+
+impl FromStr for FeeStrategy {
+    type Err = String;
+
+    /// Parses `none`, a fixed micro-lamport price, or `pNN` for a percentile.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("none") {
+            return Ok(Self::None);
+        }
+        if let Some(percentile) = value.strip_prefix(['p', 'P']) {
+            let percentile: u8 = percentile
+                .parse()
+                .map_err(|_| format!("invalid percentile in {value}"))?;
+            if percentile > 100 {
+                return Err(format!("percentile must be at most 100, got {percentile}"));
+            }
+            return Ok(Self::Percentile(percentile));
+        }
+        value
+            .parse()
+            .map(Self::Fixed)
+            .map_err(|_| format!("expected none, a micro-lamport price or pNN, got {value}"))
+    }
+}
+
+impl fmt::Display for FeeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Fixed(price) => write!(f, "{price}"),
+            Self::Percentile(percentile) => write!(f, "p{percentile}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SendConfig {
+    pub fee: FeeStrategy,
+    /// Upper bound on the compute-unit price picked by [`FeeStrategy::Percentile`].
+    pub max_unit_price: Option<u64>,
+    /// Fixed compute-unit limit; skips the estimating simulation.
+    pub compute_unit_limit: Option<u32>,
+    /// Headroom added to simulated compute units, in basis points.
+    pub compute_unit_margin_bps: u16,
+    pub rebroadcast_interval: Duration,
+    pub commitment: CommitmentConfig,
+}
+
+impl Default for SendConfig {
+    fn default() -> Self {
+        Self {
+            fee: FeeStrategy::None,
+            max_unit_price: None,
+            compute_unit_limit: None,
+            compute_unit_margin_bps: 1_000,
+            rebroadcast_interval: Duration::from_secs(2),
+            commitment: CommitmentConfig::confirmed(),
+        }
+    }
+}
+
+/// Compute-budget instructions to prepend to a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ComputeBudget {
+    pub unit_limit: Option<u32>,
+    pub unit_price: Option<u64>,
+}
+
+impl ComputeBudget {
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if let Some(units) = self.unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(price) = self.unit_price.filter(|price| *price > 0) {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        instructions
+    }
+
+    /// Prepends the budget instructions, unless the caller already set a budget.
+    ///
+    /// A durable nonce advance must still come first, so apply the budget before
+    /// inserting it.
+    pub fn apply(&self, instructions: Vec<Instruction>) -> Vec<Instruction> {
+        if has_compute_budget(&instructions) {
+            return instructions;
+        }
+        let mut budgeted = self.instructions();
+        budgeted.extend(instructions);
+        budgeted
+    }
+}
+
+fn has_compute_budget(instructions: &[Instruction]) -> bool {
+    instructions
+        .iter()
+        .any(|ix| ix.program_id == solana_compute_budget_interface::ID)
+}
+
+/// A legacy message without lookup tables, otherwise v0.
+pub fn compile_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedMessage> {
+    if lookup_tables.is_empty() {
+        return Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(payer),
+            &blockhash,
+        )));
+    }
+    let message = v0::Message::try_compile(payer, instructions, lookup_tables, blockhash)
+        .context("failed to compile v0 message")?;
+    Ok(VersionedMessage::V0(message))
+}
+
+/// A transaction with every signature slot empty, to sign or simulate.
+pub fn unsigned_transaction(message: VersionedMessage) -> VersionedTransaction {
+    VersionedTransaction {
+        signatures: vec![
+            Signature::default();
+            usize::from(message.header().num_required_signatures)
+        ],
+        message,
+    }
+}
+
+/// Instructions with their compute budget and the blockhash to sign them with.
+#[derive(Debug, Clone)]
+pub struct PreparedTx {
+    pub instructions: Vec<Instruction>,
+    pub lookup_tables: Vec<AddressLookupTableAccount>,
+    pub budget: ComputeBudget,
+    pub blockhash: Hash,
+    pub last_valid_block_height: u64,
+}
+
+impl PreparedTx {
+    pub fn message(&self, payer: &Pubkey) -> Result<VersionedMessage> {
+        compile_message(
+            payer,
+            &self.instructions,
+            &self.lookup_tables,
+            self.blockhash,
+        )
+    }
+}
+
+/// Final state of a sent transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SendOutcome {
+    /// Landed without error and reached the configured commitment.
+    Confirmed { signature: Signature, slot: u64 },
+    /// Landed, but the program returned an error.
+    Failed {
+        signature: Signature,
+        slot: u64,
+        error: TransactionError,
+    },
+    /// Not seen on chain before its blockhash expired; safe to rebuild and resend.
+    Expired { signature: Signature },
+}
+
+impl SendOutcome {
+    pub fn signature(&self) -> Signature {
+        match self {
+            Self::Confirmed { signature, .. }
+            | Self::Failed { signature, .. }
+            | Self::Expired { signature } => *signature,
+        }
+    }
+
+    /// Converts anything but [`SendOutcome::Confirmed`] into an error.
+    pub fn into_result(self) -> Result<Signature> {
+        match self {
+            Self::Confirmed { signature, .. } => Ok(signature),
+            Self::Failed {
+                signature, error, ..
+            } => Err(TransactionFailed { signature, error }.into()),
+            Self::Expired { signature } => Err(anyhow!(
+                "transaction {signature} expired before it was confirmed"
+            )),
+        }
+    }
+}
+
+/// A transaction that landed with an error; see [`crate::errors::ProgramError`] to
+/// decode it.
+#[derive(Debug, Clone)]
+pub struct TransactionFailed {
+    pub signature: Signature,
+    pub error: TransactionError,
+}
+
+impl fmt::Display for TransactionFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transaction {} failed: {}", self.signature, self.error)
+    }
+}
+
+impl std::error::Error for TransactionFailed {}
+
+/// Returned by [`TxSender::compute_budget`] when the estimating simulation fails; the
+/// full result carries logs and consumed units for reporting.
+#[derive(Debug, Clone)]
+pub struct SimulationFailed {
+    pub result: RpcSimulateTransactionResult,
+}
+
+impl fmt::Display for SimulationFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result.err {
+            Some(err) => write!(f, "transaction simulation failed: {err}"),
+            None => write!(f, "transaction simulation failed"),
+        }
+    }
+}
+
+impl std::error::Error for SimulationFailed {}
+
+#[derive(Clone)]
+pub struct TxSender {
+    rpc: Arc<RpcClient>,
+    config: SendConfig,
+}
+
+impl TxSender {
+    pub fn new(rpc: Arc<RpcClient>, config: SendConfig) -> Self {
+        Self { rpc, config }
+    }
+
+    pub fn rpc(&self) -> &Arc<RpcClient> {
+        &self.rpc
+    }
+
+    pub fn config(&self) -> &SendConfig {
+        &self.config
+    }
+
+    /// The budget from fixed settings only: the configured limit and a
+    /// [`FeeStrategy::Fixed`] price. Used when the message has to be identical across
+    /// machines, e.g. for offline signing.
+    pub fn fixed_budget(&self) -> ComputeBudget {
+        ComputeBudget {
+            unit_limit: self.config.compute_unit_limit,
+            unit_price: match self.config.fee {
+                FeeStrategy::Fixed(price) => Some(price),
+                _ => None,
+            },
+        }
+    }
+
+    /// Prices the transaction and, without a fixed limit, simulates it to size the
+    /// compute-unit limit.
+    pub async fn compute_budget(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<ComputeBudget> {
+        if has_compute_budget(instructions) {
+            return Ok(ComputeBudget::default());
+        }
+        let unit_price = self.unit_price(instructions).await?;
+        let unit_limit = match self.config.compute_unit_limit {
+            Some(units) => units,
+            None => {
+                let units = self
+                    .simulate_units(instructions, payer, lookup_tables, unit_price)
+                    .await?;
+                with_margin(units, self.config.compute_unit_margin_bps)
+            }
+        };
+        Ok(ComputeBudget {
+            unit_limit: Some(unit_limit),
+            unit_price,
+        })
+    }
+
+    async fn unit_price(&self, instructions: &[Instruction]) -> Result<Option<u64>> {
+        let price = match self.config.fee {
+            FeeStrategy::None => return Ok(None),
+            FeeStrategy::Fixed(price) => price,
+            FeeStrategy::Percentile(percentile) => {
+                let accounts = writable_accounts(instructions);
+                let mut fees: Vec<u64> = self
+                    .rpc
+                    .get_recent_prioritization_fees(&accounts)
+                    .await
+                    .context("failed to fetch recent prioritization fees")?
+                    .into_iter()
+                    .map(|fee| fee.prioritization_fee)
+                    .collect();
+                let price = fee_percentile(&mut fees, percentile);
+                self.config
+                    .max_unit_price
+                    .map_or(price, |max| price.min(max))
+            }
+        };
+        Ok(Some(price))
+    }
+
+    async fn simulate_units(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        lookup_tables: &[AddressLookupTableAccount],
+        unit_price: Option<u64>,
+    ) -> Result<u64> {
+        // Simulate with the final instruction layout so the budget instructions'
+        // own cost is included.
+        let budget = ComputeBudget {
+            unit_limit: Some(MAX_COMPUTE_UNIT_LIMIT),
+            unit_price,
+        };
+        let message = compile_message(
+            payer,
+            &budget.apply(instructions.to_vec()),
+            lookup_tables,
+            Hash::default(),
+        )?;
+        let tx = unsigned_transaction(message);
+        let result = self
+            .rpc
+            .simulate_transaction_with_config(
+                &tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(self.config.commitment),
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
+            .await
+            .context("failed to simulate transaction")?
+            .value;
+        if result.err.is_some() {
+            return Err(SimulationFailed { result }.into());
+        }
+        result
+            .units_consumed
+            .ok_or_else(|| anyhow!("simulation did not report consumed compute units"))
+    }
+
+    /// Adds the compute budget and fetches a blockhash to sign with.
+    pub async fn prepare(
+        &self,
+        instructions: Vec<Instruction>,
+        payer: &Pubkey,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<PreparedTx> {
+        let budget = self
+            .compute_budget(&instructions, payer, lookup_tables)
+            .await?;
+        let (blockhash, last_valid_block_height) = self
+            .rpc
+            .get_latest_blockhash_with_commitment(self.config.commitment)
+            .await
+            .context("failed to fetch recent blockhash")?;
+        Ok(PreparedTx {
+            instructions: budget.apply(instructions),
+            lookup_tables: lookup_tables.to_vec(),
+            budget,
+            blockhash,
+            last_valid_block_height,
+        })
+    }
+
+    /// Prepares, signs with `signers` and sends.
+    pub async fn send<T: Signers + ?Sized>(
+        &self,
+        instructions: Vec<Instruction>,
+        payer: &Pubkey,
+        lookup_tables: &[AddressLookupTableAccount],
+        signers: &T,
+    ) -> Result<SendOutcome> {
+        let prepared = self.prepare(instructions, payer, lookup_tables).await?;
+        let tx = VersionedTransaction::try_new(prepared.message(payer)?, signers)
+            .context("failed to sign transaction")?;
+        self.send_transaction(&tx, prepared.last_valid_block_height)
+            .await
+    }
+
+    /// Sends a signed transaction with preflight, then rebroadcasts it until it
+    /// reaches the configured commitment or the chain passes
+    /// `last_valid_block_height`.
+    pub async fn send_transaction(
+        &self,
+        tx: &impl SerializableTransaction,
+        last_valid_block_height: u64,
+    ) -> Result<SendOutcome> {
+        let signature = *tx.get_signature();
+        let commitment = self.config.commitment;
+        self.rpc
+            .send_transaction_with_config(
+                tx,
+                RpcSendTransactionConfig {
+                    preflight_commitment: Some(commitment.commitment),
+                    max_retries: Some(0),
+                    ..RpcSendTransactionConfig::default()
+                },
+            )
+            .await
+            .context("failed to send transaction")?;
+        let rebroadcast = RpcSendTransactionConfig {
+            skip_preflight: true,
+            max_retries: Some(0),
+            ..RpcSendTransactionConfig::default()
+        };
+        let mut last_sent = Instant::now();
+        loop {
+            sleep(STATUS_POLL_INTERVAL).await;
+            let status = self
+                .rpc
+                .get_signature_statuses(&[signature])
+                .await
+                .context("failed to fetch signature status")?
+                .value
+                .into_iter()
+                .next()
+                .flatten();
+            if let Some(status) = status {
+                if let Some(error) = status.err {
+                    return Ok(SendOutcome::Failed {
+                        signature,
+                        slot: status.slot,
+                        error,
+                    });
+                }
+                if status.satisfies_commitment(commitment) {
+                    return Ok(SendOutcome::Confirmed {
+                        signature,
+                        slot: status.slot,
+                    });
+                }
+                // Landed; wait for it to reach the commitment without resending.
+                continue;
+            }
+            let block_height = self
+                .rpc
+                .get_block_height_with_commitment(commitment)
+                .await
+                .context("failed to fetch block height")?;
+            if block_height > last_valid_block_height {
+                return Ok(SendOutcome::Expired { signature });
+            }
+            if last_sent.elapsed() >= self.config.rebroadcast_interval {
+                // Duplicate and already-processed errors are expected here.
+                let _ = self.rpc.send_transaction_with_config(tx, rebroadcast).await;
+                last_sent = Instant::now();
+            }
+        }
+    }
+}
+
+/// Writable accounts the fee market is keyed on, capped at the RPC limit.
+fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts = Vec::new();
+    for meta in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+        }
+    }
+    accounts.truncate(MAX_FEE_ACCOUNTS);
+    accounts
+}
+
+/// Nearest-rank percentile; zero when there are no samples.
+fn fee_percentile(fees: &mut [u64], percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    fees[rank]
+}
+
+fn with_margin(units: u64, margin_bps: u16) -> u32 {
+    let units = units.saturating_mul(10_000 + u64::from(margin_bps)) / 10_000;
+    units.min(u64::from(MAX_COMPUTE_UNIT_LIMIT)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use solana_client::{
+        client_error::Result as ClientResult,
+        rpc_client::RpcClientConfig,
+        rpc_request::RpcRequest,
+        rpc_sender::{RpcSender, RpcTransportStats},
+    };
+    use solana_sdk::{
+        instruction::{AccountMeta, InstructionError},
+        signature::{Keypair, Signer},
+    };
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    /// Answers the calls `send_transaction` makes. `statuses` lists, by first poll,
+    /// what `getSignatureStatuses` returns from then on; the block height grows by
+    /// one per `getBlockHeight`.
+    struct ScriptedRpc {
+        signature: Signature,
+        statuses: Vec<(usize, Value)>,
+        polls: AtomicUsize,
+        block_height: AtomicU64,
+        sends: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl RpcSender for ScriptedRpc {
+        async fn send(&self, request: RpcRequest, _params: Value) -> ClientResult<Value> {
+            Ok(match request {
+                RpcRequest::SendTransaction => {
+                    self.sends.fetch_add(1, Ordering::SeqCst);
+                    json!(self.signature.to_string())
+                }
+                RpcRequest::GetSignatureStatuses => {
+                    let poll = self.polls.fetch_add(1, Ordering::SeqCst) + 1;
+                    let status = self
+                        .statuses
+                        .iter()
+                        .rev()
+                        .find(|(from, _)| poll >= *from)
+                        .map_or(Value::Null, |(_, status)| status.clone());
+                    json!({ "context": { "slot": poll }, "value": [status] })
+                }
+                RpcRequest::GetBlockHeight => {
+                    json!(self.block_height.fetch_add(1, Ordering::SeqCst))
+                }
+                other => panic!("unexpected RPC call {other}"),
+            })
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "scripted".to_string()
+        }
+    }
+
+    fn status(slot: u64, confirmation: &str, err: Option<TransactionError>) -> Value {
+        let result = match &err {
+            Some(err) => json!({ "Err": err }),
+            None => json!({ "Ok": null }),
+        };
+        json!({
+            "slot": slot,
+            "confirmations": 0,
+            "status": result,
+            "err": err,
+            "confirmationStatus": confirmation,
+        })
+    }
+
+    /// Sends a signed transaction through `statuses`; returns the outcome and how
+    /// many times it was broadcast.
+    async fn send_scripted(
+        statuses: Vec<(usize, Value)>,
+        last_valid_block_height: u64,
+    ) -> (SendOutcome, usize) {
+        let payer = Keypair::new();
+        let ix = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![AccountMeta::new(payer.pubkey(), true)],
+            data: Vec::new(),
+        };
+        let message = compile_message(&payer.pubkey(), &[ix], &[], Hash::new_unique()).unwrap();
+        let tx = VersionedTransaction::try_new(message, &[&payer]).unwrap();
+        let sends = Arc::new(AtomicUsize::new(0));
+        let rpc = RpcClient::new_sender(
+            ScriptedRpc {
+                signature: tx.signatures[0],
+                statuses,
+                polls: AtomicUsize::new(0),
+                block_height: AtomicU64::new(100),
+                sends: sends.clone(),
+            },
+            RpcClientConfig::default(),
+        );
+        let sender = TxSender::new(Arc::new(rpc), SendConfig::default());
+        let outcome = sender
+            .send_transaction(&tx, last_valid_block_height)
+            .await
+            .unwrap();
+        assert_eq!(outcome.signature(), tx.signatures[0]);
+        (outcome, sends.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn landed_transactions_wait_for_the_commitment() {
+        // Unseen for four polls (one rebroadcast at 2s), then processed, then confirmed.
+        let (outcome, sends) = send_scripted(
+            vec![
+                (5, status(41, "processed", None)),
+                (6, status(41, "confirmed", None)),
+            ],
+            1_000,
+        )
+        .await;
+        assert!(matches!(outcome, SendOutcome::Confirmed { slot: 41, .. }));
+        assert_eq!(sends, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn program_errors_fail_without_resending() {
+        let error = TransactionError::InstructionError(0, InstructionError::Custom(6_001));
+        let (outcome, sends) = send_scripted(
+            vec![(1, status(7, "processed", Some(error.clone())))],
+            1_000,
+        )
+        .await;
+        assert_eq!(
+            outcome,
+            SendOutcome::Failed {
+                signature: outcome.signature(),
+                slot: 7,
+                error: error.clone(),
+            }
+        );
+        assert_eq!(sends, 1);
+        let failed = outcome.into_result().unwrap_err();
+        assert_eq!(
+            failed.downcast_ref::<TransactionFailed>().unwrap().error,
+            error
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unseen_transactions_expire_past_the_last_valid_height() {
+        // Rebroadcast at 2s while heights 100 to 104 are valid; the sixth poll sees 105.
+        let (outcome, sends) = send_scripted(Vec::new(), 104).await;
+        assert!(matches!(outcome, SendOutcome::Expired { .. }));
+        assert_eq!(sends, 2);
+    }
+
+    #[test]
+    fn fee_strategy_and_budget_math() {
+        assert_eq!("none".parse(), Ok(FeeStrategy::None));
+        assert_eq!("2500".parse(), Ok(FeeStrategy::Fixed(2_500)));
+        assert_eq!("p75".parse(), Ok(FeeStrategy::Percentile(75)));
+        assert!("p101".parse::<FeeStrategy>().is_err());
+        assert_eq!(FeeStrategy::Percentile(75).to_string(), "p75");
+
+        let mut fees = vec![50, 10, 40, 20, 30];
+        assert_eq!(fee_percentile(&mut fees, 0), 10);
+        assert_eq!(fee_percentile(&mut fees, 50), 30);
+        assert_eq!(fee_percentile(&mut fees, 100), 50);
+        assert_eq!(fee_percentile(&mut [], 50), 0);
+
+        assert_eq!(with_margin(100_000, 1_000), 110_000);
+        assert_eq!(with_margin(1_300_000, 1_000), MAX_COMPUTE_UNIT_LIMIT);
+
+        let writable = Pubkey::new_unique();
+        let ix = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![
+                AccountMeta::new(writable, false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                AccountMeta::new(writable, true),
+            ],
+            data: Vec::new(),
+        };
+        assert_eq!(writable_accounts(std::slice::from_ref(&ix)), vec![writable]);
+
+        let budget = ComputeBudget {
+            unit_limit: Some(200_000),
+            unit_price: Some(1_000),
+        };
+        let budgeted = budget.apply(vec![ix]);
+        assert_eq!(budgeted.len(), 3);
+        assert_eq!(budgeted[0].program_id, solana_compute_budget_interface::ID);
+        assert_eq!(budget.apply(budgeted.clone()), budgeted);
+    }
+}
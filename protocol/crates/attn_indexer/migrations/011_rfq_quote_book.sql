@@ -0,0 +1,41 @@
+create table if not exists rfq_quotes (
+    id uuid primary key,
+    quote_key text not null,
+    market text not null,
+    side text not null,
+    route text not null,
+    maturity_ts bigint not null,
+    size_yt double precision not null,
+    price_usdc double precision not null,
+    implied_apr double precision not null,
+    est_slippage double precision not null,
+    cursor text not null,
+    expires_at timestamptz not null,
+    consumed_at timestamptz,
+    consumed_by text,
+    created_at timestamptz not null default now()
+);
+
+create unique index if not exists rfq_quotes_live_key_idx
+    on rfq_quotes (quote_key)
+    where consumed_at is null;
+
+create index if not exists rfq_quotes_expires_at_idx
+    on rfq_quotes (expires_at)
+    where consumed_at is null;
+
+create table if not exists rfq_epoch_totals (
+    epoch_key text primary key,
+    market text not null,
+    maturity_ts bigint not null,
+    total_usdc double precision not null default 0 check (total_usdc >= 0),
+    updated_at timestamptz not null default now()
+);
+
+create table if not exists rfq_wallet_totals (
+    epoch_key text not null references rfq_epoch_totals(epoch_key) on delete cascade,
+    wallet text not null,
+    used_usdc double precision not null default 0 check (used_usdc >= 0),
+    updated_at timestamptz not null default now(),
+    primary key (epoch_key, wallet)
+);
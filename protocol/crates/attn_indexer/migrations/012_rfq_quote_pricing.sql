@@ -0,0 +1 @@
+alter table rfq_quotes add column if not exists pricing jsonb;
@@ -0,0 +1,12 @@
+create table if not exists market_index_snapshots(
+  market_pubkey text not null references markets(market_pubkey) on delete cascade,
+  observed_at timestamptz not null,
+  fee_index numeric not null,
+  primary key(market_pubkey, observed_at)
+);
+
+create table if not exists attnusd_share_snapshots(
+  observed_at timestamptz primary key,
+  share_index numeric not null,
+  total_supply numeric not null default 0
+);
@@ -0,0 +1,5 @@
+alter table market_index_snapshots
+    add column if not exists pt_supply numeric not null default 0,
+    add column if not exists yt_supply numeric not null default 0;
+
+create index if not exists events_program_kind_ts_idx on events(program, kind, ts);
@@ -0,0 +1,27 @@
+-- Publishes Squads safe request status changes on the attn_stream channel so
+-- attn_api can push them to `/v1/stream` subscribers without polling.
+create or replace function notify_squads_safe_request() returns trigger as $$
+begin
+    perform pg_notify(
+        'attn_stream',
+        json_build_object(
+            'topic', 'squads_safe:' || new.id::text,
+            'kind', 'squads_safe_updated',
+            'data', json_build_object(
+                'id', new.id,
+                'status', new.status,
+                'safe_address', new.safe_address,
+                'error_code', new.error_code,
+                'updated_at', new.updated_at
+            )
+        )::text
+    );
+    return new;
+end;
+$$ language plpgsql;
+
+drop trigger if exists squads_safe_requests_notify on squads_safe_requests;
+
+create trigger squads_safe_requests_notify
+    after insert or update of status, safe_address, error_code on squads_safe_requests
+    for each row execute function notify_squads_safe_request();
@@ -0,0 +1,2 @@
+alter table squads_safe_requests
+    add column if not exists creation_signature text;
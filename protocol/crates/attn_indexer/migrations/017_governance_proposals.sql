@@ -0,0 +1,22 @@
+create table if not exists governance_proposals(
+  multisig text not null,
+  transaction_index bigint not null,
+  proposal_pubkey text not null,
+  creator_vault text,
+  status text not null,
+  status_at timestamptz,
+  stale boolean not null default false,
+  threshold integer not null,
+  approved jsonb not null default '[]'::jsonb,
+  rejected jsonb not null default '[]'::jsonb,
+  actions jsonb not null default '[]'::jsonb,
+  first_seen_at timestamptz not null default now(),
+  updated_at timestamptz not null default now(),
+  primary key(multisig, transaction_index)
+);
+
+create index if not exists governance_proposals_creator_vault_idx
+  on governance_proposals(creator_vault, transaction_index desc);
+
+create index if not exists governance_proposals_status_idx
+  on governance_proposals(status);
@@ -0,0 +1,34 @@
+create table if not exists creator_vault_fee_samples(
+  creator_vault text not null,
+  observed_at timestamptz not null,
+  total_fees numeric not null,
+  primary key(creator_vault, observed_at)
+);
+
+create table if not exists launchpad_fee_recipients(
+  creator_vault text primary key,
+  launchpad text not null,
+  account text not null,
+  recipient text not null,
+  previous_recipient text,
+  changed_at timestamptz,
+  observed_at timestamptz not null default now()
+);
+
+create table if not exists alerts(
+  id bigserial primary key,
+  creator_vault text not null,
+  kind text not null,
+  severity text not null,
+  priority boolean not null default false,
+  message text not null,
+  details jsonb not null default '{}'::jsonb,
+  opened_at timestamptz not null default now(),
+  updated_at timestamptz not null default now(),
+  resolved_at timestamptz
+);
+
+create unique index if not exists alerts_open_idx
+  on alerts(creator_vault, kind) where resolved_at is null;
+
+create index if not exists alerts_updated_idx on alerts(updated_at desc);
@@ -0,0 +1,59 @@
+-- Subscriptions and delivery outbox for attn_notifier. Indexed events still
+-- land in `events`; the trigger below only wakes the notifier early.
+create table if not exists notification_subscriptions (
+    id bigserial primary key,
+    wallet text not null,
+    -- Narrows the subscription to events referencing this vault instead of
+    -- the wallet itself.
+    creator_vault text,
+    -- Event kinds to notify about; empty means every kind.
+    kinds text[] not null default '{}',
+    sink text not null check (sink in ('webhook', 'smtp', 'telegram', 'local')),
+    -- Webhook URL, email address, Telegram chat id or local label.
+    target text not null,
+    -- HMAC-SHA256 key for webhook signatures.
+    secret text,
+    active boolean not null default true,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index if not exists notification_subscriptions_wallet_idx
+    on notification_subscriptions(wallet);
+
+create table if not exists notification_deliveries (
+    id bigserial primary key,
+    subscription_id bigint not null references notification_subscriptions(id) on delete cascade,
+    event_key text not null,
+    event jsonb not null,
+    status text not null default 'pending' check (status in ('pending', 'delivered', 'failed')),
+    attempts integer not null default 0,
+    next_attempt_at timestamptz not null default now(),
+    last_error text,
+    created_at timestamptz not null default now(),
+    delivered_at timestamptz,
+    unique (subscription_id, event_key)
+);
+
+create index if not exists notification_deliveries_due_idx
+    on notification_deliveries(next_attempt_at)
+    where status = 'pending';
+
+create table if not exists notifier_cursors (
+    source text primary key,
+    position timestamptz not null,
+    updated_at timestamptz not null default now()
+);
+
+create or replace function notify_event_indexed() returns trigger as $$
+begin
+    perform pg_notify('attn_events', new.sig);
+    return new;
+end;
+$$ language plpgsql;
+
+drop trigger if exists events_notify on events;
+
+create trigger events_notify
+    after insert on events
+    for each row execute function notify_event_indexed();
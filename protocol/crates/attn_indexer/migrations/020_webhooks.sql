@@ -0,0 +1,145 @@
+-- Outbound webhooks for API integrators. Events are written to
+-- `webhook_events` by the triggers below (in the same transaction as the
+-- change they describe) and fanned out into `webhook_outbox`, which the
+-- attn_api webhook worker drains.
+create table if not exists webhook_endpoints (
+    id uuid primary key,
+    api_key_id text not null,
+    url text not null,
+    secret text not null,
+    -- Empty means every event type.
+    event_types text[] not null default '{}',
+    description text,
+    active boolean not null default true,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index if not exists webhook_endpoints_api_key_idx on webhook_endpoints(api_key_id);
+
+create table if not exists webhook_events (
+    id bigserial primary key,
+    event_type text not null,
+    payload jsonb not null,
+    created_at timestamptz not null default now()
+);
+
+create index if not exists webhook_events_type_idx on webhook_events(event_type, id);
+
+create table if not exists webhook_outbox (
+    id bigserial primary key,
+    endpoint_id uuid not null references webhook_endpoints(id) on delete cascade,
+    event_id bigint not null references webhook_events(id) on delete cascade,
+    status text not null default 'pending' check (status in ('pending', 'delivered', 'failed')),
+    attempts integer not null default 0,
+    next_attempt_at timestamptz not null default now(),
+    last_status_code integer,
+    last_error text,
+    created_at timestamptz not null default now(),
+    delivered_at timestamptz
+);
+
+create index if not exists webhook_outbox_due_idx
+    on webhook_outbox(next_attempt_at)
+    where status = 'pending';
+
+create index if not exists webhook_outbox_endpoint_idx on webhook_outbox(endpoint_id, id desc);
+
+create or replace function enqueue_webhook_event(kind text, body jsonb) returns bigint as $$
+declare
+    event_id bigint;
+begin
+    insert into webhook_events (event_type, payload) values (kind, body) returning id into event_id;
+    insert into webhook_outbox (endpoint_id, event_id)
+    select id, event_id
+    from webhook_endpoints
+    where active and (cardinality(event_types) = 0 or kind = any(event_types));
+    return event_id;
+end;
+$$ language plpgsql;
+
+create or replace function webhook_rfq_filled() returns trigger as $$
+begin
+    if old.consumed_at is null and new.consumed_at is not null then
+        perform enqueue_webhook_event('rfq.filled', jsonb_build_object(
+            'quote_id', new.id,
+            'market', new.market,
+            'side', new.side,
+            'route', new.route,
+            'maturity_ts', new.maturity_ts,
+            'size_yt', new.size_yt,
+            'price_usdc', new.price_usdc,
+            'implied_apr', new.implied_apr,
+            'wallet', new.consumed_by,
+            'filled_at', new.consumed_at
+        ));
+    end if;
+    return new;
+end;
+$$ language plpgsql;
+
+drop trigger if exists rfq_quotes_webhook on rfq_quotes;
+
+create trigger rfq_quotes_webhook
+    after update of consumed_at on rfq_quotes
+    for each row execute function webhook_rfq_filled();
+
+create or replace function webhook_squads_safe() returns trigger as $$
+begin
+    if new.status in ('ready', 'failed') and new.status is distinct from old.status then
+        perform enqueue_webhook_event('squads_safe.' || new.status::text, jsonb_build_object(
+            'request_id', new.id,
+            'status', new.status,
+            'creator_wallet', new.creator_wallet,
+            'attn_wallet', new.attn_wallet,
+            'cluster', new.cluster,
+            'safe_address', new.safe_address,
+            'transaction_url', new.transaction_url,
+            'error_code', new.error_code,
+            'error_message', new.error_message,
+            'updated_at', new.updated_at
+        ));
+    end if;
+    if old.governance_linked_at is null and new.governance_linked_at is not null then
+        perform enqueue_webhook_event('squads_safe.governance_linked', jsonb_build_object(
+            'request_id', new.id,
+            'creator_wallet', new.creator_wallet,
+            'safe_address', new.safe_address,
+            'creator_vault', new.creator_vault,
+            'linked_at', new.governance_linked_at
+        ));
+    end if;
+    return new;
+end;
+$$ language plpgsql;
+
+drop trigger if exists squads_safe_requests_webhook on squads_safe_requests;
+
+create trigger squads_safe_requests_webhook
+    after update of status, governance_linked_at on squads_safe_requests
+    for each row execute function webhook_squads_safe();
+
+create or replace function webhook_vault_event() returns trigger as $$
+declare
+    kind text;
+begin
+    kind := case
+        when new.kind in ('creator_paused', 'stable_pause', 'rewards_paused') then 'vault.pause_changed'
+        when new.kind = 'creator_lock' then 'vault.lock_changed'
+    end;
+    if kind is not null then
+        perform enqueue_webhook_event(kind, new.payload || jsonb_build_object(
+            'program', new.program,
+            'signature', new.sig,
+            'slot', new.slot
+        ));
+    end if;
+    return new;
+end;
+$$ language plpgsql;
+
+drop trigger if exists events_webhook on events;
+
+create trigger events_webhook
+    after insert on events
+    for each row execute function webhook_vault_event();
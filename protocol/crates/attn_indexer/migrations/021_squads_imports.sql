@@ -0,0 +1,7 @@
+alter table squads_safe_requests
+    add column if not exists import_source text not null default 'attn-api',
+    add column if not exists import_metadata jsonb,
+    add column if not exists imported_at timestamptz;
+
+update squads_safe_requests
+   set import_source = coalesce(import_source, 'attn-api');
@@ -0,0 +1,4 @@
+-- Notifier cursors page by (timestamp, key) so a burst of rows sharing one
+-- timestamp can't pin the cursor in place.
+alter table notifier_cursors
+    add column if not exists last_key text not null default '';
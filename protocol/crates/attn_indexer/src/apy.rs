@@ -0,0 +1,175 @@
+use chrono::{DateTime, Duration, Utc};
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// Trailing window used to measure fee index growth for implied APY.
+pub const IMPLIED_APY_WINDOW_DAYS: i64 = 7;
+/// Shortest span of history an APY is derived from; anything shorter reads 0.
+pub const MIN_SPAN_SECS: i64 = 3_600;
+
+/// An observed value of a monotonic index (market fee index or attnUSD share price).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexPoint {
+    pub observed_at: DateTime<Utc>,
+    pub value: f64,
+}
+
+impl IndexPoint {
+    pub fn new(observed_at: DateTime<Utc>, value: f64) -> Self {
+        Self { observed_at, value }
+    }
+}
+
+/// Picks the point a trailing `window` ending at `current` is measured from:
+/// the newest snapshot at or before the window start, or the oldest snapshot
+/// when history does not reach back that far.
+fn window_base(
+    history: &[IndexPoint],
+    current: &IndexPoint,
+    window: Duration,
+) -> Option<IndexPoint> {
+    let start = current.observed_at - window;
+    let mut points: Vec<&IndexPoint> = history
+        .iter()
+        .filter(|point| point.value.is_finite() && point.observed_at < current.observed_at)
+        .collect();
+    points.sort_by_key(|point| point.observed_at);
+    points
+        .iter()
+        .rev()
+        .find(|point| point.observed_at <= start)
+        .or_else(|| points.first())
+        .map(|point| **point)
+        .filter(|base| (current.observed_at - base.observed_at).num_seconds() >= MIN_SPAN_SECS)
+}
+
+/// Implied APY for a market's YT from its additive fee index (yield per unit
+/// of principal). The trailing accrual rate is projected over the time left
+/// to maturity and that period return is annualised. Matured markets read 0.
+pub fn implied_apy(history: &[IndexPoint], current: IndexPoint, maturity_ts: i64) -> f64 {
+    let seconds_to_maturity = maturity_ts - current.observed_at.timestamp();
+    if seconds_to_maturity <= 0 || !current.value.is_finite() {
+        return 0.0;
+    }
+    let Some(base) = window_base(history, &current, Duration::days(IMPLIED_APY_WINDOW_DAYS)) else {
+        return 0.0;
+    };
+    let elapsed = (current.observed_at - base.observed_at).num_seconds() as f64;
+    let rate_per_sec = ((current.value - base.value) / elapsed).max(0.0);
+    let period_return = rate_per_sec * seconds_to_maturity as f64;
+    let periods_per_year = SECONDS_PER_YEAR / seconds_to_maturity as f64;
+    finite_or_zero((1.0 + period_return).powf(periods_per_year) - 1.0)
+}
+
+/// Trailing APY of a compounding share price over `window`, annualised from
+/// the actual span covered when history is shorter than the window.
+pub fn trailing_apy(history: &[IndexPoint], current: IndexPoint, window: Duration) -> f64 {
+    if !(current.value.is_finite() && current.value > 0.0) {
+        return 0.0;
+    }
+    let Some(base) = window_base(history, &current, window) else {
+        return 0.0;
+    };
+    if base.value <= 0.0 {
+        return 0.0;
+    }
+    let elapsed = (current.observed_at - base.observed_at).num_seconds() as f64;
+    let growth = current.value / base.value;
+    finite_or_zero(growth.powf(SECONDS_PER_YEAR / elapsed) - 1.0)
+}
+
+fn finite_or_zero(value: f64) -> f64 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hourly_series(
+        now: DateTime<Utc>,
+        hours: i64,
+        value_at: impl Fn(f64) -> f64,
+    ) -> Vec<IndexPoint> {
+        (1..=hours)
+            .rev()
+            .map(|hours_ago| {
+                let days = (hours - hours_ago) as f64 / 24.0;
+                IndexPoint::new(now - Duration::hours(hours_ago), value_at(days))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn implied_apy_annualises_linear_index_growth() {
+        let now = Utc::now();
+        // 0.05% of principal per day for 14 days.
+        let history = hourly_series(now, 14 * 24, |days| 0.0005 * days);
+        let current = IndexPoint::new(now, 0.0005 * 14.0);
+
+        let one_year = implied_apy(&history, current, now.timestamp() + 365 * 86_400);
+        assert!((one_year - 0.1825).abs() < 1e-6, "{one_year}");
+
+        let short = implied_apy(&history, current, now.timestamp() + 15 * 86_400);
+        let expected = (1.0 + 0.0005 * 15.0_f64).powf(365.0 / 15.0) - 1.0;
+        assert!((short - expected).abs() < 1e-6, "{short}");
+        assert!(short > one_year);
+    }
+
+    #[test]
+    fn implied_apy_uses_trailing_week_only() {
+        let now = Utc::now();
+        // Fast accrual three weeks ago, then a flat week: APY should be ~0.
+        let history = hourly_series(now, 21 * 24, |days| (days.min(13.0)) * 0.01);
+        let current = IndexPoint::new(now, 0.13);
+        let apy = implied_apy(&history, current, now.timestamp() + 30 * 86_400);
+        assert!(apy.abs() < 1e-9, "{apy}");
+    }
+
+    #[test]
+    fn implied_apy_is_zero_when_matured_or_without_history() {
+        let now = Utc::now();
+        let history = hourly_series(now, 48, |days| 0.001 * days);
+        let current = IndexPoint::new(now, 0.002);
+        assert_eq!(implied_apy(&history, current, now.timestamp() - 1), 0.0);
+        assert_eq!(implied_apy(&[], current, now.timestamp() + 86_400), 0.0);
+        let recent = [IndexPoint::new(now - Duration::minutes(5), 0.0)];
+        assert_eq!(implied_apy(&recent, current, now.timestamp() + 86_400), 0.0);
+    }
+
+    #[test]
+    fn trailing_apy_compounds_share_price() {
+        let now = Utc::now();
+        let daily = 1.10_f64.powf(1.0 / 365.0);
+        let history = hourly_series(now, 40 * 24, |days| daily.powf(days));
+        let current = IndexPoint::new(now, daily.powf(40.0));
+
+        let seven = trailing_apy(&history, current, Duration::days(7));
+        let thirty = trailing_apy(&history, current, Duration::days(30));
+        assert!((seven - 0.10).abs() < 1e-6, "{seven}");
+        assert!((thirty - 0.10).abs() < 1e-6, "{thirty}");
+    }
+
+    #[test]
+    fn trailing_apy_falls_back_to_available_history() {
+        let now = Utc::now();
+        let daily = 1.05_f64.powf(1.0 / 365.0);
+        let history = hourly_series(now, 3 * 24, |days| daily.powf(days));
+        let current = IndexPoint::new(now, daily.powf(3.0));
+        let thirty = trailing_apy(&history, current, Duration::days(30));
+        assert!((thirty - 0.05).abs() < 1e-6, "{thirty}");
+        assert_eq!(trailing_apy(&[], current, Duration::days(7)), 0.0);
+    }
+
+    #[test]
+    fn trailing_apy_reports_losses() {
+        let now = Utc::now();
+        let history = [IndexPoint::new(now - Duration::days(7), 1.0)];
+        let current = IndexPoint::new(now, 0.99);
+        assert!(trailing_apy(&history, current, Duration::days(7)) < 0.0);
+    }
+}
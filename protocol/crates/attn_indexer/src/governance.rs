@@ -0,0 +1,495 @@
+use std::time::Duration as StdDuration;
+
+use anyhow::{anyhow, bail, Result};
+use attn_client::squads::{
+    self, MultisigAccount, ProposalAccount, ProposedAction, VaultTransactionAccount,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::json;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+use sqlx::{PgPool, Row};
+use tracing::{info, warn};
+
+/// Most recent transaction indices re-read per multisig on every poll.
+/// Older stored proposals are re-read too until they reach a final status.
+const PROPOSAL_LOOKBACK: u64 = 20;
+/// Proposal and transaction accounts per `getMultipleAccounts` call.
+const MULTIPLE_ACCOUNTS_CHUNK: usize = 100;
+/// Statuses a proposal never leaves.
+const FINAL_STATUSES: [&str; 3] = ["rejected", "executed", "cancelled"];
+
+/// Polls the Squads proposals of safes linked to a creator vault through
+/// `/v1/squads/safes/:id/governance` and mirrors them, with their decoded
+/// inner instructions, into `governance_proposals`.
+pub struct ProposalTracker {
+    pool: PgPool,
+    rpc: RpcClient,
+    interval: StdDuration,
+}
+
+impl ProposalTracker {
+    pub fn new(pool: PgPool, rpc_url: String, interval: StdDuration) -> Self {
+        let rpc = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+        Self {
+            pool,
+            rpc,
+            interval,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        info!(
+            interval_secs = self.interval.as_secs(),
+            "governance proposal tracker started"
+        );
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            ticker.tick().await;
+            let safes = match linked_safes(&self.pool).await {
+                Ok(safes) => safes,
+                Err(err) => {
+                    warn!(error = ?err, "failed to load linked safes");
+                    continue;
+                }
+            };
+            for (multisig, creator_vault) in safes {
+                if let Err(err) = self
+                    .sync_multisig(&multisig, creator_vault.as_deref())
+                    .await
+                {
+                    warn!(error = ?err, %multisig, "failed to sync governance proposals");
+                }
+            }
+        }
+    }
+
+    async fn sync_multisig(&self, multisig: &str, creator_vault: Option<&str>) -> Result<()> {
+        let address: Pubkey = multisig.parse()?;
+        let account = self.rpc.get_account(&address).await?;
+        if account.owner != squads::SQUADS_PROGRAM_ID {
+            bail!("{multisig} is not owned by the Squads v4 program");
+        }
+        let state = MultisigAccount::from_account_data(&account.data)?;
+        let newest = state.transaction_index;
+        let oldest = newest.saturating_sub(PROPOSAL_LOOKBACK - 1).max(1);
+        let mut indices = open_proposals_before(&self.pool, multisig, oldest).await?;
+        indices.extend(oldest..=newest);
+        if indices.is_empty() {
+            return Ok(());
+        }
+        let addresses: Vec<Pubkey> = indices
+            .iter()
+            .flat_map(|index| {
+                [
+                    squads::proposal_pda(&address, *index).0,
+                    squads::transaction_pda(&address, *index).0,
+                ]
+            })
+            .collect();
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MULTIPLE_ACCOUNTS_CHUNK) {
+            accounts.extend(self.rpc.get_multiple_accounts(chunk).await?);
+        }
+        for (index, pair) in indices.iter().zip(accounts.chunks(2)) {
+            let Some((proposal, actions)) =
+                decode_proposal(multisig, *index, pair[0].as_ref(), pair[1].as_ref())
+            else {
+                continue;
+            };
+            let record = ProposalRecord {
+                multisig,
+                transaction_index: *index,
+                proposal_pubkey: squads::proposal_pda(&address, *index).0.to_string(),
+                creator_vault,
+                proposal: &proposal,
+                stale: *index <= state.stale_transaction_index,
+                threshold: state.threshold,
+                actions: &actions,
+            };
+            upsert_proposal(&self.pool, &record).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the proposal at `index` and describes its vault transaction. A
+/// proposal that cannot be decoded is logged and skipped so one bad account
+/// does not stall the rest of the multisig.
+fn decode_proposal(
+    multisig: &str,
+    index: u64,
+    proposal: Option<&Account>,
+    transaction: Option<&Account>,
+) -> Option<(ProposalAccount, Vec<ProposedAction>)> {
+    let proposal = proposal?;
+    let decoded = if proposal.owner == squads::SQUADS_PROGRAM_ID {
+        ProposalAccount::from_account_data(&proposal.data)
+    } else {
+        Err(anyhow!("proposal is owned by {}", proposal.owner))
+    };
+    let proposal = match decoded {
+        Ok(proposal) => proposal,
+        Err(err) => {
+            warn!(error = ?err, %multisig, index, "failed to decode proposal");
+            return None;
+        }
+    };
+    let actions = match transaction {
+        Some(transaction)
+            if transaction.owner == squads::SQUADS_PROGRAM_ID
+                && VaultTransactionAccount::is_vault_transaction(&transaction.data) =>
+        {
+            VaultTransactionAccount::from_account_data(&transaction.data)
+                .and_then(|transaction| squads::describe_vault_transaction(&transaction))
+                .unwrap_or_else(|err| {
+                    warn!(error = ?err, %multisig, index, "failed to decode vault transaction");
+                    Vec::new()
+                })
+        }
+        _ => Vec::new(),
+    };
+    Some((proposal, actions))
+}
+
+/// Multisig address and linked creator vault of every governance-linked safe.
+async fn linked_safes(pool: &PgPool) -> Result<Vec<(String, Option<String>)>> {
+    let rows = sqlx::query(
+        r#"
+        select distinct safe_address, creator_vault
+        from squads_safe_requests
+        where governance_linked_at is not null
+          and safe_address is not null
+          and safe_address <> ''
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("safe_address"), row.get("creator_vault")))
+        .collect())
+}
+
+/// Stored proposals of `multisig` below `index` that can still change
+/// status, oldest first.
+async fn open_proposals_before(pool: &PgPool, multisig: &str, index: u64) -> Result<Vec<u64>> {
+    let rows = sqlx::query(
+        r#"
+        select transaction_index
+        from governance_proposals
+        where multisig = $1
+          and transaction_index < $2
+          and status <> all($3)
+        order by transaction_index asc
+        "#,
+    )
+    .bind(multisig)
+    .bind(index as i64)
+    .bind(&FINAL_STATUSES[..])
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| row.get::<i64, _>("transaction_index") as u64)
+        .collect())
+}
+
+struct ProposalRecord<'a> {
+    multisig: &'a str,
+    transaction_index: u64,
+    proposal_pubkey: String,
+    creator_vault: Option<&'a str>,
+    proposal: &'a ProposalAccount,
+    stale: bool,
+    threshold: u16,
+    actions: &'a [ProposedAction],
+}
+
+fn status_at(status: &squads::ProposalStatus) -> Option<DateTime<Utc>> {
+    use squads::ProposalStatus::*;
+    let timestamp = match *status {
+        Draft { timestamp }
+        | Active { timestamp }
+        | Rejected { timestamp }
+        | Approved { timestamp }
+        | Executed { timestamp }
+        | Cancelled { timestamp } => timestamp,
+        Executing => return None,
+    };
+    Utc.timestamp_opt(timestamp, 0).single()
+}
+
+/// Inserts or refreshes a proposal; `updated_at` only moves when the status,
+/// votes or staleness change.
+async fn upsert_proposal(pool: &PgPool, record: &ProposalRecord<'_>) -> Result<()> {
+    let keys = |keys: &[Pubkey]| json!(keys.iter().map(|key| key.to_string()).collect::<Vec<_>>());
+    sqlx::query(
+        r#"
+        insert into governance_proposals (
+            multisig, transaction_index, proposal_pubkey, creator_vault, status, status_at,
+            stale, threshold, approved, rejected, actions
+        )
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        on conflict (multisig, transaction_index) do update set
+            proposal_pubkey = excluded.proposal_pubkey,
+            creator_vault = excluded.creator_vault,
+            status = excluded.status,
+            status_at = excluded.status_at,
+            stale = excluded.stale,
+            threshold = excluded.threshold,
+            approved = excluded.approved,
+            rejected = excluded.rejected,
+            actions = excluded.actions,
+            updated_at = now()
+        where governance_proposals.status is distinct from excluded.status
+           or governance_proposals.stale is distinct from excluded.stale
+           or governance_proposals.approved is distinct from excluded.approved
+           or governance_proposals.rejected is distinct from excluded.rejected
+           or governance_proposals.actions is distinct from excluded.actions
+           or governance_proposals.creator_vault is distinct from excluded.creator_vault
+        "#,
+    )
+    .bind(record.multisig)
+    .bind(record.transaction_index as i64)
+    .bind(&record.proposal_pubkey)
+    .bind(record.creator_vault)
+    .bind(record.proposal.status.as_str())
+    .bind(status_at(&record.proposal.status))
+    .bind(record.stale)
+    .bind(i32::from(record.threshold))
+    .bind(keys(&record.proposal.approved))
+    .bind(keys(&record.proposal.rejected))
+    .bind(json!(record.actions))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use attn_client::{creator, rewards};
+    use solana_sdk::{instruction::Instruction, message::Message, signer::Signer};
+
+    const MULTISIG: Pubkey = Pubkey::new_from_array([4; 32]);
+    const MEMBER: Pubkey = Pubkey::new_from_array([1; 32]);
+
+    fn squads_account(data: Vec<u8>) -> Account {
+        Account {
+            lamports: 1,
+            data,
+            owner: squads::SQUADS_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    /// `Proposal` account bytes approved by `MEMBER`.
+    fn proposal_bytes(transaction_index: u64) -> Vec<u8> {
+        let mut data = vec![26, 94, 189, 187, 116, 136, 53, 33];
+        data.extend_from_slice(MULTISIG.as_ref());
+        data.extend_from_slice(&transaction_index.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.push(255);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(MEMBER.as_ref());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data
+    }
+
+    /// `VaultTransaction` account bytes wrapping `instructions` signed by `vault`.
+    fn vault_transaction_bytes(vault: &Pubkey, instructions: &[Instruction]) -> Vec<u8> {
+        let message = Message::new(instructions, Some(vault));
+        let header = message.header;
+        let mut data = vec![168, 250, 162, 100, 81, 14, 162, 207];
+        data.extend_from_slice(MULTISIG.as_ref());
+        data.extend_from_slice(MEMBER.as_ref());
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&[255, 0, 254]);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.push(header.num_required_signatures);
+        data.push(header.num_required_signatures - header.num_readonly_signed_accounts);
+        data.push(
+            (message.account_keys.len()
+                - usize::from(header.num_required_signatures)
+                - usize::from(header.num_readonly_unsigned_accounts)) as u8,
+        );
+        data.extend_from_slice(&(message.account_keys.len() as u32).to_le_bytes());
+        for key in &message.account_keys {
+            data.extend_from_slice(key.as_ref());
+        }
+        data.extend_from_slice(&(message.instructions.len() as u32).to_le_bytes());
+        for ix in &message.instructions {
+            data.push(ix.program_id_index);
+            data.extend_from_slice(&(ix.accounts.len() as u32).to_le_bytes());
+            data.extend_from_slice(&ix.accounts);
+            data.extend_from_slice(&(ix.data.len() as u32).to_le_bytes());
+            data.extend_from_slice(&ix.data);
+        }
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn decodes_proposal_and_describes_its_vault_transaction() {
+        let vault = squads::vault_pda(&MULTISIG, 0).0;
+        let creator_vault = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let transaction = vault_transaction_bytes(
+            &vault,
+            &[
+                creator::build_set_pause_ix(creator_vault, vault, true),
+                rewards::build_update_reward_bps_ix(pool, vault, 250),
+            ],
+        );
+
+        let decoded = VaultTransactionAccount::from_account_data(&transaction).unwrap();
+        assert_eq!(decoded.multisig, MULTISIG);
+        let summaries: Vec<_> = squads::describe_vault_transaction(&decoded)
+            .unwrap()
+            .into_iter()
+            .map(|action| action.summary)
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                format!("set_pause(true) on creator vault {creator_vault}"),
+                format!("update_reward_bps(250) on rewards pool {pool}"),
+            ]
+        );
+
+        let (proposal, actions) = decode_proposal(
+            "multisig",
+            7,
+            Some(&squads_account(proposal_bytes(7))),
+            Some(&squads_account(transaction)),
+        )
+        .unwrap();
+        assert_eq!(proposal.transaction_index, 7);
+        assert_eq!(proposal.status.as_str(), "active");
+        assert_eq!(proposal.approved, vec![MEMBER]);
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].instruction, "set_pause");
+    }
+
+    #[test]
+    fn undecodable_proposals_are_skipped() {
+        let proposal = squads_account(proposal_bytes(3));
+        let mut truncated = proposal.clone();
+        truncated.data.truncate(20);
+        assert!(decode_proposal("multisig", 3, Some(&truncated), None).is_none());
+
+        let mut foreign = proposal.clone();
+        foreign.owner = Pubkey::new_unique();
+        assert!(decode_proposal("multisig", 3, Some(&foreign), None).is_none());
+        assert!(decode_proposal("multisig", 3, None, None).is_none());
+
+        // A config transaction at the transaction PDA has no actions, and a
+        // corrupt vault transaction still leaves the proposal tracked.
+        let config_transaction = squads_account(vec![0; 64]);
+        let (_, actions) =
+            decode_proposal("multisig", 3, Some(&proposal), Some(&config_transaction)).unwrap();
+        assert!(actions.is_empty());
+        let mut corrupt = vault_transaction_bytes(
+            &squads::vault_pda(&MULTISIG, 0).0,
+            &[creator::build_set_pause_ix(
+                Pubkey::new_unique(),
+                MEMBER,
+                true,
+            )],
+        );
+        corrupt.truncate(100);
+        let (_, actions) = decode_proposal(
+            "multisig",
+            3,
+            Some(&proposal),
+            Some(&squads_account(corrupt)),
+        )
+        .unwrap();
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn admin_instructions_are_described() {
+        let vault = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let action = squads::describe_instruction(&creator::build_set_pause_ix(vault, admin, true));
+        assert_eq!(action.program, "creator_vault");
+        assert_eq!(action.instruction, "set_pause");
+        assert_eq!(action.args, json!({ "paused": true }));
+        assert_eq!(
+            action.summary,
+            format!("set_pause(true) on creator vault {vault}")
+        );
+
+        let pool = Pubkey::new_unique();
+        let action =
+            squads::describe_instruction(&rewards::build_update_reward_bps_ix(pool, admin, 250));
+        assert_eq!(
+            action.summary,
+            format!("update_reward_bps(250) on rewards pool {pool}")
+        );
+
+        let mut unknown = creator::build_set_pause_ix(vault, admin, false);
+        unknown.data = vec![0; 8];
+        let action = squads::describe_instruction(&unknown);
+        assert_eq!(action.instruction, "unknown");
+    }
+
+    /// Pool from `ATTN_INDEXER_TEST_DATABASE_URL`, or `None` when no test
+    /// database is configured and the test should be skipped.
+    async fn test_pool() -> Option<PgPool> {
+        let url = std::env::var("ATTN_INDEXER_TEST_DATABASE_URL").ok()?;
+        let pool = crate::store::connect_pool(&url, 2)
+            .await
+            .expect("connect test database");
+        crate::store::run_migrations(&pool)
+            .await
+            .expect("run migrations");
+        Some(pool)
+    }
+
+    #[tokio::test]
+    async fn open_proposals_outside_the_lookback_are_repolled() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        // Random key: the test database outlives a single run.
+        let multisig = solana_sdk::signature::Keypair::new().pubkey().to_string();
+        for (index, status) in [
+            (1, "active"),
+            (2, "executed"),
+            (3, "approved"),
+            (4, "cancelled"),
+            (5, "rejected"),
+            (6, "executing"),
+            (40, "active"),
+        ] {
+            sqlx::query(
+                "insert into governance_proposals
+                     (multisig, transaction_index, proposal_pubkey, status, threshold)
+                 values ($1, $2, '', $3, 1)",
+            )
+            .bind(&multisig)
+            .bind(index)
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let open = open_proposals_before(&pool, &multisig, 21).await.unwrap();
+        assert_eq!(open, vec![1, 3, 6]);
+    }
+
+    #[test]
+    fn executing_proposals_have_no_status_timestamp() {
+        assert!(status_at(&squads::ProposalStatus::Executing).is_none());
+        let approved = squads::ProposalStatus::Approved {
+            timestamp: 1_700_000_000,
+        };
+        assert_eq!(status_at(&approved).unwrap().timestamp(), 1_700_000_000);
+    }
+}
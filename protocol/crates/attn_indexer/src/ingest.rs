@@ -13,6 +13,8 @@ use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
 use sqlx::{PgPool, Row};
 use tracing::{info, warn};
 
+use crate::stream::{self, StreamUpdate, Topic};
+
 const SOL_INDEX_SCALE: f64 = 1_000_000_000_f64;
 
 #[derive(Debug, Clone)]
@@ -95,162 +97,145 @@ async fn handle_logs(
 
     let mut processed = false;
     for event in parse_anchor_events(&response) {
-        match event.name.as_str() {
+        let persisted = match event.name.as_str() {
             "FeeCollected" => {
                 let data: FeeCollectedEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_fee_collected(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_fee_collected(pool.clone(), event.signature.clone(), slot, data).await?
             }
             "VaultInitialized" => {
                 let data: VaultInitializedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_creator_vault_initialized(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_creator_vault_initialized(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "RewardsSplitUpdated" => {
                 let data: RewardsSplitUpdatedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_creator_rewards_split(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_creator_rewards_split(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "AdminUpdated" => {
                 let data: CreatorAdminUpdatedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_creator_admin_updated(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_creator_admin_updated(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "VaultPauseToggled" => {
                 let data: VaultPauseToggledEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_creator_pause(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_creator_pause(pool.clone(), event.signature.clone(), slot, data).await?
+            }
+            "VaultLockStatusChanged" => {
+                let data: VaultLockStatusChangedEvent = serde_json::from_value(event.data.clone())?;
+                persist_creator_lock(pool.clone(), event.signature.clone(), slot, data).await?
             }
             "RewardsPoolInitialized" => {
                 let data: RewardsPoolInitializedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_rewards_pool_initialized(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_rewards_pool_initialized(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "RewardsFunded" => {
                 let data: RewardsFundedEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_rewards_funded(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_rewards_funded(pool.clone(), event.signature.clone(), slot, data).await?
             }
             "Staked" => {
                 let data: RewardsStakeEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_rewards_staked(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_rewards_staked(pool.clone(), event.signature.clone(), slot, data).await?
             }
             "Unstaked" => {
                 let data: RewardsStakeEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_rewards_unstaked(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_rewards_unstaked(pool.clone(), event.signature.clone(), slot, data).await?
             }
             "RewardsClaimed" => {
                 let data: RewardsClaimedEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_rewards_claimed(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_rewards_claimed(pool.clone(), event.signature.clone(), slot, data).await?
             }
             "AllowedFunderUpdated" => {
                 let data: AllowedFunderUpdatedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_rewards_allowed_funder(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_rewards_allowed_funder(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "RewardBpsUpdated" => {
                 let data: RewardBpsUpdatedEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_rewards_reward_bps(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_rewards_reward_bps(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "RewardsAdminUpdated" => {
                 let data: RewardsAdminUpdatedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_rewards_admin_updated(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_rewards_admin_updated(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "RewardsPoolPaused" => {
                 let data: RewardsPoolPausedEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_rewards_paused(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_rewards_paused(pool.clone(), event.signature.clone(), slot, data).await?
             }
             "StableVaultInitialized" => {
                 let data: StableVaultInitializedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_stable_vault_initialized(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_stable_vault_initialized(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "StableVaultAdminUpdated" => {
                 let data: StableVaultAdminUpdatedEvent =
                     serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_stable_admin_updated(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_stable_admin_updated(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "KeeperAuthorityUpdated" => {
                 let data: KeeperAuthorityUpdatedEvent = serde_json::from_value(event.data.clone())?;
-                processed |= persist_keeper_authority_updated(
-                    pool.clone(),
-                    event.signature.clone(),
-                    slot,
-                    data,
-                )
-                .await?;
+                persist_keeper_authority_updated(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "StableVaultPauseToggled" => {
                 let data: StableVaultPauseToggledEvent =
                     serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_stable_pause_toggled(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_stable_pause_toggled(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "CreatorFeesSwept" => {
                 let data: CreatorFeesSweptEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_creator_fees_swept(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_creator_fees_swept(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
             "ConversionProcessed" => {
                 let data: ConversionProcessedEvent = serde_json::from_value(event.data.clone())?;
-                processed |=
-                    persist_conversion_processed(pool.clone(), event.signature.clone(), slot, data)
-                        .await?;
+                persist_conversion_processed(pool.clone(), event.signature.clone(), slot, data)
+                    .await?
             }
-            _ => {}
+            "AttnUsdMinted" => {
+                let data: AttnUsdMintedEvent = serde_json::from_value(event.data.clone())?;
+                persist_attnusd_share_price(
+                    pool.clone(),
+                    event.signature.clone(),
+                    slot,
+                    "attnusd_minted",
+                    json!({
+                        "user": data.user,
+                        "deposited_amount": data.deposited_amount,
+                        "minted_shares": data.minted_shares,
+                        "price_per_share": data.price_per_share,
+                    }),
+                    data.price_per_share,
+                )
+                .await?
+            }
+            "AttnUsdRedeemed" => {
+                let data: AttnUsdRedeemedEvent = serde_json::from_value(event.data.clone())?;
+                persist_attnusd_share_price(
+                    pool.clone(),
+                    event.signature.clone(),
+                    slot,
+                    "attnusd_redeemed",
+                    json!({
+                        "user": data.user,
+                        "redeemed_shares": data.redeemed_shares,
+                        "returned_amount": data.returned_amount,
+                        "price_per_share": data.price_per_share,
+                    }),
+                    data.price_per_share,
+                )
+                .await?
+            }
+            _ => false,
+        };
+        if persisted {
+            processed = true;
+            publish_event_updates(pool.as_ref(), &event).await;
         }
     }
 
@@ -262,6 +247,102 @@ async fn handle_logs(
     Ok(())
 }
 
+/// Fans a persisted event out to the live stream topics it affects. Failures
+/// are logged only; the event itself is already durable in `events`.
+async fn publish_event_updates(pool: &PgPool, event: &AnchorEvent) {
+    let topics = match event_topics(pool, event).await {
+        Ok(topics) => topics,
+        Err(err) => {
+            warn!(error = ?err, event = event.name, "failed to resolve stream topics");
+            return;
+        }
+    };
+    for topic in topics {
+        let update = StreamUpdate {
+            topic,
+            kind: event.name.clone(),
+            slot: Some(event.slot),
+            signature: Some(event.signature.clone()),
+            data: event.data.clone(),
+        };
+        if let Err(err) = stream::publish(pool, &update).await {
+            warn!(error = ?err, topic = %update.topic, "failed to publish stream update");
+        }
+    }
+}
+
+async fn event_topics(pool: &PgPool, event: &AnchorEvent) -> Result<Vec<Topic>> {
+    let field = |name: &str| {
+        event
+            .data
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let mut topics = Vec::new();
+    match event.name.as_str() {
+        "FeeCollected" => {
+            if let Some(pump_mint) = field("pump_mint") {
+                topics.extend(markets_where(pool, "pump_mint = $1", &pump_mint).await?);
+            }
+        }
+        "VaultInitialized"
+        | "RewardsSplitUpdated"
+        | "AdminUpdated"
+        | "VaultPauseToggled"
+        | "VaultLockStatusChanged" => {
+            if let Some(vault) = field("creator_vault") {
+                topics.extend(
+                    markets_where(
+                        pool,
+                        "pump_mint in (select pump_mint from creator_vaults where vault_pubkey = $1)",
+                        &vault,
+                    )
+                    .await?,
+                );
+            }
+        }
+        "RewardsPoolInitialized"
+        | "RewardsFunded"
+        | "AllowedFunderUpdated"
+        | "RewardBpsUpdated"
+        | "RewardsAdminUpdated"
+        | "RewardsPoolPaused" => {
+            topics.extend(field("pool").map(Topic::RewardsPool));
+        }
+        "Staked" | "Unstaked" | "RewardsClaimed" => {
+            topics.extend(field("pool").map(Topic::RewardsPool));
+            topics.extend(field("user").map(Topic::Portfolio));
+        }
+        "StableVaultInitialized"
+        | "StableVaultAdminUpdated"
+        | "KeeperAuthorityUpdated"
+        | "StableVaultPauseToggled"
+        | "CreatorFeesSwept"
+        | "ConversionProcessed" => {
+            topics.push(Topic::AttnUsd);
+        }
+        "AttnUsdMinted" | "AttnUsdRedeemed" => {
+            topics.push(Topic::AttnUsd);
+            topics.extend(field("user").map(Topic::Portfolio));
+        }
+        _ => {}
+    }
+    Ok(topics)
+}
+
+async fn markets_where(pool: &PgPool, predicate: &str, value: &str) -> Result<Vec<Topic>> {
+    let rows = sqlx::query(&format!(
+        "select market_pubkey from markets where {predicate}"
+    ))
+    .bind(value)
+    .fetch_all(pool)
+    .await?;
+    rows.iter()
+        .map(|row| Ok(Topic::Market(row.try_get("market_pubkey")?)))
+        .collect()
+}
+
 #[derive(Debug)]
 struct AnchorEvent {
     signature: String,
@@ -416,6 +497,14 @@ struct VaultPauseToggledEvent {
     pub paused: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct VaultLockStatusChangedEvent {
+    pub creator_vault: String,
+    pub locked: bool,
+    pub lock_expires_at: i64,
+    pub is_auto: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct AllowedFunderUpdatedEvent {
     pub pool: String,
@@ -485,6 +574,22 @@ struct ConversionProcessedEvent {
     pub operation_id: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+struct AttnUsdMintedEvent {
+    pub user: String,
+    pub deposited_amount: u64,
+    pub minted_shares: u64,
+    pub price_per_share: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AttnUsdRedeemedEvent {
+    pub user: String,
+    pub redeemed_shares: u64,
+    pub returned_amount: u64,
+    pub price_per_share: u64,
+}
+
 fn decode_sol_index(value: &str) -> Result<f64> {
     let raw = u128::from_str(value)?;
     Ok(raw as f64 / SOL_INDEX_SCALE)
@@ -1068,6 +1173,35 @@ async fn persist_creator_pause(
     Ok(true)
 }
 
+async fn persist_creator_lock(
+    pool: Arc<PgPool>,
+    signature: String,
+    slot: u64,
+    event: VaultLockStatusChangedEvent,
+) -> Result<bool> {
+    let insert_result = sqlx::query(
+        r#"
+        insert into events (sig, slot, program, kind, payload)
+        values ($1, $2, $3, $4, $5)
+        on conflict (sig) do nothing
+        "#,
+    )
+    .bind(&signature)
+    .bind(slot as i64)
+    .bind("creator_vault")
+    .bind("creator_lock")
+    .bind(json!({
+        "creator_vault": event.creator_vault,
+        "locked": event.locked,
+        "lock_expires_at": event.lock_expires_at,
+        "is_auto": event.is_auto,
+    }))
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(insert_result.rows_affected() > 0)
+}
+
 async fn persist_rewards_allowed_funder(
     pool: Arc<PgPool>,
     signature: String,
@@ -1506,3 +1640,45 @@ async fn persist_conversion_processed(
     )
     .await
 }
+
+async fn persist_attnusd_share_price(
+    pool: Arc<PgPool>,
+    signature: String,
+    slot: u64,
+    kind: &'static str,
+    payload: Value,
+    price_per_share: u64,
+) -> Result<bool> {
+    let insert_result = sqlx::query(
+        r#"
+        insert into events (sig, slot, program, kind, payload)
+        values ($1, $2, $3, $4, $5)
+        on conflict (sig) do nothing
+        "#,
+    )
+    .bind(&signature)
+    .bind(slot as i64)
+    .bind("stable_vault")
+    .bind(kind)
+    .bind(payload)
+    .execute(pool.as_ref())
+    .await?;
+
+    if insert_result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        r#"
+        insert into attnusd_stats (id, share_index, updated_at)
+        values (true, $1, now())
+        on conflict (id)
+        do update set share_index = EXCLUDED.share_index, updated_at = now()
+        "#,
+    )
+    .bind(price_per_share as f64 / SOL_INDEX_SCALE)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(true)
+}
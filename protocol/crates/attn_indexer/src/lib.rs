@@ -1,6 +1,11 @@
+pub mod apy;
+pub mod governance;
 pub mod ingest;
 pub mod models;
+pub mod snapshots;
 pub mod store;
+pub mod stream;
+pub mod watchdog;
 
 pub use models::*;
 pub use store::{
@@ -1,11 +1,16 @@
 use std::env;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use attn_indexer::{
     connect_pool,
+    governance::ProposalTracker,
     ingest::{LogIngestor, LogIngestorConfig},
-    mock_store, run_migrations, DynStore, SqlxStore,
+    mock_store, run_migrations,
+    snapshots::{snapshot_interval_from_env, IndexSnapshotter},
+    watchdog::FeeWatchdog,
+    DynStore, SqlxStore,
 };
 use solana_sdk::pubkey::Pubkey;
 use sqlx::PgPool;
@@ -23,6 +28,29 @@ async fn main() -> Result<()> {
         markets = overview.total_markets
     );
     if let Some(pool) = pool {
+        let snapshotter = IndexSnapshotter::new(pool.clone(), snapshot_interval_from_env());
+        tokio::spawn(async move {
+            if let Err(err) = snapshotter.run().await {
+                error!(error = ?err, "index snapshotter terminated with error");
+            }
+        });
+        if let Ok(rpc_url) = env::var("ATTN_INDEXER_RPC_URL") {
+            let tracker =
+                ProposalTracker::new(pool.clone(), rpc_url.clone(), parse_proposal_interval());
+            tokio::spawn(async move {
+                if let Err(err) = tracker.run().await {
+                    error!(error = ?err, "governance proposal tracker terminated with error");
+                }
+            });
+            let watchdog = FeeWatchdog::new(pool.clone(), rpc_url, parse_watchdog_interval());
+            tokio::spawn(async move {
+                if let Err(err) = watchdog.run().await {
+                    error!(error = ?err, "fee watchdog terminated with error");
+                }
+            });
+        } else {
+            warn!("ATTN_INDEXER_RPC_URL not set; skipping governance proposal tracking and fee watchdog");
+        }
         if let Some(ws_url) = env::var("ATTN_INDEXER_WS_URL").ok() {
             if let Some(programs) = parse_programs_env() {
                 let cfg = LogIngestorConfig {
@@ -94,6 +122,24 @@ fn parse_programs_env() -> Option<Vec<Pubkey>> {
     }
 }
 
+fn parse_proposal_interval() -> Duration {
+    let secs = env::var("ATTN_INDEXER_PROPOSAL_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(30)
+        .max(5);
+    Duration::from_secs(secs)
+}
+
+fn parse_watchdog_interval() -> Duration {
+    let secs = env::var("ATTN_INDEXER_WATCHDOG_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(300)
+        .max(30);
+    Duration::from_secs(secs)
+}
+
 fn parse_from_slot_arg() -> Option<u64> {
     let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
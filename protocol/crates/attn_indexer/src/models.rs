@@ -1,4 +1,8 @@
-use chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+use attn_client::squads::ProposedAction;
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -46,6 +50,12 @@ pub struct MarketDetail {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeHistoryPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub fees_sol: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PortfolioPosition {
     pub market: String,
@@ -69,6 +79,8 @@ pub struct AttnUsdStats {
     pub nav_sol: f64,
     pub price_per_share: f64,
     pub seven_day_apy: f64,
+    #[serde(default)]
+    pub thirty_day_apy: f64,
     pub last_rebalance_slot: u64,
     pub updated_at: DateTime<Utc>,
 }
@@ -163,3 +175,222 @@ pub struct GovernanceState {
     pub rewards_pools: Vec<RewardsGovernance>,
     pub stable_vault: Option<StableVaultGovernance>,
 }
+
+/// Squads proposal statuses as stored in `governance_proposals.status`.
+pub const PROPOSAL_STATUSES: [&str; 7] = [
+    "draft",
+    "active",
+    "rejected",
+    "approved",
+    "executing",
+    "executed",
+    "cancelled",
+];
+
+/// A Squads proposal on a safe linked to a creator vault via governance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GovernanceProposal {
+    pub multisig: String,
+    pub transaction_index: u64,
+    pub proposal: String,
+    pub creator_vault: Option<String>,
+    pub status: String,
+    /// When the proposal entered `status`; `None` for `executing`.
+    pub status_at: Option<DateTime<Utc>>,
+    /// Invalidated by a multisig config change; can no longer execute.
+    pub stale: bool,
+    pub threshold: u16,
+    pub approvals: u32,
+    pub rejections: u32,
+    pub approved_by: Vec<String>,
+    pub rejected_by: Vec<String>,
+    pub actions: Vec<ProposedAction>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProposalFilter {
+    pub creator_vault: Option<String>,
+    pub multisig: Option<String>,
+    pub status: Option<String>,
+    pub limit: u16,
+}
+
+/// Alert severities in ascending order, as stored in `alerts.severity`.
+pub const ALERT_SEVERITIES: [&str; 3] = ["info", "warning", "critical"];
+
+/// Alert kinds raised by the fee watchdog, as stored in `alerts.kind`.
+pub const ALERT_KINDS: [&str; 2] = ["fee_drop", "fee_recipient_rerouted"];
+
+/// A fee-reroute watchdog alert for a creator vault.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Alert {
+    pub id: i64,
+    pub creator_vault: String,
+    pub kind: String,
+    pub severity: String,
+    /// Raised against a locked vault with an active advance.
+    pub priority: bool,
+    pub message: String,
+    pub details: serde_json::Value,
+    pub opened_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AlertFilter {
+    pub creator_vault: Option<String>,
+    pub kind: Option<String>,
+    /// Minimum severity to include.
+    pub severity: Option<String>,
+    /// Include resolved alerts as well as open ones.
+    pub include_resolved: bool,
+    pub limit: u16,
+}
+
+/// Bucket width for historical series.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HistoryInterval {
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "4h")]
+    FourHours,
+    #[serde(rename = "1d")]
+    OneDay,
+}
+
+impl HistoryInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryInterval::FiveMinutes => "5m",
+            HistoryInterval::FifteenMinutes => "15m",
+            HistoryInterval::OneHour => "1h",
+            HistoryInterval::FourHours => "4h",
+            HistoryInterval::OneDay => "1d",
+        }
+    }
+
+    pub fn seconds(&self) -> i64 {
+        match self {
+            HistoryInterval::FiveMinutes => 300,
+            HistoryInterval::FifteenMinutes => 900,
+            HistoryInterval::OneHour => 3_600,
+            HistoryInterval::FourHours => 14_400,
+            HistoryInterval::OneDay => 86_400,
+        }
+    }
+
+    /// Start of the bucket containing `ts`, aligned to the unix epoch.
+    pub fn bucket_start(&self, ts: DateTime<Utc>) -> DateTime<Utc> {
+        let secs = ts.timestamp().div_euclid(self.seconds()) * self.seconds();
+        Utc.timestamp_opt(secs, 0).single().unwrap_or(ts)
+    }
+}
+
+impl fmt::Display for HistoryInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HistoryInterval {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "5m" => Ok(HistoryInterval::FiveMinutes),
+            "15m" => Ok(HistoryInterval::FifteenMinutes),
+            "1h" => Ok(HistoryInterval::OneHour),
+            "4h" => Ok(HistoryInterval::FourHours),
+            "1d" => Ok(HistoryInterval::OneDay),
+            other => Err(format!("unsupported interval: {other}")),
+        }
+    }
+}
+
+/// Half-open time window `[from, to)` split into `interval` buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryRange {
+    pub interval: HistoryInterval,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+impl HistoryRange {
+    pub fn contains(&self, ts: DateTime<Utc>) -> bool {
+        ts >= self.from && ts < self.to
+    }
+
+    pub fn bucket_count(&self) -> i64 {
+        let span = (self.to - self.from).num_seconds().max(0);
+        (span + self.interval.seconds() - 1) / self.interval.seconds()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Ohlc {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Ohlc {
+    pub fn point(value: f64) -> Self {
+        Self {
+            open: value,
+            high: value,
+            low: value,
+            close: value,
+        }
+    }
+
+    /// Combines two consecutive candles; `later` must follow `self` in time.
+    pub fn merge(self, later: Ohlc) -> Self {
+        Self {
+            open: self.open,
+            high: self.high.max(later.high),
+            low: self.low.min(later.low),
+            close: later.close,
+        }
+    }
+}
+
+/// A bucketed series. Buckets without any observation are omitted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct History<T> {
+    pub interval: HistoryInterval,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub points: Vec<T>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketHistoryPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub fee_index: Option<Ohlc>,
+    pub pt_supply: Option<Ohlc>,
+    pub yt_supply: Option<Ohlc>,
+    pub tvl_sol: Option<Ohlc>,
+    pub fees_sol: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttnUsdHistoryPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub price_per_share: Option<Ohlc>,
+    pub total_supply: Option<Ohlc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RewardsPoolHistoryPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub sol_per_share: Option<Ohlc>,
+    pub total_staked_attnusd: Option<Ohlc>,
+    pub rewards_funded_sol: f64,
+}
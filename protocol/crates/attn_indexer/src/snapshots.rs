@@ -0,0 +1,79 @@
+use std::env;
+use std::time::Duration as StdDuration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+/// Default snapshot cadence. History series are built from the snapshot
+/// tables, so this matches the finest history bucket (`5m`).
+pub const DEFAULT_SNAPSHOT_INTERVAL_SECS: u64 = 300;
+
+/// Snapshot cadence from `ATTN_INDEXER_SNAPSHOT_INTERVAL_SECS`, at least 60s.
+pub fn snapshot_interval_from_env() -> StdDuration {
+    let secs = env::var("ATTN_INDEXER_SNAPSHOT_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL_SECS)
+        .max(60);
+    StdDuration::from_secs(secs)
+}
+
+/// Periodically copies the current market fee indices and supplies and the
+/// attnUSD share index into the snapshot tables that APY calculations and
+/// history series read from.
+pub struct IndexSnapshotter {
+    pool: PgPool,
+    interval: StdDuration,
+}
+
+impl IndexSnapshotter {
+    pub fn new(pool: PgPool, interval: StdDuration) -> Self {
+        Self { pool, interval }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        info!(
+            interval_secs = self.interval.as_secs(),
+            "index snapshotter started"
+        );
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = snapshot_indices(&self.pool, Utc::now()).await {
+                warn!(error = ?err, "failed to snapshot indices");
+            }
+        }
+    }
+}
+
+/// Records one snapshot per market and one for attnUSD at `observed_at`.
+pub async fn snapshot_indices(pool: &PgPool, observed_at: DateTime<Utc>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        insert into market_index_snapshots (market_pubkey, observed_at, fee_index, pt_supply, yt_supply)
+        select market_pubkey, $1, fee_index, pt_supply, yt_supply
+        from markets
+        on conflict (market_pubkey, observed_at) do nothing
+        "#,
+    )
+    .bind(observed_at)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        r#"
+        insert into attnusd_share_snapshots (observed_at, share_index, total_supply)
+        select $1, share_index, total_supply
+        from attnusd_stats
+        limit 1
+        on conflict (observed_at) do nothing
+        "#,
+    )
+    .bind(observed_at)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
@@ -1,17 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, DurationRound, Utc};
 use serde_json::Value;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgPoolOptions, PgRow};
 use sqlx::{PgPool, Row};
 
+use attn_client::squads::ProposedAction;
+
+use crate::apy::{self, IndexPoint, IMPLIED_APY_WINDOW_DAYS};
 use crate::models::{
-    AttnUsdStats, CreatorGovernance, GovernanceState, MarketDetail, MarketStatus, MarketSummary,
-    Overview, Portfolio, RewardEvent, RewardEventKind, RewardsGovernance, RewardsPoolDetail,
-    RewardsPoolSummary, StableVaultGovernance,
+    Alert, AlertFilter, AttnUsdHistoryPoint, AttnUsdStats, CreatorGovernance, FeeHistoryPoint,
+    GovernanceProposal, GovernanceState, History, HistoryRange, MarketDetail, MarketHistoryPoint,
+    MarketStatus, MarketSummary, Ohlc, Overview, Portfolio, ProposalFilter, RewardEvent,
+    RewardEventKind, RewardsGovernance, RewardsPoolDetail, RewardsPoolHistoryPoint,
+    RewardsPoolSummary, StableVaultGovernance, ALERT_SEVERITIES,
 };
 
 #[async_trait]
@@ -19,11 +24,34 @@ pub trait ReadStore: Send + Sync {
     async fn overview(&self) -> Result<Overview>;
     async fn markets(&self) -> Result<Vec<MarketSummary>>;
     async fn market(&self, market: &str) -> Result<Option<MarketDetail>>;
+    /// Daily fee totals for the creator vault backing `market`, oldest first.
+    /// Days without fees are omitted.
+    async fn fee_history(&self, market: &str, since: DateTime<Utc>)
+        -> Result<Vec<FeeHistoryPoint>>;
+    /// Bucketed fee index, supply, TVL and fee series for `market`; `None`
+    /// when the market is unknown.
+    async fn market_history(
+        &self,
+        market: &str,
+        range: &HistoryRange,
+    ) -> Result<Option<History<MarketHistoryPoint>>>;
     async fn portfolio(&self, wallet: &str) -> Result<Option<Portfolio>>;
     async fn attnusd(&self) -> Result<AttnUsdStats>;
+    async fn attnusd_history(&self, range: &HistoryRange) -> Result<History<AttnUsdHistoryPoint>>;
     async fn rewards(&self, cursor: Option<String>, limit: u16) -> Result<RewardsPage>;
     async fn rewards_pool(&self, pool: &str) -> Result<Option<RewardsPoolDetail>>;
+    async fn rewards_pool_history(
+        &self,
+        pool: &str,
+        range: &HistoryRange,
+    ) -> Result<Option<History<RewardsPoolHistoryPoint>>>;
     async fn governance(&self) -> Result<GovernanceState>;
+    /// Tracked Squads proposals, most recently updated first.
+    async fn governance_proposals(
+        &self,
+        filter: &ProposalFilter,
+    ) -> Result<Vec<GovernanceProposal>>;
+    async fn alerts(&self, filter: &AlertFilter) -> Result<Vec<Alert>>;
     async fn health_check(&self) -> Result<()>;
 }
 
@@ -61,10 +89,33 @@ pub async fn connect_pool(database_url: &str, max_connections: u32) -> Result<Pg
 }
 
 pub async fn run_migrations(pool: &PgPool) -> Result<()> {
+    forget_creator_authority_migration(pool).await?;
     sqlx::migrate!("./migrations").run(pool).await?;
     Ok(())
 }
 
+/// Migrations used to ship two files numbered 006 (`creator_authority` and
+/// `squads_safe_requests`) and two numbered 009. On any database they reached,
+/// the migrator applied `006_creator_authority`, failed inserting the second
+/// version 6 row, and has refused to start since. `006_creator_authority` is
+/// gone (`009_creator_vault_flags` adds the same column idempotently) and
+/// `009_squads_imports` is now `021`, so drop its stale version 6 record and
+/// let those databases pick up from `006_squads_safe_requests`.
+async fn forget_creator_authority_migration(pool: &PgPool) -> Result<()> {
+    let tracked: bool = sqlx::query("select to_regclass('_sqlx_migrations') is not null")
+        .fetch_one(pool)
+        .await?
+        .get(0);
+    if tracked {
+        sqlx::query(
+            "delete from _sqlx_migrations where version = 6 and description = 'creator authority'",
+        )
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
 pub struct SqlxStore {
     pool: PgPool,
 }
@@ -73,6 +124,135 @@ impl SqlxStore {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Fee index snapshots per market since `since`, oldest first. Limited to
+    /// `market` when given.
+    async fn fee_index_history(
+        &self,
+        market: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<IndexPoint>>> {
+        let rows = sqlx::query(
+            r#"
+            select market_pubkey, observed_at, fee_index::float8 as fee_index
+            from market_index_snapshots
+            where observed_at >= $1
+              and ($2::text is null or market_pubkey = $2)
+            order by observed_at asc
+            "#,
+        )
+        .bind(since)
+        .bind(market)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut history: HashMap<String, Vec<IndexPoint>> = HashMap::new();
+        for row in rows {
+            history
+                .entry(row.get("market_pubkey"))
+                .or_default()
+                .push(IndexPoint::new(
+                    row.get("observed_at"),
+                    row.get("fee_index"),
+                ));
+        }
+        Ok(history)
+    }
+
+    async fn share_index_history(&self, since: DateTime<Utc>) -> Result<Vec<IndexPoint>> {
+        let rows = sqlx::query(
+            r#"
+            select observed_at, share_index::float8 as share_index
+            from attnusd_share_snapshots
+            where observed_at >= $1
+            order by observed_at asc
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| IndexPoint::new(row.get("observed_at"), row.get("share_index")))
+            .collect())
+    }
+}
+
+/// Snapshots are taken on a schedule, so look back one extra day to make sure
+/// a point at or before the start of the trailing window is included.
+fn history_since(now: DateTime<Utc>, window_days: i64) -> DateTime<Utc> {
+    now - Duration::days(window_days + 1)
+}
+
+/// SQL expression truncating `ts` to the bucket width bound at `$param` (seconds).
+fn bucket_sql(ts: &str, param: u8) -> String {
+    format!("to_timestamp(floor(extract(epoch from {ts}) / ${param}) * ${param})")
+}
+
+/// Open/high/low/close aggregate columns `{alias}_open` .. `{alias}_close`
+/// over the non-null values of `value`, ordered by `ts`.
+fn ohlc_sql(value: &str, ts: &str, alias: &str) -> String {
+    let filter = format!("filter (where {value} is not null)");
+    format!(
+        "(array_agg({value} order by {ts} asc) {filter})[1] as {alias}_open,
+         max({value}) {filter} as {alias}_high,
+         min({value}) {filter} as {alias}_low,
+         (array_agg({value} order by {ts} desc) {filter})[1] as {alias}_close"
+    )
+}
+
+fn row_ohlc(row: &PgRow, alias: &str) -> Option<Ohlc> {
+    Some(Ohlc {
+        open: row.get::<Option<f64>, _>(format!("{alias}_open").as_str())?,
+        high: row.get::<Option<f64>, _>(format!("{alias}_high").as_str())?,
+        low: row.get::<Option<f64>, _>(format!("{alias}_low").as_str())?,
+        close: row.get::<Option<f64>, _>(format!("{alias}_close").as_str())?,
+    })
+}
+
+fn history<T>(range: &HistoryRange, points: Vec<T>) -> History<T> {
+    History {
+        interval: range.interval,
+        from: range.from,
+        to: range.to,
+        points,
+    }
+}
+
+/// Severities at or above `minimum`; every severity when unset.
+fn severities_from(minimum: Option<&str>) -> Vec<String> {
+    let start = minimum
+        .and_then(|minimum| ALERT_SEVERITIES.iter().position(|s| *s == minimum))
+        .unwrap_or(0);
+    ALERT_SEVERITIES[start..]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn row_to_proposal(row: PgRow) -> Result<GovernanceProposal> {
+    let keys = |column: &str| -> Result<Vec<String>> {
+        Ok(serde_json::from_value(row.get::<Value, _>(column))?)
+    };
+    let approved_by = keys("approved")?;
+    let rejected_by = keys("rejected")?;
+    Ok(GovernanceProposal {
+        multisig: row.get("multisig"),
+        transaction_index: row.get::<i64, _>("transaction_index").max(0) as u64,
+        proposal: row.get("proposal_pubkey"),
+        creator_vault: row.get("creator_vault"),
+        status: row.get("status"),
+        status_at: row.get("status_at"),
+        stale: row.get("stale"),
+        threshold: row.get::<i32, _>("threshold").clamp(0, i32::from(u16::MAX)) as u16,
+        approvals: approved_by.len() as u32,
+        rejections: rejected_by.len() as u32,
+        approved_by,
+        rejected_by,
+        actions: serde_json::from_value(row.get::<Value, _>("actions"))?,
+        updated_at: row.get("updated_at"),
+    })
 }
 
 #[async_trait]
@@ -127,7 +307,7 @@ impl ReadStore for SqlxStore {
                    m.maturity_ts,
                    m.pt_supply,
                    m.yt_supply,
-                   m.fee_index,
+                   m.fee_index::float8 as fee_index,
                    m.updated_at
             from markets m
             join creator_vaults cv on cv.pump_mint = m.pump_mint
@@ -137,17 +317,27 @@ impl ReadStore for SqlxStore {
         .fetch_all(&self.pool)
         .await?;
 
+        let now = Utc::now();
+        let history = self
+            .fee_index_history(None, history_since(now, IMPLIED_APY_WINDOW_DAYS))
+            .await?;
         let markets: Vec<_> = rows
             .into_iter()
             .map(|row| {
+                let market: String = row.get("market_pubkey");
                 let maturity_ts: i64 = row.get("maturity_ts");
-                let status = if maturity_ts <= Utc::now().timestamp() {
+                let implied_apy = apy::implied_apy(
+                    history.get(&market).map(Vec::as_slice).unwrap_or_default(),
+                    IndexPoint::new(now, row.get("fee_index")),
+                    maturity_ts,
+                );
+                let status = if maturity_ts <= now.timestamp() {
                     MarketStatus::Matured
                 } else {
                     MarketStatus::Active
                 };
                 MarketSummary {
-                    market: row.get("market_pubkey"),
+                    market,
                     pump_mint: row.get("pump_mint"),
                     creator_vault: row.get("creator_vault"),
                     creator_authority: row.get("creator_authority"),
@@ -157,7 +347,7 @@ impl ReadStore for SqlxStore {
                     maturity_ts,
                     pt_supply: row.get("pt_supply"),
                     yt_supply: row.get("yt_supply"),
-                    implied_apy: row.get("fee_index"),
+                    implied_apy,
                     status,
                     admin: row.get("admin"),
                 }
@@ -181,7 +371,7 @@ impl ReadStore for SqlxStore {
                    m.maturity_ts,
                    m.pt_supply,
                    m.yt_supply,
-                   m.fee_index,
+                   m.fee_index::float8 as fee_index,
                    m.updated_at,
                    coalesce(v.total_fees_lamports, 0) as total_fees_lamports,
                    m.created_slot
@@ -198,8 +388,18 @@ impl ReadStore for SqlxStore {
             return Ok(None);
         };
 
+        let now = Utc::now();
         let maturity_ts: i64 = row.get("maturity_ts");
-        let status = if maturity_ts <= Utc::now().timestamp() {
+        let fee_index: f64 = row.get("fee_index");
+        let history = self
+            .fee_index_history(Some(market), history_since(now, IMPLIED_APY_WINDOW_DAYS))
+            .await?;
+        let implied_apy = apy::implied_apy(
+            history.get(market).map(Vec::as_slice).unwrap_or_default(),
+            IndexPoint::new(now, fee_index),
+            maturity_ts,
+        );
+        let status = if maturity_ts <= now.timestamp() {
             MarketStatus::Matured
         } else {
             MarketStatus::Active
@@ -217,19 +417,152 @@ impl ReadStore for SqlxStore {
                 maturity_ts,
                 pt_supply: row.get("pt_supply"),
                 yt_supply: row.get("yt_supply"),
-                implied_apy: row.get("fee_index"),
+                implied_apy,
                 status,
                 admin: row.get("admin"),
             },
             total_fees_distributed_sol: row.get::<f64, _>("total_fees_lamports")
                 / 1_000_000_000_f64,
-            fee_index: row.get("fee_index"),
+            fee_index,
             tvl_sol: row.get("pt_supply"),
             last_yield_slot: row.get::<i64, _>("created_slot") as u64,
             updated_at: row.get("updated_at"),
         }))
     }
 
+    async fn fee_history(
+        &self,
+        market: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<FeeHistoryPoint>> {
+        let rows = sqlx::query(
+            r#"
+            select date_trunc('day', e.ts) as bucket_start,
+                   coalesce(sum((e.payload->>'amount')::numeric), 0)::float8 as fees_lamports
+            from events e
+            join markets m on m.pump_mint = e.payload->>'pump_mint'
+            where m.market_pubkey = $1
+              and e.program = 'creator_vault'
+              and e.kind = 'fee_collected'
+              and e.ts >= $2
+            group by bucket_start
+            order by bucket_start asc
+            "#,
+        )
+        .bind(market)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| FeeHistoryPoint {
+                bucket_start: row.get("bucket_start"),
+                fees_sol: row.get::<f64, _>("fees_lamports") / 1_000_000_000_f64,
+            })
+            .collect())
+    }
+
+    async fn market_history(
+        &self,
+        market: &str,
+        range: &HistoryRange,
+    ) -> Result<Option<History<MarketHistoryPoint>>> {
+        let exists: bool =
+            sqlx::query_scalar("select exists(select 1 from markets where market_pubkey = $1)")
+                .bind(market)
+                .fetch_one(&self.pool)
+                .await?;
+        if !exists {
+            return Ok(None);
+        }
+        let bucket_secs = range.interval.seconds() as f64;
+
+        let snapshot_sql = format!(
+            r#"
+            select {bucket} as bucket_start,
+                   {fee_index},
+                   {pt_supply},
+                   {yt_supply}
+            from market_index_snapshots
+            where market_pubkey = $1
+              and observed_at >= $2
+              and observed_at < $3
+            group by 1
+            order by 1 asc
+            "#,
+            bucket = bucket_sql("observed_at", 4),
+            fee_index = ohlc_sql("fee_index::float8", "observed_at", "fee_index"),
+            pt_supply = ohlc_sql("pt_supply::float8", "observed_at", "pt_supply"),
+            yt_supply = ohlc_sql("yt_supply::float8", "observed_at", "yt_supply"),
+        );
+        let snapshot_rows = sqlx::query(&snapshot_sql)
+            .bind(market)
+            .bind(range.from)
+            .bind(range.to)
+            .bind(bucket_secs)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let fees_sql = format!(
+            r#"
+            select {bucket} as bucket_start,
+                   sum((e.payload->>'amount')::numeric)::float8 as fees_lamports
+            from events e
+            join markets m on m.pump_mint = e.payload->>'pump_mint'
+            where m.market_pubkey = $1
+              and e.program = 'creator_vault'
+              and e.kind = 'fee_collected'
+              and e.ts >= $2
+              and e.ts < $3
+            group by 1
+            "#,
+            bucket = bucket_sql("e.ts", 4),
+        );
+        let fee_rows = sqlx::query(&fees_sql)
+            .bind(market)
+            .bind(range.from)
+            .bind(range.to)
+            .bind(bucket_secs)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut points: BTreeMap<DateTime<Utc>, MarketHistoryPoint> = BTreeMap::new();
+        for row in snapshot_rows {
+            let bucket_start: DateTime<Utc> = row.get("bucket_start");
+            let pt_supply = row_ohlc(&row, "pt_supply");
+            points.insert(
+                bucket_start,
+                MarketHistoryPoint {
+                    bucket_start,
+                    fee_index: row_ohlc(&row, "fee_index"),
+                    pt_supply,
+                    yt_supply: row_ohlc(&row, "yt_supply"),
+                    // TVL is the SOL principal backing PT, as in `market`.
+                    tvl_sol: pt_supply,
+                    fees_sol: 0.0,
+                },
+            );
+        }
+        for row in fee_rows {
+            let bucket_start: DateTime<Utc> = row.get("bucket_start");
+            let fees_sol = row.get::<f64, _>("fees_lamports") / 1_000_000_000_f64;
+            points
+                .entry(bucket_start)
+                .or_insert_with(|| MarketHistoryPoint {
+                    bucket_start,
+                    fee_index: None,
+                    pt_supply: None,
+                    yt_supply: None,
+                    tvl_sol: None,
+                    fees_sol: 0.0,
+                })
+                .fees_sol = fees_sol;
+        }
+
+        Ok(Some(history(range, points.into_values().collect())))
+    }
+
     async fn portfolio(&self, wallet: &str) -> Result<Option<Portfolio>> {
         let rows = sqlx::query(
             r#"
@@ -276,7 +609,9 @@ impl ReadStore for SqlxStore {
     async fn attnusd(&self) -> Result<AttnUsdStats> {
         let row = sqlx::query(
             r#"
-            select total_supply, share_index, updated_at
+            select total_supply::float8 as total_supply,
+                   share_index::float8 as share_index,
+                   updated_at
             from attnusd_stats
             limit 1
             "#,
@@ -285,11 +620,16 @@ impl ReadStore for SqlxStore {
         .await?;
 
         if let Some(row) = row {
+            let now = Utc::now();
+            let share_index: f64 = row.get("share_index");
+            let history = self.share_index_history(history_since(now, 30)).await?;
+            let current = IndexPoint::new(now, share_index);
             Ok(AttnUsdStats {
                 total_supply: row.get("total_supply"),
-                nav_sol: row.get::<f64, _>("total_supply") * row.get::<f64, _>("share_index"),
-                price_per_share: row.get("share_index"),
-                seven_day_apy: 0.0,
+                nav_sol: row.get::<f64, _>("total_supply") * share_index,
+                price_per_share: share_index,
+                seven_day_apy: apy::trailing_apy(&history, current, Duration::days(7)),
+                thirty_day_apy: apy::trailing_apy(&history, current, Duration::days(30)),
                 last_rebalance_slot: 0,
                 updated_at: row.get("updated_at"),
             })
@@ -299,12 +639,60 @@ impl ReadStore for SqlxStore {
                 nav_sol: 0.0,
                 price_per_share: 1.0,
                 seven_day_apy: 0.0,
+                thirty_day_apy: 0.0,
                 last_rebalance_slot: 0,
                 updated_at: Utc::now(),
             })
         }
     }
 
+    async fn attnusd_history(&self, range: &HistoryRange) -> Result<History<AttnUsdHistoryPoint>> {
+        let sql = format!(
+            r#"
+            with samples as (
+                select observed_at as ts,
+                       share_index::float8 as price_per_share,
+                       total_supply::float8 as total_supply
+                from attnusd_share_snapshots
+                where observed_at >= $1 and observed_at < $2
+                union all
+                select ts,
+                       (payload->>'price_per_share')::float8 / 1000000000,
+                       null
+                from events
+                where program = 'stable_vault'
+                  and kind in ('attnusd_minted', 'attnusd_redeemed')
+                  and ts >= $1 and ts < $2
+            )
+            select {bucket} as bucket_start,
+                   {price_per_share},
+                   {total_supply}
+            from samples
+            group by 1
+            order by 1 asc
+            "#,
+            bucket = bucket_sql("ts", 3),
+            price_per_share = ohlc_sql("price_per_share", "ts", "price_per_share"),
+            total_supply = ohlc_sql("total_supply", "ts", "total_supply"),
+        );
+        let rows = sqlx::query(&sql)
+            .bind(range.from)
+            .bind(range.to)
+            .bind(range.interval.seconds() as f64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let points = rows
+            .iter()
+            .map(|row| AttnUsdHistoryPoint {
+                bucket_start: row.get("bucket_start"),
+                price_per_share: row_ohlc(row, "price_per_share"),
+                total_supply: row_ohlc(row, "total_supply"),
+            })
+            .collect();
+        Ok(history(range, points))
+    }
+
     async fn rewards(&self, cursor: Option<String>, limit: u16) -> Result<RewardsPage> {
         let limit = limit.clamp(1, 100) as i64;
         let rows = if let Some(cursor) = cursor {
@@ -541,6 +929,73 @@ impl ReadStore for SqlxStore {
         }))
     }
 
+    async fn rewards_pool_history(
+        &self,
+        pool: &str,
+        range: &HistoryRange,
+    ) -> Result<Option<History<RewardsPoolHistoryPoint>>> {
+        let exists: bool = sqlx::query_scalar(
+            "select exists(select 1 from rewards_pools where rewards_pool = $1)",
+        )
+        .bind(pool)
+        .fetch_one(&self.pool)
+        .await?;
+        if !exists {
+            return Ok(None);
+        }
+
+        let sql = format!(
+            r#"
+            with samples as (
+                select ts,
+                       kind,
+                       case when kind = 'rewards_funded'
+                            then (payload->>'sol_per_share')::numeric::float8 / 1000000000
+                       end as sol_per_share,
+                       case when kind in ('rewards_staked', 'rewards_unstaked')
+                            then (payload->>'total_staked')::float8
+                       end as total_staked,
+                       case when kind = 'rewards_funded'
+                            then (payload->>'amount')::numeric
+                       end as funded_lamports
+                from events
+                where program = 'rewards_vault'
+                  and kind in ('rewards_funded', 'rewards_staked', 'rewards_unstaked')
+                  and payload->>'pool' = $1
+                  and ts >= $2 and ts < $3
+            )
+            select {bucket} as bucket_start,
+                   {sol_per_share},
+                   {total_staked},
+                   coalesce(sum(funded_lamports), 0)::float8 as funded_lamports
+            from samples
+            group by 1
+            order by 1 asc
+            "#,
+            bucket = bucket_sql("ts", 4),
+            sol_per_share = ohlc_sql("sol_per_share", "ts", "sol_per_share"),
+            total_staked = ohlc_sql("total_staked", "ts", "total_staked"),
+        );
+        let rows = sqlx::query(&sql)
+            .bind(pool)
+            .bind(range.from)
+            .bind(range.to)
+            .bind(range.interval.seconds() as f64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let points = rows
+            .iter()
+            .map(|row| RewardsPoolHistoryPoint {
+                bucket_start: row.get("bucket_start"),
+                sol_per_share: row_ohlc(row, "sol_per_share"),
+                total_staked_attnusd: row_ohlc(row, "total_staked"),
+                rewards_funded_sol: row.get::<f64, _>("funded_lamports") / 1_000_000_000_f64,
+            })
+            .collect();
+        Ok(Some(history(range, points)))
+    }
+
     async fn governance(&self) -> Result<GovernanceState> {
         let creator_rows = sqlx::query(
             r#"
@@ -619,6 +1074,75 @@ impl ReadStore for SqlxStore {
         })
     }
 
+    async fn governance_proposals(
+        &self,
+        filter: &ProposalFilter,
+    ) -> Result<Vec<GovernanceProposal>> {
+        let rows = sqlx::query(
+            r#"
+            select multisig, transaction_index, proposal_pubkey, creator_vault, status, status_at,
+                   stale, threshold, approved, rejected, actions, updated_at
+            from governance_proposals
+            where ($1::text is null or creator_vault = $1)
+              and ($2::text is null or multisig = $2)
+              and ($3::text is null or status = $3)
+            order by updated_at desc, transaction_index desc
+            limit $4
+            "#,
+        )
+        .bind(filter.creator_vault.as_deref())
+        .bind(filter.multisig.as_deref())
+        .bind(filter.status.as_deref())
+        .bind(i64::from(filter.limit.clamp(1, 100)))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_proposal).collect()
+    }
+
+    async fn alerts(&self, filter: &AlertFilter) -> Result<Vec<Alert>> {
+        let rows = sqlx::query(
+            r#"
+            select id, creator_vault, kind, severity, priority, message, details,
+                   opened_at, updated_at, resolved_at
+            from alerts
+            where ($1::text is null or creator_vault = $1)
+              and ($2::text is null or kind = $2)
+              and severity = any($3)
+              and ($4 or resolved_at is null)
+            order by resolved_at is not null,
+                     priority desc,
+                     array_position($5::text[], severity) desc,
+                     updated_at desc
+            limit $6
+            "#,
+        )
+        .bind(filter.creator_vault.as_deref())
+        .bind(filter.kind.as_deref())
+        .bind(severities_from(filter.severity.as_deref()))
+        .bind(filter.include_resolved)
+        .bind(ALERT_SEVERITIES.map(String::from).to_vec())
+        .bind(i64::from(filter.limit.clamp(1, 100)))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Alert {
+                id: row.get("id"),
+                creator_vault: row.get("creator_vault"),
+                kind: row.get("kind"),
+                severity: row.get("severity"),
+                priority: row.get("priority"),
+                message: row.get("message"),
+                details: row.get("details"),
+                opened_at: row.get("opened_at"),
+                updated_at: row.get("updated_at"),
+                resolved_at: row.get("resolved_at"),
+            })
+            .collect())
+    }
+
     async fn health_check(&self) -> Result<()> {
         sqlx::query("select 1")
             .fetch_one(&self.pool)
@@ -664,6 +1188,42 @@ impl ReadStore for MockStore {
         Ok(self.inner.markets.get(market).cloned())
     }
 
+    async fn fee_history(
+        &self,
+        market: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<FeeHistoryPoint>> {
+        Ok(self
+            .inner
+            .fee_history
+            .get(market)
+            .map(|points| {
+                points
+                    .iter()
+                    .filter(|point| point.bucket_start >= since)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn market_history(
+        &self,
+        market: &str,
+        range: &HistoryRange,
+    ) -> Result<Option<History<MarketHistoryPoint>>> {
+        if !self.inner.markets.contains_key(market) {
+            return Ok(None);
+        }
+        let points = self
+            .inner
+            .market_history
+            .get(market)
+            .map(|points| downsample(points, range))
+            .unwrap_or_default();
+        Ok(Some(history(range, points)))
+    }
+
     async fn portfolio(&self, wallet: &str) -> Result<Option<Portfolio>> {
         Ok(self.inner.portfolios.get(wallet).cloned())
     }
@@ -672,6 +1232,13 @@ impl ReadStore for MockStore {
         Ok(self.inner.attnusd.clone())
     }
 
+    async fn attnusd_history(&self, range: &HistoryRange) -> Result<History<AttnUsdHistoryPoint>> {
+        Ok(history(
+            range,
+            downsample(&self.inner.attnusd_history, range),
+        ))
+    }
+
     async fn rewards(&self, cursor: Option<String>, limit: u16) -> Result<RewardsPage> {
         let mut summaries: Vec<_> = self
             .inner
@@ -719,23 +1286,215 @@ impl ReadStore for MockStore {
             .cloned())
     }
 
+    async fn rewards_pool_history(
+        &self,
+        pool: &str,
+        range: &HistoryRange,
+    ) -> Result<Option<History<RewardsPoolHistoryPoint>>> {
+        if !self
+            .inner
+            .rewards
+            .iter()
+            .any(|detail| detail.summary.pool == pool)
+        {
+            return Ok(None);
+        }
+        let points = self
+            .inner
+            .rewards_history
+            .get(pool)
+            .map(|points| downsample(points, range))
+            .unwrap_or_default();
+        Ok(Some(history(range, points)))
+    }
+
     async fn governance(&self) -> Result<GovernanceState> {
         Ok(self.inner.governance.clone())
     }
 
+    async fn governance_proposals(
+        &self,
+        filter: &ProposalFilter,
+    ) -> Result<Vec<GovernanceProposal>> {
+        let matches = |value: &str, wanted: &Option<String>| {
+            wanted
+                .as_deref()
+                .map(|wanted| wanted == value)
+                .unwrap_or(true)
+        };
+        let mut proposals: Vec<_> = self
+            .inner
+            .governance_proposals
+            .iter()
+            .filter(|proposal| {
+                proposal
+                    .creator_vault
+                    .as_deref()
+                    .map(|vault| matches(vault, &filter.creator_vault))
+                    .unwrap_or(filter.creator_vault.is_none())
+                    && matches(&proposal.multisig, &filter.multisig)
+                    && matches(&proposal.status, &filter.status)
+            })
+            .cloned()
+            .collect();
+        proposals.sort_by(|a, b| {
+            b.updated_at
+                .cmp(&a.updated_at)
+                .then(b.transaction_index.cmp(&a.transaction_index))
+        });
+        proposals.truncate(usize::from(filter.limit.clamp(1, 100)));
+        Ok(proposals)
+    }
+
+    async fn alerts(&self, filter: &AlertFilter) -> Result<Vec<Alert>> {
+        let severities = severities_from(filter.severity.as_deref());
+        let rank = |alert: &Alert| ALERT_SEVERITIES.iter().position(|s| *s == alert.severity);
+        let mut alerts: Vec<_> = self
+            .inner
+            .alerts
+            .iter()
+            .filter(|alert| {
+                filter
+                    .creator_vault
+                    .as_deref()
+                    .is_none_or(|vault| vault == alert.creator_vault)
+                    && filter.kind.as_deref().is_none_or(|kind| kind == alert.kind)
+                    && severities.contains(&alert.severity)
+                    && (filter.include_resolved || alert.resolved_at.is_none())
+            })
+            .cloned()
+            .collect();
+        alerts.sort_by(|a, b| {
+            a.resolved_at
+                .is_some()
+                .cmp(&b.resolved_at.is_some())
+                .then(b.priority.cmp(&a.priority))
+                .then(rank(b).cmp(&rank(a)))
+                .then(b.updated_at.cmp(&a.updated_at))
+        });
+        alerts.truncate(usize::from(filter.limit.clamp(1, 100)));
+        Ok(alerts)
+    }
+
     async fn health_check(&self) -> Result<()> {
         Ok(())
     }
 }
 
+/// A pre-bucketed fixture point that can be merged into coarser buckets.
+trait HistoryPoint: Clone {
+    fn bucket_start(&self) -> DateTime<Utc>;
+    fn with_bucket_start(self, bucket_start: DateTime<Utc>) -> Self;
+    fn merge(self, later: Self) -> Self;
+}
+
+fn merge_ohlc(earlier: Option<Ohlc>, later: Option<Ohlc>) -> Option<Ohlc> {
+    match (earlier, later) {
+        (Some(earlier), Some(later)) => Some(earlier.merge(later)),
+        (earlier, later) => earlier.or(later),
+    }
+}
+
+impl HistoryPoint for MarketHistoryPoint {
+    fn bucket_start(&self) -> DateTime<Utc> {
+        self.bucket_start
+    }
+
+    fn with_bucket_start(self, bucket_start: DateTime<Utc>) -> Self {
+        Self {
+            bucket_start,
+            ..self
+        }
+    }
+
+    fn merge(self, later: Self) -> Self {
+        Self {
+            bucket_start: self.bucket_start,
+            fee_index: merge_ohlc(self.fee_index, later.fee_index),
+            pt_supply: merge_ohlc(self.pt_supply, later.pt_supply),
+            yt_supply: merge_ohlc(self.yt_supply, later.yt_supply),
+            tvl_sol: merge_ohlc(self.tvl_sol, later.tvl_sol),
+            fees_sol: self.fees_sol + later.fees_sol,
+        }
+    }
+}
+
+impl HistoryPoint for AttnUsdHistoryPoint {
+    fn bucket_start(&self) -> DateTime<Utc> {
+        self.bucket_start
+    }
+
+    fn with_bucket_start(self, bucket_start: DateTime<Utc>) -> Self {
+        Self {
+            bucket_start,
+            ..self
+        }
+    }
+
+    fn merge(self, later: Self) -> Self {
+        Self {
+            bucket_start: self.bucket_start,
+            price_per_share: merge_ohlc(self.price_per_share, later.price_per_share),
+            total_supply: merge_ohlc(self.total_supply, later.total_supply),
+        }
+    }
+}
+
+impl HistoryPoint for RewardsPoolHistoryPoint {
+    fn bucket_start(&self) -> DateTime<Utc> {
+        self.bucket_start
+    }
+
+    fn with_bucket_start(self, bucket_start: DateTime<Utc>) -> Self {
+        Self {
+            bucket_start,
+            ..self
+        }
+    }
+
+    fn merge(self, later: Self) -> Self {
+        Self {
+            bucket_start: self.bucket_start,
+            sol_per_share: merge_ohlc(self.sol_per_share, later.sol_per_share),
+            total_staked_attnusd: merge_ohlc(self.total_staked_attnusd, later.total_staked_attnusd),
+            rewards_funded_sol: self.rewards_funded_sol + later.rewards_funded_sol,
+        }
+    }
+}
+
+/// Mirrors the SQL bucketing for fixtures: keeps points inside `range` and
+/// merges them into `range.interval` buckets. `points` must be sorted.
+fn downsample<T: HistoryPoint>(points: &[T], range: &HistoryRange) -> Vec<T> {
+    let mut buckets: Vec<T> = Vec::new();
+    for point in points
+        .iter()
+        .filter(|point| range.contains(point.bucket_start()))
+    {
+        let bucket_start = range.interval.bucket_start(point.bucket_start());
+        match buckets.last_mut() {
+            Some(last) if last.bucket_start() == bucket_start => {
+                *last = last.clone().merge(point.clone());
+            }
+            _ => buckets.push(point.clone().with_bucket_start(bucket_start)),
+        }
+    }
+    buckets
+}
+
 #[derive(Clone)]
 pub struct MockData {
     pub overview: Overview,
     pub markets: HashMap<String, MarketDetail>,
+    pub fee_history: HashMap<String, Vec<FeeHistoryPoint>>,
+    pub market_history: HashMap<String, Vec<MarketHistoryPoint>>,
     pub portfolios: HashMap<String, Portfolio>,
     pub attnusd: AttnUsdStats,
+    pub attnusd_history: Vec<AttnUsdHistoryPoint>,
     pub rewards: Vec<RewardsPoolDetail>,
+    pub rewards_history: HashMap<String, Vec<RewardsPoolHistoryPoint>>,
     pub governance: GovernanceState,
+    pub governance_proposals: Vec<GovernanceProposal>,
+    pub alerts: Vec<Alert>,
 }
 
 impl Default for MockData {
@@ -797,6 +1556,20 @@ impl MockData {
             updated_at: now - Duration::hours(2),
         };
 
+        // Thirty days of fees for the active market, oscillating around a
+        // ~18% annualised yield on its TVL.
+        let today = now.duration_trunc(Duration::days(1)).unwrap_or(now);
+        let fee_history = HashMap::from([(
+            market_one_summary.market.clone(),
+            (1..=30)
+                .rev()
+                .map(|days_ago| FeeHistoryPoint {
+                    bucket_start: today - Duration::days(days_ago),
+                    fees_sol: 48.75 + 6.0 * ((days_ago % 5) as f64 - 2.0),
+                })
+                .collect::<Vec<_>>(),
+        )]);
+
         let overview = Overview {
             total_creator_vaults: 3,
             total_markets: 2,
@@ -811,6 +1584,7 @@ impl MockData {
             nav_sol: 229_650.72,
             price_per_share: 1.0184,
             seven_day_apy: 0.1275,
+            thirty_day_apy: 0.1193,
             last_rebalance_slot: 235_001_002,
             updated_at: now,
         };
@@ -927,13 +1701,138 @@ impl MockData {
             }),
         };
 
+        // A week of hourly history for the active market, attnUSD and the
+        // rewards pool, each ending at the current snapshot values.
+        let hour = now.duration_trunc(Duration::hours(1)).unwrap_or(now);
+        let hours: Vec<(i64, DateTime<Utc>)> = (1..=7 * 24)
+            .rev()
+            .map(|hours_ago| (hours_ago, hour - Duration::hours(hours_ago)))
+            .collect();
+        let market_history = HashMap::from([(
+            market_one_summary.market.clone(),
+            hours
+                .iter()
+                .map(|(hours_ago, bucket_start)| {
+                    let ago = *hours_ago as f64;
+                    let fee_index = 1.0384 - 0.000_5 * ago / 24.0;
+                    let pt_supply = 125_000.0 - 25.0 * ago;
+                    let tvl_sol = 97_500.0 - 20.0 * ago;
+                    MarketHistoryPoint {
+                        bucket_start: *bucket_start,
+                        fee_index: Some(Ohlc {
+                            open: fee_index - 0.000_5 / 24.0,
+                            high: fee_index,
+                            low: fee_index - 0.000_5 / 24.0,
+                            close: fee_index,
+                        }),
+                        pt_supply: Some(Ohlc::point(pt_supply)),
+                        yt_supply: Some(Ohlc::point(pt_supply)),
+                        tvl_sol: Some(Ohlc::point(tvl_sol)),
+                        fees_sol: 2.03,
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )]);
+        let attnusd_history = hours
+            .iter()
+            .map(|(hours_ago, bucket_start)| {
+                let price = 1.0184 / 1.1275_f64.powf(*hours_ago as f64 / 8_760.0);
+                AttnUsdHistoryPoint {
+                    bucket_start: *bucket_start,
+                    price_per_share: Some(Ohlc::point(price)),
+                    total_supply: Some(Ohlc::point(225_500.0 - 50.0 * *hours_ago as f64)),
+                }
+            })
+            .collect();
+        let rewards_history = HashMap::from([(
+            rewards_summary.pool.clone(),
+            hours
+                .iter()
+                .map(|(hours_ago, bucket_start)| {
+                    let funded = hours_ago % 6 == 0;
+                    let ago = *hours_ago as f64;
+                    RewardsPoolHistoryPoint {
+                        bucket_start: *bucket_start,
+                        sol_per_share: funded.then(|| Ohlc::point(0.000_002_5 - 1e-9 * ago)),
+                        total_staked_attnusd: Some(Ohlc::point(85_000.0 - 10.0 * ago)),
+                        rewards_funded_sol: if funded { 0.3 } else { 0.0 },
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )]);
+
+        let governance_proposals = vec![GovernanceProposal {
+            multisig: "Multisig11111111111111111111111111111111".into(),
+            transaction_index: 3,
+            proposal: "Proposal11111111111111111111111111111111".into(),
+            creator_vault: Some("CreatorVault1111111111111111111111111111111".into()),
+            status: "active".into(),
+            status_at: Some(now - Duration::minutes(20)),
+            stale: false,
+            threshold: 2,
+            approvals: 1,
+            rejections: 0,
+            approved_by: vec!["Admin11111111111111111111111111111111111".into()],
+            rejected_by: Vec::new(),
+            actions: vec![ProposedAction {
+                program: "creator_vault".into(),
+                instruction: "set_pause".into(),
+                target: Some("CreatorVault1111111111111111111111111111111".into()),
+                args: serde_json::json!({ "paused": true }),
+                summary:
+                    "set_pause(true) on creator vault CreatorVault1111111111111111111111111111111"
+                        .into(),
+            }],
+            updated_at: now - Duration::minutes(20),
+        }];
+
+        let alerts = vec![
+            Alert {
+                id: 2,
+                creator_vault: "CreatorVault1111111111111111111111111111111".into(),
+                kind: "fee_drop".into(),
+                severity: "critical".into(),
+                priority: true,
+                message: "fee rate fell to 4% of the 30d baseline".into(),
+                details: serde_json::json!({
+                    "baseline_per_day": 2_500_000_000_u64,
+                    "recent_per_day": 100_000_000_u64,
+                    "ratio": 0.04,
+                }),
+                opened_at: now - Duration::hours(3),
+                updated_at: now - Duration::minutes(5),
+                resolved_at: None,
+            },
+            Alert {
+                id: 1,
+                creator_vault: "CreatorVault2222222222222222222222222222222".into(),
+                kind: "fee_recipient_rerouted".into(),
+                severity: "warning".into(),
+                priority: false,
+                message: "Pump.fun creator moved away from the vault".into(),
+                details: serde_json::json!({
+                    "previous_recipient": "CreatorVault2222222222222222222222222222222",
+                    "recipient": "Rerouted11111111111111111111111111111111",
+                }),
+                opened_at: now - Duration::days(2),
+                updated_at: now - Duration::days(1),
+                resolved_at: Some(now - Duration::days(1)),
+            },
+        ];
+
         Self {
             overview,
             markets,
+            fee_history,
+            market_history,
             portfolios,
             attnusd,
+            attnusd_history,
             rewards,
+            rewards_history,
             governance,
+            governance_proposals,
+            alerts,
         }
     }
 }
@@ -941,6 +1840,100 @@ impl MockData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::HistoryInterval;
+    use sqlx::migrate::Migrator;
+    use sqlx::postgres::PgConnectOptions;
+    use std::str::FromStr;
+
+    /// `006_creator_authority.sql` as it shipped before the renumbering.
+    const LEGACY_CREATOR_AUTHORITY: &str = "alter table creator_vaults
+    add column if not exists authority text not null default '';
+
+update creator_vaults
+set authority = vault_pubkey
+where authority = '';
+
+alter table creator_vaults
+    alter column authority drop default;
+";
+
+    /// Empty database created next to `ATTN_INDEXER_TEST_DATABASE_URL`, with
+    /// the pool it was created from, or `None` when no test database is
+    /// configured.
+    async fn scratch_database() -> Option<(PgPool, String, PgPool)> {
+        let url = std::env::var("ATTN_INDEXER_TEST_DATABASE_URL").ok()?;
+        let admin = connect_pool(&url, 1).await.expect("connect test database");
+        let name = format!(
+            "attn_migrations_{}_{}",
+            std::process::id(),
+            Utc::now().timestamp_micros()
+        );
+        sqlx::query(&format!("create database {name}"))
+            .execute(&admin)
+            .await
+            .unwrap();
+        let options = PgConnectOptions::from_str(&url).unwrap().database(&name);
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .connect_with(options)
+            .await
+            .unwrap();
+        Some((admin, name, pool))
+    }
+
+    #[tokio::test]
+    async fn databases_stuck_on_the_duplicate_006_migrate() {
+        let Some((admin, name, pool)) = scratch_database().await else {
+            return;
+        };
+        // Where the old migration set stopped: 001-005 and
+        // 006_creator_authority applied.
+        let dir = std::env::temp_dir().join(format!(
+            "attn_legacy_migrations_{}_{}",
+            std::process::id(),
+            Utc::now().timestamp_micros()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let shipped = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        for entry in std::fs::read_dir(&shipped).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_str().unwrap().to_string();
+            if name.as_str() < "006" {
+                std::fs::copy(&path, dir.join(&name)).unwrap();
+            }
+        }
+        std::fs::write(
+            dir.join("006_creator_authority.sql"),
+            LEGACY_CREATOR_AUTHORITY,
+        )
+        .unwrap();
+        Migrator::new(dir.as_path())
+            .await
+            .unwrap()
+            .run(&pool)
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        run_migrations(&pool).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let columns: i64 = sqlx::query(
+            "select count(*) from information_schema.columns
+             where (table_name = 'creator_vaults' and column_name = 'authority')
+                or (table_name = 'squads_safe_requests' and column_name = 'import_source')",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get(0);
+        assert_eq!(columns, 2);
+
+        pool.close().await;
+        sqlx::query(&format!("drop database {name}"))
+            .execute(&admin)
+            .await
+            .unwrap();
+    }
 
     #[tokio::test]
     async fn mock_store_returns_overview() {
@@ -965,6 +1958,104 @@ mod tests {
         assert!(detail.is_some());
     }
 
+    #[tokio::test]
+    async fn mock_store_filters_fee_history_by_start() {
+        let store = MockStore::default();
+        let market = "Market1111111111111111111111111111111111";
+        let all = store
+            .fee_history(market, Utc::now() - Duration::days(60))
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 30);
+        let recent = store
+            .fee_history(market, Utc::now() - Duration::days(7))
+            .await
+            .unwrap();
+        assert!(recent.len() < all.len());
+        assert!(recent
+            .windows(2)
+            .all(|pair| pair[0].bucket_start < pair[1].bucket_start));
+    }
+
+    #[tokio::test]
+    async fn mock_store_downsamples_market_history() {
+        let store = MockStore::default();
+        let market = "Market1111111111111111111111111111111111";
+        let to = Utc::now();
+        let hourly = HistoryRange {
+            interval: HistoryInterval::OneHour,
+            from: to - Duration::days(2),
+            to,
+        };
+        let daily = HistoryRange {
+            interval: HistoryInterval::OneDay,
+            ..hourly
+        };
+        let hourly = store
+            .market_history(market, &hourly)
+            .await
+            .unwrap()
+            .unwrap();
+        let daily = store.market_history(market, &daily).await.unwrap().unwrap();
+        assert!(hourly.points.len() >= 47);
+        assert!(daily.points.len() <= 3);
+
+        let hourly_fees: f64 = hourly.points.iter().map(|point| point.fees_sol).sum();
+        let daily_fees: f64 = daily.points.iter().map(|point| point.fees_sol).sum();
+        assert!((hourly_fees - daily_fees).abs() < 1e-9);
+
+        let first = daily.points.first().unwrap();
+        let last = daily.points.last().unwrap();
+        assert_eq!(
+            first.fee_index.unwrap().open,
+            hourly.points.first().unwrap().fee_index.unwrap().open
+        );
+        assert_eq!(
+            last.fee_index.unwrap().close,
+            hourly.points.last().unwrap().fee_index.unwrap().close
+        );
+        assert!(daily
+            .points
+            .iter()
+            .all(|point| point.bucket_start
+                == HistoryInterval::OneDay.bucket_start(point.bucket_start)));
+
+        let missing = store
+            .market_history("Unknown", &daily_range_for(to))
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn mock_store_serves_attnusd_and_rewards_history() {
+        let store = MockStore::default();
+        let range = daily_range_for(Utc::now());
+        let attnusd = store.attnusd_history(&range).await.unwrap();
+        assert!(!attnusd.points.is_empty());
+        let price = attnusd.points.last().unwrap().price_per_share.unwrap();
+        assert!(price.high >= price.low);
+
+        let pool = store.rewards(None, 1).await.unwrap().items[0].pool.clone();
+        let rewards = store
+            .rewards_pool_history(&pool, &range)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(rewards
+            .points
+            .iter()
+            .any(|point| point.rewards_funded_sol > 0.0));
+    }
+
+    fn daily_range_for(to: DateTime<Utc>) -> HistoryRange {
+        HistoryRange {
+            interval: HistoryInterval::OneDay,
+            from: to - Duration::days(7),
+            to,
+        }
+    }
+
     #[tokio::test]
     async fn mock_store_fetches_portfolio() {
         let store = MockStore::default();
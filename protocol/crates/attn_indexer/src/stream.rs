@@ -0,0 +1,139 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+
+/// Postgres `NOTIFY` channel carrying [`StreamUpdate`]s as JSON.
+pub const STREAM_CHANNEL: &str = "attn_stream";
+
+/// Something a client can subscribe to on `/v1/stream`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Topic {
+    Market(String),
+    Portfolio(String),
+    AttnUsd,
+    RewardsPool(String),
+    SquadsSafe(String),
+}
+
+impl Topic {
+    /// Portfolio and safe request updates expose wallet- and safe-specific
+    /// state, so they are only streamed to authenticated clients.
+    pub fn is_private(&self) -> bool {
+        matches!(self, Topic::Portfolio(_) | Topic::SquadsSafe(_))
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::Market(market) => write!(f, "market:{market}"),
+            Topic::Portfolio(wallet) => write!(f, "portfolio:{wallet}"),
+            Topic::AttnUsd => f.write_str("attnusd"),
+            Topic::RewardsPool(pool) => write!(f, "rewards:{pool}"),
+            Topic::SquadsSafe(id) => write!(f, "squads_safe:{id}"),
+        }
+    }
+}
+
+impl FromStr for Topic {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "attnusd" {
+            return Ok(Topic::AttnUsd);
+        }
+        let (kind, key) = value
+            .split_once(':')
+            .ok_or_else(|| format!("invalid topic: {value}"))?;
+        if key.is_empty() {
+            return Err(format!("topic {kind} requires an id"));
+        }
+        match kind {
+            "market" => Ok(Topic::Market(key.to_string())),
+            "portfolio" => Ok(Topic::Portfolio(key.to_string())),
+            "rewards" => Ok(Topic::RewardsPool(key.to_string())),
+            "squads_safe" => Ok(Topic::SquadsSafe(key.to_string())),
+            other => Err(format!("unknown topic kind: {other}")),
+        }
+    }
+}
+
+impl Serialize for Topic {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Topic {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamUpdate {
+    pub topic: Topic,
+    /// Anchor event name (e.g. `Staked`), or `squads_safe_updated` for safe
+    /// request status changes.
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub data: Value,
+}
+
+/// Sends `update` on [`STREAM_CHANNEL`]. Delivery is best effort: listeners
+/// that are not connected at the time miss it and fall back to REST reads.
+pub async fn publish(pool: &PgPool, update: &StreamUpdate) -> Result<()> {
+    let payload = serde_json::to_string(update)?;
+    sqlx::query("select pg_notify($1, $2)")
+        .bind(STREAM_CHANNEL)
+        .bind(payload)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_round_trip_through_strings() {
+        for raw in [
+            "market:Market111",
+            "portfolio:Wallet111",
+            "attnusd",
+            "rewards:Pool111",
+            "squads_safe:6f1c0c1e-1d6b-4f3e-9f0a-7d7a3c1b2e4f",
+        ] {
+            let topic: Topic = raw.parse().unwrap();
+            assert_eq!(topic.to_string(), raw);
+        }
+        assert!("market:".parse::<Topic>().is_err());
+        assert!("vault:abc".parse::<Topic>().is_err());
+        assert!("attnusd:extra".parse::<Topic>().is_err());
+    }
+
+    #[test]
+    fn updates_serialize_topic_as_string() {
+        let update = StreamUpdate {
+            topic: Topic::RewardsPool("Pool111".into()),
+            kind: "RewardsFunded".into(),
+            slot: Some(42),
+            signature: None,
+            data: serde_json::json!({ "amount": 5 }),
+        };
+        let json = serde_json::to_value(&update).unwrap();
+        assert_eq!(json["topic"], "rewards:Pool111");
+        let back: StreamUpdate = serde_json::from_value(json).unwrap();
+        assert_eq!(back, update);
+    }
+}
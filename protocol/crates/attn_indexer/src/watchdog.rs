@@ -0,0 +1,717 @@
+use std::time::Duration as StdDuration;
+
+use anyhow::Result;
+use attn_client::creator::{
+    self, CreatorVaultAccount, RevenueSourceAccount, RevenueSourceConfig, RevenueSourceKind,
+};
+use attn_client::decode_account;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+use sqlx::{PgPool, Row};
+use tracing::{info, warn};
+
+/// Trailing window the baseline fee rate is computed over.
+const BASELINE_DAYS: i64 = 30;
+/// Window compared against the baseline.
+const RECENT_HOURS: i64 = 24;
+/// Baselines shorter than this are too young to judge a drop against.
+const MIN_BASELINE_HOURS: i64 = 72;
+/// Samples are kept a little longer than the baseline window.
+const SAMPLE_RETENTION_DAYS: i64 = BASELINE_DAYS + 5;
+const WARNING_RATIO: f64 = 0.5;
+const CRITICAL_RATIO: f64 = 0.1;
+const MULTIPLE_ACCOUNTS_CHUNK: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Fee inflow per day over the baseline window and over the recent window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeRates {
+    pub baseline_per_day: f64,
+    pub recent_per_day: f64,
+}
+
+/// Watches creator vaults for the fee-reroute pattern described in
+/// `attn-fee-reroute-safeguards.md`: samples each vault's fees earned so far
+/// (`total_fees_collected` plus what is still accruing on the launchpad) to
+/// compare the recent fee rate against its trailing baseline, and tracks the Pump.fun creator-fee recipient of Pump-backed
+/// vaults. Findings are kept as open alerts in `alerts` until they clear.
+pub struct FeeWatchdog {
+    pool: PgPool,
+    rpc: RpcClient,
+    interval: StdDuration,
+}
+
+struct WatchedVault {
+    address: Pubkey,
+    state: CreatorVaultAccount,
+    /// Locked with an active advance; alerts escalate to critical.
+    priority: bool,
+}
+
+impl FeeWatchdog {
+    pub fn new(pool: PgPool, rpc_url: String, interval: StdDuration) -> Self {
+        let rpc = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+        Self {
+            pool,
+            rpc,
+            interval,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        info!(
+            interval_secs = self.interval.as_secs(),
+            "fee watchdog started"
+        );
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = self.check_vaults(Utc::now()).await {
+                warn!(error = ?err, "fee watchdog pass failed");
+            }
+        }
+    }
+
+    async fn check_vaults(&self, now: DateTime<Utc>) -> Result<()> {
+        let mut vaults = self.load_vaults(now).await?;
+        // Financed vaults are checked first so their alerts land before a
+        // slow RPC pass over the rest.
+        vaults.sort_by_key(|vault| !vault.priority);
+
+        let accrued = self.launchpad_accruals(&vaults).await?;
+        for (vault, accrued) in vaults.iter().zip(accrued) {
+            if let Err(err) = self.check_fee_rate(vault, accrued, now).await {
+                warn!(error = ?err, creator_vault = %vault.address, "fee rate check failed");
+            }
+        }
+
+        let pump_vaults: Vec<&WatchedVault> = vaults
+            .iter()
+            .filter(|vault| vault.state.source_kind == RevenueSourceKind::PumpFun)
+            .collect();
+        let curves: Vec<Pubkey> = pump_vaults
+            .iter()
+            .map(|vault| creator::pump_bonding_curve_pda(&vault.state.pump_mint).0)
+            .collect();
+        let curve_accounts = self.fetch_accounts(&curves).await?;
+        for ((vault, curve), account) in pump_vaults.iter().zip(&curves).zip(curve_accounts) {
+            let Some(recipient) = account
+                .as_ref()
+                .and_then(|account| creator::pump_bonding_curve_creator(&account.data))
+            else {
+                continue;
+            };
+            if let Err(err) = self.check_recipient(vault, curve, &recipient).await {
+                warn!(error = ?err, creator_vault = %vault.address, "recipient check failed");
+            }
+        }
+
+        sqlx::query("delete from creator_vault_fee_samples where observed_at < $1")
+            .bind(now - Duration::days(SAMPLE_RETENTION_DAYS))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn load_vaults(&self, now: DateTime<Utc>) -> Result<Vec<WatchedVault>> {
+        let mut candidates = Vec::new();
+        for (raw, financed) in vault_candidates(&self.pool, now).await? {
+            match raw.parse::<Pubkey>() {
+                Ok(address) => candidates.push((address, financed)),
+                Err(err) => warn!(error = ?err, vault = raw, "skipping invalid vault pubkey"),
+            }
+        }
+
+        let addresses: Vec<Pubkey> = candidates.iter().map(|(address, _)| *address).collect();
+        let accounts = self.fetch_accounts(&addresses).await?;
+        let mut vaults = Vec::with_capacity(candidates.len());
+        for ((address, financed), account) in candidates.into_iter().zip(accounts) {
+            let Some(account) = account else {
+                continue;
+            };
+            match CreatorVaultAccount::from_account_data(&account.data) {
+                Ok(state) => vaults.push(WatchedVault {
+                    address,
+                    priority: state.locked && financed,
+                    state,
+                }),
+                Err(err) => warn!(error = ?err, creator_vault = %address, "failed to decode vault"),
+            }
+        }
+        Ok(vaults)
+    }
+
+    async fn fetch_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MULTIPLE_ACCOUNTS_CHUNK) {
+            accounts.extend(self.rpc.get_multiple_accounts(chunk).await?);
+        }
+        Ok(accounts)
+    }
+
+    /// Fees each vault has earned on the launchpad but not yet collected:
+    /// lamports on its Pump.fun creator-fee PDA, or the unclaimed creator
+    /// quote fees of its DBC pool. Collection only moves on cranks, so the
+    /// collected total alone would read an idle crank as a fee drop.
+    async fn launchpad_accruals(&self, vaults: &[WatchedVault]) -> Result<Vec<u64>> {
+        let dbc_vaults: Vec<Pubkey> = vaults
+            .iter()
+            .filter(|vault| vault.state.source_kind == RevenueSourceKind::MeteoraDbc)
+            .map(|vault| creator::revenue_source_pda(&vault.address).0)
+            .collect();
+        let mut dbc_sources = self.fetch_accounts(&dbc_vaults).await?.into_iter();
+        let accrual_accounts: Vec<Option<Pubkey>> = vaults
+            .iter()
+            .map(|vault| match vault.state.source_kind {
+                RevenueSourceKind::PumpFun => Some(creator::pump_creator_fee_pda(&vault.address).0),
+                RevenueSourceKind::MeteoraDbc => dbc_sources
+                    .next()
+                    .flatten()
+                    .and_then(|account| decode_account(&account.data).ok())
+                    .and_then(|source: RevenueSourceAccount| match source.config {
+                        RevenueSourceConfig::MeteoraDbc { pool, .. } => Some(pool),
+                        _ => None,
+                    }),
+                RevenueSourceKind::SplTransfer => None,
+            })
+            .collect();
+        let addresses: Vec<Pubkey> = accrual_accounts.iter().flatten().copied().collect();
+        let mut accounts = self.fetch_accounts(&addresses).await?.into_iter();
+        Ok(vaults
+            .iter()
+            .zip(&accrual_accounts)
+            .map(|(vault, address)| {
+                let account = address.and_then(|_| accounts.next().flatten());
+                launchpad_accrual(vault.state.source_kind, account.as_ref())
+            })
+            .collect())
+    }
+
+    async fn check_fee_rate(
+        &self,
+        vault: &WatchedVault,
+        accrued: u64,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let creator_vault = vault.address.to_string();
+        let total_fees = vault.state.total_fees_collected.saturating_add(accrued);
+        sqlx::query(
+            r#"
+            insert into creator_vault_fee_samples (creator_vault, observed_at, total_fees)
+            values ($1, $2, $3::numeric)
+            on conflict (creator_vault, observed_at) do nothing
+            "#,
+        )
+        .bind(&creator_vault)
+        .bind(now)
+        .bind(total_fees.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        let row = sqlx::query(
+            r#"
+            with samples as (
+                select observed_at, total_fees::float8 as total_fees
+                from creator_vault_fee_samples
+                where creator_vault = $1 and observed_at >= $2
+            ),
+            first_sample as (
+                select observed_at, total_fees from samples order by observed_at asc limit 1
+            ),
+            split_sample as (
+                select observed_at, total_fees from samples
+                where observed_at <= $3
+                order by observed_at desc
+                limit 1
+            )
+            select first_sample.observed_at as first_at, first_sample.total_fees as first_fees,
+                   split_sample.observed_at as split_at, split_sample.total_fees as split_fees
+            from first_sample, split_sample
+            "#,
+        )
+        .bind(&creator_vault)
+        .bind(now - Duration::days(BASELINE_DAYS))
+        .bind(now - Duration::hours(RECENT_HOURS))
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(row) = row else {
+            return Ok(());
+        };
+        let Some(rates) = fee_rates(
+            (row.get("first_at"), row.get("first_fees")),
+            (row.get("split_at"), row.get("split_fees")),
+            (now, total_fees as f64),
+        ) else {
+            return Ok(());
+        };
+
+        match fee_drop_severity(&rates, vault.priority) {
+            Some(severity) => {
+                let ratio = rates.recent_per_day / rates.baseline_per_day;
+                let message = format!(
+                    "fee rate fell to {:.0}% of the {BASELINE_DAYS}d baseline",
+                    ratio * 100.0
+                );
+                let details = json!({
+                    "baseline_per_day": rates.baseline_per_day,
+                    "recent_per_day": rates.recent_per_day,
+                    "ratio": ratio,
+                    "locked": vault.state.locked,
+                });
+                let alert = AlertUpdate {
+                    creator_vault: &creator_vault,
+                    kind: "fee_drop",
+                    severity,
+                    priority: vault.priority,
+                    message,
+                    details,
+                };
+                open_alert(&self.pool, &alert).await
+            }
+            None => resolve_alert(&self.pool, &creator_vault, "fee_drop").await,
+        }
+    }
+
+    async fn check_recipient(
+        &self,
+        vault: &WatchedVault,
+        bonding_curve: &Pubkey,
+        recipient: &Pubkey,
+    ) -> Result<()> {
+        let creator_vault = vault.address.to_string();
+        let row = sqlx::query(
+            r#"
+            insert into launchpad_fee_recipients (creator_vault, launchpad, account, recipient)
+            values ($1, 'pump_fun', $2, $3)
+            on conflict (creator_vault) do update set
+                previous_recipient = case
+                    when launchpad_fee_recipients.recipient <> excluded.recipient
+                        then launchpad_fee_recipients.recipient
+                    else launchpad_fee_recipients.previous_recipient
+                end,
+                changed_at = case
+                    when launchpad_fee_recipients.recipient <> excluded.recipient then now()
+                    else launchpad_fee_recipients.changed_at
+                end,
+                account = excluded.account,
+                recipient = excluded.recipient,
+                observed_at = now()
+            returning previous_recipient, changed_at
+            "#,
+        )
+        .bind(&creator_vault)
+        .bind(bonding_curve.to_string())
+        .bind(recipient.to_string())
+        .fetch_one(&self.pool)
+        .await?;
+        let previous: Option<String> = row.get("previous_recipient");
+        let changed_at: Option<DateTime<Utc>> = row.get("changed_at");
+
+        let expected = expected_recipients(vault.address, &vault.state);
+        let Some(severity) = recipient_severity(recipient, &expected, vault.priority) else {
+            return resolve_alert(&self.pool, &creator_vault, "fee_recipient_rerouted").await;
+        };
+        let details = json!({
+            "launchpad": "pump_fun",
+            "bonding_curve": bonding_curve.to_string(),
+            "recipient": recipient.to_string(),
+            "previous_recipient": previous,
+            "changed_at": changed_at,
+            "expected": expected.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+        });
+        let alert = AlertUpdate {
+            creator_vault: &creator_vault,
+            kind: "fee_recipient_rerouted",
+            severity,
+            priority: vault.priority,
+            message: format!("Pump.fun creator fees now go to {recipient}"),
+            details,
+        };
+        open_alert(&self.pool, &alert).await
+    }
+}
+
+/// Vault addresses paired with whether an advance is outstanding against
+/// them: a filled RFQ quote on one of the vault's markets that has not
+/// matured as of `now`.
+async fn vault_candidates(pool: &PgPool, now: DateTime<Utc>) -> Result<Vec<(String, bool)>> {
+    let rows = sqlx::query(
+        r#"
+        select cv.vault_pubkey,
+               exists (
+                   select 1
+                   from rfq_quotes q
+                   join markets m on m.market_pubkey = q.market
+                   where m.pump_mint = cv.pump_mint
+                     and q.consumed_at is not null
+                     and q.maturity_ts > $1
+               ) as financed
+        from creator_vaults cv
+        where cv.vault_pubkey <> ''
+        "#,
+    )
+    .bind(now.timestamp())
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("vault_pubkey"), row.get("financed")))
+        .collect())
+}
+
+/// Uncollected fees held by a vault's launchpad-side account, zero when the
+/// account is missing or the source has no such account.
+fn launchpad_accrual(kind: RevenueSourceKind, account: Option<&Account>) -> u64 {
+    let Some(account) = account else {
+        return 0;
+    };
+    match kind {
+        RevenueSourceKind::PumpFun => account.lamports,
+        RevenueSourceKind::MeteoraDbc => {
+            creator::dbc_pool_creator_quote_fee(&account.data).unwrap_or_default()
+        }
+        RevenueSourceKind::SplTransfer => 0,
+    }
+}
+
+/// Per-day fee rates from the oldest sample in the baseline window, the last
+/// sample before the recent window and the current total. `None` until the
+/// baseline spans `MIN_BASELINE_HOURS`.
+pub fn fee_rates(
+    first: (DateTime<Utc>, f64),
+    split: (DateTime<Utc>, f64),
+    current: (DateTime<Utc>, f64),
+) -> Option<FeeRates> {
+    let days = |span: Duration| span.num_seconds() as f64 / 86_400.0;
+    let baseline_span = split.0 - first.0;
+    let recent_span = current.0 - split.0;
+    if baseline_span < Duration::hours(MIN_BASELINE_HOURS) || recent_span <= Duration::zero() {
+        return None;
+    }
+    Some(FeeRates {
+        baseline_per_day: (split.1 - first.1).max(0.0) / days(baseline_span),
+        recent_per_day: (current.1 - split.1).max(0.0) / days(recent_span),
+    })
+}
+
+pub fn fee_drop_severity(rates: &FeeRates, priority: bool) -> Option<Severity> {
+    if rates.baseline_per_day <= 0.0 {
+        return None;
+    }
+    let ratio = rates.recent_per_day / rates.baseline_per_day;
+    let severity = if ratio < CRITICAL_RATIO {
+        Severity::Critical
+    } else if ratio < WARNING_RATIO {
+        Severity::Warning
+    } else {
+        return None;
+    };
+    Some(if priority {
+        Severity::Critical
+    } else {
+        severity
+    })
+}
+
+/// Recipients that keep fees flowing into the vault: the vault PDA itself and
+/// the creator recorded at initialization.
+fn expected_recipients(vault: Pubkey, state: &CreatorVaultAccount) -> Vec<Pubkey> {
+    let mut expected = vec![vault];
+    if state.pump_creator != Pubkey::default() && state.pump_creator != vault {
+        expected.push(state.pump_creator);
+    }
+    expected
+}
+
+pub fn recipient_severity(
+    recipient: &Pubkey,
+    expected: &[Pubkey],
+    priority: bool,
+) -> Option<Severity> {
+    if expected.contains(recipient) {
+        None
+    } else if priority {
+        Some(Severity::Critical)
+    } else {
+        Some(Severity::Warning)
+    }
+}
+
+struct AlertUpdate<'a> {
+    creator_vault: &'a str,
+    kind: &'a str,
+    severity: Severity,
+    priority: bool,
+    message: String,
+    details: Value,
+}
+
+/// Opens an alert or refreshes the open one of the same kind; `updated_at`
+/// only moves when severity, priority or message change.
+async fn open_alert(pool: &PgPool, alert: &AlertUpdate<'_>) -> Result<()> {
+    let result = sqlx::query(
+        r#"
+        insert into alerts (creator_vault, kind, severity, priority, message, details)
+        values ($1, $2, $3, $4, $5, $6)
+        on conflict (creator_vault, kind) where resolved_at is null do update set
+            severity = excluded.severity,
+            priority = excluded.priority,
+            message = excluded.message,
+            details = excluded.details,
+            updated_at = case
+                when alerts.severity is distinct from excluded.severity
+                  or alerts.priority is distinct from excluded.priority
+                  or alerts.message is distinct from excluded.message
+                then now()
+                else alerts.updated_at
+            end
+        returning (xmax = 0) as inserted
+        "#,
+    )
+    .bind(alert.creator_vault)
+    .bind(alert.kind)
+    .bind(alert.severity.as_str())
+    .bind(alert.priority)
+    .bind(&alert.message)
+    .bind(&alert.details)
+    .fetch_one(pool)
+    .await?;
+    if result.get::<bool, _>("inserted") {
+        warn!(
+            creator_vault = alert.creator_vault,
+            kind = alert.kind,
+            severity = alert.severity.as_str(),
+            priority = alert.priority,
+            "{}",
+            alert.message
+        );
+    }
+    Ok(())
+}
+
+async fn resolve_alert(pool: &PgPool, creator_vault: &str, kind: &str) -> Result<()> {
+    let result = sqlx::query(
+        r#"
+        update alerts
+        set resolved_at = now(), updated_at = now()
+        where creator_vault = $1 and kind = $2 and resolved_at is null
+        "#,
+    )
+    .bind(creator_vault)
+    .bind(kind)
+    .execute(pool)
+    .await?;
+    if result.rows_affected() > 0 {
+        info!(creator_vault, kind, "alert resolved");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use solana_sdk::signer::Signer;
+
+    fn at(hours: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000, 0).unwrap() + Duration::hours(hours)
+    }
+
+    #[test]
+    fn fee_rates_need_a_seasoned_baseline() {
+        assert!(fee_rates((at(0), 0.0), (at(48), 10.0), (at(72), 20.0)).is_none());
+        let rates = fee_rates((at(0), 0.0), (at(96), 400.0), (at(120), 410.0)).unwrap();
+        assert_eq!(rates.baseline_per_day, 100.0);
+        assert_eq!(rates.recent_per_day, 10.0);
+    }
+
+    #[test]
+    fn fee_drops_escalate_for_financed_vaults() {
+        let rates = |recent_per_day| FeeRates {
+            baseline_per_day: 100.0,
+            recent_per_day,
+        };
+        assert_eq!(fee_drop_severity(&rates(80.0), false), None);
+        assert_eq!(
+            fee_drop_severity(&rates(30.0), false),
+            Some(Severity::Warning)
+        );
+        assert_eq!(
+            fee_drop_severity(&rates(30.0), true),
+            Some(Severity::Critical)
+        );
+        assert_eq!(
+            fee_drop_severity(&rates(0.0), false),
+            Some(Severity::Critical)
+        );
+        let idle = FeeRates {
+            baseline_per_day: 0.0,
+            recent_per_day: 0.0,
+        };
+        assert_eq!(fee_drop_severity(&idle, true), None);
+    }
+
+    #[test]
+    fn rerouted_recipients_are_flagged() {
+        let vault = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let expected = [vault, creator];
+        assert_eq!(recipient_severity(&vault, &expected, true), None);
+        assert_eq!(recipient_severity(&creator, &expected, false), None);
+        let other = Pubkey::new_unique();
+        assert_eq!(
+            recipient_severity(&other, &expected, false),
+            Some(Severity::Warning)
+        );
+        assert_eq!(
+            recipient_severity(&other, &expected, true),
+            Some(Severity::Critical)
+        );
+    }
+
+    /// Pool from `ATTN_INDEXER_TEST_DATABASE_URL`, or `None` when no test
+    /// database is configured and the test should be skipped.
+    async fn test_pool() -> Option<PgPool> {
+        let url = std::env::var("ATTN_INDEXER_TEST_DATABASE_URL").ok()?;
+        let pool = crate::store::connect_pool(&url, 2)
+            .await
+            .expect("connect test database");
+        crate::store::run_migrations(&pool)
+            .await
+            .expect("run migrations");
+        Some(pool)
+    }
+
+    async fn insert_vault_with_market(pool: &PgPool, vault: &str, market: &str, maturity_ts: i64) {
+        let mint = solana_sdk::signature::Keypair::new().pubkey().to_string();
+        sqlx::query(
+            "insert into creator_vaults (pump_mint, vault_pubkey, authority) values ($1, $2, $2)",
+        )
+        .bind(&mint)
+        .bind(vault)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "insert into markets (market_pubkey, pump_mint, maturity_ts, pt_mint, yt_mint, created_slot)
+             values ($1, $2, $3, '', '', 0)",
+        )
+        .bind(market)
+        .bind(&mint)
+        .bind(maturity_ts)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn insert_quote(pool: &PgPool, market: &str, maturity_ts: i64, filled: bool) {
+        sqlx::query(
+            r#"
+            insert into rfq_quotes (
+                id, quote_key, market, side, route, maturity_ts, size_yt, price_usdc,
+                implied_apr, est_slippage, cursor, expires_at, consumed_at
+            )
+            values (
+                gen_random_uuid(), gen_random_uuid()::text, $1, 'sell', 'rfq', $2, 1, 1,
+                0, 0, '', now(), case when $3 then now() end
+            )
+            "#,
+        )
+        .bind(market)
+        .bind(maturity_ts)
+        .bind(filled)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn vaults_with_filled_unmatured_quotes_are_financed() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let now = at(0);
+        let maturity = (now + Duration::days(30)).timestamp();
+        let matured = (now - Duration::days(1)).timestamp();
+        // Random keys: the test database outlives a single run.
+        let key = || solana_sdk::signature::Keypair::new().pubkey().to_string();
+        let (financed, open_quote, expired) = (key(), key(), key());
+        let (financed_market, open_market, expired_market) = (key(), key(), key());
+
+        insert_vault_with_market(&pool, &financed, &financed_market, maturity).await;
+        insert_quote(&pool, &financed_market, maturity, true).await;
+        insert_vault_with_market(&pool, &open_quote, &open_market, maturity).await;
+        insert_quote(&pool, &open_market, maturity, false).await;
+        insert_vault_with_market(&pool, &expired, &expired_market, matured).await;
+        insert_quote(&pool, &expired_market, matured, true).await;
+
+        let candidates = vault_candidates(&pool, now).await.unwrap();
+        let flag = |vault: &str| {
+            candidates
+                .iter()
+                .find(|(address, _)| address == vault)
+                .map(|(_, financed)| *financed)
+        };
+        assert_eq!(flag(&financed), Some(true));
+        assert_eq!(flag(&open_quote), Some(false));
+        assert_eq!(flag(&expired), Some(false));
+    }
+
+    #[test]
+    fn bonding_curve_creator_is_decoded() {
+        let creator = Pubkey::new_unique();
+        let mut data = vec![0u8; 8 + 5 * 8 + 1];
+        data.extend_from_slice(creator.as_ref());
+        assert_eq!(creator::pump_bonding_curve_creator(&data), Some(creator));
+        assert_eq!(creator::pump_bonding_curve_creator(&data[..60]), None);
+    }
+
+    #[test]
+    fn launchpad_accruals_are_read_per_source() {
+        let account = |lamports, data| Account {
+            lamports,
+            data,
+            ..Account::default()
+        };
+        let mut pool = vec![0u8; 360];
+        pool.extend_from_slice(&42u64.to_le_bytes());
+        pool.extend_from_slice(&[0u8; 56]);
+        let pool = account(1_000, pool);
+        assert_eq!(
+            launchpad_accrual(RevenueSourceKind::MeteoraDbc, Some(&pool)),
+            42
+        );
+        assert_eq!(
+            launchpad_accrual(RevenueSourceKind::PumpFun, Some(&pool)),
+            1_000
+        );
+        assert_eq!(
+            launchpad_accrual(RevenueSourceKind::SplTransfer, Some(&pool)),
+            0
+        );
+        assert_eq!(launchpad_accrual(RevenueSourceKind::PumpFun, None), 0);
+        let truncated = account(0, vec![0u8; 100]);
+        assert_eq!(
+            launchpad_accrual(RevenueSourceKind::MeteoraDbc, Some(&truncated)),
+            0
+        );
+    }
+}
@@ -0,0 +1,477 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use attn_webhook as webhook;
+use serde_json::Value;
+use sqlx::postgres::PgListener;
+use sqlx::{PgPool, Row};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use crate::events::{EventBatch, EventSource, NotificationEvent, EVENTS_CHANNEL};
+use crate::sinks::{Notification, SinkError, Sinks};
+use crate::subscriptions::{active_subscriptions, Subscription};
+
+const RETRY_BASE: Duration = Duration::from_secs(30);
+const RETRY_MAX: Duration = Duration::from_secs(3_600);
+/// Claimed deliveries are hidden from other dispatchers for this long.
+const CLAIM_LEASE_SECS: f64 = 120.0;
+
+#[derive(Debug, Clone)]
+pub struct DispatcherConfig {
+    /// Fallback poll when no `NOTIFY` arrives; also paces retries.
+    pub poll_interval: Duration,
+    pub batch_size: i64,
+    /// Attempts before a delivery is marked `failed`.
+    pub max_attempts: i32,
+}
+
+impl Default for DispatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(10),
+            batch_size: 100,
+            max_attempts: 8,
+        }
+    }
+}
+
+/// Backoff before retry number `attempts + 1`: 30s doubling up to an hour.
+pub fn retry_delay(attempts: i32) -> Duration {
+    webhook::retry_delay(attempts, RETRY_BASE, RETRY_MAX)
+}
+
+/// Matches new events against subscriptions, records one delivery per
+/// (subscription, event) and drains due deliveries through the sinks.
+pub struct Dispatcher {
+    pool: PgPool,
+    source: EventSource,
+    sinks: Sinks,
+    config: DispatcherConfig,
+}
+
+impl Dispatcher {
+    pub fn new(pool: PgPool, sinks: Sinks, config: DispatcherConfig) -> Self {
+        Self {
+            source: EventSource::new(pool.clone()),
+            pool,
+            sinks,
+            config,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        info!(
+            poll_interval_secs = self.config.poll_interval.as_secs(),
+            sinks = ?self.sinks.kinds(),
+            "notification dispatcher started"
+        );
+        let mut listener = None;
+        loop {
+            if let Err(err) = self.tick().await {
+                warn!(error = ?err, "notification pass failed");
+            }
+            if listener.is_none() {
+                listener = match self.listen().await {
+                    Ok(listener) => Some(listener),
+                    Err(err) => {
+                        warn!(error = ?err, "event listener unavailable; polling only");
+                        None
+                    }
+                };
+            }
+            match listener.as_mut() {
+                Some(active) => {
+                    tokio::select! {
+                        received = active.recv() => {
+                            if let Err(err) = received {
+                                warn!(error = ?err, "event listener dropped");
+                                listener = None;
+                            }
+                        }
+                        _ = sleep(self.config.poll_interval) => {}
+                    }
+                }
+                None => sleep(self.config.poll_interval).await,
+            }
+        }
+    }
+
+    async fn listen(&self) -> Result<PgListener> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(EVENTS_CHANNEL).await?;
+        Ok(listener)
+    }
+
+    /// One enqueue and delivery pass.
+    pub async fn tick(&self) -> Result<()> {
+        let batch = self.source.poll().await?;
+        if !batch.events.is_empty() {
+            let subscriptions = active_subscriptions(&self.pool).await?;
+            let queued = self.enqueue(&batch, &subscriptions).await?;
+            if queued > 0 {
+                info!(events = batch.events.len(), queued, "queued notifications");
+            }
+        }
+        self.deliver_due().await
+    }
+
+    /// Records the batch's deliveries and advances the cursors past it in one
+    /// transaction, so every polled event is queued at least once.
+    async fn enqueue(&self, batch: &EventBatch, subscriptions: &[Subscription]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut queued = 0;
+        for event in &batch.events {
+            let payload = serde_json::to_value(event)?;
+            for subscription in subscriptions.iter().filter(|sub| sub.matches(event)) {
+                // Re-polled events hit the unique key and are skipped.
+                let result = sqlx::query(
+                    r#"
+                    insert into notification_deliveries (subscription_id, event_key, event)
+                    values ($1, $2, $3)
+                    on conflict (subscription_id, event_key) do nothing
+                    "#,
+                )
+                .bind(subscription.id)
+                .bind(&event.key)
+                .bind(&payload)
+                .execute(&mut *tx)
+                .await?;
+                queued += result.rows_affected();
+            }
+        }
+        batch.advance(&mut tx).await?;
+        tx.commit().await?;
+        Ok(queued)
+    }
+
+    async fn deliver_due(&self) -> Result<()> {
+        let rows = sqlx::query(
+            r#"
+            with due as (
+                select id
+                from notification_deliveries
+                where status = 'pending' and next_attempt_at <= now()
+                order by next_attempt_at asc
+                limit $1
+                for update skip locked
+            )
+            update notification_deliveries d
+            set next_attempt_at = now() + make_interval(secs => $2)
+            from due, notification_subscriptions s
+            where d.id = due.id and s.id = d.subscription_id
+            returning d.id, d.event, d.attempts, s.id as subscription_id, s.wallet,
+                      s.creator_vault, s.kinds, s.sink, s.target, s.secret, s.active
+            "#,
+        )
+        .bind(self.config.batch_size)
+        .bind(CLAIM_LEASE_SECS)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for row in rows {
+            let delivery_id: i64 = row.get("id");
+            let attempts: i32 = row.get::<i32, _>("attempts") + 1;
+            let outcome = match self.prepare(&row) {
+                Ok((subscription, notification)) => self.send(&subscription, &notification).await,
+                Err(err) => Err(err),
+            };
+            self.record(delivery_id, attempts, outcome).await?;
+        }
+        Ok(())
+    }
+
+    fn prepare(
+        &self,
+        row: &sqlx::postgres::PgRow,
+    ) -> Result<(Subscription, Notification), SinkError> {
+        if !row.get::<bool, _>("active") {
+            return Err(SinkError::permanent("subscription is no longer active"));
+        }
+        let sink = row
+            .get::<String, _>("sink")
+            .parse()
+            .map_err(|err: anyhow::Error| SinkError::permanent(err.to_string()))?;
+        let subscription = Subscription {
+            id: row.get("subscription_id"),
+            wallet: row.get("wallet"),
+            creator_vault: row.get("creator_vault"),
+            kinds: row.get("kinds"),
+            sink,
+            target: row.get("target"),
+            secret: row.get("secret"),
+        };
+        let event: NotificationEvent = serde_json::from_value(row.get::<Value, _>("event"))
+            .map_err(|err| SinkError::permanent(format!("malformed event: {err}")))?;
+        let notification = Notification {
+            delivery_id: row.get("id"),
+            subscription_id: subscription.id,
+            wallet: subscription.wallet.clone(),
+            event,
+        };
+        Ok((subscription, notification))
+    }
+
+    async fn send(
+        &self,
+        subscription: &Subscription,
+        notification: &Notification,
+    ) -> Result<(), SinkError> {
+        let sink = self.sinks.get(subscription.sink).ok_or_else(|| {
+            SinkError::permanent(format!("{} sink is not configured", subscription.sink))
+        })?;
+        sink.send(subscription, notification).await
+    }
+
+    async fn record(
+        &self,
+        delivery_id: i64,
+        attempts: i32,
+        outcome: Result<(), SinkError>,
+    ) -> Result<()> {
+        match outcome {
+            Ok(()) => {
+                sqlx::query(
+                    r#"
+                    update notification_deliveries
+                    set status = 'delivered', attempts = $2, delivered_at = now(), last_error = null
+                    where id = $1
+                    "#,
+                )
+                .bind(delivery_id)
+                .bind(attempts)
+                .execute(&self.pool)
+                .await?;
+            }
+            Err(err) => {
+                let give_up = !err.retryable || attempts >= self.config.max_attempts;
+                warn!(delivery_id, attempts, give_up, error = %err, "notification delivery failed");
+                sqlx::query(
+                    r#"
+                    update notification_deliveries
+                    set status = case when $3 then 'failed' else 'pending' end,
+                        attempts = $2,
+                        last_error = $4,
+                        next_attempt_at = now() + make_interval(secs => $5)
+                    where id = $1
+                    "#,
+                )
+                .bind(delivery_id)
+                .bind(attempts)
+                .bind(give_up)
+                .bind(&err.message)
+                .bind(retry_delay(attempts).as_secs_f64())
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    use super::*;
+    use crate::events::BATCH_LIMIT;
+    use crate::sinks::{LocalSink, Sink};
+    use crate::subscriptions::SinkKind;
+
+    #[test]
+    fn retries_back_off_exponentially_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(8), Duration::from_secs(3_600));
+        assert_eq!(retry_delay(i32::MAX), Duration::from_secs(3_600));
+    }
+
+    /// Pool from `ATTN_NOTIFIER_TEST_DATABASE_URL`, or `None` when no test
+    /// database is configured and the test should be skipped.
+    async fn test_pool() -> Option<PgPool> {
+        let url = std::env::var("ATTN_NOTIFIER_TEST_DATABASE_URL").ok()?;
+        let pool = attn_indexer::connect_pool(&url, 2)
+            .await
+            .expect("connect test database");
+        attn_indexer::run_migrations(&pool)
+            .await
+            .expect("run migrations");
+        Some(pool)
+    }
+
+    /// Fresh base58-looking string; the test database outlives a single run.
+    fn random_address() -> String {
+        const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+        (0..40)
+            .map(|_| {
+                let value = RandomState::new().build_hasher().finish();
+                ALPHABET[(value % ALPHABET.len() as u64) as usize] as char
+            })
+            .collect()
+    }
+
+    /// Telegram stand-in whose endpoint is always down.
+    struct UnreachableSink;
+
+    #[async_trait]
+    impl Sink for UnreachableSink {
+        fn kind(&self) -> SinkKind {
+            SinkKind::Telegram
+        }
+
+        async fn send(
+            &self,
+            _subscription: &Subscription,
+            _notification: &Notification,
+        ) -> Result<(), SinkError> {
+            Err(SinkError::retryable(
+                "endpoint returned 503 Service Unavailable",
+            ))
+        }
+    }
+
+    async fn subscribe(pool: &PgPool, wallet: &str, sink: SinkKind) -> i64 {
+        sqlx::query(
+            "insert into notification_subscriptions (wallet, sink, target)
+             values ($1, $2, 'test') returning id",
+        )
+        .bind(wallet)
+        .bind(sink.as_str())
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .get("id")
+    }
+
+    async fn delivery(pool: &PgPool, subscription_id: i64) -> (String, i32, Option<String>, f64) {
+        let row = sqlx::query(
+            r#"
+            select status, attempts, last_error,
+                   extract(epoch from next_attempt_at - now())::float8 as retry_in
+            from notification_deliveries
+            where subscription_id = $1
+            "#,
+        )
+        .bind(subscription_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        (
+            row.get("status"),
+            row.get("attempts"),
+            row.get("last_error"),
+            row.get("retry_in"),
+        )
+    }
+
+    #[tokio::test]
+    async fn events_are_delivered_retried_and_dead_lettered() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let local = LocalSink::default();
+        let sinks = Sinks::default().with(local.clone()).with(UnreachableSink);
+        let config = DispatcherConfig {
+            batch_size: 1_000,
+            max_attempts: 2,
+            ..DispatcherConfig::default()
+        };
+        let dispatcher = Dispatcher::new(pool.clone(), sinks, config);
+        // Creates the cursors so the rows below are read.
+        dispatcher.tick().await.unwrap();
+
+        let wallet = random_address();
+        let local_id = subscribe(&pool, &wallet, SinkKind::Local).await;
+        let telegram_id = subscribe(&pool, &wallet, SinkKind::Telegram).await;
+        let sig = random_address();
+        sqlx::query(
+            "insert into events (sig, slot, program, kind, payload)
+             values ($1, 9, 'creator_vault', 'fees_swept', $2)",
+        )
+        .bind(&sig)
+        .bind(json!({ "authority": wallet, "amount": 5 }))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        dispatcher.tick().await.unwrap();
+        let delivered: Vec<Notification> = local
+            .deliveries()
+            .into_iter()
+            .filter(|notification| notification.subscription_id == local_id)
+            .collect();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].event.key, sig);
+        assert_eq!(delivered[0].wallet, wallet);
+        let (status, attempts, _, _) = delivery(&pool, local_id).await;
+        assert_eq!((status.as_str(), attempts), ("delivered", 1));
+
+        let (status, attempts, last_error, retry_in) = delivery(&pool, telegram_id).await;
+        assert_eq!((status.as_str(), attempts), ("pending", 1));
+        assert!(last_error.unwrap().contains("503"));
+        let backoff = retry_delay(1).as_secs_f64();
+        assert!(retry_in > backoff - 5.0 && retry_in <= backoff);
+
+        // Not due yet: another pass leaves it alone.
+        dispatcher.tick().await.unwrap();
+        assert_eq!(delivery(&pool, telegram_id).await.1, 1);
+
+        sqlx::query(
+            "update notification_deliveries set next_attempt_at = now() where subscription_id = $1",
+        )
+        .bind(telegram_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        dispatcher.tick().await.unwrap();
+        let (status, attempts, _, _) = delivery(&pool, telegram_id).await;
+        assert_eq!((status.as_str(), attempts), ("failed", 2));
+        let local_count = local
+            .deliveries()
+            .iter()
+            .filter(|notification| notification.subscription_id == local_id)
+            .count();
+        assert_eq!(local_count, 1);
+
+        // A burst sharing one timestamp spans batches instead of pinning the
+        // cursor.
+        let burst = random_address();
+        sqlx::query(
+            r#"
+            insert into events (sig, slot, program, kind, payload)
+            select $1 || lpad(n::text, 4, '0'), n, 'creator_vault', 'fees_swept', '{}'::jsonb
+            from generate_series(1, $2) as n
+            "#,
+        )
+        .bind(&burst)
+        .bind(BATCH_LIMIT + 1)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let in_burst = |batch: &EventBatch| {
+            batch
+                .events
+                .iter()
+                .filter(|event| event.key.starts_with(&burst))
+                .count()
+        };
+        // Until a batch is advanced it is polled again.
+        let first = dispatcher.source.poll().await.unwrap();
+        let replayed = dispatcher.source.poll().await.unwrap();
+        assert_eq!(in_burst(&first), in_burst(&replayed));
+        let mut seen = 0;
+        for _ in 0..2 {
+            let batch = dispatcher.source.poll().await.unwrap();
+            seen += in_burst(&batch);
+            let mut tx = pool.begin().await.unwrap();
+            batch.advance(&mut tx).await.unwrap();
+            tx.commit().await.unwrap();
+        }
+        assert_eq!(seen as i64, BATCH_LIMIT + 1);
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{PgConnection, PgPool, Row};
+
+/// `NOTIFY` channel the `events` insert trigger publishes signatures on.
+pub const EVENTS_CHANNEL: &str = "attn_events";
+
+pub(crate) const BATCH_LIMIT: i64 = 500;
+
+const EVENTS_SOURCE: &str = "events";
+const SAFES_SOURCE: &str = "squads_safe_requests";
+
+/// Something a subscriber can be notified about: an indexed program event or
+/// a Squads safe request status change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationEvent {
+    /// Dedupe key: the transaction signature for program events,
+    /// `squads_safe:<id>:<status>` for safe status changes.
+    pub key: String,
+    pub kind: String,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    pub occurred_at: DateTime<Utc>,
+    pub payload: Value,
+}
+
+impl NotificationEvent {
+    /// Base58-looking strings anywhere in the payload; subscriptions match
+    /// their wallet or creator vault against these.
+    pub fn addresses(&self) -> BTreeSet<&str> {
+        let mut addresses = BTreeSet::new();
+        collect_addresses(&self.payload, &mut addresses);
+        addresses
+    }
+
+    pub fn references(&self, address: &str) -> bool {
+        self.addresses().contains(address)
+    }
+
+    /// One-line human readable summary for chat and email sinks.
+    pub fn summary(&self) -> String {
+        let title = self.kind.replace('_', " ");
+        match self.slot {
+            Some(slot) => format!("{title} ({} at slot {slot})", self.source),
+            None => format!("{title} ({})", self.source),
+        }
+    }
+
+    /// Summary followed by the top-level payload fields, one per line.
+    pub fn render_text(&self) -> String {
+        let mut text = self.summary();
+        if let Value::Object(fields) = &self.payload {
+            for (name, value) in fields {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    Value::Null => continue,
+                    other => other.to_string(),
+                };
+                text.push_str(&format!("\n{name}: {value}"));
+            }
+        }
+        if self.source != SAFES_SOURCE {
+            text.push_str(&format!("\nsignature: {}", self.key));
+        }
+        text
+    }
+}
+
+fn collect_addresses<'a>(value: &'a Value, out: &mut BTreeSet<&'a str>) {
+    match value {
+        Value::String(text) if looks_like_address(text) => {
+            out.insert(text);
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_addresses(item, out)),
+        Value::Object(fields) => fields
+            .values()
+            .for_each(|field| collect_addresses(field, out)),
+        _ => {}
+    }
+}
+
+fn looks_like_address(text: &str) -> bool {
+    (32..=44).contains(&text.len())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l'))
+}
+
+/// Events read past the persisted cursors, with the positions the cursors
+/// move to once the events are queued.
+#[derive(Debug, Default)]
+pub struct EventBatch {
+    pub events: Vec<NotificationEvent>,
+    cursors: Vec<(&'static str, DateTime<Utc>, String)>,
+}
+
+impl EventBatch {
+    /// Moves the cursors past this batch. Runs in the transaction that queues
+    /// the batch's deliveries, so a failure between the two replays the batch
+    /// rather than dropping it.
+    pub async fn advance(&self, conn: &mut PgConnection) -> Result<()> {
+        for (source, position, key) in &self.cursors {
+            sqlx::query(
+                r#"
+                update notifier_cursors
+                set position = $2, last_key = $3, updated_at = now()
+                where source = $1 and (position, last_key) < ($2, $3)
+                "#,
+            )
+            .bind(source)
+            .bind(position)
+            .bind(key)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Polls `events` and `squads_safe_requests` past persisted cursors. Each
+/// cursor is a (timestamp, key) pair and rows are read in that order, so a
+/// full batch always moves it forward once advanced.
+pub struct EventSource {
+    pool: PgPool,
+}
+
+impl EventSource {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Events recorded since the last advanced batch. A fresh database starts
+    /// at the current time rather than replaying history.
+    pub async fn poll(&self) -> Result<EventBatch> {
+        let mut batch = EventBatch::default();
+        self.poll_program_events(&mut batch).await?;
+        self.poll_safe_changes(&mut batch).await?;
+        Ok(batch)
+    }
+
+    async fn cursor(&self, source: &str) -> Result<(DateTime<Utc>, String)> {
+        let row = sqlx::query(
+            r#"
+            insert into notifier_cursors (source, position)
+            values ($1, now())
+            on conflict (source) do update set source = excluded.source
+            returning position, last_key
+            "#,
+        )
+        .bind(source)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((row.get("position"), row.get("last_key")))
+    }
+
+    async fn poll_program_events(&self, batch: &mut EventBatch) -> Result<()> {
+        let (position, last_sig) = self.cursor(EVENTS_SOURCE).await?;
+        let rows = sqlx::query(
+            r#"
+            select sig, slot, program, kind, payload, ts
+            from events
+            where (ts, sig) > ($1, $2)
+            order by ts asc, sig asc
+            limit $3
+            "#,
+        )
+        .bind(position)
+        .bind(&last_sig)
+        .bind(BATCH_LIMIT)
+        .fetch_all(&self.pool)
+        .await?;
+        let events: Vec<NotificationEvent> = rows
+            .into_iter()
+            .map(|row| NotificationEvent {
+                key: row.get("sig"),
+                kind: row.get("kind"),
+                source: row.get("program"),
+                slot: Some(row.get::<i64, _>("slot") as u64),
+                occurred_at: row.get("ts"),
+                payload: row.get("payload"),
+            })
+            .collect();
+        if let Some(last) = events.last() {
+            batch
+                .cursors
+                .push((EVENTS_SOURCE, last.occurred_at, last.key.clone()));
+        }
+        batch.events.extend(events);
+        Ok(())
+    }
+
+    async fn poll_safe_changes(&self, batch: &mut EventBatch) -> Result<()> {
+        let (position, last_id) = self.cursor(SAFES_SOURCE).await?;
+        let rows = sqlx::query(
+            r#"
+            select id::text as id, status::text as status, creator_wallet, creator_vault,
+                   safe_address, error_code, updated_at
+            from squads_safe_requests
+            where (updated_at, id::text) > ($1, $2)
+            order by updated_at asc, id::text asc
+            limit $3
+            "#,
+        )
+        .bind(position)
+        .bind(&last_id)
+        .bind(BATCH_LIMIT)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut last = None;
+        let events: Vec<NotificationEvent> = rows
+            .into_iter()
+            .map(|row| {
+                let id: String = row.get("id");
+                let status: String = row.get("status");
+                last = Some((row.get("updated_at"), id.clone()));
+                NotificationEvent {
+                    key: format!("squads_safe:{id}:{status}"),
+                    kind: format!("squads_safe_{status}"),
+                    source: SAFES_SOURCE.to_string(),
+                    slot: None,
+                    occurred_at: row.get("updated_at"),
+                    payload: json!({
+                        "request_id": id,
+                        "status": status,
+                        "creator_wallet": row.get::<String, _>("creator_wallet"),
+                        "creator_vault": row.get::<Option<String>, _>("creator_vault"),
+                        "safe_address": row.get::<Option<String>, _>("safe_address"),
+                        "error_code": row.get::<Option<String>, _>("error_code"),
+                    }),
+                }
+            })
+            .collect();
+        if let Some((updated_at, id)) = last {
+            batch.cursors.push((SAFES_SOURCE, updated_at, id));
+        }
+        batch.events.extend(events);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_are_collected_from_nested_payloads() {
+        let event = NotificationEvent {
+            key: "sig".into(),
+            kind: "creator_rewards_split".into(),
+            source: "creator_vault".into(),
+            slot: Some(7),
+            occurred_at: Utc::now(),
+            payload: json!({
+                "creator_vault": "CreatorVau1t1111111111111111111111111111111",
+                "amount": 5,
+                "status": "ready",
+                "splits": [{ "wallet": "Wa11et11111111111111111111111111111111111111" }],
+            }),
+        };
+        assert!(event.references("CreatorVau1t1111111111111111111111111111111"));
+        assert!(event.references("Wa11et11111111111111111111111111111111111111"));
+        assert!(!event.references("ready"));
+        assert_eq!(event.addresses().len(), 2);
+        let text = event.render_text();
+        assert!(text.starts_with("creator rewards split (creator_vault at slot 7)"));
+        assert!(text.contains("\namount: 5"));
+        assert!(text.ends_with("signature: sig"));
+    }
+}
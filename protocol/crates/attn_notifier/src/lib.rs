@@ -0,0 +1,5 @@
+pub mod dispatcher;
+pub mod events;
+pub mod sinks;
+pub mod smtp;
+pub mod subscriptions;
@@ -1,9 +1,35 @@
-use anyhow::Result;
+use std::env;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use attn_indexer::{connect_pool, run_migrations};
+use attn_notifier::{
+    dispatcher::{Dispatcher, DispatcherConfig},
+    sinks::Sinks,
+};
 use tracing::{info, Level};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt().with_max_level(Level::INFO).init();
-    info!("attn_notifier started (stub)");
-    Ok(())
+    let database_url =
+        env::var("ATTN_NOTIFIER_DATABASE_URL").context("ATTN_NOTIFIER_DATABASE_URL required")?;
+    let pool = connect_pool(&database_url, 5).await?;
+    run_migrations(&pool).await?;
+    let sinks = Sinks::from_env()?;
+    let config = DispatcherConfig {
+        poll_interval: parse_poll_interval(),
+        ..DispatcherConfig::default()
+    };
+    info!("attn_notifier starting");
+    Dispatcher::new(pool, sinks, config).run().await
+}
+
+fn parse_poll_interval() -> Duration {
+    let secs = env::var("ATTN_NOTIFIER_POLL_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(10)
+        .max(1);
+    Duration::from_secs(secs)
 }
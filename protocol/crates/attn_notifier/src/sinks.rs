@@ -0,0 +1,503 @@
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use attn_webhook::{
+    self as webhook, sign_payload, DELIVERY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use axum::{extract::State, routing::get, Json, Router};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info};
+
+use crate::events::NotificationEvent;
+use crate::smtp::{self, SmtpConfig};
+use crate::subscriptions::{SinkKind, Subscription};
+
+const HTTP_TIMEOUT_SECS: u64 = 10;
+/// Deliveries the local sink keeps for inspection.
+const LOCAL_SINK_CAPACITY: usize = 200;
+const DEFAULT_TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// What a sink delivers: one event for one subscription.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Notification {
+    pub delivery_id: i64,
+    pub subscription_id: i64,
+    pub wallet: String,
+    pub event: NotificationEvent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkError {
+    pub message: String,
+    /// Whether the dispatcher should try again later.
+    pub retryable: bool,
+}
+
+impl SinkError {
+    pub fn retryable(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: true,
+        }
+    }
+
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: false,
+        }
+    }
+
+    fn from_status(status: StatusCode, body: &str) -> Self {
+        let message = format!("endpoint returned {status}: {body}");
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            Self::retryable(message)
+        } else {
+            Self::permanent(message)
+        }
+    }
+}
+
+impl fmt::Display for SinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<reqwest::Error> for SinkError {
+    fn from(err: reqwest::Error) -> Self {
+        SinkError::retryable(err.to_string())
+    }
+}
+
+#[async_trait]
+pub trait Sink: Send + Sync {
+    fn kind(&self) -> SinkKind;
+
+    async fn send(
+        &self,
+        subscription: &Subscription,
+        notification: &Notification,
+    ) -> Result<(), SinkError>;
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default()
+}
+
+/// POSTs the notification as JSON to the subscription's URL, signed with its
+/// secret. Targets on private, loopback or link-local addresses are refused
+/// unless the sink is built with `allow_private`.
+pub struct WebhookSink {
+    client: reqwest::Client,
+    allow_private: bool,
+}
+
+impl WebhookSink {
+    pub fn new(allow_private: bool) -> Result<Self> {
+        Ok(Self {
+            client: webhook::client(Duration::from_secs(HTTP_TIMEOUT_SECS), allow_private)?,
+            allow_private,
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for WebhookSink {
+    fn kind(&self) -> SinkKind {
+        SinkKind::Webhook
+    }
+
+    async fn send(
+        &self,
+        subscription: &Subscription,
+        notification: &Notification,
+    ) -> Result<(), SinkError> {
+        let secret = subscription
+            .secret
+            .as_deref()
+            .filter(|secret| !secret.is_empty())
+            .ok_or_else(|| SinkError::permanent("webhook subscription has no secret"))?;
+        let url = webhook::check_url(&subscription.target, self.allow_private)
+            .map_err(SinkError::permanent)?;
+        let body = serde_json::to_vec(notification)
+            .map_err(|err| SinkError::permanent(err.to_string()))?;
+        let timestamp = chrono::Utc::now().timestamp();
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                format!("v1={}", sign_payload(secret, timestamp, &body)),
+            )
+            .header(DELIVERY_HEADER, notification.delivery_id.to_string())
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(SinkError::from_status(status, &body))
+    }
+}
+
+/// Sends the rendered event to the subscription's chat id through the
+/// Telegram Bot API.
+pub struct TelegramSink {
+    client: reqwest::Client,
+    api_url: String,
+    bot_token: String,
+}
+
+impl TelegramSink {
+    pub fn new(api_url: impl Into<String>, bot_token: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            bot_token: bot_token.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Sink for TelegramSink {
+    fn kind(&self) -> SinkKind {
+        SinkKind::Telegram
+    }
+
+    async fn send(
+        &self,
+        subscription: &Subscription,
+        notification: &Notification,
+    ) -> Result<(), SinkError> {
+        let url = format!("{}/bot{}/sendMessage", self.api_url, self.bot_token);
+        let response = self
+            .client
+            .post(url)
+            .json(&json!({
+                "chat_id": subscription.target,
+                "text": notification.event.render_text(),
+                "disable_web_page_preview": true,
+            }))
+            .send()
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(SinkError::from_status(status, &body))
+    }
+}
+
+/// Mails the rendered event to the subscription's address.
+pub struct SmtpSink {
+    config: SmtpConfig,
+}
+
+impl SmtpSink {
+    pub fn new(config: SmtpConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Sink for SmtpSink {
+    fn kind(&self) -> SinkKind {
+        SinkKind::Smtp
+    }
+
+    async fn send(
+        &self,
+        subscription: &Subscription,
+        notification: &Notification,
+    ) -> Result<(), SinkError> {
+        let subject = format!("[attn] {}", notification.event.summary());
+        smtp::send_mail(
+            &self.config,
+            &subscription.target,
+            &subject,
+            &notification.event.render_text(),
+        )
+        .await
+        .map_err(|err| SinkError {
+            message: err.to_string(),
+            retryable: err.is_transient(),
+        })
+    }
+}
+
+/// Keeps recent deliveries in memory and serves them on
+/// `GET /notifications`, so subscriptions can be exercised without any
+/// external service.
+#[derive(Clone, Default)]
+pub struct LocalSink {
+    deliveries: Arc<Mutex<VecDeque<Notification>>>,
+}
+
+impl LocalSink {
+    /// Most recent deliveries, newest first.
+    pub fn deliveries(&self) -> Vec<Notification> {
+        let deliveries = self.deliveries.lock().expect("local sink lock poisoned");
+        deliveries.iter().rev().cloned().collect()
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/notifications", get(list_local_notifications))
+            .with_state(self.clone())
+    }
+
+    pub fn serve(&self, addr: SocketAddr) {
+        let router = self.router();
+        tokio::spawn(async move {
+            let listener = match tokio::net::TcpListener::bind(addr).await {
+                Ok(listener) => listener,
+                Err(err) => {
+                    error!(error = ?err, %addr, "failed to bind local sink");
+                    return;
+                }
+            };
+            info!(%addr, "local notification sink listening");
+            if let Err(err) = axum::serve(listener, router).await {
+                error!(error = ?err, "local notification sink stopped");
+            }
+        });
+    }
+}
+
+async fn list_local_notifications(State(sink): State<LocalSink>) -> Json<Vec<Notification>> {
+    Json(sink.deliveries())
+}
+
+#[async_trait]
+impl Sink for LocalSink {
+    fn kind(&self) -> SinkKind {
+        SinkKind::Local
+    }
+
+    async fn send(
+        &self,
+        _subscription: &Subscription,
+        notification: &Notification,
+    ) -> Result<(), SinkError> {
+        let mut deliveries = self.deliveries.lock().expect("local sink lock poisoned");
+        if deliveries.len() == LOCAL_SINK_CAPACITY {
+            deliveries.pop_front();
+        }
+        deliveries.push_back(notification.clone());
+        Ok(())
+    }
+}
+
+/// Configured sinks by kind. Deliveries for a kind without a sink fail
+/// permanently.
+#[derive(Clone, Default)]
+pub struct Sinks {
+    sinks: HashMap<SinkKind, Arc<dyn Sink>>,
+}
+
+impl Sinks {
+    pub fn with(mut self, sink: impl Sink + 'static) -> Self {
+        self.sinks.insert(sink.kind(), Arc::new(sink));
+        self
+    }
+
+    pub fn get(&self, kind: SinkKind) -> Option<Arc<dyn Sink>> {
+        self.sinks.get(&kind).cloned()
+    }
+
+    pub fn kinds(&self) -> Vec<SinkKind> {
+        let mut kinds: Vec<_> = self.sinks.keys().copied().collect();
+        kinds.sort_by_key(|kind| kind.as_str());
+        kinds
+    }
+
+    /// Webhooks are always available; SMTP, Telegram and the local sink are
+    /// enabled by their `ATTN_NOTIFIER_*` settings.
+    /// `ATTN_NOTIFIER_WEBHOOK_ALLOW_PRIVATE=1` lets webhooks reach private
+    /// addresses for local development.
+    pub fn from_env() -> Result<Self> {
+        let allow_private = env::var("ATTN_NOTIFIER_WEBHOOK_ALLOW_PRIVATE")
+            .is_ok_and(|raw| matches!(raw.as_str(), "1" | "true"));
+        let mut sinks = Sinks::default().with(WebhookSink::new(allow_private)?);
+        if let Ok(addr) = env::var("ATTN_NOTIFIER_SMTP_ADDR") {
+            let from = env::var("ATTN_NOTIFIER_SMTP_FROM")
+                .map_err(|_| anyhow::anyhow!("ATTN_NOTIFIER_SMTP_FROM required with SMTP"))?;
+            sinks = sinks.with(SmtpSink::new(SmtpConfig {
+                addr,
+                from,
+                username: env::var("ATTN_NOTIFIER_SMTP_USERNAME").ok(),
+                password: env::var("ATTN_NOTIFIER_SMTP_PASSWORD").ok(),
+                hello_name: env::var("ATTN_NOTIFIER_SMTP_HELO")
+                    .unwrap_or_else(|_| "attn-notifier".to_string()),
+            }));
+        }
+        if let Ok(token) = env::var("ATTN_NOTIFIER_TELEGRAM_BOT_TOKEN") {
+            let api_url = env::var("ATTN_NOTIFIER_TELEGRAM_API_URL")
+                .unwrap_or_else(|_| DEFAULT_TELEGRAM_API_URL.to_string());
+            sinks = sinks.with(TelegramSink::new(api_url, token));
+        }
+        if let Ok(raw) = env::var("ATTN_NOTIFIER_LOCAL_ADDR") {
+            let addr: SocketAddr = raw.parse()?;
+            let local = LocalSink::default();
+            local.serve(addr);
+            sinks = sinks.with(local);
+        }
+        Ok(sinks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use httpmock::prelude::*;
+
+    fn subscription(sink: SinkKind, target: String, secret: Option<&str>) -> Subscription {
+        Subscription {
+            id: 3,
+            wallet: "Wa11et11111111111111111111111111111111111111".into(),
+            creator_vault: None,
+            kinds: Vec::new(),
+            sink,
+            target,
+            secret: secret.map(str::to_string),
+        }
+    }
+
+    fn notification() -> Notification {
+        Notification {
+            delivery_id: 9,
+            subscription_id: 3,
+            wallet: "Wa11et11111111111111111111111111111111111111".into(),
+            event: NotificationEvent {
+                key: "sig".into(),
+                kind: "fees_swept".into(),
+                source: "creator_vault".into(),
+                slot: Some(1),
+                occurred_at: Utc::now(),
+                payload: json!({ "amount": 5 }),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn webhooks_are_signed_and_classify_failures() {
+        let server = MockServer::start_async().await;
+        let ok = server.mock(|when, then| {
+            when.method(POST)
+                .path("/hook")
+                .header_exists(SIGNATURE_HEADER)
+                .header_exists(TIMESTAMP_HEADER)
+                .header(DELIVERY_HEADER, "9");
+            then.status(204);
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/busy");
+            then.status(503);
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/gone");
+            then.status(410);
+        });
+
+        // The mock server listens on loopback.
+        let sink = WebhookSink::new(true).unwrap();
+        let hook = subscription(SinkKind::Webhook, server.url("/hook"), Some("secret"));
+        sink.send(&hook, &notification()).await.unwrap();
+        ok.assert();
+
+        let busy = subscription(SinkKind::Webhook, server.url("/busy"), Some("secret"));
+        assert!(
+            sink.send(&busy, &notification())
+                .await
+                .unwrap_err()
+                .retryable
+        );
+        let gone = subscription(SinkKind::Webhook, server.url("/gone"), Some("secret"));
+        assert!(
+            !sink
+                .send(&gone, &notification())
+                .await
+                .unwrap_err()
+                .retryable
+        );
+        let unsigned = subscription(SinkKind::Webhook, server.url("/hook"), None);
+        assert!(
+            !sink
+                .send(&unsigned, &notification())
+                .await
+                .unwrap_err()
+                .retryable
+        );
+
+        let guarded = WebhookSink::new(false).unwrap();
+        assert!(
+            !guarded
+                .send(&hook, &notification())
+                .await
+                .unwrap_err()
+                .retryable
+        );
+        ok.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn telegram_messages_go_to_the_chat() {
+        let server = MockServer::start_async().await;
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/bottoken/sendMessage")
+                .json_body_partial(r#"{ "chat_id": "4242" }"#);
+            then.status(200).json_body(json!({ "ok": true }));
+        });
+        let sink = TelegramSink::new(server.url(""), "token");
+        sink.send(
+            &subscription(SinkKind::Telegram, "4242".into(), None),
+            &notification(),
+        )
+        .await
+        .unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn local_sink_serves_recent_deliveries() {
+        let sink = LocalSink::default();
+        sink.send(
+            &subscription(SinkKind::Local, "dev".into(), None),
+            &notification(),
+        )
+        .await
+        .unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = sink.router();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        let served: Vec<Notification> = reqwest::get(format!("http://{addr}/notifications"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(served.len(), 1);
+        assert_eq!(served[0].delivery_id, 9);
+        assert_eq!(served[0].event.kind, "fees_swept");
+    }
+}
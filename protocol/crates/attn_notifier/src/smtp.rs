@@ -0,0 +1,352 @@
+//! Minimal SMTP submission client for plain-text notification mail.
+//!
+//! Speaks unencrypted SMTP with optional `AUTH PLAIN`, which is enough for a
+//! local relay or sidecar (Postfix, an SES/SendGrid SMTP proxy) that handles
+//! TLS to the outside world. Without STARTTLS, credentials are only sent to a
+//! relay on a loopback address.
+
+use std::fmt;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::Utc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+
+/// Upper bound on a whole session, connect through `QUIT`, so a stalled
+/// relay can't hold up the delivery pass.
+const SESSION_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    /// `host:port` of the relay.
+    pub addr: String,
+    pub from: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Name announced in `EHLO`.
+    pub hello_name: String,
+}
+
+#[derive(Debug)]
+pub enum SmtpError {
+    Io(std::io::Error),
+    Rejected {
+        code: u16,
+        reply: String,
+    },
+    InvalidAddress(String),
+    /// Credentials set for a relay that isn't on loopback.
+    InsecureAuth(IpAddr),
+}
+
+impl SmtpError {
+    /// Connection failures and 4xx replies are worth retrying.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            SmtpError::Io(_) => true,
+            SmtpError::Rejected { code, .. } => (400..500).contains(code),
+            SmtpError::InvalidAddress(_) | SmtpError::InsecureAuth(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for SmtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmtpError::Io(err) => write!(f, "smtp connection failed: {err}"),
+            SmtpError::Rejected { code, reply } => write!(f, "smtp server replied {code}: {reply}"),
+            SmtpError::InvalidAddress(address) => write!(f, "invalid email address: {address}"),
+            SmtpError::InsecureAuth(ip) => {
+                write!(
+                    f,
+                    "refusing AUTH PLAIN over plaintext to non-loopback relay {ip}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SmtpError {}
+
+impl From<std::io::Error> for SmtpError {
+    fn from(err: std::io::Error) -> Self {
+        SmtpError::Io(err)
+    }
+}
+
+struct Connection {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl Connection {
+    async fn reply(&mut self) -> Result<(u16, String), SmtpError> {
+        let mut text = String::new();
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).await? == 0 {
+                return Err(SmtpError::Io(std::io::ErrorKind::UnexpectedEof.into()));
+            }
+            let line = line.trim_end();
+            let code = line
+                .get(..3)
+                .and_then(|code| code.parse::<u16>().ok())
+                .ok_or_else(|| SmtpError::Rejected {
+                    code: 0,
+                    reply: line.to_string(),
+                })?;
+            text.push_str(line.get(4..).unwrap_or_default());
+            // `250-` continues a multi-line reply, `250 ` ends it.
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok((code, text));
+            }
+            text.push('\n');
+        }
+    }
+
+    async fn expect(&mut self, accepted: &[u16]) -> Result<(), SmtpError> {
+        let (code, reply) = self.reply().await?;
+        if accepted.contains(&code) {
+            Ok(())
+        } else {
+            Err(SmtpError::Rejected { code, reply })
+        }
+    }
+
+    async fn command(&mut self, line: &str, accepted: &[u16]) -> Result<(), SmtpError> {
+        self.writer.write_all(line.as_bytes()).await?;
+        self.writer.write_all(b"\r\n").await?;
+        self.expect(accepted).await
+    }
+}
+
+fn check_address(address: &str) -> Result<(), SmtpError> {
+    let valid = address.contains('@')
+        && !address
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>'));
+    if valid {
+        Ok(())
+    } else {
+        Err(SmtpError::InvalidAddress(address.to_string()))
+    }
+}
+
+/// RFC 5322 message with CRLF line endings and dot-stuffed body, terminated
+/// for the `DATA` phase.
+fn format_message(from: &str, to: &str, subject: &str, body: &str) -> String {
+    let subject: String = subject.chars().filter(|c| !c.is_control()).collect();
+    let mut message = format!(
+        "From: {from}\r\nTo: {to}\r\nSubject: {subject}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n",
+        Utc::now().to_rfc2822()
+    );
+    for line in body.lines() {
+        if line.starts_with('.') {
+            message.push('.');
+        }
+        message.push_str(line);
+        message.push_str("\r\n");
+    }
+    message.push_str(".\r\n");
+    message
+}
+
+pub async fn send_mail(
+    config: &SmtpConfig,
+    to: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), SmtpError> {
+    send_mail_within(
+        Duration::from_secs(SESSION_TIMEOUT_SECS),
+        config,
+        to,
+        subject,
+        body,
+    )
+    .await
+}
+
+async fn send_mail_within(
+    timeout: Duration,
+    config: &SmtpConfig,
+    to: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), SmtpError> {
+    check_address(&config.from)?;
+    check_address(to)?;
+    tokio::time::timeout(timeout, session(config, to, subject, body))
+        .await
+        .map_err(|_| SmtpError::Io(std::io::ErrorKind::TimedOut.into()))?
+}
+
+async fn session(
+    config: &SmtpConfig,
+    to: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), SmtpError> {
+    let stream = TcpStream::connect(&config.addr).await?;
+    let relay = stream.peer_addr()?.ip();
+    let (reader, writer) = stream.into_split();
+    let mut conn = Connection {
+        reader: BufReader::new(reader),
+        writer,
+    };
+    conn.expect(&[220]).await?;
+    conn.command(&format!("EHLO {}", config.hello_name), &[250])
+        .await?;
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        if !relay.is_loopback() {
+            return Err(SmtpError::InsecureAuth(relay));
+        }
+        let token = STANDARD.encode(format!("\0{username}\0{password}"));
+        conn.command(&format!("AUTH PLAIN {token}"), &[235]).await?;
+    }
+    conn.command(&format!("MAIL FROM:<{}>", config.from), &[250])
+        .await?;
+    conn.command(&format!("RCPT TO:<{to}>"), &[250, 251])
+        .await?;
+    conn.command("DATA", &[354]).await?;
+    conn.writer
+        .write_all(format_message(&config.from, to, subject, body).as_bytes())
+        .await?;
+    conn.expect(&[250]).await?;
+    // The message is accepted at this point; a failed QUIT changes nothing.
+    let _ = conn.command("QUIT", &[221]).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// Accepts one session, answers each command from `replies` and returns
+    /// the transcript written by the client.
+    async fn fake_server(
+        replies: &'static [&'static str],
+    ) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut reader = BufReader::new(reader);
+            let mut transcript = String::new();
+            writer.write_all(b"220 relay ready\r\n").await.unwrap();
+            for reply in replies {
+                let mut line = String::new();
+                if reader.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                transcript.push_str(&line);
+                if line.starts_with("DATA") {
+                    writer.write_all(b"354 go ahead\r\n").await.unwrap();
+                    loop {
+                        let mut data = String::new();
+                        reader.read_line(&mut data).await.unwrap();
+                        transcript.push_str(&data);
+                        if data == ".\r\n" {
+                            break;
+                        }
+                    }
+                }
+                writer.write_all(reply.as_bytes()).await.unwrap();
+            }
+            let mut rest = String::new();
+            let _ = reader.read_to_string(&mut rest).await;
+            transcript
+        });
+        (addr, handle)
+    }
+
+    fn config(addr: String) -> SmtpConfig {
+        SmtpConfig {
+            addr,
+            from: "alerts@attn.markets".into(),
+            username: Some("user".into()),
+            password: Some("pass".into()),
+            hello_name: "attn-notifier".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn mail_is_submitted_with_auth_and_dot_stuffing() {
+        let (addr, server) = fake_server(&[
+            "250-relay\r\n250 AUTH PLAIN\r\n",
+            "235 ok\r\n",
+            "250 ok\r\n",
+            "250 ok\r\n",
+            "250 queued\r\n",
+            "221 bye\r\n",
+        ])
+        .await;
+        send_mail(
+            &config(addr),
+            "creator@example.com",
+            "fees swept",
+            "line\n.dot",
+        )
+        .await
+        .unwrap();
+        let transcript = server.await.unwrap();
+        assert!(transcript.starts_with("EHLO attn-notifier\r\n"));
+        assert!(transcript.contains(&format!(
+            "AUTH PLAIN {}\r\n",
+            STANDARD.encode("\0user\0pass")
+        )));
+        assert!(transcript.contains("RCPT TO:<creator@example.com>\r\n"));
+        assert!(transcript.contains("Subject: fees swept\r\n"));
+        assert!(transcript.contains("\r\nline\r\n..dot\r\n.\r\n"));
+        assert!(transcript.ends_with("QUIT\r\n"));
+    }
+
+    #[tokio::test]
+    async fn rejected_recipients_are_classified() {
+        let (addr, _server) = fake_server(&[
+            "250 relay\r\n",
+            "235 ok\r\n",
+            "250 ok\r\n",
+            "550 no such user\r\n",
+        ])
+        .await;
+        let err = send_mail(&config(addr), "nobody@example.com", "s", "b")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SmtpError::Rejected { code: 550, .. }));
+        assert!(!err.is_transient());
+        assert!(SmtpError::Rejected {
+            code: 451,
+            reply: "try later".into()
+        }
+        .is_transient());
+        assert!(check_address("bad\r\naddress@x").is_err());
+    }
+
+    #[tokio::test]
+    async fn stalled_relays_time_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        // Accepts but never greets.
+        let _server = tokio::spawn(async move { listener.accept().await });
+        let err = send_mail_within(
+            Duration::from_millis(100),
+            &config(addr),
+            "creator@example.com",
+            "s",
+            "b",
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(&err, SmtpError::Io(io) if io.kind() == std::io::ErrorKind::TimedOut));
+        assert!(err.is_transient());
+        assert!(!SmtpError::InsecureAuth("10.0.0.5".parse().unwrap()).is_transient());
+    }
+}
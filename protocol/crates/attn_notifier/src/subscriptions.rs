@@ -0,0 +1,162 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+
+use crate::events::NotificationEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkKind {
+    Webhook,
+    Smtp,
+    Telegram,
+    Local,
+}
+
+impl SinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SinkKind::Webhook => "webhook",
+            SinkKind::Smtp => "smtp",
+            SinkKind::Telegram => "telegram",
+            SinkKind::Local => "local",
+        }
+    }
+}
+
+impl fmt::Display for SinkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SinkKind {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "webhook" => Ok(SinkKind::Webhook),
+            "smtp" => Ok(SinkKind::Smtp),
+            "telegram" => Ok(SinkKind::Telegram),
+            "local" => Ok(SinkKind::Local),
+            other => Err(anyhow!("unknown sink: {other}")),
+        }
+    }
+}
+
+/// A row of `notification_subscriptions`: which events a wallet wants and
+/// where to send them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subscription {
+    pub id: i64,
+    pub wallet: String,
+    pub creator_vault: Option<String>,
+    pub kinds: Vec<String>,
+    pub sink: SinkKind,
+    pub target: String,
+    pub secret: Option<String>,
+}
+
+impl Subscription {
+    /// Events must reference the subscribed creator vault when one is set,
+    /// otherwise the wallet, and be one of `kinds` unless that list is empty.
+    pub fn matches(&self, event: &NotificationEvent) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.kind) {
+            return false;
+        }
+        match &self.creator_vault {
+            Some(vault) => event.references(vault),
+            None => event.references(&self.wallet),
+        }
+    }
+}
+
+pub async fn active_subscriptions(pool: &PgPool) -> Result<Vec<Subscription>> {
+    let rows = sqlx::query(
+        r#"
+        select id, wallet, creator_vault, kinds, sink, target, secret
+        from notification_subscriptions
+        where active
+        order by id asc
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    rows.into_iter()
+        .map(|row| {
+            Ok(Subscription {
+                id: row.get("id"),
+                wallet: row.get("wallet"),
+                creator_vault: row.get("creator_vault"),
+                kinds: row.get("kinds"),
+                sink: row.get::<String, _>("sink").parse()?,
+                target: row.get("target"),
+                secret: row.get("secret"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    const WALLET: &str = "Wa11et11111111111111111111111111111111111111";
+    const VAULT: &str = "CreatorVau1t1111111111111111111111111111111";
+
+    fn subscription(creator_vault: Option<&str>, kinds: &[&str]) -> Subscription {
+        Subscription {
+            id: 1,
+            wallet: WALLET.into(),
+            creator_vault: creator_vault.map(str::to_string),
+            kinds: kinds.iter().map(|kind| kind.to_string()).collect(),
+            sink: SinkKind::Local,
+            target: "dev".into(),
+            secret: None,
+        }
+    }
+
+    fn event(kind: &str, payload: serde_json::Value) -> NotificationEvent {
+        NotificationEvent {
+            key: "sig".into(),
+            kind: kind.into(),
+            source: "creator_vault".into(),
+            slot: Some(1),
+            occurred_at: Utc::now(),
+            payload,
+        }
+    }
+
+    #[test]
+    fn subscriptions_match_wallet_vault_and_kinds() {
+        let swept = event(
+            "fees_swept",
+            json!({ "creator_vault": VAULT, "authority": WALLET }),
+        );
+        let other = event("fees_swept", json!({ "creator_vault": VAULT }));
+
+        assert!(subscription(None, &[]).matches(&swept));
+        assert!(!subscription(None, &[]).matches(&other));
+        assert!(subscription(Some(VAULT), &[]).matches(&other));
+        assert!(subscription(Some(VAULT), &["fees_swept"]).matches(&other));
+        assert!(!subscription(Some(VAULT), &["creator_paused"]).matches(&other));
+    }
+
+    #[test]
+    fn sink_kinds_round_trip() {
+        for sink in [
+            SinkKind::Webhook,
+            SinkKind::Smtp,
+            SinkKind::Telegram,
+            SinkKind::Local,
+        ] {
+            assert_eq!(sink.as_str().parse::<SinkKind>().unwrap(), sink);
+        }
+        assert!("sms".parse::<SinkKind>().is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Cross-program invariants checked against a [`Snapshot`] of on-chain state.
+
+use std::fmt;
+
+use attn_client::{
+    creator::CreatorVaultAccount,
+    rewards::RewardsPoolAccount,
+    splitter::{MarketAccount, UserPositionAccount},
+    stable::StableVaultAccount,
+};
+
+/// Balances and account mirrors read at one point of a scenario.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub creator_vault: CreatorVaultAccount,
+    pub market: MarketAccount,
+    pub sy_supply: u64,
+    pub pt_supply: u64,
+    pub yt_supply: u64,
+    pub fee_vault_balance: u64,
+    /// Yield owed to every YT holder the scenario knows of, at the market index.
+    pub owed_yield: u64,
+    /// Fees that reached the fee vault but are not yet in the market's fee index.
+    pub unindexed_fees: u64,
+    pub stable_vault: StableVaultAccount,
+    pub treasury_balance: u64,
+    pub share_supply: u64,
+    pub sol_vault_lamports: u64,
+    pub rewards_pool: RewardsPoolAccount,
+    pub attn_vault_balance: u64,
+    pub s_attn_supply: u64,
+    pub sol_treasury_lamports: u64,
+    /// Lamports every stake position would be paid on its next settlement.
+    pub owed_rewards: u64,
+    /// Rent-exempt minimum of a data-less account, held by both lamport vaults.
+    pub rent_exempt_minimum: u64,
+}
+
+/// A broken invariant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub invariant: &'static str,
+    pub detail: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.invariant, self.detail)
+    }
+}
+
+/// Every invariant a [`Snapshot`] broke.
+#[derive(Debug, Clone)]
+pub struct InvariantViolations(pub Vec<Violation>);
+
+impl fmt::Display for InvariantViolations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} invariant(s) violated", self.0.len())?;
+        for violation in &self.0 {
+            write!(f, "\n  {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvariantViolations {}
+
+/// Yield `position` could claim holding `yt_balance` if the market index stopped here.
+pub fn owed_yield(market: &MarketAccount, position: &UserPositionAccount, yt_balance: u64) -> u64 {
+    let delta = market.fee_index.saturating_sub(position.last_fee_index);
+    let scaled = delta
+        .saturating_mul(yt_balance as u128)
+        .saturating_add(position.pending_yield_scaled);
+    (scaled / splitter::FEE_INDEX_SCALE) as u64
+}
+
+pub fn check(snapshot: &Snapshot) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut require = |invariant: &'static str, holds: bool, detail: String| {
+        if !holds {
+            violations.push(Violation { invariant, detail });
+        }
+    };
+
+    // Each wrapped quote unit is either SY or has been split into PT + YT.
+    let vault = &snapshot.creator_vault;
+    let wrapped = snapshot.sy_supply as u128 + snapshot.pt_supply as u128;
+    require(
+        "sy_backing",
+        wrapped == vault.total_sy_minted as u128,
+        format!(
+            "sy supply {} + pt supply {} != total_sy_minted {}",
+            snapshot.sy_supply, snapshot.pt_supply, vault.total_sy_minted
+        ),
+    );
+    let market = &snapshot.market;
+    require(
+        "pt_issued",
+        snapshot.pt_supply == market.total_pt_issued,
+        format!(
+            "pt supply {} != total_pt_issued {}",
+            snapshot.pt_supply, market.total_pt_issued
+        ),
+    );
+    require(
+        "yt_issued",
+        snapshot.yt_supply == market.total_yt_issued,
+        format!(
+            "yt supply {} != total_yt_issued {}",
+            snapshot.yt_supply, market.total_yt_issued
+        ),
+    );
+    let fee_liabilities = vault.total_sy_minted as u128
+        + snapshot.owed_yield as u128
+        + snapshot.unindexed_fees as u128;
+    require(
+        "fee_vault_solvent",
+        snapshot.fee_vault_balance as u128 >= fee_liabilities,
+        format!(
+            "fee vault {} < principal {} + owed yield {} + unindexed fees {}",
+            snapshot.fee_vault_balance,
+            vault.total_sy_minted,
+            snapshot.owed_yield,
+            snapshot.unindexed_fees
+        ),
+    );
+
+    let stable = &snapshot.stable_vault;
+    require(
+        "stable_treasury_solvent",
+        snapshot.treasury_balance >= stable.total_assets,
+        format!(
+            "treasury {} < total_assets {}",
+            snapshot.treasury_balance, stable.total_assets
+        ),
+    );
+    require(
+        "share_supply",
+        snapshot.share_supply == stable.total_shares,
+        format!(
+            "share supply {} != total_shares {}",
+            snapshot.share_supply, stable.total_shares
+        ),
+    );
+    require(
+        "sol_vault_solvent",
+        snapshot.sol_vault_lamports as u128
+            >= stable.pending_sol as u128 + snapshot.rent_exempt_minimum as u128,
+        format!(
+            "sol vault {} < pending_sol {} + rent {}",
+            snapshot.sol_vault_lamports, stable.pending_sol, snapshot.rent_exempt_minimum
+        ),
+    );
+
+    let pool = &snapshot.rewards_pool;
+    let reward_liabilities = snapshot.rent_exempt_minimum as u128
+        + pool.pending_rewards as u128
+        + snapshot.owed_rewards as u128;
+    require(
+        "rewards_treasury_solvent",
+        snapshot.sol_treasury_lamports as u128 >= reward_liabilities,
+        format!(
+            "sol treasury {} < rent {} + pending_rewards {} + owed rewards {}",
+            snapshot.sol_treasury_lamports,
+            snapshot.rent_exempt_minimum,
+            pool.pending_rewards,
+            snapshot.owed_rewards
+        ),
+    );
+    require(
+        "stake_backing",
+        snapshot.attn_vault_balance == pool.total_staked
+            && snapshot.s_attn_supply == pool.total_staked,
+        format!(
+            "attn vault {} / sAttn supply {} != total_staked {}",
+            snapshot.attn_vault_balance, snapshot.s_attn_supply, pool.total_staked
+        ),
+    );
+
+    violations
+}
@@ -0,0 +1,934 @@
+//! Scripted multi-actor lifecycles against the attn programs on `solana-program-test`.
+//!
+//! [`ScenarioBuilder`] deploys creator_vault, splitter, stable_vault and rewards_vault,
+//! then wires up one creator vault, its market, a stable vault and a rewards pool. The
+//! returned [`Scenario`] drives the protocol step by step through the `attn_client`
+//! instruction builders, warps the clock, and checks the cross-program
+//! [`invariants`] after every step:
+//!
+//! ```ignore
+//! let mut scenario = ScenarioBuilder::new().actor("alice").build().await?;
+//! scenario
+//!     .fund_quote(CREATOR, 1_000_000).await?
+//!     .wrap(CREATOR, 1_000_000).await?
+//!     .split(CREATOR, 1_000_000).await?
+//!     .accrue_fees(50_000).await?
+//!     .redeem_yield(CREATOR).await?
+//!     .warp_to_maturity().await?
+//!     .redeem_principal(CREATOR, 1_000_000).await?;
+//! ```
+//!
+//! Creator fees are simulated by minting quote straight into the fee vault; the
+//! scenario tracks what has not been folded into the market's fee index yet and
+//! publishes it on the next `redeem_yield`, as the keeper would.
+//!
+//! The programs are loaded from their `cargo build-sbf` output, found through
+//! `SBF_OUT_DIR`; Anchor's CPIs don't run as native builtins.
+
+pub mod invariants;
+
+use std::collections::BTreeMap;
+
+use anchor_lang::{
+    solana_program::{program_option::COption, program_pack::Pack},
+    AnchorDeserialize,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token::spl_token::{
+        self,
+        state::{Account as TokenAccountState, Mint as MintState},
+    },
+};
+use anyhow::{anyhow, bail, Result};
+use attn_client::{
+    creator::{self, CreatorVaultAccount, CreatorVaultPdas},
+    decode_account,
+    errors::ProgramError,
+    rewards::{self, RewardsPoolAccount, RewardsVaultPdas, StakePositionAccount},
+    splitter::{self as splitter_client, MarketAccount, UserPositionAccount},
+    stable::{self, StableVaultAccount, StableVaultPdas},
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use crate::invariants::{InvariantViolations, Snapshot};
+
+/// The built-in actor: creator vault authority, pump creator and the rewards pool's
+/// allowed funder.
+pub const CREATOR: &str = "creator";
+
+const DEFAULT_LAMPORTS: u64 = 1_000 * LAMPORTS_PER_SOL;
+const DECIMALS: u8 = 6;
+const DEFAULT_MATURITY_SECS: i64 = 30 * 24 * 60 * 60;
+/// Warps set the clock directly; slots only move far enough for a fresh bank.
+const WARP_SLOTS: u64 = 100;
+
+#[derive(Debug, Clone)]
+pub struct ScenarioBuilder {
+    actors: Vec<(String, u64)>,
+    maturity_secs: i64,
+    sol_rewards_bps: u16,
+    reward_bps: u16,
+    check_each_step: bool,
+}
+
+impl Default for ScenarioBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScenarioBuilder {
+    pub fn new() -> Self {
+        Self {
+            actors: vec![(CREATOR.to_string(), DEFAULT_LAMPORTS)],
+            maturity_secs: DEFAULT_MATURITY_SECS,
+            sol_rewards_bps: 2_000,
+            reward_bps: 0,
+            check_each_step: true,
+        }
+    }
+
+    pub fn actor(self, name: &str) -> Self {
+        self.actor_with_lamports(name, DEFAULT_LAMPORTS)
+    }
+
+    /// Adds a funded wallet; naming an existing actor replaces its balance.
+    pub fn actor_with_lamports(mut self, name: &str, lamports: u64) -> Self {
+        self.actors.retain(|(existing, _)| existing != name);
+        self.actors.push((name.to_string(), lamports));
+        self
+    }
+
+    /// Seconds from genesis until the market matures.
+    pub fn maturity_after(mut self, secs: i64) -> Self {
+        self.maturity_secs = secs;
+        self
+    }
+
+    /// Share of each sweep routed to the rewards pool (`CreatorVault::sol_rewards_bps`).
+    pub fn sol_rewards_bps(mut self, bps: u16) -> Self {
+        self.sol_rewards_bps = bps;
+        self
+    }
+
+    pub fn reward_bps(mut self, bps: u16) -> Self {
+        self.reward_bps = bps;
+        self
+    }
+
+    /// Whether every step runs [`Scenario::check_invariants`]; on by default.
+    pub fn check_invariants_each_step(mut self, enabled: bool) -> Self {
+        self.check_each_step = enabled;
+        self
+    }
+
+    pub async fn build(self) -> Result<Scenario> {
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        program_test.add_program("creator_vault", ::creator_vault::ID, None);
+        program_test.add_program("splitter", ::splitter::ID, None);
+        program_test.add_program("stable_vault", ::stable_vault::ID, None);
+        program_test.add_program("rewards_vault", ::rewards_vault::ID, None);
+
+        let rent = Rent::default();
+        let operator = Keypair::new();
+        program_test.add_account(operator.pubkey(), wallet(DEFAULT_LAMPORTS));
+        let mut actors = BTreeMap::new();
+        for (name, lamports) in self.actors {
+            let keypair = Keypair::new();
+            program_test.add_account(keypair.pubkey(), wallet(lamports));
+            actors.insert(name, keypair);
+        }
+
+        let pump_mint = Keypair::new().pubkey();
+        let quote_mint = Keypair::new().pubkey();
+        let stable_mint = Keypair::new().pubkey();
+        for mint in [pump_mint, quote_mint, stable_mint] {
+            program_test.add_account(mint, mint_account(&rent, operator.pubkey()));
+        }
+        // `sweep_creator_fees` tops the SOL vault up with plain transfers, so it must
+        // already be rent exempt.
+        let stable_pdas = stable::derive_pdas(&operator.pubkey(), &stable_mint);
+        program_test.add_account(stable_pdas.sol_vault, wallet(rent.minimum_balance(0)));
+
+        let mut context = program_test.start_with_context().await;
+        let creator = actors[CREATOR].insecure_clone();
+
+        let (init_vault, creator_pdas) = creator::build_initialize_vault_ix(
+            creator.pubkey(),
+            creator.pubkey(),
+            pump_mint,
+            quote_mint,
+            ::splitter::ID,
+            operator.pubkey(),
+        );
+        let set_split = creator::build_set_rewards_split_ix(
+            creator_pdas.creator_vault,
+            operator.pubkey(),
+            self.sol_rewards_bps,
+        );
+        process(
+            &mut context,
+            &[init_vault, set_split],
+            &[&creator, &operator],
+        )
+        .await?;
+
+        let (init_stable, _) = stable::build_initialize_vault_ix(
+            operator.pubkey(),
+            stable_mint,
+            vec![stable_mint],
+            operator.pubkey(),
+        );
+        let rewards_pdas = rewards::derive_pdas(&creator_pdas.creator_vault);
+        let init_pool = rewards::build_initialize_pool_ix(
+            operator.pubkey(),
+            operator.pubkey(),
+            creator_pdas.creator_vault,
+            stable_pdas.share_mint,
+            self.reward_bps,
+            creator.pubkey(),
+            &rewards_pdas,
+        );
+        process(&mut context, &[init_stable, init_pool], &[&operator]).await?;
+
+        let market = Keypair::new();
+        let pt_mint = Keypair::new();
+        let yt_mint = Keypair::new();
+        let clock: Clock = context.banks_client.get_sysvar().await?;
+        let create_market = splitter_client::build_create_market_ix(
+            &splitter_client::CreateMarketAccounts {
+                authority: creator.pubkey(),
+                creator_vault: creator_pdas.creator_vault,
+                pump_mint,
+                sy_mint: creator_pdas.sy_mint,
+                market: market.pubkey(),
+                pt_mint: pt_mint.pubkey(),
+                yt_mint: yt_mint.pubkey(),
+            },
+            clock.unix_timestamp + self.maturity_secs,
+        );
+        process(
+            &mut context,
+            &[create_market],
+            &[&creator, &market, &pt_mint, &yt_mint],
+        )
+        .await?;
+
+        let mut scenario = Scenario {
+            context,
+            operator,
+            actors,
+            accounts: ScenarioAccounts {
+                pump_mint,
+                quote_mint,
+                stable_mint,
+                creator_vault: creator_pdas,
+                market: market.pubkey(),
+                pt_mint: pt_mint.pubkey(),
+                yt_mint: yt_mint.pubkey(),
+                stable_vault: stable_pdas,
+                rewards: rewards_pdas,
+            },
+            unindexed_fees: 0,
+            last_operation_id: 0,
+            check_each_step: self.check_each_step,
+        };
+        scenario.step().await?;
+        Ok(scenario)
+    }
+}
+
+/// Addresses of everything a scenario deploys.
+#[derive(Debug, Clone)]
+pub struct ScenarioAccounts {
+    pub pump_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub stable_mint: Pubkey,
+    pub creator_vault: CreatorVaultPdas,
+    pub market: Pubkey,
+    pub pt_mint: Pubkey,
+    pub yt_mint: Pubkey,
+    pub stable_vault: StableVaultPdas,
+    pub rewards: RewardsVaultPdas,
+}
+
+/// A deployed protocol plus named actors. The operator key is the creator vault
+/// admin, the stable vault authority and keeper, the rewards pool admin and the mint
+/// authority of every test mint; the test validator's payer pays transaction fees.
+pub struct Scenario {
+    context: ProgramTestContext,
+    operator: Keypair,
+    actors: BTreeMap<String, Keypair>,
+    accounts: ScenarioAccounts,
+    unindexed_fees: u64,
+    last_operation_id: u64,
+    check_each_step: bool,
+}
+
+impl Scenario {
+    pub fn context(&mut self) -> &mut ProgramTestContext {
+        &mut self.context
+    }
+
+    pub fn accounts(&self) -> &ScenarioAccounts {
+        &self.accounts
+    }
+
+    pub fn operator(&self) -> Pubkey {
+        self.operator.pubkey()
+    }
+
+    pub fn pubkey(&self, name: &str) -> Result<Pubkey> {
+        Ok(actor(&self.actors, name)?.pubkey())
+    }
+
+    /// Fees in the fee vault that no `redeem_yield` has indexed yet.
+    pub fn unindexed_fees(&self) -> u64 {
+        self.unindexed_fees
+    }
+
+    pub async fn fund_quote(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let owner = self.pubkey(name)?;
+        let mint = self.accounts.quote_mint;
+        self.mint_to_owner(mint, owner, amount).await?;
+        self.step().await
+    }
+
+    pub async fn fund_stable(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let owner = self.pubkey(name)?;
+        let mint = self.accounts.stable_mint;
+        self.mint_to_owner(mint, owner, amount).await?;
+        self.step().await
+    }
+
+    /// Deposits quote into the creator vault for SY.
+    pub async fn wrap(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let payer = self.context.payer.pubkey();
+        let user = actor(&self.actors, name)?;
+        let a = &self.accounts;
+        let instructions = [
+            create_ata_ix(&payer, &user.pubkey(), &a.creator_vault.sy_mint),
+            creator::build_wrap_fees_ix(
+                a.pump_mint,
+                a.quote_mint,
+                user.pubkey(),
+                ata(&user.pubkey(), &a.quote_mint),
+                ata(&user.pubkey(), &a.creator_vault.sy_mint),
+                amount,
+            ),
+        ];
+        process(&mut self.context, &instructions, &[user]).await?;
+        self.step().await
+    }
+
+    /// Splits SY into PT + YT. An existing position is settled at the current fee
+    /// index first, since `mint_pt_yt` does not checkpoint it.
+    pub async fn split(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let owner = self.pubkey(name)?;
+        let market = self.market_account().await?;
+        let mut instructions = self.checkpoint_ix(owner, &market).await?;
+        let payer = self.context.payer.pubkey();
+        let a = &self.accounts;
+        instructions.push(create_ata_ix(&payer, &owner, &a.pt_mint));
+        instructions.push(create_ata_ix(&payer, &owner, &a.yt_mint));
+        let (mint_pt_yt, _) = splitter_client::build_mint_pt_yt_ix(
+            a.market,
+            a.creator_vault.creator_vault,
+            owner,
+            a.creator_vault.sy_mint,
+            a.pt_mint,
+            a.yt_mint,
+            ata(&owner, &a.creator_vault.sy_mint),
+            ata(&owner, &a.pt_mint),
+            ata(&owner, &a.yt_mint),
+            amount,
+        );
+        instructions.push(mint_pt_yt);
+        let user = actor(&self.actors, name)?;
+        process(&mut self.context, &instructions, &[user]).await?;
+        self.step().await
+    }
+
+    /// Transfers `amount` YT from `seller` to `buyer` for `price` quote. YT transfers
+    /// are not checkpointed on chain, so both positions are settled first; the buyer
+    /// needs a position of its own from an earlier `split`.
+    pub async fn sell_yt(
+        &mut self,
+        seller: &str,
+        buyer: &str,
+        amount: u64,
+        price: u64,
+    ) -> Result<&mut Self> {
+        let seller_key = self.pubkey(seller)?;
+        let buyer_key = self.pubkey(buyer)?;
+        if self.position_of(buyer_key).await?.is_none() {
+            bail!("{buyer} has no position in the market; split before buying YT");
+        }
+        let market = self.market_account().await?;
+        let mut instructions = self.checkpoint_ix(seller_key, &market).await?;
+        instructions.extend(self.checkpoint_ix(buyer_key, &market).await?);
+        let payer = self.context.payer.pubkey();
+        let a = &self.accounts;
+        instructions.push(create_ata_ix(&payer, &buyer_key, &a.yt_mint));
+        instructions.push(spl_token::instruction::transfer(
+            &spl_token::ID,
+            &ata(&seller_key, &a.yt_mint),
+            &ata(&buyer_key, &a.yt_mint),
+            &seller_key,
+            &[],
+            amount,
+        )?);
+        if price > 0 {
+            instructions.push(create_ata_ix(&payer, &seller_key, &a.quote_mint));
+            instructions.push(spl_token::instruction::transfer(
+                &spl_token::ID,
+                &ata(&buyer_key, &a.quote_mint),
+                &ata(&seller_key, &a.quote_mint),
+                &buyer_key,
+                &[],
+                price,
+            )?);
+        }
+        let seller = actor(&self.actors, seller)?;
+        let buyer = actor(&self.actors, buyer)?;
+        process(&mut self.context, &instructions, &[seller, buyer]).await?;
+        self.step().await
+    }
+
+    /// Lands `amount` of creator fees in the fee vault.
+    pub async fn accrue_fees(&mut self, amount: u64) -> Result<&mut Self> {
+        let a = &self.accounts;
+        let mint_to = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            &a.quote_mint,
+            &a.creator_vault.fee_vault,
+            &self.operator.pubkey(),
+            &[],
+            amount,
+        )?;
+        process(&mut self.context, &[mint_to], &[&self.operator]).await?;
+        self.unindexed_fees = self
+            .unindexed_fees
+            .checked_add(amount)
+            .ok_or_else(|| anyhow!("unindexed fees overflow"))?;
+        self.step().await
+    }
+
+    /// Publishes unindexed fees into the fee index and claims `name`'s yield.
+    pub async fn redeem_yield(&mut self, name: &str) -> Result<&mut Self> {
+        let owner = self.pubkey(name)?;
+        let market = self.market_account().await?;
+        let (new_fee_index, indexed) = next_fee_index(&market, self.unindexed_fees);
+        let payer = self.context.payer.pubkey();
+        let a = &self.accounts;
+        let instructions = [
+            create_ata_ix(&payer, &owner, &a.quote_mint),
+            redeem_yield_ix(a, owner, new_fee_index),
+        ];
+        let user = actor(&self.actors, name)?;
+        process(&mut self.context, &instructions, &[user]).await?;
+        self.unindexed_fees -= indexed;
+        self.step().await
+    }
+
+    /// Burns PT + YT for SY; fails before maturity.
+    pub async fn redeem_principal(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let owner = self.pubkey(name)?;
+        let a = &self.accounts;
+        let redeem = splitter_client::build_redeem_principal_ix(
+            a.market,
+            a.creator_vault.creator_vault,
+            owner,
+            ata(&owner, &a.pt_mint),
+            ata(&owner, &a.creator_vault.sy_mint),
+            a.pt_mint,
+            a.creator_vault.sy_mint,
+            ata(&owner, &a.yt_mint),
+            a.yt_mint,
+            amount,
+        );
+        let user = actor(&self.actors, name)?;
+        process(&mut self.context, &[redeem], &[user]).await?;
+        self.step().await
+    }
+
+    pub async fn deposit(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let payer = self.context.payer.pubkey();
+        let user = actor(&self.actors, name)?;
+        let a = &self.accounts;
+        let pdas = &a.stable_vault;
+        let instructions = [
+            create_ata_ix(&payer, &user.pubkey(), &pdas.share_mint),
+            stable::build_deposit_stable_ix(
+                pdas,
+                a.stable_mint,
+                user.pubkey(),
+                ata(&user.pubkey(), &a.stable_mint),
+                ata(&user.pubkey(), &pdas.share_mint),
+                amount,
+            ),
+        ];
+        process(&mut self.context, &instructions, &[user]).await?;
+        self.step().await
+    }
+
+    /// Sweeps `lamports` of creator SOL fees, funded by the creator, splitting them
+    /// between the rewards pool and the stable vault's SOL vault.
+    pub async fn sweep(&mut self, lamports: u64) -> Result<&mut Self> {
+        let operation_id = self.last_operation_id + 1;
+        let creator = actor(&self.actors, CREATOR)?;
+        let a = &self.accounts;
+        let sweep = stable::build_sweep_creator_fees_ix(
+            &a.stable_vault,
+            self.operator.pubkey(),
+            creator.pubkey(),
+            a.creator_vault.creator_vault,
+            a.rewards.rewards_pool,
+            a.rewards.sol_treasury,
+            lamports,
+            operation_id,
+        );
+        process(&mut self.context, &[sweep], &[&self.operator, creator]).await?;
+        self.last_operation_id = operation_id;
+        self.step().await
+    }
+
+    /// Books a keeper conversion: `amount_stable` bought off-chain lands in the
+    /// treasury and `sol_spent` leaves the SOL vault.
+    pub async fn convert(&mut self, amount_stable: u64, sol_spent: u64) -> Result<&mut Self> {
+        let operation_id = self.last_operation_id + 1;
+        let payer = self.context.payer.pubkey();
+        let operator = self.operator.pubkey();
+        let a = &self.accounts;
+        let source = ata(&operator, &a.stable_mint);
+        let instructions = [
+            create_ata_ix(&payer, &operator, &a.stable_mint),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &a.stable_mint,
+                &source,
+                &operator,
+                &[],
+                amount_stable,
+            )?,
+            stable::build_process_conversion_ix(
+                &a.stable_vault,
+                operator,
+                operator,
+                a.stable_mint,
+                source,
+                amount_stable,
+                sol_spent,
+                operation_id,
+            ),
+        ];
+        process(&mut self.context, &instructions, &[&self.operator]).await?;
+        self.last_operation_id = operation_id;
+        self.step().await
+    }
+
+    /// Stakes attnUSD shares for sAttn.
+    pub async fn stake(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let payer = self.context.payer.pubkey();
+        let user = actor(&self.actors, name)?;
+        let a = &self.accounts;
+        let instructions = [
+            create_ata_ix(&payer, &user.pubkey(), &a.rewards.s_attn_mint),
+            stake_ix(a, user.pubkey(), amount, rewards::build_stake_attnusd_ix),
+        ];
+        process(&mut self.context, &instructions, &[user]).await?;
+        self.step().await
+    }
+
+    pub async fn unstake(&mut self, name: &str, amount: u64) -> Result<&mut Self> {
+        let user = actor(&self.actors, name)?;
+        let unstake = stake_ix(
+            &self.accounts,
+            user.pubkey(),
+            amount,
+            rewards::build_unstake_attnusd_ix,
+        );
+        process(&mut self.context, &[unstake], &[user]).await?;
+        self.step().await
+    }
+
+    pub async fn claim(&mut self, name: &str) -> Result<&mut Self> {
+        let user = actor(&self.actors, name)?;
+        let pdas = &self.accounts.rewards;
+        let (stake_position, _) = rewards::stake_position_pda(&pdas.rewards_pool, &user.pubkey());
+        let claim = rewards::build_claim_rewards_ix(
+            pdas.rewards_pool,
+            user.pubkey(),
+            stake_position,
+            pdas.sol_treasury,
+        );
+        process(&mut self.context, &[claim], &[user]).await?;
+        self.step().await
+    }
+
+    /// Moves the clock `secs` forward.
+    pub async fn warp_by(&mut self, secs: i64) -> Result<&mut Self> {
+        let clock: Clock = self.context.banks_client.get_sysvar().await?;
+        self.context.warp_to_slot(clock.slot + WARP_SLOTS)?;
+        let mut warped: Clock = self.context.banks_client.get_sysvar().await?;
+        warped.unix_timestamp = clock.unix_timestamp + secs;
+        self.context.set_sysvar(&warped);
+        self.context.last_blockhash = self.context.get_new_latest_blockhash().await?;
+        self.step().await
+    }
+
+    pub async fn warp_to_maturity(&mut self) -> Result<&mut Self> {
+        let maturity_ts = self.market_account().await?.maturity_ts;
+        let now = self.now().await?;
+        self.warp_by((maturity_ts - now).max(0)).await
+    }
+
+    pub async fn now(&mut self) -> Result<i64> {
+        let clock: Clock = self.context.banks_client.get_sysvar().await?;
+        Ok(clock.unix_timestamp)
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> Result<u64> {
+        Ok(self.context.banks_client.get_balance(address).await?)
+    }
+
+    /// Balance of a token account; zero when it does not exist.
+    pub async fn token_balance(&mut self, address: Pubkey) -> Result<u64> {
+        match self.context.banks_client.get_account(address).await? {
+            Some(account) => Ok(TokenAccountState::unpack(&account.data)?.amount),
+            None => Ok(0),
+        }
+    }
+
+    /// Balance of `name`'s associated token account for `mint`.
+    pub async fn balance(&mut self, name: &str, mint: Pubkey) -> Result<u64> {
+        let owner = self.pubkey(name)?;
+        self.token_balance(ata(&owner, &mint)).await
+    }
+
+    pub async fn mint_supply(&mut self, mint: Pubkey) -> Result<u64> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(mint)
+            .await?
+            .ok_or_else(|| anyhow!("mint {mint} not found"))?;
+        Ok(MintState::unpack(&account.data)?.supply)
+    }
+
+    pub async fn creator_vault_account(&mut self) -> Result<CreatorVaultAccount> {
+        self.required(self.accounts.creator_vault.creator_vault)
+            .await
+    }
+
+    pub async fn market_account(&mut self) -> Result<MarketAccount> {
+        self.required(self.accounts.market).await
+    }
+
+    pub async fn stable_vault_account(&mut self) -> Result<StableVaultAccount> {
+        self.required(self.accounts.stable_vault.stable_vault).await
+    }
+
+    pub async fn rewards_pool_account(&mut self) -> Result<RewardsPoolAccount> {
+        self.required(self.accounts.rewards.rewards_pool).await
+    }
+
+    /// `None` until `name` first splits.
+    pub async fn user_position(&mut self, name: &str) -> Result<Option<UserPositionAccount>> {
+        let owner = self.pubkey(name)?;
+        self.position_of(owner).await
+    }
+
+    /// `None` until `name` first stakes.
+    pub async fn stake_position(&mut self, name: &str) -> Result<Option<StakePositionAccount>> {
+        let owner = self.pubkey(name)?;
+        self.stake_position_of(owner).await
+    }
+
+    /// Reads every balance the [`invariants`] are checked against.
+    pub async fn snapshot(&mut self) -> Result<Snapshot> {
+        let a = self.accounts.clone();
+        let market = self.market_account().await?;
+        let rewards_pool = self.rewards_pool_account().await?;
+        let owners: Vec<Pubkey> = self
+            .actors
+            .values()
+            .map(|keypair| keypair.pubkey())
+            .collect();
+        let mut owed_yield = 0u64;
+        let mut owed_rewards = 0u64;
+        for owner in owners {
+            if let Some(position) = self.position_of(owner).await? {
+                let yt_balance = self.token_balance(ata(&owner, &a.yt_mint)).await?;
+                owed_yield += invariants::owed_yield(&market, &position, yt_balance);
+            }
+            if let Some(position) = self.stake_position_of(owner).await? {
+                owed_rewards += rewards_pool.settle_rewards(&position)?;
+            }
+        }
+        Ok(Snapshot {
+            creator_vault: self.creator_vault_account().await?,
+            sy_supply: self.mint_supply(a.creator_vault.sy_mint).await?,
+            pt_supply: self.mint_supply(a.pt_mint).await?,
+            yt_supply: self.mint_supply(a.yt_mint).await?,
+            fee_vault_balance: self.token_balance(a.creator_vault.fee_vault).await?,
+            market,
+            owed_yield,
+            unindexed_fees: self.unindexed_fees,
+            stable_vault: self.stable_vault_account().await?,
+            treasury_balance: self.token_balance(a.stable_vault.treasury).await?,
+            share_supply: self.mint_supply(a.stable_vault.share_mint).await?,
+            sol_vault_lamports: self.lamports(a.stable_vault.sol_vault).await?,
+            rewards_pool,
+            attn_vault_balance: self.token_balance(a.rewards.attn_vault).await?,
+            s_attn_supply: self.mint_supply(a.rewards.s_attn_mint).await?,
+            sol_treasury_lamports: self.lamports(a.rewards.sol_treasury).await?,
+            owed_rewards,
+            rent_exempt_minimum: self
+                .context
+                .banks_client
+                .get_rent()
+                .await?
+                .minimum_balance(0),
+        })
+    }
+
+    /// Fails with [`InvariantViolations`] listing every broken invariant.
+    pub async fn check_invariants(&mut self) -> Result<&mut Self> {
+        let violations = invariants::check(&self.snapshot().await?);
+        if !violations.is_empty() {
+            return Err(InvariantViolations(violations).into());
+        }
+        Ok(self)
+    }
+
+    async fn step(&mut self) -> Result<&mut Self> {
+        if self.check_each_step {
+            self.check_invariants().await?;
+        }
+        Ok(self)
+    }
+
+    async fn mint_to_owner(&mut self, mint: Pubkey, owner: Pubkey, amount: u64) -> Result<()> {
+        let payer = self.context.payer.pubkey();
+        let instructions = [
+            create_ata_ix(&payer, &owner, &mint),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &mint,
+                &ata(&owner, &mint),
+                &self.operator.pubkey(),
+                &[],
+                amount,
+            )?,
+        ];
+        process(&mut self.context, &instructions, &[&self.operator]).await
+    }
+
+    /// A `redeem_yield` at the market's current index, settling `owner`'s position
+    /// without publishing new fees; empty when there is nothing to settle.
+    async fn checkpoint_ix(
+        &mut self,
+        owner: Pubkey,
+        market: &MarketAccount,
+    ) -> Result<Vec<Instruction>> {
+        if self.position_of(owner).await?.is_none() {
+            return Ok(Vec::new());
+        }
+        if self
+            .token_balance(ata(&owner, &self.accounts.yt_mint))
+            .await?
+            == 0
+        {
+            return Ok(Vec::new());
+        }
+        let payer = self.context.payer.pubkey();
+        Ok(vec![
+            create_ata_ix(&payer, &owner, &self.accounts.quote_mint),
+            redeem_yield_ix(&self.accounts, owner, market.fee_index),
+        ])
+    }
+
+    async fn position_of(&mut self, owner: Pubkey) -> Result<Option<UserPositionAccount>> {
+        let (address, _) = splitter_client::user_position_pda(&self.accounts.market, &owner);
+        self.optional(address).await
+    }
+
+    async fn stake_position_of(&mut self, owner: Pubkey) -> Result<Option<StakePositionAccount>> {
+        let (address, _) = rewards::stake_position_pda(&self.accounts.rewards.rewards_pool, &owner);
+        self.optional(address).await
+    }
+
+    async fn optional<T: AnchorDeserialize>(&mut self, address: Pubkey) -> Result<Option<T>> {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await?
+            .map(|account| decode_account(&account.data))
+            .transpose()
+    }
+
+    async fn required<T: AnchorDeserialize>(&mut self, address: Pubkey) -> Result<T> {
+        self.optional(address)
+            .await?
+            .ok_or_else(|| anyhow!("account {address} not found"))
+    }
+}
+
+/// Signs with the test payer plus `signers` and processes `instructions`. A program
+/// failure comes back as a [`ProgramError`] that [`ProgramError::find`] recovers.
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<()> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    let outcome = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await?;
+    // A fresh blockhash keeps a repeated step from replaying an identical signature.
+    context.last_blockhash = context.get_new_latest_blockhash().await?;
+    let Err(error) = outcome.result else {
+        return Ok(());
+    };
+    let logs = outcome
+        .metadata
+        .map(|metadata| metadata.log_messages)
+        .unwrap_or_default();
+    match ProgramError::from_logs(&logs[..])
+        .or_else(|| ProgramError::from_transaction_error(&error))
+    {
+        Some(program_error) => {
+            Err(anyhow::Error::new(program_error).context(format!("transaction failed: {error}")))
+        }
+        None => Err(anyhow!("transaction failed: {error}\n{}", logs.join("\n"))),
+    }
+}
+
+fn actor<'a>(actors: &'a BTreeMap<String, Keypair>, name: &str) -> Result<&'a Keypair> {
+    actors
+        .get(name)
+        .ok_or_else(|| anyhow!("unknown actor {name}"))
+}
+
+fn wallet(lamports: u64) -> Account {
+    Account {
+        lamports,
+        ..Account::default()
+    }
+}
+
+fn mint_account(rent: &Rent, authority: Pubkey) -> Account {
+    let mut data = vec![0; MintState::LEN];
+    MintState {
+        mint_authority: COption::Some(authority),
+        supply: 0,
+        decimals: DECIMALS,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account {
+        lamports: rent.minimum_balance(MintState::LEN),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, mint)
+}
+
+fn create_ata_ix(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    create_associated_token_account_idempotent(payer, owner, mint, &spl_token::ID)
+}
+
+/// Index that folds `unindexed_fees` into the market, and the fees it accounts for;
+/// the division dust stays unindexed.
+fn next_fee_index(market: &MarketAccount, unindexed_fees: u64) -> (u128, u64) {
+    if market.total_yt_issued == 0 {
+        return (market.fee_index, 0);
+    }
+    let total_yt = market.total_yt_issued as u128;
+    let delta = unindexed_fees as u128 * splitter::FEE_INDEX_SCALE / total_yt;
+    let indexed = (delta * total_yt / splitter::FEE_INDEX_SCALE) as u64;
+    (market.fee_index + delta, indexed)
+}
+
+fn redeem_yield_ix(accounts: &ScenarioAccounts, owner: Pubkey, new_fee_index: u128) -> Instruction {
+    let (user_position, _) = splitter_client::user_position_pda(&accounts.market, &owner);
+    splitter_client::build_redeem_yield_ix(
+        accounts.market,
+        accounts.creator_vault.creator_vault,
+        owner,
+        user_position,
+        ata(&owner, &accounts.yt_mint),
+        accounts.creator_vault.fee_vault,
+        ata(&owner, &accounts.quote_mint),
+        new_fee_index,
+    )
+}
+
+type StakeIxBuilder = fn(
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    u64,
+) -> Instruction;
+
+/// `stake_attnusd` and `unstake_attnusd` take the same accounts.
+fn stake_ix(
+    accounts: &ScenarioAccounts,
+    staker: Pubkey,
+    amount: u64,
+    build: StakeIxBuilder,
+) -> Instruction {
+    let pdas = &accounts.rewards;
+    let share_mint = accounts.stable_vault.share_mint;
+    let (stake_position, _) = rewards::stake_position_pda(&pdas.rewards_pool, &staker);
+    build(
+        pdas.rewards_pool,
+        pdas.rewards_authority,
+        staker,
+        ata(&staker, &share_mint),
+        ata(&staker, &pdas.s_attn_mint),
+        pdas.attn_vault,
+        share_mint,
+        pdas.s_attn_mint,
+        stake_position,
+        pdas.sol_treasury,
+        amount,
+    )
+}
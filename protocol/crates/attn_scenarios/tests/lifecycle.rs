@@ -0,0 +1,124 @@
+use attn_client::errors::{AttnProgramError, ProgramError};
+use attn_scenarios::{ScenarioBuilder, CREATOR};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use splitter::SplitterError;
+
+#[tokio::test]
+async fn full_lifecycle_keeps_invariants() -> anyhow::Result<()> {
+    let mut scenario = ScenarioBuilder::new()
+        .actor("alice")
+        .sol_rewards_bps(2_000)
+        .build()
+        .await?;
+    let accounts = scenario.accounts().clone();
+
+    // The creator wraps fees and sells part of its YT to alice, who also splits.
+    scenario
+        .fund_quote(CREATOR, 1_000_000)
+        .await?
+        .wrap(CREATOR, 1_000_000)
+        .await?
+        .split(CREATOR, 600_000)
+        .await?
+        .fund_quote("alice", 500_000)
+        .await?
+        .wrap("alice", 100_000)
+        .await?
+        .split("alice", 100_000)
+        .await?
+        .sell_yt(CREATOR, "alice", 200_000, 20_000)
+        .await?;
+    assert_eq!(scenario.balance("alice", accounts.yt_mint).await?, 300_000);
+    assert_eq!(scenario.balance(CREATOR, accounts.yt_mint).await?, 400_000);
+
+    // Alice deposits stable and stakes the attnUSD shares.
+    scenario
+        .fund_stable("alice", 1_000_000)
+        .await?
+        .deposit("alice", 1_000_000)
+        .await?;
+    let shares = scenario
+        .balance("alice", accounts.stable_vault.share_mint)
+        .await?;
+    scenario.stake("alice", shares).await?;
+
+    // 70_000 of fees over 700_000 YT index at 0.1 per YT.
+    let alice_quote = scenario.balance("alice", accounts.quote_mint).await?;
+    let creator_quote = scenario.balance(CREATOR, accounts.quote_mint).await?;
+    scenario
+        .accrue_fees(70_000)
+        .await?
+        .redeem_yield("alice")
+        .await?
+        .redeem_yield(CREATOR)
+        .await?;
+    assert_eq!(scenario.unindexed_fees(), 0);
+    assert_eq!(
+        scenario.balance("alice", accounts.quote_mint).await?,
+        alice_quote + 30_000
+    );
+    assert_eq!(
+        scenario.balance(CREATOR, accounts.quote_mint).await?,
+        creator_quote + 40_000
+    );
+
+    scenario
+        .warp_to_maturity()
+        .await?
+        .redeem_principal(CREATOR, 400_000)
+        .await?
+        .redeem_principal("alice", 100_000)
+        .await?;
+    let market = scenario.market_account().await?;
+    assert_eq!(market.total_pt_issued, 200_000);
+    assert_eq!(market.total_yt_issued, 200_000);
+
+    // 20% of the sweep funds stakers; the rest waits in the SOL vault for conversion.
+    let alice_lamports = scenario.lamports(scenario.pubkey("alice")?).await?;
+    scenario
+        .sweep(2 * LAMPORTS_PER_SOL)
+        .await?
+        .convert(300_000, LAMPORTS_PER_SOL)
+        .await?
+        .claim("alice")
+        .await?;
+    let stable_vault = scenario.stable_vault_account().await?;
+    assert_eq!(stable_vault.pending_sol, 6 * LAMPORTS_PER_SOL / 10);
+    assert_eq!(stable_vault.total_assets, 1_300_000);
+    assert_eq!(
+        scenario.lamports(scenario.pubkey("alice")?).await?,
+        alice_lamports + 4 * LAMPORTS_PER_SOL / 10
+    );
+
+    scenario
+        .unstake("alice", shares)
+        .await?
+        .check_invariants()
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn redeem_principal_before_maturity_is_rejected() -> anyhow::Result<()> {
+    let mut scenario = ScenarioBuilder::new().build().await?;
+    scenario
+        .fund_quote(CREATOR, 1_000)
+        .await?
+        .wrap(CREATOR, 1_000)
+        .await?
+        .split(CREATOR, 1_000)
+        .await?;
+
+    let err = scenario
+        .redeem_principal(CREATOR, 1_000)
+        .await
+        .err()
+        .expect("redeem before maturity should fail");
+    let error = ProgramError::find(&err).expect("program error");
+    assert!(matches!(
+        error.attn,
+        Some(AttnProgramError::Splitter(SplitterError::MarketNotMatured))
+    ));
+    scenario.check_invariants().await?;
+    Ok(())
+}
@@ -0,0 +1,217 @@
+//! Pieces shared by every signed webhook attn sends: the notifier's webhook
+//! sink and the API's integrator webhooks.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::{redirect, Url};
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "x-attn-signature";
+pub const TIMESTAMP_HEADER: &str = "x-attn-timestamp";
+pub const DELIVERY_HEADER: &str = "x-attn-delivery";
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{body}"`, sent as `v1=<hex>` in
+/// [`SIGNATURE_HEADER`]. Receivers recompute it with their secret and reject
+/// stale timestamps.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Backoff after `attempts` failed deliveries: `base` doubling up to `max`.
+pub fn retry_delay(attempts: i32, base: Duration, max: Duration) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    base.saturating_mul(1 << exponent).min(max)
+}
+
+/// Whether `ip` is reachable on the public internet. Private, loopback,
+/// link-local, CGNAT, documentation and multicast ranges are not, nor are
+/// IPv4-mapped IPv6 forms of them.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b)))
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+/// Parses a webhook target. Targets must be HTTPS and must not name a
+/// private, loopback or link-local host; `allow_private` lifts both rules for
+/// local development and tests. Hostnames are checked again when they are
+/// resolved, see [`client`].
+pub fn check_url(raw: &str, allow_private: bool) -> Result<Url, String> {
+    let url = Url::parse(raw.trim()).map_err(|_| "url must be an absolute URL".to_string())?;
+    if allow_private {
+        return match url.scheme() {
+            "https" | "http" => Ok(url),
+            _ => Err("url must use https".to_string()),
+        };
+    }
+    if url.scheme() != "https" {
+        return Err("url must use https".to_string());
+    }
+    let host = url.host_str().unwrap_or_default();
+    let literal = host.trim_start_matches('[').trim_end_matches(']');
+    let public = match literal.parse::<IpAddr>() {
+        Ok(ip) => is_public_ip(ip),
+        Err(_) => {
+            let domain = host.trim_end_matches('.').to_ascii_lowercase();
+            !domain.is_empty() && domain != "localhost" && !domain.ends_with(".localhost")
+        }
+    };
+    if public {
+        Ok(url)
+    } else {
+        Err("url must not point at a private, loopback or link-local address".to_string())
+    }
+}
+
+/// HTTP client for webhook deliveries. Redirects are not followed and,
+/// unless `allow_private` is set, hostnames that resolve to a non-public
+/// address fail to connect, so a DNS change after registration can't point
+/// deliveries at internal services. Fails rather than handing back a client
+/// without those guards.
+pub fn client(timeout: Duration, allow_private: bool) -> Result<reqwest::Client> {
+    let builder = reqwest::Client::builder()
+        .timeout(timeout)
+        .redirect(redirect::Policy::none());
+    let builder = if allow_private {
+        builder
+    } else {
+        // A proxy would resolve the host itself, past the check below.
+        builder.no_proxy().dns_resolver(Arc::new(PublicResolver))
+    };
+    builder.build().context("failed to build webhook client")
+}
+
+/// System resolver that refuses hosts with any non-public address.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+                return Err(format!("{host} resolves to non-public address {}", addr.ip()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_cover_timestamp_and_body() {
+        let signature = sign_payload("secret", 1_700_000_000, b"{}");
+        assert_eq!(signature.len(), 64);
+        assert_eq!(signature, sign_payload("secret", 1_700_000_000, b"{}"));
+        assert_ne!(signature, sign_payload("secret", 1_700_000_001, b"{}"));
+        assert_ne!(signature, sign_payload("other", 1_700_000_000, b"{}"));
+        assert_ne!(signature, sign_payload("secret", 1_700_000_000, b"[]"));
+    }
+
+    #[test]
+    fn retries_double_up_to_the_cap() {
+        let delay =
+            |attempts| retry_delay(attempts, Duration::from_secs(15), Duration::from_secs(60));
+        assert_eq!(delay(0), Duration::from_secs(15));
+        assert_eq!(delay(1), Duration::from_secs(15));
+        assert_eq!(delay(2), Duration::from_secs(30));
+        assert_eq!(delay(3), Duration::from_secs(60));
+        assert_eq!(delay(i32::MAX), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn internal_addresses_are_not_public() {
+        for ip in [
+            "10.0.0.8",
+            "172.16.4.1",
+            "192.168.1.1",
+            "127.0.0.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn webhook_urls_must_be_public_https() {
+        assert!(check_url("https://hooks.example.com/attn", false).is_ok());
+        for url in [
+            "http://hooks.example.com/attn",
+            "https://localhost/attn",
+            "https://api.localhost/attn",
+            "https://127.0.0.1/attn",
+            "https://10.1.2.3/attn",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/attn",
+            "https://[::ffff:192.168.0.1]/attn",
+            "not a url",
+        ] {
+            assert!(check_url(url, false).is_err(), "{url}");
+        }
+        assert!(check_url("http://127.0.0.1:8080/attn", true).is_ok());
+        assert!(check_url("ftp://127.0.0.1/attn", true).is_err());
+    }
+
+    #[tokio::test]
+    async fn deliveries_refuse_hosts_resolving_to_private_addresses() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let err = client(Duration::from_secs(5), false)
+            .unwrap()
+            .post(format!("http://localhost:{port}/hook"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_connect(), "{err:?}");
+    }
+}
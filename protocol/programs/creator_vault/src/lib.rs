@@ -1,10 +1,30 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token::{self, spl_token, Mint, MintTo, SyncNative, Token, TokenAccount, Transfer};
 
 declare_id!("HDztZyNcij21HhF5SR6rhk9wx9qx6yViebUrVU9W6C86");
 
 const TOTAL_BPS: u64 = 10_000;
 
+/// Anchor sighash of `claim_creator_trading_fee` on Meteora DBC-style programs.
+pub const DBC_CLAIM_CREATOR_TRADING_FEE: [u8; 8] = [82, 220, 250, 189, 3, 85, 107, 45];
+/// Account count of `claim_creator_trading_fee` and the positions
+/// `claim_source_fees` checks before signing for the vault.
+pub const DBC_CLAIM_ACCOUNTS: usize = 13;
+const DBC_CLAIM_POOL: usize = 1;
+const DBC_CLAIM_BASE_RECEIVER: usize = 2;
+const DBC_CLAIM_QUOTE_RECEIVER: usize = 3;
+const DBC_CLAIM_BASE_MINT: usize = 6;
+const DBC_CLAIM_QUOTE_MINT: usize = 7;
+const DBC_CLAIM_CREATOR: usize = 8;
+
+pub const PUMP_PROGRAM_ID: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+/// Anchor sighash of Pump.fun `collect_creator_fee`.
+pub const PUMP_COLLECT_CREATOR_FEE: [u8; 8] = [20, 22, 86, 123, 198, 28, 219, 132];
+
 #[program]
 pub mod creator_vault {
     use super::*;
@@ -33,7 +53,7 @@ pub mod creator_vault {
         vault.paused = false;
         vault.locked = false;
         vault.lock_expires_at = 0;
-        vault.padding = [0; 1];
+        vault.source_kind = RevenueSourceKind::PumpFun;
         emit!(VaultInitialized {
             creator_vault: vault.key(),
             pump_mint: vault.pump_mint,
@@ -45,6 +65,280 @@ pub mod creator_vault {
         Ok(())
     }
 
+    pub fn initialize_source_vault(
+        ctx: Context<InitializeSourceVault>,
+        config: RevenueSourceConfig,
+        splitter_program: Pubkey,
+        admin: Pubkey,
+    ) -> Result<()> {
+        config.validate()?;
+        require!(admin != Pubkey::default(), AttnError::InvalidAdmin);
+
+        let vault = &mut ctx.accounts.creator_vault;
+        let bumps = &ctx.bumps;
+        vault.bump = bumps.creator_vault;
+        vault.fee_vault_bump = bumps.fee_vault;
+        vault.sy_mint_bump = bumps.sy_mint;
+        vault.authority = ctx.accounts.authority.key();
+        vault.pump_creator = match config {
+            RevenueSourceConfig::PumpFun { creator } => creator,
+            _ => Pubkey::default(),
+        };
+        vault.pump_mint = ctx.accounts.source_id.key();
+        vault.quote_mint = ctx.accounts.quote_mint.key();
+        vault.sy_mint = ctx.accounts.sy_mint.key();
+        vault.splitter_program = splitter_program;
+        vault.total_fees_collected = 0;
+        vault.total_sy_minted = 0;
+        vault.admin = admin;
+        vault.sol_rewards_bps = 0;
+        vault.paused = false;
+        vault.locked = false;
+        vault.lock_expires_at = 0;
+        vault.source_kind = config.kind();
+
+        let source = &mut ctx.accounts.revenue_source;
+        source.bump = bumps.revenue_source;
+        source.creator_vault = vault.key();
+        source.config = config.clone();
+        source.total_collected = 0;
+        source.last_collected_at = 0;
+
+        emit!(VaultInitialized {
+            creator_vault: vault.key(),
+            pump_mint: vault.pump_mint,
+            quote_mint: vault.quote_mint,
+            sy_mint: vault.sy_mint,
+            authority: ctx.accounts.authority.key(),
+            admin,
+        });
+        emit!(RevenueSourceConfigured {
+            creator_vault: vault.key(),
+            source_id: vault.pump_mint,
+            config,
+        });
+        Ok(())
+    }
+
+    /// Claims only the quote side; base-side fees stay in the pool because
+    /// the vault has no way to account for them.
+    pub fn claim_source_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimSourceFees<'info>>,
+        max_quote_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.creator_vault.assert_not_paused()?;
+        let (program, pool) = match ctx.accounts.revenue_source.config {
+            RevenueSourceConfig::MeteoraDbc { program, pool } => (program, pool),
+            _ => return err!(AttnError::SourceClaimUnsupported),
+        };
+        require_keys_eq!(
+            ctx.accounts.source_program.key(),
+            program,
+            AttnError::InvalidSourceProgram
+        );
+
+        // The vault PDA signs as the pool creator, so every account that
+        // decides where fees go is pinned before the CPI.
+        let claim_accounts = ctx.remaining_accounts;
+        require!(
+            claim_accounts.len() == DBC_CLAIM_ACCOUNTS,
+            AttnError::InvalidClaimAccounts
+        );
+        require_keys_eq!(
+            claim_accounts[DBC_CLAIM_POOL].key(),
+            pool,
+            AttnError::RevenueSourceMismatch
+        );
+        let vault_key = ctx.accounts.creator_vault.key();
+        let base_mint = ctx.accounts.creator_vault.pump_mint;
+        require!(
+            claim_accounts[DBC_CLAIM_QUOTE_RECEIVER].key() == ctx.accounts.fee_vault.key()
+                && claim_accounts[DBC_CLAIM_BASE_MINT].key() == base_mint
+                && claim_accounts[DBC_CLAIM_QUOTE_MINT].key()
+                    == ctx.accounts.creator_vault.quote_mint
+                && claim_accounts[DBC_CLAIM_CREATOR].key() == vault_key,
+            AttnError::InvalidClaimAccounts
+        );
+        let base_receiver =
+            Account::<TokenAccount>::try_from(&claim_accounts[DBC_CLAIM_BASE_RECEIVER])
+                .map_err(|_| error!(AttnError::InvalidClaimAccounts))?;
+        require!(
+            base_receiver.owner == vault_key && base_receiver.mint == base_mint,
+            AttnError::InvalidClaimAccounts
+        );
+
+        let metas = claim_accounts
+            .iter()
+            .enumerate()
+            .map(|(index, account)| AccountMeta {
+                pubkey: account.key(),
+                is_signer: index == DBC_CLAIM_CREATOR,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        let mut data = DBC_CLAIM_CREATOR_TRADING_FEE.to_vec();
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&max_quote_amount.to_le_bytes());
+        let claim = Instruction {
+            program_id: program,
+            accounts: metas,
+            data,
+        };
+        let mut infos = ctx.remaining_accounts.to_vec();
+        infos.push(ctx.accounts.source_program.to_account_info());
+
+        let before = ctx.accounts.fee_vault.amount;
+        let vault_bump = [ctx.accounts.creator_vault.bump];
+        let seeds: [&[u8]; 3] = [
+            b"creator-vault",
+            ctx.accounts.creator_vault.pump_mint.as_ref(),
+            &vault_bump,
+        ];
+        invoke_signed(&claim, &infos, &[&seeds[..]])?;
+        ctx.accounts.fee_vault.reload()?;
+        let amount = ctx
+            .accounts
+            .fee_vault
+            .amount
+            .checked_sub(before)
+            .ok_or(AttnError::MathOverflow)?;
+        require!(amount > 0, AttnError::NothingClaimed);
+
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = &mut *ctx.accounts;
+        accounts
+            .revenue_source
+            .record(&mut accounts.creator_vault, amount, now)?;
+        emit!(SourceRevenueCollected {
+            creator_vault: vault_key,
+            kind: RevenueSourceKind::MeteoraDbc,
+            amount,
+            total_collected: accounts.revenue_source.total_collected,
+        });
+        Ok(())
+    }
+
+    pub fn deposit_source_revenue(ctx: Context<DepositSourceRevenue>, amount: u64) -> Result<()> {
+        require!(amount > 0, AttnError::InvalidAmount);
+        ctx.accounts.creator_vault.assert_not_paused()?;
+        match ctx.accounts.revenue_source.config {
+            RevenueSourceConfig::SplTransfer { depositor } => {
+                if let Some(depositor) = depositor {
+                    require_keys_eq!(
+                        ctx.accounts.depositor.key(),
+                        depositor,
+                        AttnError::UnauthorizedDepositor
+                    );
+                }
+            }
+            _ => return err!(AttnError::SourceDepositUnsupported),
+        }
+
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = &mut *ctx.accounts;
+        accounts
+            .revenue_source
+            .record(&mut accounts.creator_vault, amount, now)?;
+        emit!(SourceRevenueCollected {
+            creator_vault: accounts.creator_vault.key(),
+            kind: RevenueSourceKind::SplTransfer,
+            amount,
+            total_collected: accounts.revenue_source.total_collected,
+        });
+        Ok(())
+    }
+
+    pub fn collect_creator_fees(ctx: Context<CollectCreatorFees>) -> Result<()> {
+        let vault = &ctx.accounts.creator_vault;
+        vault.assert_not_paused()?;
+        require!(
+            vault.source_kind == RevenueSourceKind::PumpFun,
+            AttnError::SourceClaimUnsupported
+        );
+        require_keys_eq!(
+            vault.quote_mint,
+            spl_token::native_mint::ID,
+            AttnError::QuoteMintNotNative
+        );
+
+        let vault_key = vault.key();
+        let vault_info = vault.to_account_info();
+        let claim = Instruction {
+            program_id: PUMP_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(vault_key, true),
+                AccountMeta::new(ctx.accounts.pump_creator_vault.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.pump_event_authority.key(), false),
+                AccountMeta::new_readonly(PUMP_PROGRAM_ID, false),
+            ],
+            data: PUMP_COLLECT_CREATOR_FEE.to_vec(),
+        };
+        let before = vault_info.lamports();
+        let vault_bump = [vault.bump];
+        let seeds: [&[u8]; 3] = [b"creator-vault", vault.pump_mint.as_ref(), &vault_bump];
+        invoke_signed(
+            &claim,
+            &[
+                vault_info.clone(),
+                ctx.accounts.pump_creator_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.pump_event_authority.to_account_info(),
+                ctx.accounts.pump_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+        let amount = vault_info
+            .lamports()
+            .checked_sub(before)
+            .ok_or(AttnError::MathOverflow)?;
+
+        if amount > 0 {
+            // Claimed SOL lands on the vault PDA; move it into the wSOL fee
+            // vault and sync so it counts as quote balance.
+            let fee_vault_info = ctx.accounts.fee_vault.to_account_info();
+            **vault_info.try_borrow_mut_lamports()? -= amount;
+            **fee_vault_info.try_borrow_mut_lamports()? += amount;
+            token::sync_native(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                SyncNative {
+                    account: fee_vault_info,
+                },
+            ))?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = &mut *ctx.accounts;
+        match accounts.revenue_source.as_mut() {
+            Some(source) => source.record(&mut accounts.creator_vault, amount, now)?,
+            None => {
+                accounts.creator_vault.total_fees_collected = accounts
+                    .creator_vault
+                    .total_fees_collected
+                    .checked_add(amount)
+                    .ok_or(AttnError::MathOverflow)?;
+            }
+        }
+        emit!(CreatorFeesCollected {
+            creator_vault: vault_key,
+            cranker: accounts.cranker.key(),
+            amount,
+            total_fees_collected: accounts.creator_vault.total_fees_collected,
+        });
+        Ok(())
+    }
+
     pub fn wrap_fees(ctx: Context<WrapFees>, amount: u64) -> Result<()> {
         require!(amount > 0, AttnError::InvalidAmount);
 
@@ -169,6 +463,8 @@ pub mod creator_vault {
             AttnError::InvalidSweeperDelegate
         );
         require!(fee_bps as u64 <= TOTAL_BPS, AttnError::InvalidSweeperFee);
+        ctx.accounts.allowance.check_active()?;
+        ctx.accounts.allowance.check_fee(fee_bps)?;
 
         let sweeper = &mut ctx.accounts.sweeper;
         sweeper.bump = ctx.bumps.sweeper;
@@ -192,6 +488,50 @@ pub mod creator_vault {
         Ok(())
     }
 
+    pub fn allow_sweeper_delegate(
+        ctx: Context<AllowSweeperDelegate>,
+        delegate: Pubkey,
+        limits: SweeperLimits,
+    ) -> Result<()> {
+        require!(
+            delegate != Pubkey::default(),
+            AttnError::InvalidSweeperDelegate
+        );
+        limits.validate()?;
+        ctx.accounts
+            .creator_vault
+            .assert_admin(&ctx.accounts.admin.key())?;
+
+        // Updating or re-allowing an existing entry keeps the current window
+        // so neither tightening the limits nor a revoke/allow round trip can
+        // reset what was already swept.
+        let allowance = &mut ctx.accounts.allowance;
+        allowance.bump = ctx.bumps.allowance;
+        allowance.creator_vault = ctx.accounts.creator_vault.key();
+        allowance.delegate = delegate;
+        allowance.limits = limits.clone();
+        allowance.revoked = false;
+
+        emit!(SweeperDelegateAllowed {
+            creator_vault: allowance.creator_vault,
+            delegate,
+            limits,
+        });
+        Ok(())
+    }
+
+    pub fn revoke_sweeper_delegate(ctx: Context<RevokeSweeperDelegate>) -> Result<()> {
+        ctx.accounts
+            .creator_vault
+            .assert_admin(&ctx.accounts.admin.key())?;
+        ctx.accounts.allowance.revoked = true;
+        emit!(SweeperDelegateRevoked {
+            creator_vault: ctx.accounts.creator_vault.key(),
+            delegate: ctx.accounts.allowance.delegate,
+        });
+        Ok(())
+    }
+
     pub fn delegate_sweep(ctx: Context<DelegateSweep>, amount: u64) -> Result<()> {
         require!(amount > 0, AttnError::InvalidAmount);
 
@@ -218,6 +558,11 @@ pub mod creator_vault {
             AttnError::UnauthorizedSweeper
         );
 
+        let allowance = &mut ctx.accounts.allowance;
+        allowance.check_active()?;
+        allowance.check_fee(sweeper.fee_bps)?;
+        allowance.record_sweep(amount, now)?;
+
         require!(
             ctx.accounts.fee_vault.amount >= amount,
             AttnError::InsufficientVaultBalance
@@ -533,6 +878,136 @@ pub struct InitializeVault<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeSourceVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Mint identifying the revenue stream (the launched token for launchpad
+    /// sources); seeds every vault PDA in place of the Pump.fun mint.
+    pub source_id: Account<'info, Mint>,
+    pub quote_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CreatorVault::INIT_SPACE,
+        seeds = [b"creator-vault", source_id.key().as_ref()],
+        bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = creator_vault,
+        seeds = [b"fee-vault", source_id.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = quote_mint.decimals,
+        mint::authority = creator_vault,
+        seeds = [b"sy-mint", source_id.key().as_ref()],
+        bump
+    )]
+    pub sy_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RevenueSource::INIT_SPACE,
+        seeds = [b"revenue-source", creator_vault.key().as_ref()],
+        bump
+    )]
+    pub revenue_source: Account<'info, RevenueSource>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSourceFees<'info> {
+    #[account(mut)]
+    pub creator_vault: Account<'info, CreatorVault>,
+    #[account(
+        mut,
+        seeds = [b"revenue-source", creator_vault.key().as_ref()],
+        bump = revenue_source.bump,
+        has_one = creator_vault
+    )]
+    pub revenue_source: Account<'info, RevenueSource>,
+    #[account(
+        mut,
+        seeds = [b"fee-vault", creator_vault.pump_mint.as_ref()],
+        bump = creator_vault.fee_vault_bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    /// CHECK: must match the program recorded in the revenue source config
+    #[account(executable)]
+    pub source_program: UncheckedAccount<'info>,
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSourceRevenue<'info> {
+    #[account(mut, has_one = quote_mint)]
+    pub creator_vault: Account<'info, CreatorVault>,
+    #[account(
+        mut,
+        seeds = [b"revenue-source", creator_vault.key().as_ref()],
+        bump = revenue_source.bump,
+        has_one = creator_vault
+    )]
+    pub revenue_source: Account<'info, RevenueSource>,
+    pub quote_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"fee-vault", creator_vault.pump_mint.as_ref()],
+        bump = creator_vault.fee_vault_bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    pub depositor: Signer<'info>,
+    #[account(mut, constraint = depositor_token_account.mint == quote_mint.key(), constraint = depositor_token_account.owner == depositor.key())]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CollectCreatorFees<'info> {
+    #[account(mut)]
+    pub creator_vault: Account<'info, CreatorVault>,
+    #[account(
+        mut,
+        seeds = [b"revenue-source", creator_vault.key().as_ref()],
+        bump = revenue_source.bump,
+        has_one = creator_vault
+    )]
+    pub revenue_source: Option<Account<'info, RevenueSource>>,
+    #[account(
+        mut,
+        seeds = [b"fee-vault", creator_vault.pump_mint.as_ref()],
+        bump = creator_vault.fee_vault_bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    /// CHECK: Pump.fun creator fee PDA for the vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"creator-vault", creator_vault.key().as_ref()],
+        bump,
+        seeds::program = PUMP_PROGRAM_ID
+    )]
+    pub pump_creator_vault: UncheckedAccount<'info>,
+    /// CHECK: Pump.fun event authority, validated by seeds
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = PUMP_PROGRAM_ID)]
+    pub pump_event_authority: UncheckedAccount<'info>,
+    /// CHECK: address constrained to the Pump.fun program
+    #[account(address = PUMP_PROGRAM_ID)]
+    pub pump_program: UncheckedAccount<'info>,
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct WrapFees<'info> {
     #[account(mut, has_one = quote_mint, has_one = sy_mint, has_one = pump_mint)]
@@ -580,6 +1055,7 @@ pub struct WithdrawFees<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
 pub struct SetSweeperDelegate<'info> {
     #[account(mut, has_one = authority)]
     pub creator_vault: Account<'info, CreatorVault>,
@@ -593,9 +1069,50 @@ pub struct SetSweeperDelegate<'info> {
         bump
     )]
     pub sweeper: Account<'info, CreatorVaultSweeper>,
+    /// Only delegates the admin has allowlisted can be appointed.
+    #[account(
+        seeds = [b"sweeper-allowance", creator_vault.key().as_ref(), delegate.as_ref()],
+        bump = allowance.bump,
+        has_one = creator_vault
+    )]
+    pub allowance: Account<'info, SweeperAllowance>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct AllowSweeperDelegate<'info> {
+    #[account(has_one = admin)]
+    pub creator_vault: Account<'info, CreatorVault>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + SweeperAllowance::INIT_SPACE,
+        seeds = [b"sweeper-allowance", creator_vault.key().as_ref(), delegate.as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, SweeperAllowance>,
     pub system_program: Program<'info, System>,
 }
 
+/// Revoking keeps the allowance account, flagged, so its sweep window
+/// survives a later `allow_sweeper_delegate`.
+#[derive(Accounts)]
+pub struct RevokeSweeperDelegate<'info> {
+    #[account(has_one = admin)]
+    pub creator_vault: Account<'info, CreatorVault>,
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"sweeper-allowance", creator_vault.key().as_ref(), allowance.delegate.as_ref()],
+        bump = allowance.bump,
+        has_one = creator_vault
+    )]
+    pub allowance: Account<'info, SweeperAllowance>,
+}
+
 #[derive(Accounts)]
 pub struct ClearSweeperDelegate<'info> {
     #[account(mut, has_one = authority)]
@@ -623,6 +1140,13 @@ pub struct DelegateSweep<'info> {
         has_one = delegate
     )]
     pub sweeper: Account<'info, CreatorVaultSweeper>,
+    #[account(
+        mut,
+        seeds = [b"sweeper-allowance", creator_vault.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump,
+        has_one = creator_vault
+    )]
+    pub allowance: Account<'info, SweeperAllowance>,
     pub delegate: Signer<'info>,
     #[account(
         mut,
@@ -709,7 +1233,9 @@ pub struct CreatorVault {
     pub paused: bool,
     pub locked: bool,
     pub lock_expires_at: i64,
-    pub padding: [u8; 1],
+    /// Revenue source feeding the fee vault. Vaults created before sources
+    /// existed read the former zeroed padding byte as `PumpFun`.
+    pub source_kind: RevenueSourceKind,
 }
 
 impl CreatorVault {
@@ -755,6 +1281,166 @@ impl CreatorVaultSweeper {
     pub const INIT_SPACE: usize = 1 + 32 + 32 + 2 + 8 + 5;
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SweeperLimits {
+    /// Highest `fee_bps` the authority may grant this delegate.
+    pub max_fee_bps: u16,
+    /// Gross amount (creator share plus delegate fee) sweepable per window.
+    pub max_amount_per_window: u64,
+    pub window_secs: i64,
+    /// Minimum seconds between sweeps, measured from the allowance's
+    /// `last_sweep_ts`.
+    pub min_interval_secs: i64,
+}
+
+impl SweeperLimits {
+    pub const SPACE: usize = 2 + 8 + 8 + 8;
+
+    fn validate(&self) -> Result<()> {
+        require!(
+            self.max_fee_bps as u64 <= TOTAL_BPS,
+            AttnError::InvalidSweeperFee
+        );
+        require!(
+            self.max_amount_per_window > 0 && self.window_secs > 0 && self.min_interval_secs >= 0,
+            AttnError::InvalidSweeperLimits
+        );
+        Ok(())
+    }
+}
+
+/// Admin-managed allowlist entry for one sweeper delegate, carrying its
+/// limits and the fixed-window sweep accounting.
+#[account]
+pub struct SweeperAllowance {
+    pub bump: u8,
+    pub creator_vault: Pubkey,
+    pub delegate: Pubkey,
+    pub limits: SweeperLimits,
+    pub window_start_ts: i64,
+    pub window_swept: u64,
+    /// Set by `revoke_sweeper_delegate`, cleared by `allow_sweeper_delegate`.
+    pub revoked: bool,
+    /// Last sweep by this delegate. Kept here rather than on the sweeper,
+    /// which `set_sweeper_delegate` rewrites, so re-setting the delegate
+    /// can't skip `min_interval_secs`.
+    pub last_sweep_ts: i64,
+    pub padding: [u8; 7],
+}
+
+impl SweeperAllowance {
+    pub const INIT_SPACE: usize = 1 + 32 + 32 + SweeperLimits::SPACE + 8 + 8 + 1 + 8 + 7;
+
+    fn check_active(&self) -> Result<()> {
+        require!(!self.revoked, AttnError::UnauthorizedSweeper);
+        Ok(())
+    }
+
+    fn check_fee(&self, fee_bps: u16) -> Result<()> {
+        require!(
+            fee_bps <= self.limits.max_fee_bps,
+            AttnError::SweeperFeeAboveLimit
+        );
+        Ok(())
+    }
+
+    fn record_sweep(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.last_sweep_ts > 0 {
+            let elapsed = now.saturating_sub(self.last_sweep_ts);
+            require!(
+                elapsed >= self.limits.min_interval_secs,
+                AttnError::SweepTooFrequent
+            );
+        }
+        if now >= self.window_start_ts.saturating_add(self.limits.window_secs) {
+            self.window_start_ts = now;
+            self.window_swept = 0;
+        }
+        let swept = self
+            .window_swept
+            .checked_add(amount)
+            .ok_or(AttnError::MathOverflow)?;
+        require!(
+            swept <= self.limits.max_amount_per_window,
+            AttnError::SweepWindowLimitExceeded
+        );
+        self.window_swept = swept;
+        self.last_sweep_ts = now;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RevenueSourceKind {
+    #[default]
+    PumpFun,
+    MeteoraDbc,
+    SplTransfer,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum RevenueSourceConfig {
+    /// Pump.fun creator rewards, pulled by `collect_creator_fees` once the
+    /// vault PDA is the coin creator or wrapped manually via `wrap_fees`.
+    PumpFun { creator: Pubkey },
+    /// Meteora DBC-style pool whose creator trading fees are claimed by CPI
+    /// with the vault PDA as pool creator.
+    MeteoraDbc { program: Pubkey, pool: Pubkey },
+    /// Plain SPL transfers into the fee vault, optionally from one depositor.
+    SplTransfer { depositor: Option<Pubkey> },
+}
+
+impl RevenueSourceConfig {
+    pub const MAX_SPACE: usize = 1 + 32 + 32;
+
+    pub fn kind(&self) -> RevenueSourceKind {
+        match self {
+            RevenueSourceConfig::PumpFun { .. } => RevenueSourceKind::PumpFun,
+            RevenueSourceConfig::MeteoraDbc { .. } => RevenueSourceKind::MeteoraDbc,
+            RevenueSourceConfig::SplTransfer { .. } => RevenueSourceKind::SplTransfer,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let valid = match self {
+            RevenueSourceConfig::PumpFun { creator } => *creator != Pubkey::default(),
+            RevenueSourceConfig::MeteoraDbc { program, pool } => {
+                *program != Pubkey::default() && *program != crate::ID && *pool != Pubkey::default()
+            }
+            RevenueSourceConfig::SplTransfer { depositor } => *depositor != Some(Pubkey::default()),
+        };
+        require!(valid, AttnError::InvalidRevenueSource);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct RevenueSource {
+    pub bump: u8,
+    pub creator_vault: Pubkey,
+    pub config: RevenueSourceConfig,
+    pub total_collected: u64,
+    pub last_collected_at: i64,
+    pub padding: [u8; 32],
+}
+
+impl RevenueSource {
+    pub const INIT_SPACE: usize = 1 + 32 + RevenueSourceConfig::MAX_SPACE + 8 + 8 + 32;
+
+    fn record(&mut self, vault: &mut CreatorVault, amount: u64, now: i64) -> Result<()> {
+        vault.total_fees_collected = vault
+            .total_fees_collected
+            .checked_add(amount)
+            .ok_or(AttnError::MathOverflow)?;
+        self.total_collected = self
+            .total_collected
+            .checked_add(amount)
+            .ok_or(AttnError::MathOverflow)?;
+        self.last_collected_at = now;
+        Ok(())
+    }
+}
+
 #[event]
 pub struct VaultInitialized {
     pub creator_vault: Pubkey,
@@ -765,6 +1451,29 @@ pub struct VaultInitialized {
     pub admin: Pubkey,
 }
 
+#[event]
+pub struct RevenueSourceConfigured {
+    pub creator_vault: Pubkey,
+    pub source_id: Pubkey,
+    pub config: RevenueSourceConfig,
+}
+
+#[event]
+pub struct SourceRevenueCollected {
+    pub creator_vault: Pubkey,
+    pub kind: RevenueSourceKind,
+    pub amount: u64,
+    pub total_collected: u64,
+}
+
+#[event]
+pub struct CreatorFeesCollected {
+    pub creator_vault: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+    pub total_fees_collected: u64,
+}
+
 #[event]
 pub struct SyMinted {
     pub user: Pubkey,
@@ -801,6 +1510,19 @@ pub struct SweeperDelegateCleared {
     pub creator_vault: Pubkey,
 }
 
+#[event]
+pub struct SweeperDelegateAllowed {
+    pub creator_vault: Pubkey,
+    pub delegate: Pubkey,
+    pub limits: SweeperLimits,
+}
+
+#[event]
+pub struct SweeperDelegateRevoked {
+    pub creator_vault: Pubkey,
+    pub delegate: Pubkey,
+}
+
 #[event]
 pub struct DelegatedFeesSwept {
     pub creator_vault: Pubkey,
@@ -871,6 +1593,32 @@ pub enum AttnError {
     DelegateFeeDestinationRequired,
     #[msg("Invalid delegate fee destination")]
     InvalidDelegateFeeDestination,
+    #[msg("Invalid revenue source configuration")]
+    InvalidRevenueSource,
+    #[msg("Revenue source does not support fee claims")]
+    SourceClaimUnsupported,
+    #[msg("Revenue source does not accept deposits")]
+    SourceDepositUnsupported,
+    #[msg("Source program does not match the revenue source")]
+    InvalidSourceProgram,
+    #[msg("Claim accounts do not reference the configured pool")]
+    RevenueSourceMismatch,
+    #[msg("Depositor is not allowed for this revenue source")]
+    UnauthorizedDepositor,
+    #[msg("Pump.fun fees can only be collected into a wSOL fee vault")]
+    QuoteMintNotNative,
+    #[msg("Sweeper limits must set a positive window and amount")]
+    InvalidSweeperLimits,
+    #[msg("Sweeper fee exceeds the delegate's allowlisted maximum")]
+    SweeperFeeAboveLimit,
+    #[msg("Delegate sweep attempted before the minimum interval elapsed")]
+    SweepTooFrequent,
+    #[msg("Delegate sweep exceeds the amount allowed in the current window")]
+    SweepWindowLimitExceeded,
+    #[msg("Claim accounts do not match the DBC claim layout")]
+    InvalidClaimAccounts,
+    #[msg("Source claim moved no fees into the fee vault")]
+    NothingClaimed,
 }
 
 #[cfg(test)]
@@ -895,7 +1643,18 @@ mod tests {
             paused: false,
             locked: false,
             lock_expires_at: 0,
-            padding: [0; 1],
+            source_kind: RevenueSourceKind::PumpFun,
+        }
+    }
+
+    fn mock_revenue_source(config: RevenueSourceConfig) -> RevenueSource {
+        RevenueSource {
+            bump: 1,
+            creator_vault: Pubkey::new_unique(),
+            config,
+            total_collected: 0,
+            last_collected_at: 0,
+            padding: [0; 32],
         }
     }
 
@@ -935,4 +1694,171 @@ mod tests {
         assert!(!vault.refresh_lock(150));
         assert!(vault.locked);
     }
+
+    #[test]
+    fn legacy_padding_decodes_as_pump_fun() {
+        let mut vault = mock_creator_vault();
+        vault.source_kind = RevenueSourceKind::SplTransfer;
+        let mut data = Vec::new();
+        vault.serialize(&mut data).unwrap();
+        *data.last_mut().unwrap() = 0;
+        let decoded = CreatorVault::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded.source_kind, RevenueSourceKind::PumpFun);
+    }
+
+    #[test]
+    fn revenue_source_config_validation() {
+        let key = Pubkey::new_unique();
+        assert!(RevenueSourceConfig::PumpFun { creator: key }
+            .validate()
+            .is_ok());
+        assert!(RevenueSourceConfig::SplTransfer { depositor: None }
+            .validate()
+            .is_ok());
+        let invalid = [
+            RevenueSourceConfig::PumpFun {
+                creator: Pubkey::default(),
+            },
+            RevenueSourceConfig::MeteoraDbc {
+                program: crate::ID,
+                pool: key,
+            },
+            RevenueSourceConfig::MeteoraDbc {
+                program: key,
+                pool: Pubkey::default(),
+            },
+            RevenueSourceConfig::SplTransfer {
+                depositor: Some(Pubkey::default()),
+            },
+        ];
+        for config in invalid {
+            let err = config.validate().unwrap_err();
+            assert_eq!(err, AttnError::InvalidRevenueSource.into());
+        }
+    }
+
+    #[test]
+    fn revenue_source_fits_largest_config() {
+        let source = mock_revenue_source(RevenueSourceConfig::MeteoraDbc {
+            program: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+        });
+        let mut data = Vec::new();
+        source.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), RevenueSource::INIT_SPACE);
+    }
+
+    fn mock_allowance(limits: SweeperLimits) -> SweeperAllowance {
+        SweeperAllowance {
+            bump: 1,
+            creator_vault: Pubkey::new_unique(),
+            delegate: Pubkey::new_unique(),
+            limits,
+            window_start_ts: 0,
+            window_swept: 0,
+            revoked: false,
+            last_sweep_ts: 0,
+            padding: [0; 7],
+        }
+    }
+
+    fn limits() -> SweeperLimits {
+        SweeperLimits {
+            max_fee_bps: 100,
+            max_amount_per_window: 1_000,
+            window_secs: 3_600,
+            min_interval_secs: 60,
+        }
+    }
+
+    #[test]
+    fn sweeper_limits_validation() {
+        assert!(limits().validate().is_ok());
+        let invalid = [
+            SweeperLimits {
+                max_amount_per_window: 0,
+                ..limits()
+            },
+            SweeperLimits {
+                window_secs: 0,
+                ..limits()
+            },
+            SweeperLimits {
+                min_interval_secs: -1,
+                ..limits()
+            },
+        ];
+        for limits in invalid {
+            let err = limits.validate().unwrap_err();
+            assert_eq!(err, AttnError::InvalidSweeperLimits.into());
+        }
+        let err = SweeperLimits {
+            max_fee_bps: 10_001,
+            ..limits()
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(err, AttnError::InvalidSweeperFee.into());
+    }
+
+    #[test]
+    fn sweeper_allowance_fits_space() {
+        let mut data = Vec::new();
+        mock_allowance(limits()).serialize(&mut data).unwrap();
+        assert_eq!(data.len(), SweeperAllowance::INIT_SPACE);
+    }
+
+    #[test]
+    fn allowance_caps_delegate_fee() {
+        let allowance = mock_allowance(limits());
+        assert!(allowance.check_fee(100).is_ok());
+        let err = allowance.check_fee(101).unwrap_err();
+        assert_eq!(err, AttnError::SweeperFeeAboveLimit.into());
+    }
+
+    #[test]
+    fn revoked_allowance_is_inactive() {
+        let mut allowance = mock_allowance(limits());
+        assert!(allowance.check_active().is_ok());
+        allowance.revoked = true;
+        let err = allowance.check_active().unwrap_err();
+        assert_eq!(err, AttnError::UnauthorizedSweeper.into());
+    }
+
+    #[test]
+    fn record_sweep_enforces_interval_and_window() {
+        let mut allowance = mock_allowance(limits());
+        allowance.record_sweep(600, 10_000).unwrap();
+        assert_eq!(allowance.window_start_ts, 10_000);
+        assert_eq!(allowance.last_sweep_ts, 10_000);
+
+        let err = allowance.record_sweep(100, 10_030).unwrap_err();
+        assert_eq!(err, AttnError::SweepTooFrequent.into());
+
+        let err = allowance.record_sweep(401, 10_060).unwrap_err();
+        assert_eq!(err, AttnError::SweepWindowLimitExceeded.into());
+        assert_eq!(allowance.window_swept, 600);
+        assert_eq!(allowance.last_sweep_ts, 10_000);
+        allowance.record_sweep(400, 10_060).unwrap();
+        assert_eq!(allowance.window_swept, 1_000);
+
+        allowance.record_sweep(1_000, 13_600).unwrap();
+        assert_eq!(allowance.window_start_ts, 13_600);
+        assert_eq!(allowance.window_swept, 1_000);
+    }
+
+    #[test]
+    fn record_accumulates_vault_and_source_totals() {
+        let mut vault = mock_creator_vault();
+        let mut source = mock_revenue_source(RevenueSourceConfig::SplTransfer { depositor: None });
+        source.record(&mut vault, 400, 10).unwrap();
+        source.record(&mut vault, 100, 20).unwrap();
+        assert_eq!(vault.total_fees_collected, 500);
+        assert_eq!(source.total_collected, 500);
+        assert_eq!(source.last_collected_at, 20);
+
+        vault.total_fees_collected = u64::MAX;
+        let err = source.record(&mut vault, 1, 30).unwrap_err();
+        assert_eq!(err, AttnError::MathOverflow.into());
+    }
 }
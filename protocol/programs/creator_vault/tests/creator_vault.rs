@@ -1,6 +1,620 @@
-use creator_vault::CreatorVault;
+#![cfg(feature = "program-test")]
 
-#[test]
-fn creator_vault_space_constant_defined() {
-    assert!(CreatorVault::INIT_SPACE > 0);
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use creator_vault::{
+    accounts, instruction, AttnError, CreatorVault, RevenueSourceKind, SweeperAllowance,
+    SweeperLimits,
+};
+use solana_program::program_pack::Pack;
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::{Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token::instruction as token_instruction;
+use spl_token::state::{Account as TokenAccountState, Mint as MintState};
+
+const DECIMALS: u8 = 6;
+
+/// A Pump.fun vault created through `initialize_vault`, seeded by the pump mint.
+struct PumpVault {
+    pump_mint: Pubkey,
+    quote_mint: Pubkey,
+    creator_vault: Pubkey,
+    fee_vault: Pubkey,
+    sy_mint: Pubkey,
+}
+
+impl PumpVault {
+    fn new(pump_mint: Pubkey, quote_mint: Pubkey) -> Self {
+        let pda = |seed: &[u8]| {
+            Pubkey::find_program_address(&[seed, pump_mint.as_ref()], &creator_vault::id()).0
+        };
+        Self {
+            pump_mint,
+            quote_mint,
+            creator_vault: pda(b"creator-vault"),
+            fee_vault: pda(b"fee-vault"),
+            sy_mint: pda(b"sy-mint"),
+        }
+    }
+
+    fn initialize_ix(&self, authority: Pubkey, pump_creator: Pubkey, admin: Pubkey) -> Instruction {
+        let accounts = accounts::InitializeVault {
+            authority,
+            pump_creator,
+            pump_mint: self.pump_mint,
+            quote_mint: self.quote_mint,
+            creator_vault: self.creator_vault,
+            fee_vault: self.fee_vault,
+            sy_mint: self.sy_mint,
+            system_program: system_program::id(),
+            token_program: spl_token::id(),
+            rent: sysvar::rent::id(),
+        };
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts.to_account_metas(None),
+            data: instruction::InitializeVault {
+                splitter_program: Pubkey::new_unique(),
+                admin,
+            }
+            .data(),
+        }
+    }
+
+    fn wrap_ix(
+        &self,
+        user: Pubkey,
+        user_quote_ata: Pubkey,
+        user_sy_ata: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let accounts = accounts::WrapFees {
+            creator_vault: self.creator_vault,
+            user,
+            pump_mint: self.pump_mint,
+            quote_mint: self.quote_mint,
+            fee_vault: self.fee_vault,
+            user_quote_ata,
+            sy_mint: self.sy_mint,
+            user_sy_ata,
+            token_program: spl_token::id(),
+        };
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts.to_account_metas(None),
+            data: instruction::WrapFees { amount }.data(),
+        }
+    }
+
+    fn withdraw_ix(
+        &self,
+        authority: Pubkey,
+        admin: Pubkey,
+        admin_signs: bool,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let mut accounts = accounts::WithdrawFees {
+            creator_vault: self.creator_vault,
+            authority,
+            fee_vault: self.fee_vault,
+            destination,
+            token_program: spl_token::id(),
+            admin,
+        }
+        .to_account_metas(None);
+        if admin_signs {
+            accounts.last_mut().unwrap().is_signer = true;
+        }
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts,
+            data: instruction::WithdrawFees { amount }.data(),
+        }
+    }
+
+    fn set_pause_ix(&self, admin: Pubkey, paused: bool) -> Instruction {
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts::SetPause {
+                creator_vault: self.creator_vault,
+                admin,
+            }
+            .to_account_metas(None),
+            data: instruction::SetPause { paused }.data(),
+        }
+    }
+
+    fn lock_ix(&self, admin: Pubkey, lock_expires_at: Option<i64>) -> Instruction {
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts::UpdateLockState {
+                creator_vault: self.creator_vault,
+                admin,
+            }
+            .to_account_metas(None),
+            data: instruction::LockCollateral { lock_expires_at }.data(),
+        }
+    }
+
+    fn allowance(&self, delegate: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"sweeper-allowance",
+                self.creator_vault.as_ref(),
+                delegate.as_ref(),
+            ],
+            &creator_vault::id(),
+        )
+        .0
+    }
+
+    fn allow_sweeper_ix(
+        &self,
+        admin: Pubkey,
+        delegate: Pubkey,
+        limits: SweeperLimits,
+    ) -> Instruction {
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts::AllowSweeperDelegate {
+                creator_vault: self.creator_vault,
+                admin,
+                allowance: self.allowance(delegate),
+                system_program: system_program::id(),
+            }
+            .to_account_metas(None),
+            data: instruction::AllowSweeperDelegate { delegate, limits }.data(),
+        }
+    }
+
+    fn revoke_sweeper_ix(&self, admin: Pubkey, delegate: Pubkey) -> Instruction {
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts::RevokeSweeperDelegate {
+                creator_vault: self.creator_vault,
+                admin,
+                allowance: self.allowance(delegate),
+            }
+            .to_account_metas(None),
+            data: instruction::RevokeSweeperDelegate {}.data(),
+        }
+    }
+
+    fn set_sweeper_ix(&self, authority: Pubkey, delegate: Pubkey, fee_bps: u16) -> Instruction {
+        let sweeper = Pubkey::find_program_address(
+            &[b"sweeper", self.creator_vault.as_ref()],
+            &creator_vault::id(),
+        )
+        .0;
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts::SetSweeperDelegate {
+                creator_vault: self.creator_vault,
+                authority,
+                sweeper,
+                allowance: self.allowance(delegate),
+                system_program: system_program::id(),
+            }
+            .to_account_metas(None),
+            data: instruction::SetSweeperDelegate { delegate, fee_bps }.data(),
+        }
+    }
+
+    fn delegate_sweep_ix(&self, delegate: Pubkey, destination: Pubkey, amount: u64) -> Instruction {
+        let sweeper = Pubkey::find_program_address(
+            &[b"sweeper", self.creator_vault.as_ref()],
+            &creator_vault::id(),
+        )
+        .0;
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts::DelegateSweep {
+                creator_vault: self.creator_vault,
+                sweeper,
+                allowance: self.allowance(delegate),
+                delegate,
+                fee_vault: self.fee_vault,
+                destination,
+                delegate_fee_destination: None,
+                token_program: spl_token::id(),
+            }
+            .to_account_metas(None),
+            data: instruction::DelegateSweep { amount }.data(),
+        }
+    }
+}
+
+struct Fixture {
+    context: ProgramTestContext,
+    payer: Keypair,
+    admin: Keypair,
+    vault: PumpVault,
+    quote_ata: Pubkey,
+    sy_ata: Pubkey,
+}
+
+/// Initializes a Pump.fun vault owned by the payer and funds the payer with
+/// `quote` units of the quote mint.
+async fn setup(quote: u64) -> Fixture {
+    // Anchor's CPIs only run on-chain, so the program is loaded from the
+    // `cargo build-sbf` output in `SBF_OUT_DIR`.
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(true);
+    program_test.add_program("creator_vault", creator_vault::id(), None);
+    let mut context = program_test.start_with_context().await;
+    let payer = clone_keypair(&context.payer);
+    let admin = Keypair::new();
+    let pump_mint = create_mint(&mut context, &payer).await;
+    let quote_mint = create_mint(&mut context, &payer).await;
+    let vault = PumpVault::new(pump_mint, quote_mint);
+
+    let pump_creator = Pubkey::new_unique();
+    let init_ix = vault.initialize_ix(payer.pubkey(), pump_creator, admin.pubkey());
+    send_tx(&mut context, &[init_ix], &[&payer]).await;
+
+    let quote_ata = create_token_account(&mut context, &payer, quote_mint, payer.pubkey()).await;
+    let sy_ata = create_token_account(&mut context, &payer, vault.sy_mint, payer.pubkey()).await;
+    mint_to(&mut context, &payer, quote_mint, quote_ata, quote).await;
+    Fixture {
+        context,
+        payer,
+        admin,
+        vault,
+        quote_ata,
+        sy_ata,
+    }
+}
+
+#[tokio::test]
+async fn pump_vault_wraps_and_withdraws_fees() {
+    let mut fixture = setup(1_000).await;
+    let payer = clone_keypair(&fixture.payer);
+    let vault_state: CreatorVault =
+        get_anchor_account(&mut fixture.context, fixture.vault.creator_vault).await;
+    assert_eq!(vault_state.source_kind, RevenueSourceKind::PumpFun);
+    assert_eq!(vault_state.pump_mint, fixture.vault.pump_mint);
+    assert_eq!(vault_state.admin, fixture.admin.pubkey());
+
+    let wrap_ix = fixture
+        .vault
+        .wrap_ix(payer.pubkey(), fixture.quote_ata, fixture.sy_ata, 600);
+    send_tx(&mut fixture.context, &[wrap_ix], &[&payer]).await;
+    assert_eq!(
+        get_token_account(&mut fixture.context, fixture.sy_ata)
+            .await
+            .amount,
+        600
+    );
+    let vault_state: CreatorVault =
+        get_anchor_account(&mut fixture.context, fixture.vault.creator_vault).await;
+    assert_eq!(vault_state.total_fees_collected, 600);
+    assert_eq!(vault_state.total_sy_minted, 600);
+
+    let withdraw_ix = fixture.vault.withdraw_ix(
+        payer.pubkey(),
+        fixture.admin.pubkey(),
+        false,
+        fixture.quote_ata,
+        250,
+    );
+    send_tx(&mut fixture.context, &[withdraw_ix], &[&payer]).await;
+    assert_eq!(
+        get_token_account(&mut fixture.context, fixture.vault.fee_vault)
+            .await
+            .amount,
+        350
+    );
+    assert_eq!(
+        get_token_account(&mut fixture.context, fixture.quote_ata)
+            .await
+            .amount,
+        650
+    );
+
+    let overdraw_ix = fixture.vault.withdraw_ix(
+        payer.pubkey(),
+        fixture.admin.pubkey(),
+        false,
+        fixture.quote_ata,
+        351,
+    );
+    let err = process_tx(&mut fixture.context, &[overdraw_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::InsufficientVaultBalance.into());
+}
+
+#[tokio::test]
+async fn paused_and_locked_vaults_need_the_admin() {
+    let mut fixture = setup(1_000).await;
+    let payer = clone_keypair(&fixture.payer);
+    let admin = clone_keypair(&fixture.admin);
+    let wrap_ix = fixture
+        .vault
+        .wrap_ix(payer.pubkey(), fixture.quote_ata, fixture.sy_ata, 500);
+    send_tx(&mut fixture.context, &[wrap_ix.clone()], &[&payer]).await;
+
+    // Only the admin can pause, and a paused vault refuses new deposits.
+    let pause_ix = fixture.vault.set_pause_ix(payer.pubkey(), true);
+    let err = process_tx(&mut fixture.context, &[pause_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        TransportError::TransactionError(TransactionError::InstructionError(..))
+    ));
+    let pause_ix = fixture.vault.set_pause_ix(admin.pubkey(), true);
+    send_tx(&mut fixture.context, &[pause_ix], &[&payer, &admin]).await;
+    let wrap_ix = fixture
+        .vault
+        .wrap_ix(payer.pubkey(), fixture.quote_ata, fixture.sy_ata, 100);
+    let err = process_tx(&mut fixture.context, &[wrap_ix.clone()], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::VaultPaused.into());
+    let unpause_ix = fixture.vault.set_pause_ix(admin.pubkey(), false);
+    send_tx(&mut fixture.context, &[unpause_ix], &[&payer, &admin]).await;
+    send_tx(&mut fixture.context, &[wrap_ix], &[&payer]).await;
+
+    // Locked collateral can only leave with the admin co-signing.
+    let lock_ix = fixture.vault.lock_ix(admin.pubkey(), None);
+    send_tx(&mut fixture.context, &[lock_ix], &[&payer, &admin]).await;
+    let withdraw_ix = fixture.vault.withdraw_ix(
+        payer.pubkey(),
+        admin.pubkey(),
+        false,
+        fixture.quote_ata,
+        100,
+    );
+    let err = process_tx(&mut fixture.context, &[withdraw_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::AdminSignatureRequired.into());
+    let withdraw_ix =
+        fixture
+            .vault
+            .withdraw_ix(payer.pubkey(), admin.pubkey(), true, fixture.quote_ata, 100);
+    send_tx(&mut fixture.context, &[withdraw_ix], &[&payer, &admin]).await;
+    assert_eq!(
+        get_token_account(&mut fixture.context, fixture.vault.fee_vault)
+            .await
+            .amount,
+        500
+    );
+}
+
+#[tokio::test]
+async fn revoked_sweepers_keep_their_window() {
+    let mut fixture = setup(1_000).await;
+    let payer = clone_keypair(&fixture.payer);
+    let admin = clone_keypair(&fixture.admin);
+    let delegate = Keypair::new();
+    let wrap_ix = fixture
+        .vault
+        .wrap_ix(payer.pubkey(), fixture.quote_ata, fixture.sy_ata, 1_000);
+    send_tx(&mut fixture.context, &[wrap_ix], &[&payer]).await;
+    let limits = SweeperLimits {
+        max_fee_bps: 50,
+        max_amount_per_window: 300,
+        window_secs: 86_400,
+        min_interval_secs: 0,
+    };
+
+    // Only allowlisted delegates can be appointed, within their fee cap.
+    // Fees differ between attempts so no transaction repeats a signature.
+    let set_ix = |fee_bps| {
+        fixture
+            .vault
+            .set_sweeper_ix(payer.pubkey(), delegate.pubkey(), fee_bps)
+    };
+    let (unlisted, greedy, appoint, while_revoked_set) =
+        (set_ix(1), set_ix(51), set_ix(0), set_ix(2));
+    let err = process_tx(&mut fixture.context, &[unlisted], &[&payer])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        TransportError::TransactionError(TransactionError::InstructionError(..))
+    ));
+    let allow_ix =
+        fixture
+            .vault
+            .allow_sweeper_ix(admin.pubkey(), delegate.pubkey(), limits.clone());
+    send_tx(&mut fixture.context, &[allow_ix], &[&payer, &admin]).await;
+    let err = process_tx(&mut fixture.context, &[greedy], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::SweeperFeeAboveLimit.into());
+    send_tx(&mut fixture.context, &[appoint], &[&payer]).await;
+
+    let sweep = |amount| {
+        fixture
+            .vault
+            .delegate_sweep_ix(delegate.pubkey(), fixture.quote_ata, amount)
+    };
+    let (first, over, while_revoked, still_over, rest) =
+        (sweep(200), sweep(101), sweep(50), sweep(150), sweep(100));
+    send_tx(&mut fixture.context, &[first], &[&payer, &delegate]).await;
+    let err = process_tx(&mut fixture.context, &[over], &[&payer, &delegate])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::SweepWindowLimitExceeded.into());
+
+    // Revoking flags the entry and stops both sweeping and re-appointing.
+    let revoke_ix = fixture
+        .vault
+        .revoke_sweeper_ix(admin.pubkey(), delegate.pubkey());
+    send_tx(&mut fixture.context, &[revoke_ix], &[&payer, &admin]).await;
+    let allowance_address = fixture.vault.allowance(delegate.pubkey());
+    let allowance: SweeperAllowance =
+        get_anchor_account(&mut fixture.context, allowance_address).await;
+    assert!(allowance.revoked);
+    assert_eq!(allowance.window_swept, 200);
+    let err = process_tx(&mut fixture.context, &[while_revoked], &[&payer, &delegate])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::UnauthorizedSweeper.into());
+    let err = process_tx(&mut fixture.context, &[while_revoked_set], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::UnauthorizedSweeper.into());
+
+    // Allowing the delegate again, even with new limits, resumes the same
+    // window.
+    let reallow_ix = fixture.vault.allow_sweeper_ix(
+        admin.pubkey(),
+        delegate.pubkey(),
+        SweeperLimits {
+            max_fee_bps: 40,
+            ..limits
+        },
+    );
+    send_tx(&mut fixture.context, &[reallow_ix], &[&payer, &admin]).await;
+    let allowance: SweeperAllowance =
+        get_anchor_account(&mut fixture.context, allowance_address).await;
+    assert!(!allowance.revoked);
+    assert_eq!(allowance.window_swept, 200);
+    let err = process_tx(&mut fixture.context, &[still_over], &[&payer, &delegate])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::SweepWindowLimitExceeded.into());
+    send_tx(&mut fixture.context, &[rest], &[&payer, &delegate]).await;
+    assert_eq!(
+        get_token_account(&mut fixture.context, fixture.vault.fee_vault)
+            .await
+            .amount,
+        700
+    );
+}
+
+async fn process_tx(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&signers[0].pubkey()),
+        signers,
+        context.last_blockhash,
+    );
+    let result = context.banks_client.process_transaction(tx).await;
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    result.map_err(Into::into)
+}
+
+async fn send_tx(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) {
+    if let Err(err) = process_tx(context, instructions, signers).await {
+        panic!("process_transaction failed: {:?}", err);
+    }
+}
+
+fn assert_custom_error(err: TransportError, expected: u32) {
+    match err {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => assert_eq!(code, expected, "unexpected custom error code"),
+        other => panic!("unexpected transport error: {other:?}"),
+    }
+}
+
+async fn create_mint(context: &mut ProgramTestContext, authority: &Keypair) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create_ix = system_instruction::create_account(
+        &authority.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(MintState::LEN),
+        MintState::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_ix = token_instruction::initialize_mint(
+        &spl_token::id(),
+        &mint.pubkey(),
+        &authority.pubkey(),
+        None,
+        DECIMALS,
+    )
+    .unwrap();
+    send_tx(context, &[create_ix, init_ix], &[authority, &mint]).await;
+    mint.pubkey()
+}
+
+async fn create_token_account(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create_ix = system_instruction::create_account(
+        &payer.pubkey(),
+        &account.pubkey(),
+        rent.minimum_balance(TokenAccountState::LEN),
+        TokenAccountState::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_ix =
+        token_instruction::initialize_account3(&spl_token::id(), &account.pubkey(), &mint, &owner)
+            .unwrap();
+    send_tx(context, &[create_ix, init_ix], &[payer, &account]).await;
+    account.pubkey()
+}
+
+async fn mint_to(
+    context: &mut ProgramTestContext,
+    authority: &Keypair,
+    mint: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+) {
+    let ix = token_instruction::mint_to(
+        &spl_token::id(),
+        &mint,
+        &destination,
+        &authority.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+    send_tx(context, &[ix], &[authority]).await;
+}
+
+async fn get_account(context: &mut ProgramTestContext, address: Pubkey) -> SolanaAccount {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account exists")
+}
+
+async fn get_token_account(context: &mut ProgramTestContext, address: Pubkey) -> TokenAccountState {
+    TokenAccountState::unpack(&get_account(context, address).await.data).unwrap()
+}
+
+async fn get_anchor_account<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = get_account(context, address).await;
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn clone_keypair(keypair: &Keypair) -> Keypair {
+    Keypair::from_bytes(&keypair.to_bytes()).unwrap()
 }
@@ -0,0 +1,733 @@
+#![cfg(feature = "program-test")]
+
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use creator_vault::{
+    accounts, instruction, AttnError, CreatorVault, RevenueSource, RevenueSourceConfig,
+    RevenueSourceKind, DBC_CLAIM_CREATOR_TRADING_FEE, PUMP_COLLECT_CREATOR_FEE, PUMP_PROGRAM_ID,
+};
+use solana_program::{
+    entrypoint::ProgramResult, program::invoke_signed, program_error::ProgramError,
+    program_option::COption, program_pack::Pack, rent::Rent,
+};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token::instruction as token_instruction;
+use spl_token::state::{Account as TokenAccountState, Mint as MintState};
+
+const DECIMALS: u8 = 6;
+const POOL_AUTHORITY_SEED: &[u8] = b"pool_authority";
+
+/// Stand-in for a Meteora DBC pool: pays up to `max_quote_amount` from the
+/// pool's quote vault to the creator's quote account when the creator signs.
+fn mock_dbc_process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.len() != 24 || data[..8] != DBC_CLAIM_CREATOR_TRADING_FEE {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let max_quote_amount = u64::from_le_bytes(data[16..24].try_into().unwrap());
+    let pool_authority = &accounts[0];
+    let creator_quote = &accounts[3];
+    let quote_vault = &accounts[5];
+    let creator = &accounts[8];
+    let token_program = &accounts[10];
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let available = TokenAccountState::unpack(&quote_vault.data.borrow())?.amount;
+    let amount = available.min(max_quote_amount);
+    let (_, bump) = Pubkey::find_program_address(&[POOL_AUTHORITY_SEED], program_id);
+    let transfer = token_instruction::transfer(
+        token_program.key,
+        quote_vault.key,
+        creator_quote.key,
+        pool_authority.key,
+        &[],
+        amount,
+    )?;
+    invoke_signed(
+        &transfer,
+        &[
+            quote_vault.clone(),
+            creator_quote.clone(),
+            pool_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[POOL_AUTHORITY_SEED, &[bump]]],
+    )
+}
+
+/// Stand-in for Pump.fun `collect_creator_fee`: pays the creator fee PDA's
+/// balance above rent to the signing creator.
+fn mock_pump_process(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data != PUMP_COLLECT_CREATOR_FEE {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let creator = &accounts[0];
+    let fee_pda = &accounts[1];
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let floor = Rent::default().minimum_balance(fee_pda.data_len());
+    let amount = fee_pda.lamports().saturating_sub(floor);
+    **fee_pda.try_borrow_mut_lamports()? -= amount;
+    **creator.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+struct SourceVault {
+    source_id: Pubkey,
+    creator_vault: Pubkey,
+    fee_vault: Pubkey,
+    sy_mint: Pubkey,
+    revenue_source: Pubkey,
+}
+
+impl SourceVault {
+    fn new(source_id: Pubkey) -> Self {
+        let pda = |seed: &[u8], key: &Pubkey| {
+            Pubkey::find_program_address(&[seed, key.as_ref()], &creator_vault::id()).0
+        };
+        let creator_vault = pda(b"creator-vault", &source_id);
+        Self {
+            source_id,
+            creator_vault,
+            fee_vault: pda(b"fee-vault", &source_id),
+            sy_mint: pda(b"sy-mint", &source_id),
+            revenue_source: pda(b"revenue-source", &creator_vault),
+        }
+    }
+
+    fn initialize_ix(
+        &self,
+        authority: Pubkey,
+        quote_mint: Pubkey,
+        config: RevenueSourceConfig,
+    ) -> Instruction {
+        let accounts = accounts::InitializeSourceVault {
+            authority,
+            source_id: self.source_id,
+            quote_mint,
+            creator_vault: self.creator_vault,
+            fee_vault: self.fee_vault,
+            sy_mint: self.sy_mint,
+            revenue_source: self.revenue_source,
+            system_program: system_program::id(),
+            token_program: spl_token::id(),
+            rent: sysvar::rent::id(),
+        };
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts.to_account_metas(None),
+            data: instruction::InitializeSourceVault {
+                config,
+                splitter_program: Pubkey::new_unique(),
+                admin: authority,
+            }
+            .data(),
+        }
+    }
+
+    fn deposit_ix(
+        &self,
+        quote_mint: Pubkey,
+        depositor: Pubkey,
+        depositor_token_account: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let accounts = accounts::DepositSourceRevenue {
+            creator_vault: self.creator_vault,
+            revenue_source: self.revenue_source,
+            quote_mint,
+            fee_vault: self.fee_vault,
+            depositor,
+            depositor_token_account,
+            token_program: spl_token::id(),
+        };
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts.to_account_metas(None),
+            data: instruction::DepositSourceRevenue { amount }.data(),
+        }
+    }
+
+    fn claim_ix(
+        &self,
+        source_program: Pubkey,
+        cranker: Pubkey,
+        max_quote_amount: u64,
+        claim_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = accounts::ClaimSourceFees {
+            creator_vault: self.creator_vault,
+            revenue_source: self.revenue_source,
+            fee_vault: self.fee_vault,
+            source_program,
+            cranker,
+        }
+        .to_account_metas(None);
+        accounts.extend(claim_accounts);
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts,
+            data: instruction::ClaimSourceFees { max_quote_amount }.data(),
+        }
+    }
+
+    fn pump_fee_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"creator-vault", self.creator_vault.as_ref()],
+            &PUMP_PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn collect_creator_fees_ix(&self, cranker: Pubkey) -> Instruction {
+        let accounts = accounts::CollectCreatorFees {
+            creator_vault: self.creator_vault,
+            revenue_source: Some(self.revenue_source),
+            fee_vault: self.fee_vault,
+            pump_creator_vault: self.pump_fee_pda(),
+            pump_event_authority: Pubkey::find_program_address(
+                &[b"__event_authority"],
+                &PUMP_PROGRAM_ID,
+            )
+            .0,
+            pump_program: PUMP_PROGRAM_ID,
+            cranker,
+            system_program: system_program::id(),
+            token_program: spl_token::id(),
+        };
+        Instruction {
+            program_id: creator_vault::id(),
+            accounts: accounts.to_account_metas(None),
+            data: instruction::CollectCreatorFees {}.data(),
+        }
+    }
+}
+
+struct Fixture {
+    context: ProgramTestContext,
+    payer: Keypair,
+    quote_mint: Pubkey,
+    dbc_program: Pubkey,
+}
+
+async fn setup() -> Fixture {
+    let dbc_program = Pubkey::new_unique();
+    // The mocks stay native (their CPIs go through the program-test syscall
+    // stubs) while the vault, whose Anchor CPIs only run on-chain, is loaded
+    // from the `cargo build-sbf` output in `SBF_OUT_DIR`. The preference is
+    // read per `add_program` call.
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(false);
+    program_test.add_program("mock_dbc", dbc_program, processor!(mock_dbc_process));
+    program_test.add_program("mock_pump", PUMP_PROGRAM_ID, processor!(mock_pump_process));
+    program_test.prefer_bpf(true);
+    program_test.add_program("creator_vault", creator_vault::id(), None);
+    let mut native_mint = vec![0; MintState::LEN];
+    MintState::pack(
+        MintState {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        &mut native_mint,
+    )
+    .unwrap();
+    program_test.add_account(
+        spl_token::native_mint::id(),
+        SolanaAccount {
+            lamports: Rent::default().minimum_balance(MintState::LEN),
+            data: native_mint,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+    let payer = clone_keypair(&context.payer);
+    let quote_mint = create_mint(&mut context, &payer).await;
+    Fixture {
+        context,
+        payer,
+        quote_mint,
+        dbc_program,
+    }
+}
+
+#[tokio::test]
+async fn meteora_dbc_claim_lands_in_fee_vault() {
+    let mut fixture = setup().await;
+    let payer = clone_keypair(&fixture.payer);
+    let base_mint = create_mint(&mut fixture.context, &payer).await;
+    let vault = SourceVault::new(base_mint);
+    let pool = Pubkey::new_unique();
+    let (pool_authority, _) =
+        Pubkey::find_program_address(&[POOL_AUTHORITY_SEED], &fixture.dbc_program);
+
+    let config = RevenueSourceConfig::MeteoraDbc {
+        program: fixture.dbc_program,
+        pool,
+    };
+    let init_ix = vault.initialize_ix(payer.pubkey(), fixture.quote_mint, config);
+    send_tx(&mut fixture.context, &[init_ix], &[&payer]).await;
+
+    let vault_state: CreatorVault =
+        get_anchor_account(&mut fixture.context, vault.creator_vault).await;
+    assert_eq!(vault_state.source_kind, RevenueSourceKind::MeteoraDbc);
+    assert_eq!(vault_state.pump_mint, base_mint);
+    assert_eq!(vault_state.pump_creator, Pubkey::default());
+
+    let quote_vault = create_token_account(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        pool_authority,
+    )
+    .await;
+    mint_to(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        quote_vault,
+        900_000,
+    )
+    .await;
+
+    let base_fee_account =
+        create_token_account(&mut fixture.context, &payer, base_mint, vault.creator_vault).await;
+    let claim_accounts = vec![
+        AccountMeta::new_readonly(pool_authority, false),
+        AccountMeta::new(pool, false),
+        AccountMeta::new(base_fee_account, false),
+        AccountMeta::new(vault.fee_vault, false),
+        AccountMeta::new(Pubkey::new_unique(), false),
+        AccountMeta::new(quote_vault, false),
+        AccountMeta::new_readonly(base_mint, false),
+        AccountMeta::new_readonly(fixture.quote_mint, false),
+        AccountMeta::new_readonly(vault.creator_vault, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        AccountMeta::new_readonly(fixture.dbc_program, false),
+    ];
+
+    let claim_ix = vault.claim_ix(
+        fixture.dbc_program,
+        payer.pubkey(),
+        600_000,
+        claim_accounts.clone(),
+    );
+    send_tx(&mut fixture.context, &[claim_ix], &[&payer]).await;
+    let claim_ix = vault.claim_ix(
+        fixture.dbc_program,
+        payer.pubkey(),
+        u64::MAX,
+        claim_accounts.clone(),
+    );
+    send_tx(&mut fixture.context, &[claim_ix], &[&payer]).await;
+
+    let fee_vault = get_token_account(&mut fixture.context, vault.fee_vault).await;
+    assert_eq!(fee_vault.amount, 900_000);
+    let vault_state: CreatorVault =
+        get_anchor_account(&mut fixture.context, vault.creator_vault).await;
+    assert_eq!(vault_state.total_fees_collected, 900_000);
+    let source: RevenueSource =
+        get_anchor_account(&mut fixture.context, vault.revenue_source).await;
+    assert_eq!(source.total_collected, 900_000);
+    assert!(source.last_collected_at > 0);
+
+    let wrong_program = vault.claim_ix(
+        Pubkey::new_unique(),
+        payer.pubkey(),
+        1,
+        claim_accounts.clone(),
+    );
+    let err = process_tx(&mut fixture.context, &[wrong_program], &[&payer])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        TransportError::TransactionError(TransactionError::InstructionError(..))
+    ));
+
+    // The quote vault is drained, so a further claim moves nothing.
+    let claim_ix = vault.claim_ix(
+        fixture.dbc_program,
+        payer.pubkey(),
+        u64::MAX,
+        claim_accounts.clone(),
+    );
+    let err = process_tx(&mut fixture.context, &[claim_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::NothingClaimed.into());
+
+    let mut other_pool = claim_accounts.clone();
+    other_pool[1] = AccountMeta::new(Pubkey::new_unique(), false);
+    let claim_ix = vault.claim_ix(fixture.dbc_program, payer.pubkey(), 1, other_pool);
+    let err = process_tx(&mut fixture.context, &[claim_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::RevenueSourceMismatch.into());
+
+    // Fees routed to an account the vault doesn't control are refused
+    // before the vault signs.
+    mint_to(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        quote_vault,
+        100,
+    )
+    .await;
+    let thief = Keypair::new();
+    let thief_quote = create_token_account(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        thief.pubkey(),
+    )
+    .await;
+    let thief_base =
+        create_token_account(&mut fixture.context, &payer, base_mint, thief.pubkey()).await;
+    let mut to_thief = claim_accounts.clone();
+    to_thief[3] = AccountMeta::new(thief_quote, false);
+    let mut base_to_thief = claim_accounts.clone();
+    base_to_thief[2] = AccountMeta::new(thief_base, false);
+    let mut without_pool = claim_accounts;
+    without_pool.remove(1);
+    for accounts in [to_thief, base_to_thief, without_pool] {
+        let claim_ix = vault.claim_ix(fixture.dbc_program, payer.pubkey(), 100, accounts);
+        let err = process_tx(&mut fixture.context, &[claim_ix], &[&payer])
+            .await
+            .unwrap_err();
+        assert_custom_error(err, AttnError::InvalidClaimAccounts.into());
+    }
+    assert_eq!(
+        get_token_account(&mut fixture.context, thief_quote)
+            .await
+            .amount,
+        0
+    );
+}
+
+#[tokio::test]
+async fn spl_transfer_deposits_respect_depositor() {
+    let mut fixture = setup().await;
+    let payer = clone_keypair(&fixture.payer);
+    let source_id = create_mint(&mut fixture.context, &payer).await;
+    let vault = SourceVault::new(source_id);
+    let depositor = Keypair::new();
+
+    let config = RevenueSourceConfig::SplTransfer {
+        depositor: Some(depositor.pubkey()),
+    };
+    let init_ix = vault.initialize_ix(payer.pubkey(), fixture.quote_mint, config);
+    send_tx(&mut fixture.context, &[init_ix], &[&payer]).await;
+
+    let depositor_ata = create_token_account(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        depositor.pubkey(),
+    )
+    .await;
+    let payer_ata = create_token_account(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        payer.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        depositor_ata,
+        500,
+    )
+    .await;
+    mint_to(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        payer_ata,
+        500,
+    )
+    .await;
+
+    let deposit_ix = vault.deposit_ix(fixture.quote_mint, depositor.pubkey(), depositor_ata, 300);
+    send_tx(&mut fixture.context, &[deposit_ix], &[&payer, &depositor]).await;
+
+    let fee_vault = get_token_account(&mut fixture.context, vault.fee_vault).await;
+    assert_eq!(fee_vault.amount, 300);
+    let source: RevenueSource =
+        get_anchor_account(&mut fixture.context, vault.revenue_source).await;
+    assert_eq!(source.total_collected, 300);
+
+    let deposit_ix = vault.deposit_ix(fixture.quote_mint, payer.pubkey(), payer_ata, 300);
+    let err = process_tx(&mut fixture.context, &[deposit_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::UnauthorizedDepositor.into());
+}
+
+#[tokio::test]
+async fn pump_fun_source_keeps_wrap_flow_and_rejects_claims() {
+    let mut fixture = setup().await;
+    let payer = clone_keypair(&fixture.payer);
+    let pump_mint = create_mint(&mut fixture.context, &payer).await;
+    let vault = SourceVault::new(pump_mint);
+    let pump_creator = Pubkey::new_unique();
+
+    let config = RevenueSourceConfig::PumpFun {
+        creator: pump_creator,
+    };
+    let init_ix = vault.initialize_ix(payer.pubkey(), fixture.quote_mint, config);
+    send_tx(&mut fixture.context, &[init_ix], &[&payer]).await;
+
+    let vault_state: CreatorVault =
+        get_anchor_account(&mut fixture.context, vault.creator_vault).await;
+    assert_eq!(vault_state.source_kind, RevenueSourceKind::PumpFun);
+    assert_eq!(vault_state.pump_creator, pump_creator);
+
+    let quote_ata = create_token_account(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        payer.pubkey(),
+    )
+    .await;
+    let sy_ata =
+        create_token_account(&mut fixture.context, &payer, vault.sy_mint, payer.pubkey()).await;
+    mint_to(
+        &mut fixture.context,
+        &payer,
+        fixture.quote_mint,
+        quote_ata,
+        1_000,
+    )
+    .await;
+    let wrap_ix = Instruction {
+        program_id: creator_vault::id(),
+        accounts: accounts::WrapFees {
+            creator_vault: vault.creator_vault,
+            user: payer.pubkey(),
+            pump_mint,
+            quote_mint: fixture.quote_mint,
+            fee_vault: vault.fee_vault,
+            user_quote_ata: quote_ata,
+            sy_mint: vault.sy_mint,
+            user_sy_ata: sy_ata,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: instruction::WrapFees { amount: 1_000 }.data(),
+    };
+    send_tx(&mut fixture.context, &[wrap_ix], &[&payer]).await;
+    assert_eq!(
+        get_token_account(&mut fixture.context, sy_ata).await.amount,
+        1_000
+    );
+
+    let claim_ix = vault.claim_ix(fixture.dbc_program, payer.pubkey(), 1, Vec::new());
+    let err = process_tx(&mut fixture.context, &[claim_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::SourceClaimUnsupported.into());
+}
+
+#[tokio::test]
+async fn pump_creator_fees_are_collected_as_wsol() {
+    let mut fixture = setup().await;
+    let payer = clone_keypair(&fixture.payer);
+    let pump_mint = create_mint(&mut fixture.context, &payer).await;
+    let vault = SourceVault::new(pump_mint);
+    let config = RevenueSourceConfig::PumpFun {
+        creator: vault.creator_vault,
+    };
+    let init_ix = vault.initialize_ix(payer.pubkey(), spl_token::native_mint::id(), config);
+    send_tx(&mut fixture.context, &[init_ix], &[&payer]).await;
+
+    let accrued = 2_000_000_000;
+    fixture.context.set_account(
+        &vault.pump_fee_pda(),
+        &SolanaAccount {
+            lamports: Rent::default().minimum_balance(0) + accrued,
+            data: Vec::new(),
+            owner: PUMP_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+    let vault_lamports = get_account(&mut fixture.context, vault.creator_vault)
+        .await
+        .lamports;
+
+    let collect_ix = vault.collect_creator_fees_ix(payer.pubkey());
+    send_tx(&mut fixture.context, &[collect_ix], &[&payer]).await;
+
+    let fee_vault = get_token_account(&mut fixture.context, vault.fee_vault).await;
+    assert_eq!(fee_vault.amount, accrued);
+    let vault_account = get_account(&mut fixture.context, vault.creator_vault).await;
+    assert_eq!(vault_account.lamports, vault_lamports);
+    let vault_state: CreatorVault =
+        get_anchor_account(&mut fixture.context, vault.creator_vault).await;
+    assert_eq!(vault_state.total_fees_collected, accrued);
+    let source: RevenueSource =
+        get_anchor_account(&mut fixture.context, vault.revenue_source).await;
+    assert_eq!(source.total_collected, accrued);
+
+    // Nothing accrued since the last crank: succeeds without moving funds.
+    let collect_ix = vault.collect_creator_fees_ix(payer.pubkey());
+    send_tx(&mut fixture.context, &[collect_ix], &[&payer]).await;
+    let fee_vault = get_token_account(&mut fixture.context, vault.fee_vault).await;
+    assert_eq!(fee_vault.amount, accrued);
+
+    let usdc_vault = SourceVault::new(create_mint(&mut fixture.context, &payer).await);
+    let config = RevenueSourceConfig::PumpFun {
+        creator: usdc_vault.creator_vault,
+    };
+    let init_ix = usdc_vault.initialize_ix(payer.pubkey(), fixture.quote_mint, config);
+    send_tx(&mut fixture.context, &[init_ix], &[&payer]).await;
+    let collect_ix = usdc_vault.collect_creator_fees_ix(payer.pubkey());
+    let err = process_tx(&mut fixture.context, &[collect_ix], &[&payer])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, AttnError::QuoteMintNotNative.into());
+}
+
+async fn process_tx(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&signers[0].pubkey()),
+        signers,
+        context.last_blockhash,
+    );
+    let result = context.banks_client.process_transaction(tx).await;
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    result.map_err(Into::into)
+}
+
+async fn send_tx(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) {
+    if let Err(err) = process_tx(context, instructions, signers).await {
+        panic!("process_transaction failed: {:?}", err);
+    }
+}
+
+fn assert_custom_error(err: TransportError, expected: u32) {
+    match err {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => assert_eq!(code, expected, "unexpected custom error code"),
+        other => panic!("unexpected transport error: {other:?}"),
+    }
+}
+
+async fn create_mint(context: &mut ProgramTestContext, authority: &Keypair) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create_ix = system_instruction::create_account(
+        &authority.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(MintState::LEN),
+        MintState::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_ix = token_instruction::initialize_mint(
+        &spl_token::id(),
+        &mint.pubkey(),
+        &authority.pubkey(),
+        None,
+        DECIMALS,
+    )
+    .unwrap();
+    send_tx(context, &[create_ix, init_ix], &[authority, &mint]).await;
+    mint.pubkey()
+}
+
+async fn create_token_account(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create_ix = system_instruction::create_account(
+        &payer.pubkey(),
+        &account.pubkey(),
+        rent.minimum_balance(TokenAccountState::LEN),
+        TokenAccountState::LEN as u64,
+        &spl_token::id(),
+    );
+    let init_ix =
+        token_instruction::initialize_account3(&spl_token::id(), &account.pubkey(), &mint, &owner)
+            .unwrap();
+    send_tx(context, &[create_ix, init_ix], &[payer, &account]).await;
+    account.pubkey()
+}
+
+async fn mint_to(
+    context: &mut ProgramTestContext,
+    authority: &Keypair,
+    mint: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+) {
+    let ix = token_instruction::mint_to(
+        &spl_token::id(),
+        &mint,
+        &destination,
+        &authority.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+    send_tx(context, &[ix], &[authority]).await;
+}
+
+async fn get_account(context: &mut ProgramTestContext, address: Pubkey) -> SolanaAccount {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account exists")
+}
+
+async fn get_token_account(context: &mut ProgramTestContext, address: Pubkey) -> TokenAccountState {
+    TokenAccountState::unpack(&get_account(context, address).await.data).unwrap()
+}
+
+async fn get_anchor_account<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = get_account(context, address).await;
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn clone_keypair(keypair: &Keypair) -> Keypair {
+    Keypair::from_bytes(&keypair.to_bytes()).unwrap()
+}
@@ -870,7 +870,7 @@ pub enum RewardsError {
     OperationOutOfOrder,
 }
 
-fn distribute_pending(pool: &mut RewardsPool) -> Result<()> {
+pub fn distribute_pending(pool: &mut RewardsPool) -> Result<()> {
     if pool.total_staked > 0 && pool.pending_rewards > 0 {
         let increment = (pool.pending_rewards as u128)
             .checked_mul(INDEX_SCALE)
@@ -886,7 +886,7 @@ fn distribute_pending(pool: &mut RewardsPool) -> Result<()> {
     Ok(())
 }
 
-fn pending_amount(pool: &RewardsPool, position: &StakePosition) -> Result<u64> {
+pub fn pending_amount(pool: &RewardsPool, position: &StakePosition) -> Result<u64> {
     let accrued = (position.staked_amount as u128)
         .checked_mul(pool.sol_per_share)
         .ok_or(RewardsError::MathOverflow)?
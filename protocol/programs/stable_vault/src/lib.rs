@@ -835,7 +835,7 @@ impl StableVault {
         8 + Self::BASE_SIZE + 4 + (max_mints * 32) + Self::PADDING_SIZE
     }
 
-    fn price_per_share_scaled(&self) -> u64 {
+    pub fn price_per_share_scaled(&self) -> u64 {
         if self.total_shares == 0 {
             PRICE_SCALE as u64
         } else {
@@ -847,7 +847,7 @@ impl StableVault {
         }
     }
 
-    fn preview_deposit(&self, deposit_amount: u64) -> Result<u64> {
+    pub fn preview_deposit(&self, deposit_amount: u64) -> Result<u64> {
         require!(deposit_amount > 0, AttnError::InvalidAmount);
         if self.total_shares == 0 || self.total_assets == 0 {
             return Ok(deposit_amount);
@@ -864,7 +864,7 @@ impl StableVault {
         Ok(shares)
     }
 
-    fn preview_redeem(&self, shares: u64) -> Result<u64> {
+    pub fn preview_redeem(&self, shares: u64) -> Result<u64> {
         require!(shares > 0, AttnError::InvalidAmount);
         require!(self.total_shares >= shares, AttnError::InsufficientShares);
         if shares == self.total_shares {
@@ -0,0 +1,46 @@
+#!/usr/bin/env bash
+# Dumps the deployed Squads v4 program so attn_api's program tests can load it:
+#   scripts/dump-squads-program.sh && cargo test -p attn_api --features program-test
+# Equivalent to `solana program dump -u m SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf <out>`.
+#
+# The dump must match the SHA-256 pinned in squads_multisig_program.so.sha256
+# next to it, so an upgrade of the mainnet program can't silently change what
+# the tests run against. After reviewing an upgrade, re-pin with RECORD=1 and
+# commit the updated hash file.
+set -euo pipefail
+
+PROGRAM_ID=SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf
+RPC_URL=${RPC_URL:-https://api.mainnet-beta.solana.com}
+OUT=${1:-"$(dirname "$0")/../crates/attn_api/tests/fixtures/squads_multisig_program.so"}
+HASH_FILE="$OUT.sha256"
+
+account_info() {
+  curl -sSf "$RPC_URL" -H 'content-type: application/json' -d \
+    "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"getAccountInfo\",\"params\":[\"$1\",{\"encoding\":\"$2\"}]}"
+}
+
+# The upgradeable loader keeps the ELF in the program data account.
+PROGRAM_DATA=$(account_info "$PROGRAM_ID" jsonParsed | jq -r '.result.value.data.parsed.info.programData')
+if [[ -z "$PROGRAM_DATA" || "$PROGRAM_DATA" == null ]]; then
+  echo "could not resolve program data account for $PROGRAM_ID" >&2
+  exit 1
+fi
+
+mkdir -p "$(dirname "$OUT")"
+# Skip the 45-byte UpgradeableLoaderState::ProgramData header.
+account_info "$PROGRAM_DATA" base64 | jq -r '.result.value.data[0]' | base64 -d | tail -c +46 > "$OUT"
+echo "wrote $(wc -c < "$OUT") bytes to $OUT"
+
+cd "$(dirname "$OUT")"
+if [[ "${RECORD:-0}" == 1 ]]; then
+  sha256sum "$(basename "$OUT")" > "$(basename "$HASH_FILE")"
+  echo "pinned $(cut -d' ' -f1 < "$(basename "$HASH_FILE")") in $HASH_FILE"
+elif [[ ! -f "$(basename "$HASH_FILE")" ]]; then
+  rm -f "$(basename "$OUT")"
+  echo "no pinned hash at $HASH_FILE; review the program and re-run with RECORD=1" >&2
+  exit 1
+elif ! sha256sum --quiet -c "$(basename "$HASH_FILE")"; then
+  rm -f "$(basename "$OUT")"
+  echo "mainnet $PROGRAM_ID no longer matches $HASH_FILE; review the upgrade and re-pin with RECORD=1" >&2
+  exit 1
+fi